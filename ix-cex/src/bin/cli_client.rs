@@ -3,8 +3,14 @@ use tokio::time::{sleep, timeout, Duration};
 use tracing::{error, warn};
 
 use ix_cex::{
-    exchanges::{BinanceClient, CoinbaseClient, ExchangeClient, KrakenClient},
-    models::orderbook::{Orderbook, OrderbookSummary, TradingPair},
+    exchanges::{
+        BinanceClient, CoinbaseClient, DeribitClient, ExchangeClient, KrakenClient,
+        OkxClient,
+    },
+    models::{
+        instruments::Instrument,
+        orderbook::{Orderbook, OrderbookSummary},
+    },
     ExchangeError,
 };
 
@@ -17,9 +23,9 @@ struct Cli {
     #[arg(short, long, value_enum)]
     exchange: Option<Exchange>,
 
-    /// Trading pair to query
-    #[arg(short, long, value_enum)]
-    pair: TradingPairArg,
+    /// Trading pair to query, e.g. SOL/USDC or BTC-USDT
+    #[arg(short, long)]
+    pair: Instrument,
 
     /// Maximum number of order book levels to fetch
     #[arg(short, long, default_value = "100")]
@@ -51,12 +57,8 @@ enum Exchange {
     Binance,
     Coinbase,
     Kraken,
-}
-
-#[derive(ValueEnum, Clone, Debug)]
-enum TradingPairArg {
-    BtcUsdc,
-    SolUsdc,
+    Okx,
+    Deribit,
 }
 
 #[derive(ValueEnum, Clone, Debug)]
@@ -66,15 +68,6 @@ enum OutputFormat {
     Json,
 }
 
-impl From<TradingPairArg> for TradingPair {
-    fn from(pair: TradingPairArg) -> Self {
-        match pair {
-            TradingPairArg::BtcUsdc => TradingPair::BtcUsdc,
-            TradingPairArg::SolUsdc => TradingPair::SolUsdc,
-        }
-    }
-}
-
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
@@ -91,7 +84,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // println!("Starting crypto exchange client");
 
-    let trading_pair = TradingPair::from(cli.pair);
+    let trading_pair = cli.pair;
 
     if cli.all {
         // Query all exchanges
@@ -114,7 +107,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
 async fn query_exchange(
     exchange: Exchange,
-    pair: TradingPair,
+    pair: Instrument,
     depth: u32,
     timeout_secs: u64,
     timewait_millis: u64,
@@ -125,6 +118,8 @@ async fn query_exchange(
         Exchange::Binance => Box::new(BinanceClient::new()?),
         Exchange::Coinbase => Box::new(CoinbaseClient::new()?),
         Exchange::Kraken => Box::new(KrakenClient::new()?),
+        Exchange::Okx => Box::new(OkxClient::new()?),
+        Exchange::Deribit => Box::new(DeribitClient::new()?),
     };
 
     sleep(Duration::from_millis(timewait_millis)).await;
@@ -141,14 +136,20 @@ async fn query_exchange(
 }
 
 async fn query_all_exchanges(
-    pair: TradingPair,
+    pair: Instrument,
     depth: u32,
     timeout_secs: u64,
     timewait_millis: u64,
 ) -> Vec<(Exchange, Result<Orderbook, ExchangeError>)> {
     println!("Querying all exchanges for {pair} with depth {depth}");
 
-    let exchanges = vec![Exchange::Binance, Exchange::Coinbase, Exchange::Kraken];
+    let exchanges = vec![
+        Exchange::Binance,
+        Exchange::Coinbase,
+        Exchange::Kraken,
+        Exchange::Okx,
+        Exchange::Deribit,
+    ];
     let mut results = Vec::new();
 
     // Query exchanges concurrently
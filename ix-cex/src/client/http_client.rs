@@ -2,8 +2,8 @@
 use async_rate_limiter::RateLimiter;
 use ix_results::errors::{ExchangeError, Result};
 use reqwest::{
-    header::{HeaderMap, HeaderName, HeaderValue},
-    Client, Response,
+    header::{CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue},
+    Client, Method, Response,
 };
 use serde::de::DeserializeOwned;
 use std::{
@@ -31,7 +31,8 @@ pub struct HttpClient {
 }
 
 impl RetryableHttpClient {
-    /// Make a POST request with parameters and automatic retries
+    /// Make a POST request with parameters and automatic retries of the
+    /// attempts that did not reach the exchange
     pub async fn post_with_params_retry<T>(
         &self,
         endpoint: &str,
@@ -48,9 +49,10 @@ impl RetryableHttpClient {
                 Ok(result) => return Ok(result),
 
                 Err(error) => {
+                    let resend = was_not_processed(&error);
                     last_error = Some(error);
 
-                    if attempt < self.retry_config.max_retries {
+                    if resend && attempt < self.retry_config.max_retries {
                         info!(
                             "Request failed (attempt {}/{}), retrying in {:?}",
                             attempt + 1,
@@ -90,22 +92,36 @@ impl RetryableHttpClient {
     where
         T: DeserializeOwned,
     {
-        // (This retry logic is copied from get_with_params_retry)
+        self.post_body_with_headers_retry(endpoint, &json_body(params), headers)
+            .await
+    }
+
+    /// Make a POST request with a JSON body, custom headers and automatic retries
+    /// of the attempts that did not reach the exchange
+    pub async fn post_body_with_headers_retry<T>(
+        &self,
+        endpoint: &str,
+        body: &str,
+        headers: HashMap<&str, &str>,
+    ) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
         let mut last_error = None;
         let mut delay = self.retry_config.initial_delay;
 
         for attempt in 0..=self.retry_config.max_retries {
-            // Call the new header-aware function
             match self
                 .client
-                .post_with_headers(endpoint, params, headers.clone())
+                .post_body_with_headers(endpoint, body, headers.clone())
                 .await
             {
                 Ok(result) => return Ok(result),
                 Err(error) => {
+                    let resend = was_not_processed(&error);
                     last_error = Some(error);
 
-                    if attempt < self.retry_config.max_retries {
+                    if resend && attempt < self.retry_config.max_retries {
                         info!(
                             "Request failed (attempt {}/{}), retrying in {:?}",
                             attempt + 1,
@@ -132,6 +148,36 @@ impl RetryableHttpClient {
     }
 }
 
+/// The request was refused before the exchange acted on it, so sending it
+/// again cannot repeat its effect: it did not connect or was rate limited.
+/// POST requests change state, they are retried on these errors only.
+fn was_not_processed(error: &ExchangeError) -> bool {
+    match error {
+        ExchangeError::Network(e) => e.is_connect(),
+        ExchangeError::RateLimit { .. } => true,
+        _ => false,
+    }
+}
+
+/// Numeric `code` of a JSON error body, `{"code":-2013,"msg":"..."}` on
+/// Binance
+pub fn error_code(body: &str) -> Option<i64> {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()?
+        .get("code")?
+        .as_i64()
+}
+
+/// JSON object of string parameters, keys sorted
+pub fn json_body(params: &[(&str, &str)]) -> String {
+    if params.is_empty() {
+        "{}".to_string()
+    } else {
+        let body_map: BTreeMap<_, _> = params.iter().cloned().collect();
+        serde_json::to_string(&body_map).unwrap_or_default()
+    }
+}
+
 // --- POST --- //
 impl HttpClient {
     /// Make a request with rate limiting
@@ -168,13 +214,28 @@ impl HttpClient {
         self.handle_response(response).await
     }
 
-    /// Make a GET request with custom headers
+    /// Make a POST request with custom headers, the parameters are sent as a
+    /// JSON object
     pub async fn post_with_headers<T>(
         &self,
         endpoint: &str,
         params: &[(&str, &str)],
         headers: HashMap<&str, &str>,
     ) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        self.post_body_with_headers(endpoint, &json_body(params), headers)
+            .await
+    }
+
+    /// Make a POST request with a JSON body and custom headers
+    pub async fn post_body_with_headers<T>(
+        &self,
+        endpoint: &str,
+        body: &str,
+        headers: HashMap<&str, &str>,
+    ) -> Result<T>
     where
         T: DeserializeOwned,
     {
@@ -183,35 +244,53 @@ impl HttpClient {
 
         let url = self.build_url(endpoint, &[])?;
 
-        // Build the header map for reqwest
-        let mut header_map = HeaderMap::new();
-        for (key, value) in headers {
-            let header_name =
-                HeaderName::from_str(key).map_err(|_| ExchangeError::Configuration {
-                    message: format!("Invalid header name: {}", key),
-                })?;
-            let header_value = HeaderValue::from_str(value).map_err(|_| {
-                ExchangeError::Configuration {
-                    message: "Invalid header value".to_string(),
-                }
-            })?;
-            header_map.insert(header_name, header_value);
-        }
+        let mut header_map = header_map(headers)?;
+        header_map
+            .entry(CONTENT_TYPE)
+            .or_insert(HeaderValue::from_static("application/json"));
 
         debug!("Making POST request to: {} with custom headers", url);
 
-        let json_body = if params.is_empty() {
-            serde_json::json!({})
-        } else {
-            let body_map: BTreeMap<_, _> = params.iter().cloned().collect();
-            serde_json::to_value(&body_map).unwrap_or_default()
-        };
-
+        // The body is sent as signed, byte for byte
         let response = self
             .client
             .post(&url)
-            .headers(header_map) // Attach the headers here
-            .json(&json_body)
+            .headers(header_map)
+            .body(body.to_string())
+            .send()
+            .await
+            .map_err(ExchangeError::Network)?;
+
+        self.handle_response(response).await
+    }
+
+    /// Make a request of any method with an encoded query string, sent as
+    /// signed, and custom headers
+    pub async fn query_with_headers<T>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        query: &str,
+        headers: HashMap<&str, &str>,
+    ) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        // Wait for rate limiter
+        self.rate_limiter.acquire().await;
+
+        let mut url = self.build_url(endpoint, &[])?;
+        if !query.is_empty() {
+            url = format!("{url}?{query}");
+        }
+        let header_map = header_map(headers)?;
+
+        debug!("Making {} request to: {} with custom headers", method, url);
+
+        let response = self
+            .client
+            .request(method, &url)
+            .headers(header_map)
             .send()
             .await
             .map_err(ExchangeError::Network)?;
@@ -220,6 +299,23 @@ impl HttpClient {
     }
 }
 
+/// Header map of reqwest
+fn header_map(headers: HashMap<&str, &str>) -> Result<HeaderMap> {
+    let mut header_map = HeaderMap::new();
+    for (key, value) in headers {
+        let header_name =
+            HeaderName::from_str(key).map_err(|_| ExchangeError::Configuration {
+                message: format!("Invalid header name: {}", key),
+            })?;
+        let header_value =
+            HeaderValue::from_str(value).map_err(|_| ExchangeError::Configuration {
+                message: "Invalid header value".to_string(),
+            })?;
+        header_map.insert(header_name, header_value);
+    }
+    Ok(header_map)
+}
+
 impl HttpClient {
     /// Create a new HTTP client for an exchange
     pub fn new(
@@ -272,20 +368,7 @@ impl HttpClient {
 
         let url = self.build_url(endpoint, params)?;
 
-        // Build the header map for reqwest
-        let mut header_map = HeaderMap::new();
-        for (key, value) in headers {
-            let header_name =
-                HeaderName::from_str(key).map_err(|_| ExchangeError::Configuration {
-                    message: format!("Invalid header name: {}", key),
-                })?;
-            let header_value = HeaderValue::from_str(value).map_err(|_| {
-                ExchangeError::Configuration {
-                    message: "Invalid header value".to_string(),
-                }
-            })?;
-            header_map.insert(header_name, header_value);
-        }
+        let header_map = header_map(headers)?;
 
         debug!("Making GET request to: {} with custom headers", url);
 
@@ -391,14 +474,17 @@ impl HttpClient {
                 400..=499 => ExchangeError::ApiError {
                     exchange: self.exchange_name.clone(),
                     message: format!("Client error ({status}): {error_text}"),
+                    code: error_code(&error_text),
                 },
                 500..=599 => ExchangeError::ApiError {
                     exchange: self.exchange_name.clone(),
                     message: format!("Server error ({status}): {error_text}"),
+                    code: error_code(&error_text),
                 },
                 _ => ExchangeError::ApiError {
                     exchange: self.exchange_name.clone(),
                     message: format!("HTTP error ({status}): {error_text}"),
+                    code: error_code(&error_text),
                 },
             };
 
@@ -1,3 +1,6 @@
 pub mod http_client;
+pub mod private;
+pub mod wss_client;
 
 pub use http_client::*;
+pub use wss_client::*;
@@ -0,0 +1,97 @@
+//! Pieces shared by the private REST clients: the path and loading of their
+//! configuration file, their HTTP client, and the HMAC-SHA256 signature and
+//! millisecond timestamp of the signed requests.
+
+use crate::client::http_client::{HttpClient, RetryConfig, RetryableHttpClient};
+
+use config::{Config, ConfigError};
+use hmac::{Hmac, Mac};
+use ix_results::errors::{ExchangeError, Result};
+use serde::de::DeserializeOwned;
+use sha2::Sha256;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Requests per second of the private clients, to stay under the limits
+const REQUESTS_PER_SECOND: u32 = 10;
+
+/// Path of `file_name` in the `ix-cex/config` directory of the workspace
+pub fn config_path(file_name: &str) -> String {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let workspace_root = Path::new(manifest_dir)
+        .parent()
+        .expect("Failed to get workspace root");
+
+    workspace_root
+        .join("ix-cex")
+        .join("config")
+        .join(file_name)
+        .to_str()
+        .unwrap()
+        .to_owned()
+}
+
+/// Read the configuration file of `exchange` at `config_path`
+pub fn load_config<T>(config_path: &str, exchange: &str) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    let settings = Config::builder()
+        .add_source(config::File::with_name(config_path))
+        .build()
+        .map_err(|e: ConfigError| ExchangeError::Configuration {
+            message: format!("Failed to load {} config: {}", exchange, e),
+        })?;
+
+    settings
+        .try_deserialize()
+        .map_err(|e: ConfigError| ExchangeError::Configuration {
+            message: format!("Failed to parse {} config: {}", exchange, e),
+        })
+}
+
+/// HTTP client of the private endpoints of `exchange`
+pub fn http_client(
+    exchange: &str,
+    base_url: String,
+    timeout_seconds: u64,
+) -> Result<RetryableHttpClient> {
+    let http_client = HttpClient::new(
+        exchange.to_string(),
+        base_url,
+        REQUESTS_PER_SECOND,
+        timeout_seconds,
+    )?;
+
+    Ok(RetryableHttpClient::new(
+        http_client,
+        RetryConfig::default(),
+    ))
+}
+
+/// Hex encoded HMAC-SHA256 of `payload` with `api_secret`, an error when the
+/// client has no secret
+pub fn sign(api_secret: Option<&str>, payload: &str) -> Result<String> {
+    let api_secret = api_secret.ok_or_else(|| {
+        ExchangeError::Authentication(
+            "API secret is required for private endpoints".to_string(),
+        )
+    })?;
+
+    let mut mac = HmacSha256::new_from_slice(api_secret.as_bytes()).map_err(|e| {
+        ExchangeError::Authentication(format!("Invalid API secret: {}", e))
+    })?;
+    mac.update(payload.as_bytes());
+
+    Ok(hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Milliseconds since the epoch, the timestamp of the signed requests
+pub fn timestamp_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
@@ -0,0 +1,458 @@
+// ix-cex/src/client/wss_client.rs
+use crate::client::http_client::RetryConfig;
+use crate::results::errors::ExchangeError;
+use futures_util::{SinkExt, StreamExt};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, interval_at, sleep, sleep_until};
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::protocol::Message;
+use tracing::{debug, error, info, warn};
+use url::Url;
+
+/// Capacity of the channel between the supervisor and its caller
+const EVENT_CHANNEL_CAPACITY: usize = 10_000;
+
+/// Capacity of the command channel of a connection handle
+const COMMAND_CHANNEL_CAPACITY: usize = 100;
+
+/// Configuration for a supervised WebSocket connection
+#[derive(Debug, Clone)]
+pub struct WssConfig {
+    pub url: String,
+    pub retry: RetryConfig,
+    pub ping_interval: Duration,
+    pub pong_timeout: Duration,
+    /// Application level ping sent along the protocol ping, for venues that
+    /// expect one (e.g. Bybit `{"op":"ping"}`)
+    pub text_ping: Option<String>,
+}
+
+impl WssConfig {
+    /// Create a configuration that keeps reconnecting until the caller
+    /// stops listening, backing off with the default retry delays
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            retry: RetryConfig {
+                max_retries: u32::MAX,
+                ..RetryConfig::default()
+            },
+            ping_interval: Duration::from_secs(20),
+            pong_timeout: Duration::from_secs(10),
+            text_ping: None,
+        }
+    }
+
+    pub fn with_retry_config(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn with_ping(mut self, ping_interval: Duration, pong_timeout: Duration) -> Self {
+        self.ping_interval = ping_interval;
+        self.pong_timeout = pong_timeout;
+        self
+    }
+
+    pub fn with_text_ping(mut self, text_ping: impl Into<String>) -> Self {
+        self.text_ping = Some(text_ping.into());
+        self
+    }
+}
+
+/// Connection lifecycle events reported to the caller
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectionEvent {
+    /// Connection established and the login and subscriptions (re)sent
+    Connected { attempt: u32 },
+    /// Connection lost, a reconnect follows unless retries are exhausted
+    Disconnected { reason: String },
+    /// Waiting before the next connection attempt
+    Reconnecting { attempt: u32, delay: Duration },
+}
+
+impl fmt::Display for ConnectionEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConnectionEvent::Connected { attempt } => {
+                write!(f, "connected (attempt {attempt})")
+            }
+            ConnectionEvent::Disconnected { reason } => {
+                write!(f, "disconnected: {reason}")
+            }
+            ConnectionEvent::Reconnecting { attempt, delay } => {
+                write!(f, "reconnecting in {delay:?} (attempt {attempt})")
+            }
+        }
+    }
+}
+
+/// Event delivered by a supervised connection
+#[derive(Debug, Clone, PartialEq)]
+pub enum WssEvent {
+    Connection(ConnectionEvent),
+    Text(String),
+}
+
+/// Commands accepted by a running connection
+#[derive(Debug)]
+enum WssCommand {
+    /// Send now and replay after every reconnect
+    Subscribe(String),
+    /// Send once on the current connection
+    Send(String),
+}
+
+/// Handle to send messages on a running connection
+#[derive(Debug, Clone)]
+pub struct WssHandle {
+    commands: mpsc::Sender<WssCommand>,
+}
+
+impl WssHandle {
+    /// Send a subscription message, it is replayed after every reconnect
+    pub async fn subscribe(
+        &self,
+        message: impl Into<String>,
+    ) -> Result<(), ExchangeError> {
+        self.commands
+            .send(WssCommand::Subscribe(message.into()))
+            .await
+            .map_err(|_| ExchangeError::ChannelSendError)
+    }
+
+    /// Send a message on the current connection only
+    pub async fn send(&self, message: impl Into<String>) -> Result<(), ExchangeError> {
+        self.commands
+            .send(WssCommand::Send(message.into()))
+            .await
+            .map_err(|_| ExchangeError::ChannelSendError)
+    }
+}
+
+/// Builds the login message of a new connection
+type LoginFn = Arc<dyn Fn() -> ix_results::errors::Result<String> + Send + Sync>;
+
+/// Why a single connection ended
+enum Disconnect {
+    /// The connection dropped, reconnect
+    Lost { reason: String, received: bool },
+    /// The caller stopped listening, stop the supervisor
+    Shutdown,
+    /// The connection cannot be set up again, stop the supervisor
+    Failed(ExchangeError),
+}
+
+/// Reconnecting WebSocket client.
+///
+/// Keeps a connection open, replaying the subscription messages after every
+/// reconnect and backing off exponentially between failed attempts. A
+/// login message, built anew for every connection, precedes them. A
+/// protocol ping is sent every `ping_interval`, a connection that receives
+/// nothing within `pong_timeout` of a ping is considered stale and replaced.
+pub struct WssClient {
+    config: WssConfig,
+    login: Option<LoginFn>,
+    subscriptions: Vec<String>,
+    commands_tx: mpsc::Sender<WssCommand>,
+    commands_rx: mpsc::Receiver<WssCommand>,
+}
+
+impl WssClient {
+    pub fn new(config: WssConfig) -> Self {
+        let (commands_tx, commands_rx) = mpsc::channel(COMMAND_CHANNEL_CAPACITY);
+
+        Self {
+            config,
+            login: None,
+            subscriptions: Vec::new(),
+            commands_tx,
+            commands_rx,
+        }
+    }
+
+    /// Add a subscription message sent after every (re)connect
+    pub fn with_subscription(mut self, message: impl Into<String>) -> Self {
+        self.subscriptions.push(message.into());
+        self
+    }
+
+    /// Build a login message, e.g. a signed authentication, sent first on
+    /// every (re)connect. An error stops the client.
+    pub fn with_login(
+        mut self,
+        login: impl Fn() -> ix_results::errors::Result<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.login = Some(Arc::new(login));
+        self
+    }
+
+    /// Handle to send messages once the client is running
+    pub fn handle(&self) -> WssHandle {
+        WssHandle {
+            commands: self.commands_tx.clone(),
+        }
+    }
+
+    /// Run the client on a background task
+    pub fn spawn(self) -> WssConnection {
+        let handle = self.handle();
+        let (tx, events) = mpsc::channel(EVENT_CHANNEL_CAPACITY);
+        let task = tokio::spawn(self.run(tx));
+
+        WssConnection {
+            events,
+            handle,
+            task: Some(task),
+        }
+    }
+
+    /// Keep the connection alive and forward its events to `tx`.
+    ///
+    /// Returns `Ok` once `tx` is closed, or an error when `max_retries`
+    /// consecutive connection attempts failed.
+    pub async fn run(mut self, tx: mpsc::Sender<WssEvent>) -> Result<(), ExchangeError> {
+        let url = Url::parse(&self.config.url)?;
+        let retry = self.config.retry.clone();
+
+        let mut attempt: u32 = 0;
+        let mut delay = retry.initial_delay;
+
+        loop {
+            attempt = attempt.saturating_add(1);
+            info!("Connecting to WebSocket URL: {} (attempt {})", url, attempt);
+
+            let reason = match connect_async(url.clone()).await {
+                Ok((ws_stream, _)) => {
+                    match self.serve(ws_stream, attempt, &tx).await {
+                        Disconnect::Shutdown => return Ok(()),
+                        Disconnect::Failed(e) => {
+                            error!("WebSocket {} stopped: {}", url, e);
+                            return Err(e);
+                        }
+                        Disconnect::Lost { reason, received } => {
+                            // Only a connection that delivered data resets the
+                            // backoff, so a server that accepts and drops
+                            // immediately is still retried with increasing delays
+                            if received {
+                                attempt = 0;
+                                delay = retry.initial_delay;
+                            }
+                            reason
+                        }
+                    }
+                }
+                Err(e) => format!("connection failed: {e}"),
+            };
+
+            warn!("WebSocket {} disconnected: {}", url, reason);
+            let event = ConnectionEvent::Disconnected { reason };
+            if tx.send(WssEvent::Connection(event)).await.is_err() {
+                return Ok(());
+            }
+
+            if attempt > retry.max_retries {
+                error!("Giving up on WebSocket {} after {} attempts", url, attempt);
+                return Err(ExchangeError::RetriesExhausted {
+                    url: url.to_string(),
+                    attempts: attempt,
+                });
+            }
+
+            let event = ConnectionEvent::Reconnecting {
+                attempt: attempt + 1,
+                delay,
+            };
+            if tx.send(WssEvent::Connection(event)).await.is_err() {
+                return Ok(());
+            }
+
+            tokio::select! {
+                _ = sleep(delay) => {}
+                _ = tx.closed() => return Ok(()),
+            }
+
+            delay = std::cmp::min(
+                Duration::from_millis(
+                    (delay.as_millis() as f64 * retry.backoff_factor) as u64,
+                ),
+                retry.max_delay,
+            );
+        }
+    }
+
+    /// Serve one established connection until it drops, reporting it as
+    /// connected once the login and subscriptions are written
+    async fn serve<S>(
+        &mut self,
+        ws_stream: tokio_tungstenite::WebSocketStream<S>,
+        attempt: u32,
+        tx: &mpsc::Sender<WssEvent>,
+    ) -> Disconnect
+    where
+        S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+    {
+        let (mut write, mut read) = ws_stream.split();
+
+        if let Some(login) = &self.login {
+            let message = match login() {
+                Ok(message) => message,
+                Err(e) => return Disconnect::Failed(e.into()),
+            };
+            if let Err(e) = write.send(Message::Text(message)).await {
+                return Disconnect::Lost {
+                    reason: format!("failed to log in: {e}"),
+                    received: false,
+                };
+            }
+        }
+
+        for subscription in &self.subscriptions {
+            debug!("Sending subscription: {}", subscription);
+            if let Err(e) = write.send(Message::Text(subscription.clone())).await {
+                return Disconnect::Lost {
+                    reason: format!("failed to subscribe: {e}"),
+                    received: false,
+                };
+            }
+        }
+
+        let event = ConnectionEvent::Connected { attempt };
+        if tx.send(WssEvent::Connection(event)).await.is_err() {
+            return Disconnect::Shutdown;
+        }
+
+        let ping_interval = self.config.ping_interval;
+        let mut ping = interval_at(Instant::now() + ping_interval, ping_interval);
+        let mut pong_deadline: Option<Instant> = None;
+        let mut received = false;
+
+        loop {
+            tokio::select! {
+                _ = ping.tick() => {
+                    if let Err(e) = write.send(Message::Ping(Vec::new())).await {
+                        return Disconnect::Lost {
+                            reason: format!("failed to send ping: {e}"),
+                            received,
+                        };
+                    }
+                    if let Some(text_ping) = &self.config.text_ping
+                        && let Err(e) = write.send(Message::Text(text_ping.clone())).await
+                    {
+                        return Disconnect::Lost {
+                            reason: format!("failed to send ping: {e}"),
+                            received,
+                        };
+                    }
+                    pong_deadline.get_or_insert(Instant::now() + self.config.pong_timeout);
+                }
+                _ = sleep_until(pong_deadline.unwrap_or_else(Instant::now)),
+                    if pong_deadline.is_some() =>
+                {
+                    return Disconnect::Lost {
+                        reason: "stale connection, no pong received".to_string(),
+                        received,
+                    };
+                }
+                command = self.commands_rx.recv() => {
+                    let message = match command {
+                        Some(WssCommand::Subscribe(message)) => {
+                            self.subscriptions.push(message.clone());
+                            message
+                        }
+                        Some(WssCommand::Send(message)) => message,
+                        // The client itself holds a sender, the channel never closes
+                        None => continue,
+                    };
+                    if let Err(e) = write.send(Message::Text(message)).await {
+                        return Disconnect::Lost {
+                            reason: format!("failed to send message: {e}"),
+                            received,
+                        };
+                    }
+                }
+                _ = tx.closed() => return Disconnect::Shutdown,
+                msg = read.next() => {
+                    // Any frame proves the connection is alive
+                    pong_deadline = None;
+
+                    match msg {
+                        Some(Ok(Message::Text(text))) => {
+                            received = true;
+                            if tx.send(WssEvent::Text(text)).await.is_err() {
+                                return Disconnect::Shutdown;
+                            }
+                        }
+                        Some(Ok(Message::Ping(payload))) => {
+                            if let Err(e) = write.send(Message::Pong(payload)).await {
+                                return Disconnect::Lost {
+                                    reason: format!("failed to send pong: {e}"),
+                                    received,
+                                };
+                            }
+                        }
+                        Some(Ok(Message::Close(frame))) => {
+                            let reason = match frame {
+                                Some(frame) => format!("closed by server: {}", frame.reason),
+                                None => "closed by server".to_string(),
+                            };
+                            return Disconnect::Lost { reason, received };
+                        }
+                        Some(Ok(_)) => {}
+                        Some(Err(e)) => {
+                            return Disconnect::Lost {
+                                reason: format!("read error: {e}"),
+                                received,
+                            };
+                        }
+                        None => {
+                            return Disconnect::Lost {
+                                reason: "stream ended".to_string(),
+                                received,
+                            };
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A running supervised connection, the connection is closed on drop
+pub struct WssConnection {
+    events: mpsc::Receiver<WssEvent>,
+    handle: WssHandle,
+    task: Option<JoinHandle<Result<(), ExchangeError>>>,
+}
+
+impl WssConnection {
+    /// Next event, `None` once the supervisor stopped
+    pub async fn recv(&mut self) -> Option<WssEvent> {
+        self.events.recv().await
+    }
+
+    pub fn handle(&self) -> WssHandle {
+        self.handle.clone()
+    }
+
+    /// Wait for the supervisor to stop and return its result
+    pub async fn finish(mut self) -> Result<(), ExchangeError> {
+        self.events.close();
+        match self.task.take() {
+            Some(task) => task.await.unwrap_or(Ok(())),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for WssConnection {
+    fn drop(&mut self) {
+        if let Some(task) = &self.task {
+            task.abort();
+        }
+    }
+}
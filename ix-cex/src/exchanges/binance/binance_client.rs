@@ -1,7 +1,9 @@
 use crate::client::http_client::{HttpClient, RetryConfig, RetryableHttpClient};
-use crate::models::orderbook::{Orderbook, PriceLevel, TradingPair};
+use crate::models::instruments::{Instrument, ListedInstrument};
+use crate::models::orderbook::{Orderbook, PriceLevel};
 use chrono::Utc;
 use ix_results::errors::{ExchangeError, Result};
+use rust_decimal::Decimal;
 use serde::Deserialize;
 use std::str::FromStr;
 use tracing::{debug, info, warn};
@@ -32,7 +34,7 @@ impl BinanceClient {
     /// Get order book snapshot for a trading pair
     pub async fn get_orderbook(
         &self,
-        pair: TradingPair,
+        pair: Instrument,
         depth: Option<u32>,
     ) -> Result<Orderbook> {
         let symbol = pair.to_exchange_symbol("binance");
@@ -56,15 +58,16 @@ impl BinanceClient {
             response.asks.len()
         );
 
-        self.convert_to_orderbook(response, symbol)
+        self.convert_to_orderbook(response, &pair)
     }
 
     /// Convert Binance response to our OrderBook format
-    fn convert_to_orderbook(
+    pub fn convert_to_orderbook(
         &self,
         response: BinanceDepthResponse,
-        symbol: String,
+        pair: &Instrument,
     ) -> Result<Orderbook> {
+        let symbol = pair.to_exchange_symbol("binance");
         let mut v_bids = Vec::new();
         let mut v_asks = Vec::new();
 
@@ -79,15 +82,17 @@ impl BinanceClient {
             }
 
             let price =
-                f64::from_str(&bid_array[0]).map_err(|e| ExchangeError::ApiError {
+                Decimal::from_str(&bid_array[0]).map_err(|e| ExchangeError::ApiError {
                     exchange: "Binance".to_string(),
                     message: format!("Invalid bid price '{}': {}", bid_array[0], e),
+                    code: None,
                 })?;
 
             let quantity =
-                f64::from_str(&bid_array[1]).map_err(|e| ExchangeError::ApiError {
+                Decimal::from_str(&bid_array[1]).map_err(|e| ExchangeError::ApiError {
                     exchange: "Binance".to_string(),
                     message: format!("Invalid bid quantity '{}': {}", bid_array[1], e),
+                    code: None,
                 })?;
 
             v_bids.push(PriceLevel { price, quantity });
@@ -104,15 +109,17 @@ impl BinanceClient {
             }
 
             let price =
-                f64::from_str(&ask_array[0]).map_err(|e| ExchangeError::ApiError {
+                Decimal::from_str(&ask_array[0]).map_err(|e| ExchangeError::ApiError {
                     exchange: "Binance".to_string(),
                     message: format!("Invalid ask price '{}': {}", ask_array[0], e),
+                    code: None,
                 })?;
 
             let quantity =
-                f64::from_str(&ask_array[1]).map_err(|e| ExchangeError::ApiError {
+                Decimal::from_str(&ask_array[1]).map_err(|e| ExchangeError::ApiError {
                     exchange: "Binance".to_string(),
                     message: format!("Invalid ask quantity '{}': {}", ask_array[1], e),
+                    code: None,
                 })?;
 
             v_asks.push(PriceLevel { price, quantity });
@@ -127,13 +134,15 @@ impl BinanceClient {
             v_asks,
             Some(response.last_update_id),
             None,
-        );
+        )
+        .with_registered_tick_size(pair);
 
         // Validate the orderbook
         if !orderbook.is_valid() {
             return Err(ExchangeError::ApiError {
                 exchange: "Binance".to_string(),
                 message: "Received invalid orderbook data".to_string(),
+                code: None,
             });
         }
 
@@ -154,13 +163,32 @@ impl BinanceClient {
         self.client.get_with_retry("/api/v3/exchangeInfo").await
     }
 
+    /// List the spot instruments currently trading, with their symbols
+    pub async fn list_instruments(&self) -> Result<Vec<ListedInstrument>> {
+        let exchange_info = self.get_exchange_info().await?;
+
+        Ok(exchange_info
+            .symbols
+            .into_iter()
+            .filter(|symbol| symbol.status == "TRADING")
+            .map(|symbol| {
+                let instrument = Instrument::spot(&symbol.base_asset, &symbol.quote_asset);
+                let tick_size = symbol.filters.iter().find_map(|filter| match filter {
+                    BinanceSymbolFilter::PriceFilter { tick_size } => Some(*tick_size),
+                    _ => None,
+                });
+                ListedInstrument::new(instrument, symbol.symbol).with_tick_size(tick_size)
+            })
+            .collect())
+    }
+
     /// Get server time (useful for synchronization)
     pub async fn get_server_time(&self) -> Result<BinanceServerTime> {
         self.client.get_with_retry("/api/v3/time").await
     }
 
     /// Get 24hr ticker statistics
-    pub async fn get_24hr_ticker(&self, pair: TradingPair) -> Result<Binance24hrTicker> {
+    pub async fn get_24hr_ticker(&self, pair: Instrument) -> Result<Binance24hrTicker> {
         let symbol = pair.to_exchange_symbol("binance");
         let params = vec![("symbol", symbol.as_str())];
 
@@ -173,10 +201,10 @@ impl BinanceClient {
 /// Binance depth/orderbook response format
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct BinanceDepthResponse {
-    last_update_id: u64,
-    bids: Vec<Vec<String>>, // [price, quantity] pairs
-    asks: Vec<Vec<String>>, // [price, quantity] pairs
+pub struct BinanceDepthResponse {
+    pub last_update_id: u64,
+    pub bids: Vec<Vec<String>>, // [price, quantity] pairs
+    pub asks: Vec<Vec<String>>, // [price, quantity] pairs
 }
 
 /// Binance exchange info response
@@ -198,6 +226,29 @@ pub struct BinanceSymbol {
     pub quote_asset: String,
     pub base_asset_precision: u32,
     pub quote_precision: u32,
+    /// Trading rules, the ones not used here are `Other`
+    #[serde(default)]
+    pub filters: Vec<BinanceSymbolFilter>,
+}
+
+/// Binance symbol trading rule
+#[derive(Debug, Deserialize)]
+#[serde(tag = "filterType")]
+pub enum BinanceSymbolFilter {
+    #[serde(rename = "PRICE_FILTER", rename_all = "camelCase")]
+    PriceFilter { tick_size: Decimal },
+    #[serde(rename = "LOT_SIZE", rename_all = "camelCase")]
+    LotSize {
+        min_qty: Decimal,
+        max_qty: Decimal,
+        step_size: Decimal,
+    },
+    #[serde(rename = "NOTIONAL", rename_all = "camelCase")]
+    Notional { min_notional: Decimal },
+    #[serde(rename = "MIN_NOTIONAL", rename_all = "camelCase")]
+    MinNotional { min_notional: Decimal },
+    #[serde(other)]
+    Other,
 }
 
 /// Binance server time response
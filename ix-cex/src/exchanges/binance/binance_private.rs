@@ -0,0 +1,681 @@
+// ix-cex/src/exchanges/binance/binance_private.rs
+use crate::client::http_client::RetryableHttpClient;
+use crate::client::private::{self, timestamp_millis};
+use crate::exchanges::binance::binance_client::{
+    BinanceExchangeInfo, BinanceSymbol, BinanceSymbolFilter,
+};
+use crate::exchanges::binance::models::{
+    BinanceAccount, BinanceAccountTrade, BinanceCancelReplace, BinanceCancelledOrder,
+    BinanceOrder, BinanceOrderAck,
+};
+use crate::exchanges::{TradingClient, timestamp_from_millis};
+use crate::models::trades::TradeSide;
+use crate::models::trading::{
+    AmendOrder, Balance, Fill, LotSize, NewOrder, OrderAck, OrderKey, OrderQty,
+    OrderState, OrderStatus, OrderType,
+};
+use chrono::{DateTime, Utc};
+use ix_results::errors::{ExchangeError, Result};
+use reqwest::Method;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::collections::{BTreeSet, HashMap};
+use tracing::{debug, info};
+use url::form_urlencoded;
+
+pub const BINANCE_API_URL: &str = "https://api.binance.com";
+pub const BINANCE_TESTNET_API_URL: &str = "https://testnet.binance.vision";
+
+/// Binance error code of an unknown order
+const UNKNOWN_ORDER_CODE: i64 = -2013;
+
+/// Largest page of `/api/v3/myTrades`
+const TRADES_PAGE_SIZE: u32 = 1000;
+
+/// Longest time range of `/api/v3/myTrades`, a day in milliseconds
+const TRADES_RANGE_MILLIS: u64 = 24 * 60 * 60 * 1000;
+
+/// Range of the fills read without a start, 7 days as on Bybit
+const DEFAULT_FILLS_MILLIS: u64 = 7 * TRADES_RANGE_MILLIS;
+
+/// Sections of `binance.toml` read by the private client
+#[derive(Debug, Deserialize)]
+struct BinancePrivateConfig {
+    exchange: BinanceExchangeConfig,
+    api: Option<BinanceApiConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BinanceExchangeConfig {
+    base_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BinanceApiConfig {
+    api_key: String,
+    api_secret: String,
+    recv_window: Option<u64>,
+    testnet: Option<bool>,
+}
+
+/// Binance spot REST client of the signed endpoints.
+///
+/// Signed requests carry the API key in `X-MBX-APIKEY` and the HMAC-SHA256
+/// of their query string, timestamp and receive window included, in
+/// `signature`. They are not retried, a repeated order could be placed twice.
+#[derive(Clone)]
+pub struct BinancePrivateClient {
+    pub client: RetryableHttpClient,
+    pub api_key: Option<String>,
+    pub api_secret: Option<String>,
+    pub recv_window: u64,
+    pub testnet: bool,
+}
+
+impl BinancePrivateClient {
+    /// Create a new Binance client with the credentials of `binance.toml`
+    pub fn new() -> Result<Self> {
+        Self::from_config(&private::config_path("binance.toml"))
+    }
+
+    /// Create Binance client from configuration file
+    pub fn from_config(config_path: &str) -> Result<Self> {
+        let config: BinancePrivateConfig = private::load_config(config_path, "Binance")?;
+
+        let api = config.api;
+        let testnet = api.as_ref().and_then(|a| a.testnet).unwrap_or(false);
+        let base_url = if testnet {
+            BINANCE_TESTNET_API_URL.to_string()
+        } else {
+            config.exchange.base_url
+        };
+
+        Ok(Self {
+            client: private::http_client("Binance", base_url, 30)?,
+            api_key: api.as_ref().map(|a| a.api_key.clone()),
+            api_secret: api.as_ref().map(|a| a.api_secret.clone()),
+            recv_window: api.as_ref().and_then(|a| a.recv_window).unwrap_or(5000),
+            testnet,
+        })
+    }
+
+    /// Create Binance client with explicit credentials
+    pub fn with_credentials(
+        api_key: String,
+        api_secret: String,
+        testnet: bool,
+    ) -> Result<Self> {
+        let base_url = if testnet {
+            BINANCE_TESTNET_API_URL
+        } else {
+            BINANCE_API_URL
+        };
+
+        Ok(Self {
+            client: private::http_client("Binance", base_url.to_string(), 30)?,
+            api_key: Some(api_key),
+            api_secret: Some(api_secret),
+            recv_window: 5000,
+            testnet,
+        })
+    }
+
+    /// Hex encoded HMAC-SHA256 of `payload` with the API secret
+    pub fn sign(&self, payload: &str) -> Result<String> {
+        private::sign(self.api_secret.as_deref(), payload)
+    }
+
+    /// Query string of a signed request: the encoded `params`, the receive
+    /// window and `timestamp` in milliseconds, then their signature
+    pub fn signed_query(
+        &self,
+        params: &[(&str, &str)],
+        timestamp: u64,
+    ) -> Result<String> {
+        let query = form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(params)
+            .append_pair("recvWindow", &self.recv_window.to_string())
+            .append_pair("timestamp", &timestamp.to_string())
+            .finish();
+        debug!("Query string for signing: {}", query);
+
+        let signature = self.sign(&query)?;
+        Ok(format!("{query}&signature={signature}"))
+    }
+
+    /// Make a signed request, the parameters are sent in the query string
+    pub async fn request_signed<T>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        params: &[(&str, &str)],
+    ) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let api_key = self.api_key.as_ref().ok_or_else(|| {
+            ExchangeError::Authentication(
+                "API key is required for signed endpoints".to_string(),
+            )
+        })?;
+        let query = self.signed_query(params, timestamp_millis())?;
+
+        let mut headers = HashMap::new();
+        headers.insert("X-MBX-APIKEY", api_key.as_str());
+
+        info!("Making signed {} request to: {}", method, endpoint);
+        self.client
+            .client()
+            .query_with_headers(method, endpoint, &query, headers)
+            .await
+    }
+
+    /// Balances of the account, zero balances omitted
+    pub async fn get_account(&self) -> Result<BinanceAccount> {
+        info!("Fetching Binance account");
+        let params = [("omitZeroBalances", "true")];
+        self.request_signed(Method::GET, "/api/v3/account", &params)
+            .await
+    }
+
+    /// Place an order, answered once accepted
+    pub async fn new_order(&self, order: &NewOrder) -> Result<BinanceOrderAck> {
+        info!(
+            "Post Binance {} {} {:?} {}",
+            order.order_type, order.side, order.qty, order.symbol
+        );
+        let params = order_params(order)?;
+        self.request_signed(Method::POST, "/api/v3/order", &as_refs(&params))
+            .await
+    }
+
+    /// Cancel an open order
+    pub async fn cancel_order(
+        &self,
+        symbol: &str,
+        order: &OrderKey,
+    ) -> Result<BinanceCancelledOrder> {
+        info!("Cancel Binance order {:?}", order);
+        let params = [
+            ("symbol", symbol),
+            order_key_param(order, "orderId", "origClientOrderId"),
+        ];
+        self.request_signed(Method::DELETE, "/api/v3/order", &params)
+            .await
+    }
+
+    /// Cancel the open orders of a symbol
+    pub async fn cancel_open_orders(&self, symbol: &str) -> Result<serde_json::Value> {
+        info!("Cancel Binance open orders of {}", symbol);
+        let params = [("symbol", symbol)];
+        self.request_signed(Method::DELETE, "/api/v3/openOrders", &params)
+            .await
+    }
+
+    /// Get an order, open or closed
+    pub async fn get_order(
+        &self,
+        symbol: &str,
+        order: &OrderKey,
+    ) -> Result<BinanceOrder> {
+        info!("Fetching Binance order {:?}", order);
+        let params = [
+            ("symbol", symbol),
+            order_key_param(order, "orderId", "origClientOrderId"),
+        ];
+        self.request_signed(Method::GET, "/api/v3/order", &params)
+            .await
+    }
+
+    /// Get the open orders of a symbol, or of every symbol
+    pub async fn get_open_orders(
+        &self,
+        symbol: Option<&str>,
+    ) -> Result<Vec<BinanceOrder>> {
+        info!("Fetching Binance open orders");
+        let params: Vec<(&str, &str)> =
+            symbol.map(|s| ("symbol", s)).into_iter().collect();
+        self.request_signed(Method::GET, "/api/v3/openOrders", &params)
+            .await
+    }
+
+    /// Get a page of the fills of a symbol between `start_time` and
+    /// `end_time` in milliseconds, at most a day apart, oldest first
+    pub async fn get_my_trades(
+        &self,
+        symbol: &str,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<BinanceAccountTrade>> {
+        info!("Fetching Binance trades of {}", symbol);
+        let limit = TRADES_PAGE_SIZE.to_string();
+        let start_time = start_time.to_string();
+        let end_time = end_time.to_string();
+        let params = [
+            ("symbol", symbol),
+            ("startTime", start_time.as_str()),
+            ("endTime", end_time.as_str()),
+            ("limit", limit.as_str()),
+        ];
+        self.request_signed(Method::GET, "/api/v3/myTrades", &params)
+            .await
+    }
+
+    /// Get a page of the fills of a symbol from the trade id `from_id`,
+    /// oldest first
+    pub async fn get_my_trades_from_id(
+        &self,
+        symbol: &str,
+        from_id: u64,
+    ) -> Result<Vec<BinanceAccountTrade>> {
+        info!("Fetching Binance trades of {} from {}", symbol, from_id);
+        let limit = TRADES_PAGE_SIZE.to_string();
+        let from_id = from_id.to_string();
+        let params = [
+            ("symbol", symbol),
+            ("fromId", from_id.as_str()),
+            ("limit", limit.as_str()),
+        ];
+        self.request_signed(Method::GET, "/api/v3/myTrades", &params)
+            .await
+    }
+
+    /// Every fill of a symbol since `start_time` in milliseconds, oldest
+    /// first. The first fill is looked up a day at a time, the longest range
+    /// of a request, and the later ones are paged by trade id.
+    pub async fn get_all_my_trades(
+        &self,
+        symbol: &str,
+        start_time: u64,
+    ) -> Result<Vec<BinanceAccountTrade>> {
+        let now = timestamp_millis();
+        let mut trades = Vec::new();
+        let mut start = start_time;
+        while trades.is_empty() && start <= now {
+            let end = start + TRADES_RANGE_MILLIS - 1;
+            trades = self.get_my_trades(symbol, start, end).await?;
+            start = end + 1;
+        }
+
+        // The first page ends with its day, the next ones with the last fill
+        let mut from_id = trades.last().map(|trade| trade.id + 1);
+        while let Some(id) = from_id {
+            let page = self.get_my_trades_from_id(symbol, id).await?;
+            from_id = match page.last() {
+                Some(last) if page.len() == TRADES_PAGE_SIZE as usize => {
+                    Some(last.id + 1)
+                }
+                _ => None,
+            };
+            trades.extend(page);
+        }
+
+        Ok(trades)
+    }
+
+    /// Replace an open limit order with the amended one, the filled part of
+    /// `order` is not ordered again
+    pub async fn cancel_replace(
+        &self,
+        order: &BinanceOrder,
+        amend: &AmendOrder,
+    ) -> Result<BinanceCancelReplace> {
+        info!("Replace Binance order {}", order.order_id);
+        let params = cancel_replace_params(order, amend)?;
+        self.request_signed(
+            Method::POST,
+            "/api/v3/order/cancelReplace",
+            &as_refs(&params),
+        )
+        .await
+    }
+
+    /// Get the trading rules of a symbol (public endpoint)
+    pub async fn get_symbol_info(&self, symbol: &str) -> Result<BinanceSymbol> {
+        info!("Fetching Binance symbol {}", symbol);
+        let info: BinanceExchangeInfo = self
+            .client
+            .get_with_params_retry("/api/v3/exchangeInfo", &[("symbol", symbol)])
+            .await?;
+
+        info.symbols
+            .into_iter()
+            .find(|info| info.symbol == symbol)
+            .ok_or_else(|| ExchangeError::InvalidTradingPair {
+                pair: symbol.to_string(),
+            })
+    }
+}
+
+#[async_trait::async_trait]
+impl TradingClient for BinancePrivateClient {
+    fn exchange_name(&self) -> &str {
+        "Binance"
+    }
+
+    async fn balances(&self) -> Result<Vec<Balance>> {
+        let account = self.get_account().await?;
+        Ok(account
+            .balances
+            .into_iter()
+            .map(|balance| Balance {
+                coin: balance.asset,
+                total: balance.free + balance.locked,
+                free: balance.free,
+                locked: balance.locked,
+            })
+            .collect())
+    }
+
+    async fn place_order(&self, order: &NewOrder) -> Result<OrderAck> {
+        let ack = self.new_order(order).await?;
+        Ok(OrderAck {
+            order_id: ack.order_id.to_string(),
+            client_order_id: ack.client_order_id,
+        })
+    }
+
+    async fn amend_order(&self, amend: &AmendOrder) -> Result<OrderAck> {
+        amend.validate()?;
+
+        let order = self.get_order(&amend.symbol, &amend.order).await?;
+        let replaced = self.cancel_replace(&order, amend).await?;
+        let ack = replaced
+            .new_order_response
+            .ok_or_else(|| ExchangeError::ApiError {
+                exchange: "Binance".to_string(),
+                message: format!(
+                    "Order {} not replaced: cancel {}, new order {}",
+                    order.order_id, replaced.cancel_result, replaced.new_order_result
+                ),
+                code: None,
+            })?;
+
+        Ok(OrderAck {
+            order_id: ack.order_id.to_string(),
+            client_order_id: ack.client_order_id,
+        })
+    }
+
+    async fn cancel_order(&self, symbol: &str, order: &OrderKey) -> Result<OrderAck> {
+        let cancelled = BinancePrivateClient::cancel_order(self, symbol, order).await?;
+        Ok(OrderAck {
+            order_id: cancelled.order_id.to_string(),
+            client_order_id: cancelled.orig_client_order_id,
+        })
+    }
+
+    async fn cancel_all_orders(&self, symbol: Option<&str>) -> Result<()> {
+        let symbols: BTreeSet<String> = match symbol {
+            Some(symbol) => BTreeSet::from([symbol.to_string()]),
+            // Binance cancels one symbol at a time
+            None => self
+                .get_open_orders(None)
+                .await?
+                .into_iter()
+                .map(|order| order.symbol)
+                .collect(),
+        };
+
+        for symbol in symbols {
+            self.cancel_open_orders(&symbol).await?;
+        }
+        Ok(())
+    }
+
+    async fn get_order(
+        &self,
+        symbol: &str,
+        order: &OrderKey,
+    ) -> Result<Option<OrderState>> {
+        match BinancePrivateClient::get_order(self, symbol, order).await {
+            Ok(order) => OrderState::try_from(&order).map(Some),
+            Err(e) if e.api_code() == Some(UNKNOWN_ORDER_CODE) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn open_orders(&self, symbol: Option<&str>) -> Result<Vec<OrderState>> {
+        self.get_open_orders(symbol)
+            .await?
+            .iter()
+            .map(OrderState::try_from)
+            .collect()
+    }
+
+    async fn fills(
+        &self,
+        symbol: &str,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Fill>> {
+        let start_time = match since {
+            Some(since) => since.timestamp_millis().max(0) as u64,
+            None => timestamp_millis().saturating_sub(DEFAULT_FILLS_MILLIS),
+        };
+        Ok(self
+            .get_all_my_trades(symbol, start_time)
+            .await?
+            .iter()
+            .map(Fill::from)
+            .collect())
+    }
+
+    async fn lot_size(&self, symbol: &str) -> Result<LotSize> {
+        let info = self.get_symbol_info(symbol).await?;
+        LotSize::try_from(&info)
+    }
+}
+
+/// Parameters of `POST /api/v3/order`, answered with the ids only
+pub fn order_params(order: &NewOrder) -> Result<Vec<(&'static str, String)>> {
+    order.validate()?;
+
+    let mut params = vec![
+        ("symbol", order.symbol.clone()),
+        ("side", binance_side(order.side).to_string()),
+        ("type", binance_order_type(order.order_type).to_string()),
+    ];
+    match order.qty {
+        OrderQty::Base(qty) => params.push(("quantity", qty.normalize().to_string())),
+        OrderQty::Quote(qty) => {
+            params.push(("quoteOrderQty", qty.normalize().to_string()))
+        }
+    }
+    if let Some(price) = order.price {
+        params.push(("timeInForce", "GTC".to_string()));
+        params.push(("price", price.normalize().to_string()));
+    }
+    if let Some(client_order_id) = &order.client_order_id {
+        params.push(("newClientOrderId", client_order_id.clone()));
+    }
+    params.push(("newOrderRespType", "ACK".to_string()));
+    Ok(params)
+}
+
+/// Parameters of `POST /api/v3/order/cancelReplace` replacing `order`. The
+/// new order keeps the client id of `order` and is not placed if the cancel
+/// fails, e.g. once `order` is filled.
+pub fn cancel_replace_params(
+    order: &BinanceOrder,
+    amend: &AmendOrder,
+) -> Result<Vec<(&'static str, String)>> {
+    amend.validate()?;
+    if order.order_type != "LIMIT" {
+        return Err(ExchangeError::InvalidRequest(format!(
+            "Binance amends limit orders only, not {}",
+            order.order_type
+        )));
+    }
+
+    // The new order takes the quantity left
+    let qty = amend.qty.unwrap_or(order.orig_qty) - order.executed_qty;
+    if qty <= Decimal::ZERO {
+        return Err(ExchangeError::InvalidRequest(format!(
+            "Order quantity below the filled {}",
+            order.executed_qty
+        )));
+    }
+    let price = amend.price.unwrap_or(order.price);
+
+    Ok(vec![
+        ("symbol", order.symbol.clone()),
+        ("side", order.side.clone()),
+        ("type", order.order_type.clone()),
+        ("cancelReplaceMode", "STOP_ON_FAILURE".to_string()),
+        ("cancelOrderId", order.order_id.to_string()),
+        ("timeInForce", order.time_in_force.clone()),
+        ("quantity", qty.normalize().to_string()),
+        ("price", price.normalize().to_string()),
+        ("newClientOrderId", order.client_order_id.clone()),
+        ("newOrderRespType", "ACK".to_string()),
+    ])
+}
+
+/// Normalized status of a Binance order `status`
+pub fn order_status(status: &str) -> OrderStatus {
+    match status {
+        "PARTIALLY_FILLED" => OrderStatus::PartiallyFilled,
+        "FILLED" => OrderStatus::Filled,
+        "CANCELED" | "PENDING_CANCEL" | "EXPIRED" | "EXPIRED_IN_MATCH" => {
+            OrderStatus::Cancelled
+        }
+        "REJECTED" => OrderStatus::Rejected,
+        // NEW, PENDING_NEW
+        _ => OrderStatus::New,
+    }
+}
+
+impl TryFrom<&BinanceOrder> for OrderState {
+    type Error = ExchangeError;
+
+    fn try_from(order: &BinanceOrder) -> Result<Self> {
+        let order_type = match order.order_type.as_str() {
+            "MARKET" => OrderType::Market,
+            _ => OrderType::Limit,
+        };
+        let qty = match order_type {
+            OrderType::Market if order.orig_quote_order_qty > Decimal::ZERO => {
+                order.orig_quote_order_qty
+            }
+            _ => order.orig_qty,
+        };
+        let avg_price = if order.executed_qty > Decimal::ZERO {
+            order.cummulative_quote_qty / order.executed_qty
+        } else {
+            Decimal::ZERO
+        };
+
+        Ok(Self {
+            symbol: order.symbol.clone(),
+            order_id: order.order_id.to_string(),
+            client_order_id: order.client_order_id.clone(),
+            side: trade_side(&order.side)?,
+            order_type,
+            qty,
+            price: order.price,
+            status: order_status(&order.status),
+            filled_qty: order.executed_qty,
+            avg_price,
+            // Reported on the fills only
+            fee: Decimal::ZERO,
+            reject_reason: String::new(),
+            updated: timestamp_from_millis(order.update_time),
+        })
+    }
+}
+
+impl From<&BinanceAccountTrade> for Fill {
+    fn from(trade: &BinanceAccountTrade) -> Self {
+        Self {
+            symbol: trade.symbol.clone(),
+            order_id: trade.order_id.to_string(),
+            trade_id: trade.id.to_string(),
+            side: if trade.is_buyer {
+                TradeSide::Buy
+            } else {
+                TradeSide::Sell
+            },
+            price: trade.price,
+            qty: trade.qty,
+            fee: trade.commission,
+            fee_coin: trade.commission_asset.clone(),
+            is_maker: trade.is_maker,
+            timestamp: timestamp_from_millis(trade.time),
+        }
+    }
+}
+
+impl TryFrom<&BinanceSymbol> for LotSize {
+    type Error = ExchangeError;
+
+    fn try_from(symbol: &BinanceSymbol) -> Result<Self> {
+        let mut lot_size = None;
+        let mut min_notional = Decimal::ZERO;
+        for filter in &symbol.filters {
+            match filter {
+                BinanceSymbolFilter::LotSize {
+                    min_qty, step_size, ..
+                } => lot_size = Some((*min_qty, *step_size)),
+                BinanceSymbolFilter::Notional { min_notional: min }
+                | BinanceSymbolFilter::MinNotional { min_notional: min } => {
+                    min_notional = *min
+                }
+                BinanceSymbolFilter::PriceFilter { .. } | BinanceSymbolFilter::Other => {}
+            }
+        }
+
+        let (min_qty, step_size) = lot_size.ok_or_else(|| ExchangeError::ApiError {
+            exchange: "Binance".to_string(),
+            message: format!("No LOT_SIZE filter for {}", symbol.symbol),
+            code: None,
+        })?;
+
+        Ok(Self {
+            base_precision: step_size.normalize(),
+            quote_precision: Decimal::new(1, symbol.quote_precision),
+            min_order_qty: min_qty.normalize(),
+            min_order_amt: min_notional.normalize(),
+        })
+    }
+}
+
+fn binance_side(side: TradeSide) -> &'static str {
+    match side {
+        TradeSide::Buy => "BUY",
+        TradeSide::Sell => "SELL",
+    }
+}
+
+fn binance_order_type(order_type: OrderType) -> &'static str {
+    match order_type {
+        OrderType::Market => "MARKET",
+        OrderType::Limit => "LIMIT",
+    }
+}
+
+fn order_key_param<'a>(
+    order: &'a OrderKey,
+    id_key: &'static str,
+    client_id_key: &'static str,
+) -> (&'static str, &'a str) {
+    match order {
+        OrderKey::OrderId(id) => (id_key, id.as_str()),
+        OrderKey::ClientOrderId(id) => (client_id_key, id.as_str()),
+    }
+}
+
+fn trade_side(side: &str) -> Result<TradeSide> {
+    TradeSide::parse_from_str(side).ok_or_else(|| ExchangeError::ApiError {
+        exchange: "Binance".to_string(),
+        message: format!("Unknown side {side}"),
+        code: None,
+    })
+}
+
+fn as_refs<'a>(params: &'a [(&'static str, String)]) -> Vec<(&'static str, &'a str)> {
+    params
+        .iter()
+        .map(|(key, value)| (*key, value.as_str()))
+        .collect()
+}
@@ -1,73 +1,199 @@
 // ix-cex/src/binance_ws/client.rs
-use crate::exchanges::binance::models::{DepthOrDiff, StreamEvent};
+use crate::client::wss_client::{WssClient, WssConfig, WssEvent};
+use crate::exchanges::binance::binance_client::BinanceClient;
+use crate::exchanges::binance::local_orderbook::LocalOrderbook;
+use crate::exchanges::binance::models::{DepthOrDiff, ForceOrder, StreamEvent, Trade};
+use crate::exchanges::{
+    LiquidationStream, OrderbookStream, STREAM_CHANNEL_CAPACITY, TradeStream,
+    receiver_stream, timestamp_from_millis,
+};
+use crate::models::instruments::Instrument;
+use crate::models::trades::{Liquidation, PublicTrade, TradeSide};
 use crate::results::errors::ExchangeError;
-use futures_util::{SinkExt, StreamExt};
+use rust_decimal::prelude::ToPrimitive;
+use serde::de::DeserializeOwned;
 use tokio::sync::mpsc;
-use tokio_tungstenite::connect_async;
-use tokio_tungstenite::tungstenite::protocol::Message;
 use tracing::{error, info, warn};
 use url::Url;
 
-pub async fn run_websocket_client(
-    tx: mpsc::Sender<DepthOrDiff>,
+pub const BINANCE_WS_URL: &str = "wss://stream.binance.com:9443/stream";
+pub const BINANCE_FUTURES_WS_URL: &str = "wss://fstream.binance.com/stream";
+
+/// Levels per side published when no depth is requested
+const DEFAULT_DEPTH: u32 = 25;
+
+pub async fn run_websocket_client<T: DeserializeOwned>(
+    tx: mpsc::Sender<T>,
     streams: Vec<String>,
 ) -> Result<(), ExchangeError> {
-    const BINANCE_WS_URL: &str = "wss://stream.binance.com:9443/stream";
+    run_websocket_client_with_url(BINANCE_WS_URL, tx, streams).await
+}
 
+pub async fn run_websocket_client_with_url<T: DeserializeOwned>(
+    base_url: &str,
+    tx: mpsc::Sender<T>,
+    streams: Vec<String>,
+) -> Result<(), ExchangeError> {
     // let streams = [
     //     "btcusdt@depth5@100ms", // Partial book depth
     //     "btcusdt@depth@100ms",  // Diff. depth stream
     // ];
 
+    // Streams are part of the URL, every reconnect resubscribes them
     let stream_names = streams.join("/");
-    let url_str = format!("{BINANCE_WS_URL}?streams={stream_names}");
-    let url = Url::parse(&url_str)?;
+    let url_str = format!("{base_url}?streams={stream_names}");
+    Url::parse(&url_str)?;
 
-    info!("Connecting to WebSocket URL: {}", url);
-
-    let (ws_stream, _) = connect_async(url).await.expect("Failed to connect");
-    info!("WebSocket connection established.");
-
-    let (mut write, mut read) = ws_stream.split();
+    let mut connection = WssClient::new(WssConfig::new(url_str)).spawn();
 
     // Main message processing loop
-    loop {
-        tokio::select! {
-            Some(msg) = read.next() => {
-                match msg {
-                    Ok(Message::Text(text)) => {
-                        match serde_json::from_str::<StreamEvent>(&text) {
-                            Ok(event) => {
-                                if tx.send(event.data).await.is_err() {
-                                    error!("Receiver dropped. Shutting down websocket client.");
-                                    break;
-                                }
-                            }
-                            Err(e) => warn!("Failed to deserialize message: {}. Text: {}", e, text),
-                        }
-                    }
-                    Ok(Message::Ping(ping)) => {
-                        // Respond to pings to keep the connection alive
-                        if let Err(e) = write.send(Message::Pong(ping)).await {
-                            error!("Failed to send pong: {}", e);
-                            break;
-                        }
-                    }
-                    Ok(Message::Close(_)) => {
-                        info!("WebSocket connection closed by server.");
-                        break;
-                    }
-                    Err(e) => {
-                        error!("WebSocket read error: {}", e);
-                        break;
+    while let Some(event) = connection.recv().await {
+        match event {
+            WssEvent::Connection(event) => info!("Binance WebSocket {}", event),
+            WssEvent::Text(text) => match serde_json::from_str::<StreamEvent<T>>(&text) {
+                Ok(event) => {
+                    if tx.send(event.data).await.is_err() {
+                        error!("Receiver dropped. Shutting down websocket client.");
+                        return Ok(());
                     }
-                    _ => {} // Ignore other message types
                 }
+                Err(e) => warn!("Failed to deserialize message: {}. Text: {}", e, text),
             },
-            else => break,
         }
     }
 
     warn!("WebSocket client loop terminated.");
-    Ok(())
+    connection.finish().await
+}
+
+impl BinanceClient {
+    /// Stream the local order book maintained from the diff depth stream
+    pub fn subscribe_orderbook(
+        &self,
+        pair: Instrument,
+        depth: Option<u32>,
+    ) -> ix_results::errors::Result<OrderbookStream> {
+        let stream =
+            format!("{}@depth@100ms", pair.to_exchange_symbol("binance")).to_lowercase();
+
+        let (diff_tx, diff_rx) = mpsc::channel::<DepthOrDiff>(STREAM_CHANNEL_CAPACITY);
+        let (tx, rx) = mpsc::channel(STREAM_CHANNEL_CAPACITY);
+
+        tokio::spawn(async move {
+            if let Err(e) = run_websocket_client(diff_tx, vec![stream]).await {
+                error!("Binance depth stream failed: {}", e);
+            }
+        });
+
+        let local_book =
+            LocalOrderbook::new(self.clone(), pair, depth.unwrap_or(DEFAULT_DEPTH));
+
+        tokio::spawn(async move {
+            if let Err(e) = local_book.run(diff_rx, tx).await {
+                error!("Binance local orderbook failed: {}", e);
+            }
+        });
+
+        Ok(receiver_stream(rx))
+    }
+
+    /// Stream public trades from <symbol>@trade
+    pub fn subscribe_trades(
+        &self,
+        pair: Instrument,
+    ) -> ix_results::errors::Result<TradeStream> {
+        let stream =
+            format!("{}@trade", pair.to_exchange_symbol("binance")).to_lowercase();
+
+        let (trade_tx, mut trade_rx) = mpsc::channel::<Trade>(STREAM_CHANNEL_CAPACITY);
+        let (tx, rx) = mpsc::channel(STREAM_CHANNEL_CAPACITY);
+
+        tokio::spawn(async move {
+            if let Err(e) = run_websocket_client(trade_tx, vec![stream]).await {
+                error!("Binance trade stream failed: {}", e);
+            }
+        });
+
+        tokio::spawn(async move {
+            while let Some(trade) = trade_rx.recv().await {
+                if tx.send(to_public_trade(&trade)).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(receiver_stream(rx))
+    }
+
+    /// Stream liquidations of the USD-M perpetual from <symbol>@forceOrder
+    pub fn subscribe_liquidations(
+        &self,
+        pair: Instrument,
+    ) -> ix_results::errors::Result<LiquidationStream> {
+        let stream =
+            format!("{}@forceOrder", pair.to_exchange_symbol("binance")).to_lowercase();
+
+        let (order_tx, mut order_rx) =
+            mpsc::channel::<ForceOrder>(STREAM_CHANNEL_CAPACITY);
+        let (tx, rx) = mpsc::channel(STREAM_CHANNEL_CAPACITY);
+
+        tokio::spawn(async move {
+            let result = run_websocket_client_with_url(
+                BINANCE_FUTURES_WS_URL,
+                order_tx,
+                vec![stream],
+            )
+            .await;
+            if let Err(e) = result {
+                error!("Binance liquidation stream failed: {}", e);
+            }
+        });
+
+        tokio::spawn(async move {
+            while let Some(order) = order_rx.recv().await {
+                let Some(liquidation) = to_liquidation(&order) else {
+                    warn!("Unknown Binance liquidation side: {}", order.order.side);
+                    continue;
+                };
+                if tx.send(liquidation).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(receiver_stream(rx))
+    }
+}
+
+/// Normalize a trade, the aggressor sold when the buyer is the maker
+pub fn to_public_trade(trade: &Trade) -> PublicTrade {
+    let side = if trade.is_maker {
+        TradeSide::Sell
+    } else {
+        TradeSide::Buy
+    };
+
+    PublicTrade {
+        symbol: trade.symbol.clone(),
+        exchange: "Binance".to_string(),
+        timestamp: timestamp_from_millis(trade.trade_ts),
+        side,
+        price: trade.price.to_f64().unwrap_or_default(),
+        amount: trade.quantity.to_f64().unwrap_or_default(),
+        trade_id: trade.trade_id.to_string(),
+    }
+}
+
+/// Normalize a liquidation order
+pub fn to_liquidation(order: &ForceOrder) -> Option<Liquidation> {
+    let details = &order.order;
+
+    Some(Liquidation {
+        symbol: details.symbol.clone(),
+        exchange: "Binance".to_string(),
+        timestamp: timestamp_from_millis(details.trade_ts),
+        side: TradeSide::parse_from_str(&details.side)?,
+        price: details.average_price.to_f64().unwrap_or_default(),
+        amount: details.filled_quantity.to_f64().unwrap_or_default(),
+    })
 }
@@ -0,0 +1,355 @@
+// ix-cex/src/exchanges/binance/local_orderbook.rs
+use crate::exchanges::binance::binance_client::BinanceClient;
+use crate::exchanges::binance::models::{DepthOrDiff, DiffDepth};
+use crate::models::book::LevelBook;
+use crate::models::instruments::Instrument;
+use crate::models::orderbook::Orderbook;
+use chrono::{TimeZone, Utc};
+use ix_results::errors::{ExchangeError, Result};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+/// Maximum number of diff events kept while waiting for a snapshot
+const MAX_BUFFERED_EVENTS: usize = 10_000;
+
+/// Number of levels requested for the REST snapshot
+const SNAPSHOT_DEPTH: u32 = 1000;
+
+/// Delay before the second snapshot attempt, doubled on every failure
+const SNAPSHOT_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Longest delay between two snapshot attempts
+const MAX_SNAPSHOT_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+type SnapshotFuture = Pin<Box<dyn Future<Output = Result<Orderbook>> + Send>>;
+
+/// Synchronization state of the local order book
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncState {
+    /// No valid snapshot, incoming diffs are buffered
+    Syncing,
+    /// Snapshot applied and diffs are being applied in sequence
+    Synced,
+}
+
+/// Result of applying a single diff depth event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffOutcome {
+    /// Event is older than the current book and was discarded
+    Stale,
+    /// Event was applied to the book
+    Applied,
+    /// Event does not follow the last applied update, a resync is required
+    Gap { expected: u64, received: u64 },
+}
+
+/// Local order book maintained from a Binance diff depth stream.
+///
+/// Follows the Binance procedure to manage a local order book: diff events
+/// are buffered until a REST snapshot is available, events with
+/// `final_update_id <= lastUpdateId` are dropped and every applied event must
+/// satisfy `first_update_id <= last_update_id + 1 <= final_update_id`.
+/// Any gap in the sequence triggers a new snapshot. A single snapshot request
+/// is in flight at a time, diffs keep being buffered until it returns and
+/// failed attempts are retried with an exponential backoff.
+pub struct LocalOrderbook {
+    client: BinanceClient,
+    pair: Instrument,
+    symbol: String,
+    depth: u32,
+    book: LevelBook,
+    last_update_id: Option<u64>,
+    buffer: VecDeque<DiffDepth>,
+    state: SyncState,
+}
+
+impl LocalOrderbook {
+    /// Create a new local order book publishing `depth` levels per side
+    pub fn new(client: BinanceClient, pair: Instrument, depth: u32) -> Self {
+        let symbol = pair.to_exchange_symbol("binance");
+
+        Self {
+            client,
+            pair,
+            symbol,
+            depth,
+            book: LevelBook::new(),
+            last_update_id: None,
+            buffer: VecDeque::new(),
+            state: SyncState::Syncing,
+        }
+    }
+
+    /// Exchange symbol maintained by this book
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// Current synchronization state
+    pub fn state(&self) -> SyncState {
+        self.state
+    }
+
+    /// Last update id applied to the book
+    pub fn last_update_id(&self) -> Option<u64> {
+        self.last_update_id
+    }
+
+    /// Consume diff events from `rx` and publish a consistent order book on
+    /// `tx` after every applied update. Returns when either channel closes.
+    pub async fn run(
+        mut self,
+        mut rx: mpsc::Receiver<DepthOrDiff>,
+        tx: mpsc::Sender<Orderbook>,
+    ) -> Result<()> {
+        info!("Starting Binance local orderbook for {}", self.symbol);
+
+        let mut snapshot: Option<SnapshotFuture> = None;
+        let mut retry_delay = Duration::ZERO;
+
+        loop {
+            // Binance asks for the snapshot once the first event is buffered
+            if snapshot.is_none()
+                && self.state == SyncState::Syncing
+                && !self.buffer.is_empty()
+            {
+                snapshot = Some(self.request_snapshot(retry_delay));
+            }
+
+            let orderbook = tokio::select! {
+                event = rx.recv() => {
+                    let diff = match event {
+                        Some(DepthOrDiff::Diff(diff)) => diff,
+                        Some(DepthOrDiff::PartialBook(_)) => continue,
+                        None => break,
+                    };
+
+                    if !diff.symbol.eq_ignore_ascii_case(&self.symbol) {
+                        continue;
+                    }
+
+                    self.on_diff(diff)
+                }
+                result = async { snapshot.as_mut().expect("pending snapshot").await },
+                    if snapshot.is_some() =>
+                {
+                    snapshot = None;
+
+                    match result.and_then(|snapshot| self.on_snapshot(&snapshot)) {
+                        Ok(Some(orderbook)) => {
+                            retry_delay = Duration::ZERO;
+                            Some(orderbook)
+                        }
+                        Ok(None) => {
+                            retry_delay = next_retry_delay(retry_delay);
+                            None
+                        }
+                        Err(e) => {
+                            retry_delay = next_retry_delay(retry_delay);
+                            warn!(
+                                "Failed to resync Binance orderbook {}, retrying in {:?}: {}",
+                                self.symbol, retry_delay, e
+                            );
+                            None
+                        }
+                    }
+                }
+            };
+
+            if let Some(orderbook) = orderbook
+                && tx.send(orderbook).await.is_err()
+            {
+                warn!("Orderbook receiver dropped, stopping local orderbook");
+                break;
+            }
+        }
+
+        info!("Binance local orderbook for {} terminated", self.symbol);
+        Ok(())
+    }
+
+    /// Handle one diff event. Returns the updated order book when the event
+    /// is applied to a synced book, buffers it otherwise.
+    pub fn on_diff(&mut self, diff: DiffDepth) -> Option<Orderbook> {
+        let event_time = diff.event_time;
+
+        if self.state == SyncState::Synced {
+            match self.apply_diff(&diff) {
+                DiffOutcome::Applied => return Some(self.to_orderbook_at(event_time)),
+                DiffOutcome::Stale => return None,
+                DiffOutcome::Gap { expected, received } => {
+                    warn!(
+                        "Gap in Binance depth stream for {}: expected {}, received {}",
+                        self.symbol, expected, received
+                    );
+                    self.reset();
+                }
+            }
+        }
+
+        self.buffer_event(diff);
+        None
+    }
+
+    /// Number of diff events waiting for a snapshot
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    fn buffer_event(&mut self, diff: DiffDepth) {
+        if self.buffer.len() >= MAX_BUFFERED_EVENTS {
+            self.buffer.pop_front();
+        }
+        self.buffer.push_back(diff);
+    }
+
+    /// Fetch a REST snapshot after `delay`, without borrowing the book so
+    /// diffs keep being buffered meanwhile
+    fn request_snapshot(&self, delay: Duration) -> SnapshotFuture {
+        debug!(
+            "Requesting Binance orderbook snapshot for {} in {:?}",
+            self.symbol, delay
+        );
+
+        let client = self.client.clone();
+        let pair = self.pair.clone();
+
+        Box::pin(async move {
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+            client.get_orderbook(pair, Some(SNAPSHOT_DEPTH)).await
+        })
+    }
+
+    /// Seed the book with a REST snapshot and replay the buffered events on
+    /// top of it. Returns the order book when it ends up synced, `None` when
+    /// the snapshot is older than the buffered events and another is needed.
+    pub fn on_snapshot(&mut self, snapshot: &Orderbook) -> Result<Option<Orderbook>> {
+        self.seed(snapshot)?;
+
+        let mut event_time = None;
+
+        // The snapshot must not be older than the first buffered event
+        while let Some(diff) = self.buffer.pop_front() {
+            match self.apply_diff(&diff) {
+                DiffOutcome::Applied => event_time = Some(diff.event_time),
+                DiffOutcome::Stale => {}
+                DiffOutcome::Gap { expected, received } => {
+                    debug!(
+                        "Snapshot for {} older than buffered events ({} < {}), retrying",
+                        self.symbol, expected, received
+                    );
+                    self.buffer.push_front(diff);
+                    self.state = SyncState::Syncing;
+                    return Ok(None);
+                }
+            }
+        }
+
+        info!(
+            "Binance orderbook {} synced at update id {:?}",
+            self.symbol, self.last_update_id
+        );
+
+        Ok(Some(match event_time {
+            Some(event_time) => self.to_orderbook_at(event_time),
+            None => self.to_orderbook(),
+        }))
+    }
+
+    /// Replace the book with a REST snapshot
+    pub fn seed(&mut self, snapshot: &Orderbook) -> Result<()> {
+        let last_update_id =
+            snapshot
+                .last_update_id
+                .ok_or_else(|| ExchangeError::ApiError {
+                    exchange: "Binance".to_string(),
+                    message: "Orderbook snapshot without lastUpdateId".to_string(),
+                    code: None,
+                })?;
+
+        self.book = LevelBook::from_orderbook(snapshot);
+        self.last_update_id = Some(last_update_id);
+        self.state = SyncState::Synced;
+
+        Ok(())
+    }
+
+    /// Apply a diff event following the Binance sequencing rules
+    pub fn apply_diff(&mut self, diff: &DiffDepth) -> DiffOutcome {
+        let last_update_id = match self.last_update_id {
+            Some(id) => id,
+            None => {
+                return DiffOutcome::Gap {
+                    expected: 0,
+                    received: diff.first_update_id,
+                };
+            }
+        };
+
+        if diff.final_update_id <= last_update_id {
+            return DiffOutcome::Stale;
+        }
+
+        if diff.first_update_id > last_update_id + 1 {
+            return DiffOutcome::Gap {
+                expected: last_update_id + 1,
+                received: diff.first_update_id,
+            };
+        }
+
+        for level in &diff.bids {
+            self.book.update_bid(level.price, level.qty);
+        }
+        for level in &diff.asks {
+            self.book.update_ask(level.price, level.qty);
+        }
+        self.last_update_id = Some(diff.final_update_id);
+
+        DiffOutcome::Applied
+    }
+
+    /// Drop the current book and go back to buffering
+    fn reset(&mut self) {
+        self.book.clear();
+        self.buffer.clear();
+        self.last_update_id = None;
+        self.state = SyncState::Syncing;
+    }
+
+    /// Build the normalized order book, truncated to the configured depth
+    pub fn to_orderbook(&self) -> Orderbook {
+        self.book
+            .to_orderbook(
+                &self.symbol,
+                "Binance",
+                Utc::now(),
+                Some(self.depth as usize),
+                self.last_update_id,
+                None,
+            )
+            .with_registered_tick_size(&self.pair)
+    }
+
+    /// Build the normalized order book stamped with a diff event time (ms)
+    pub fn to_orderbook_at(&self, event_time: u64) -> Orderbook {
+        let mut orderbook = self.to_orderbook();
+        if let Some(ts) = Utc.timestamp_millis_opt(event_time as i64).single() {
+            orderbook.timestamp = ts;
+        }
+        orderbook
+    }
+}
+
+fn next_retry_delay(delay: Duration) -> Duration {
+    if delay.is_zero() {
+        SNAPSHOT_RETRY_DELAY
+    } else {
+        (delay * 2).min(MAX_SNAPSHOT_RETRY_DELAY)
+    }
+}
@@ -1,3 +1,5 @@
 pub mod binance_client;
+pub mod binance_private;
 pub mod binance_wss;
 pub mod models;
+pub mod local_orderbook;
@@ -0,0 +1,94 @@
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+// Represents the response of /api/v3/account
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceAccount {
+    pub can_trade: bool,
+    pub account_type: String,
+    pub balances: Vec<BinanceBalance>,
+    pub update_time: u64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BinanceBalance {
+    pub asset: String,
+    pub free: Decimal,
+    pub locked: Decimal,
+}
+
+// Represents an order of /api/v3/order and /api/v3/openOrders
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceOrder {
+    pub symbol: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+    pub price: Decimal,
+    pub orig_qty: Decimal,
+    pub executed_qty: Decimal,
+    /// Quote coin value of the fills, the spelling is Binance's
+    pub cummulative_quote_qty: Decimal,
+    pub status: String, // "NEW", "PARTIALLY_FILLED", "FILLED", "CANCELED", ...
+    pub time_in_force: String,
+    #[serde(rename = "type")]
+    pub order_type: String, // "LIMIT", "MARKET", ...
+    pub side: String, // "BUY", "SELL"
+    /// Milliseconds
+    pub time: u64,
+    pub update_time: u64,
+    pub is_working: bool,
+    /// Quote coin quantity of the market orders sized in it
+    #[serde(default)]
+    pub orig_quote_order_qty: Decimal,
+}
+
+// Represents the ACK response of POST /api/v3/order
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceOrderAck {
+    pub symbol: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+    pub transact_time: u64,
+}
+
+// Represents the response of DELETE /api/v3/order
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceCancelledOrder {
+    pub symbol: String,
+    pub order_id: u64,
+    /// Client id of the cancelled order, `client_order_id` is the one of
+    /// the cancel request
+    pub orig_client_order_id: String,
+    pub status: String,
+}
+
+// Represents the response of POST /api/v3/order/cancelReplace
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceCancelReplace {
+    pub cancel_result: String,
+    pub new_order_result: String,
+    pub new_order_response: Option<BinanceOrderAck>,
+}
+
+// Represents a fill of /api/v3/myTrades
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceAccountTrade {
+    pub symbol: String,
+    pub id: u64,
+    pub order_id: u64,
+    pub price: Decimal,
+    pub qty: Decimal,
+    pub quote_qty: Decimal,
+    pub commission: Decimal,
+    pub commission_asset: String,
+    /// Milliseconds
+    pub time: u64,
+    pub is_buyer: bool,
+    pub is_maker: bool,
+}
@@ -0,0 +1,36 @@
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+// Represents the payload for <symbol>@forceOrder (USD-M futures)
+#[derive(Deserialize, Debug, Clone)]
+pub struct ForceOrder {
+    #[serde(rename = "e")]
+    pub event_type: String,
+    #[serde(rename = "E")]
+    pub event_ts: u64,
+    #[serde(rename = "o")]
+    pub order: ForceOrderDetails,
+}
+
+// The liquidated order, side is the side of the liquidation order
+#[derive(Deserialize, Debug, Clone)]
+pub struct ForceOrderDetails {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "S")]
+    pub side: String,
+    #[serde(rename = "o")]
+    pub order_type: String,
+    #[serde(rename = "q")]
+    pub quantity: Decimal,
+    #[serde(rename = "p")]
+    pub price: Decimal,
+    #[serde(rename = "ap")]
+    pub average_price: Decimal,
+    #[serde(rename = "X")]
+    pub status: String,
+    #[serde(rename = "z")]
+    pub filled_quantity: Decimal,
+    #[serde(rename = "T")]
+    pub trade_ts: u64,
+}
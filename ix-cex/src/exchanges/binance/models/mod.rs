@@ -2,3 +2,7 @@ pub mod trade;
 pub use trade::*;
 pub mod orderbook;
 pub use orderbook::*;
+pub mod liquidation;
+pub use liquidation::*;
+pub mod account;
+pub use account::*;
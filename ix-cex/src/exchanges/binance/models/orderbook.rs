@@ -11,9 +11,9 @@ pub struct Level {
 
 // The outer wrapper for combined streams
 #[derive(Deserialize, Debug)]
-pub struct StreamEvent {
+pub struct StreamEvent<T = DepthOrDiff> {
     pub stream: String,
-    pub data: DepthOrDiff,
+    pub data: T,
 }
 
 // An enum to represent either a partial book depth snapshot or a diff update
@@ -1,3 +1,4 @@
+use rust_decimal::Decimal;
 use serde::Deserialize;
 
 // An enum to represent either a partial book depth snapshot or a diff update
@@ -7,7 +8,7 @@ pub enum AggOrTrade {
     Trade(Trade),
 }
 
-// Represents the payload for <symbol>@depth<levels>
+// Represents the payload for <symbol>@trade
 #[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Trade {
@@ -20,9 +21,9 @@ pub struct Trade {
     #[serde(rename = "t")]
     pub trade_id: u64,
     #[serde(rename = "p")]
-    pub price: f64,
+    pub price: Decimal,
     #[serde(rename = "q")]
-    pub quantity: f64,
+    pub quantity: Decimal,
     #[serde(rename = "T")]
     pub trade_ts: u64,
     #[serde(rename = "m")]
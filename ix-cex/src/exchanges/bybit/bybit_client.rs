@@ -1,9 +1,11 @@
 use crate::client::http_client::{HttpClient, RetryConfig, RetryableHttpClient};
 use crate::exchanges::bybit::responses;
-use crate::models::orderbook::{Orderbook, TradingPair, PriceLevel};
+use crate::models::instruments::{Instrument, ListedInstrument};
+use crate::models::orderbook::{Orderbook, PriceLevel};
 use chrono::Utc;
 
 use ix_results::errors::{ExchangeError, Result};
+use rust_decimal::Decimal;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::str::FromStr;
@@ -39,7 +41,7 @@ impl BybitClient {
     /// Get order book snapshot for a trading pair
     pub async fn get_orderbook(
         &self,
-        pair: TradingPair,
+        pair: Instrument,
         depth: Option<u32>,
     ) -> Result<Orderbook> {
         let symbol_id = pair.to_exchange_symbol("bybit");
@@ -77,14 +79,15 @@ impl BybitClient {
             response.result.asks.len()
         );
 
-        self.convert_to_orderbook(response, symbol_id)
+        self.convert_to_orderbook(response, &pair)
     }
 
-    fn convert_to_orderbook(
+    pub fn convert_to_orderbook(
         &self,
         response: responses::BybitOrderbookResponse,
-        symbol: String,
+        pair: &Instrument,
     ) -> Result<Orderbook> {
+        let symbol = pair.to_exchange_symbol("bybit");
 
         let mut v_bids = Vec::new();
         let mut v_asks = Vec::new();
@@ -93,15 +96,17 @@ impl BybitClient {
         for bid in response.result.bids {
             
             let price =
-                f64::from_str(&bid.price).map_err(|e| ExchangeError::ApiError {
+                Decimal::from_str(&bid.price).map_err(|e| ExchangeError::ApiError {
                     exchange: "Coinbase".to_string(),
                     message: format!("Invalid bid price '{}': {}", bid.price, e),
+                    code: None,
                 })?;
 
             let quantity =
-                f64::from_str(&bid.qty).map_err(|e| ExchangeError::ApiError {
+                Decimal::from_str(&bid.qty).map_err(|e| ExchangeError::ApiError {
                     exchange: "Coinbase".to_string(),
                     message: format!("Invalid bid size '{}': {}", bid.qty, e),
+                    code: None,
                 })?;
 
             v_bids.push(PriceLevel {
@@ -115,15 +120,17 @@ impl BybitClient {
         for ask in response.result.asks {
 
             let price =
-                f64::from_str(&ask.price).map_err(|e| ExchangeError::ApiError {
+                Decimal::from_str(&ask.price).map_err(|e| ExchangeError::ApiError {
                     exchange: "Coinbase".to_string(),
                     message: format!("Invalid bid price '{}': {}", ask.price, e),
+                    code: None,
                 })?;
 
             let quantity =
-                f64::from_str(&ask.qty).map_err(|e| ExchangeError::ApiError {
+                Decimal::from_str(&ask.qty).map_err(|e| ExchangeError::ApiError {
                     exchange: "Coinbase".to_string(),
                     message: format!("Invalid bid size '{}': {}", ask.qty, e),
+                    code: None,
                 })?;
 
             v_asks.push(PriceLevel {
@@ -141,12 +148,14 @@ impl BybitClient {
             v_asks,
             None,
             None,
-        );
+        )
+        .with_registered_tick_size(pair);
 
         if !orderbook.is_valid() {
             return Err(ExchangeError::ApiError {
                 exchange: "Bybit".to_string(),
                 message: "Received invalidad orderbook data".to_string(),
+                code: None,
             });
         }
 
@@ -163,6 +172,84 @@ impl BybitClient {
 
     }
 
+    /// Get the public instrument info of a category (`spot`, `linear`)
+    pub async fn get_instruments_info(
+        &self,
+        category: &str,
+    ) -> Result<Vec<BybitInstrumentSummary>> {
+        debug!("Fetching Bybit {} instruments", category);
+
+        let mut instruments = Vec::new();
+        let mut cursor = String::new();
+
+        loop {
+            let mut params = vec![("category", category), ("limit", "1000")];
+            if !cursor.is_empty() {
+                params.push(("cursor", cursor.as_str()));
+            }
+
+            let response: BybitInstrumentsResponse = self
+                .client
+                .get_with_params_retry("/v5/market/instruments-info", &params)
+                .await?;
+
+            if response.ret_code != 0 {
+                return Err(ExchangeError::ApiError {
+                    exchange: "Bybit".to_string(),
+                    message: format!(
+                        "Bybit API Error\n Code: {:?} Message: {:?}",
+                        response.ret_code, response.ret_msg,
+                    ),
+                    code: i64::try_from(response.ret_code).ok(),
+                });
+            }
+
+            instruments.extend(response.result.list);
+
+            match response.result.next_page_cursor {
+                Some(next) if !next.is_empty() => cursor = next,
+                _ => break,
+            }
+        }
+
+        Ok(instruments)
+    }
+
+    /// List the trading spot pairs and linear perpetuals, with their symbols
+    pub async fn list_instruments(&self) -> Result<Vec<ListedInstrument>> {
+        let mut listed = Vec::new();
+
+        for instrument in self.get_instruments_info("spot").await? {
+            if instrument.status == "Trading" {
+                let tick_size = instrument.tick_size();
+                listed.push(
+                    ListedInstrument::new(
+                        Instrument::spot(&instrument.base_coin, &instrument.quote_coin),
+                        instrument.symbol,
+                    )
+                    .with_tick_size(tick_size),
+                );
+            }
+        }
+
+        for instrument in self.get_instruments_info("linear").await? {
+            if instrument.status == "Trading"
+                && instrument.contract_type.as_deref() == Some("LinearPerpetual")
+            {
+                let tick_size = instrument.tick_size();
+                listed.push(
+                    ListedInstrument::new(
+                        Instrument::perpetual(&instrument.base_coin, &instrument.quote_coin),
+                        instrument.symbol,
+                    )
+                    .with_tick_size(tick_size),
+                );
+            }
+        }
+
+        Ok(listed)
+    }
+
     /// Get Bybit server time
     pub async fn get_server_time(&self) -> Result<BybitServerTime> {
         debug!("Fetching Bybit Server Time");
@@ -183,6 +270,7 @@ impl BybitClient {
                     response.ret_ext_info,
                     response.time,
                 ),
+                code: i64::try_from(response.ret_code).ok(),
             });
         }
 
@@ -207,6 +295,7 @@ impl BybitClient {
                     "Bybit API Error\n Code: {:?} Message: {:?}",
                     response.ret_code, response.ret_msg,
                 ),
+                code: i64::try_from(response.ret_code).ok(),
             });
         }
         Ok(response.result)
@@ -231,12 +320,50 @@ impl BybitClient {
                     "Bybit API Error\n Code: {:?} Message: {:?}",
                     response.ret_code, response.ret_msg,
                 ),
+                code: i64::try_from(response.ret_code).ok(),
             });
         }
         Ok(response.result)
     }
 }
 
+/// Bybit public instruments info response
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BybitInstrumentsResponse {
+    ret_code: u64,
+    ret_msg: String,
+    result: BybitInstrumentsResult,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BybitInstrumentsResult {
+    list: Vec<BybitInstrumentSummary>,
+    next_page_cursor: Option<String>,
+}
+
+/// Bybit instrument, common fields of the spot and derivatives categories
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BybitInstrumentSummary {
+    pub symbol: String,
+    pub base_coin: String,
+    pub quote_coin: String,
+    pub status: String,
+    /// `LinearPerpetual`, `LinearFutures`, ... for derivatives
+    pub contract_type: Option<String>,
+    pub price_filter: Option<responses::instruments::PriceFilter>,
+}
+
+impl BybitInstrumentSummary {
+    /// Minimum price increment, when reported
+    pub fn tick_size(&self) -> Option<Decimal> {
+        let filter = self.price_filter.as_ref()?;
+        Decimal::from_str(&filter.tick_size).ok()
+    }
+}
+
 /// Bybit server time
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -0,0 +1,251 @@
+// ix-cex/src/exchanges/bybit/bybit_private_wss.rs
+use crate::client::private::timestamp_millis;
+use crate::client::wss_client::{ConnectionEvent, WssClient, WssConfig, WssEvent};
+use crate::exchanges::STREAM_CHANNEL_CAPACITY;
+use crate::exchanges::bybit::clients::BybitPrivateClient;
+use crate::exchanges::bybit::responses::{
+    BybitPrivateStreamEvent, BybitStreamExecution, BybitStreamOrder, BybitStreamPosition,
+    WalletBalance,
+};
+use crate::results::errors::ExchangeError;
+use ix_results::errors::ExchangeError as ClientError;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use std::fmt;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{debug, error, info, warn};
+
+pub const BYBIT_PRIVATE_WS_URL: &str = "wss://stream.bybit.com/v5/private";
+pub const BYBIT_PRIVATE_TESTNET_WS_URL: &str =
+    "wss://stream-testnet.bybit.com/v5/private";
+
+/// Bybit drops connections without a ping within 20 seconds
+const PING_INTERVAL: Duration = Duration::from_secs(20);
+
+/// Time allowed for the pong before the connection is considered stale
+const PONG_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Consecutive refused authentications before giving up
+const MAX_AUTH_FAILURES: u32 = 3;
+
+/// Topics of the private stream, covering every category
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PrivateTopic {
+    Order,
+    Execution,
+    Position,
+    Wallet,
+}
+
+impl PrivateTopic {
+    pub const ALL: [PrivateTopic; 4] = [
+        PrivateTopic::Order,
+        PrivateTopic::Execution,
+        PrivateTopic::Position,
+        PrivateTopic::Wallet,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PrivateTopic::Order => "order",
+            PrivateTopic::Execution => "execution",
+            PrivateTopic::Position => "position",
+            PrivateTopic::Wallet => "wallet",
+        }
+    }
+}
+
+impl fmt::Display for PrivateTopic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Event of the private stream
+#[derive(Debug)]
+pub enum BybitPrivateEvent {
+    /// Connection lifecycle, updates sent while disconnected are lost and
+    /// should be read back from the REST endpoints
+    Connection(ConnectionEvent),
+    /// Authentication accepted, the topics are subscribed next
+    Authenticated,
+    Order(Vec<BybitStreamOrder>),
+    Execution(Vec<BybitStreamExecution>),
+    Position(Vec<BybitStreamPosition>),
+    Wallet(Vec<WalletBalance>),
+}
+
+impl BybitPrivateClient {
+    /// Authentication message of the private stream, valid until `expires`
+    /// in milliseconds
+    pub fn ws_auth_message(&self, expires: u64) -> ix_results::errors::Result<String> {
+        let api_key = self.api_key.as_ref().ok_or_else(|| {
+            ClientError::Authentication(
+                "API key is required for the private stream".to_string(),
+            )
+        })?;
+        let signature = self.sign(&format!("GET/realtime{}", expires))?;
+
+        Ok(json!({ "op": "auth", "args": [api_key, expires, signature] }).to_string())
+    }
+
+    /// Authentication message expiring after `recv_window`
+    pub(crate) fn fresh_ws_auth_message(&self) -> ix_results::errors::Result<String> {
+        self.ws_auth_message(timestamp_millis() + self.recv_window)
+    }
+
+    /// Private stream URL of the configured environment
+    pub fn private_ws_url(&self) -> &'static str {
+        if self.testnet {
+            BYBIT_PRIVATE_TESTNET_WS_URL
+        } else {
+            BYBIT_PRIVATE_WS_URL
+        }
+    }
+
+    /// Subscribe to private topics on a background task. The events end
+    /// when the receiver is dropped or the credentials are refused.
+    pub fn subscribe_private(
+        &self,
+        topics: &[PrivateTopic],
+    ) -> ix_results::errors::Result<mpsc::Receiver<BybitPrivateEvent>> {
+        // Fail here on missing credentials rather than in the task
+        self.fresh_ws_auth_message()?;
+
+        let (tx, rx) = mpsc::channel(STREAM_CHANNEL_CAPACITY);
+        let client = self.clone();
+        let topics = topics.to_vec();
+
+        tokio::spawn(async move {
+            let url = client.private_ws_url();
+            if let Err(e) = run_private_websocket_client(client, url, tx, topics).await {
+                error!("Bybit private stream failed: {}", e);
+            }
+        });
+
+        Ok(rx)
+    }
+}
+
+/// Authenticate on every connection, subscribe to `topics` once accepted and
+/// forward the typed events to `tx`
+pub async fn run_private_websocket_client(
+    client: BybitPrivateClient,
+    base_url: &str,
+    tx: mpsc::Sender<BybitPrivateEvent>,
+    topics: Vec<PrivateTopic>,
+) -> Result<(), ExchangeError> {
+    let args: Vec<&str> = topics.iter().map(PrivateTopic::as_str).collect();
+    let subscribe = json!({ "op": "subscribe", "args": args }).to_string();
+
+    let config = WssConfig::new(base_url)
+        .with_ping(PING_INTERVAL, PONG_TIMEOUT)
+        .with_text_ping(json!({ "op": "ping" }).to_string());
+
+    // Signed on every connect, a replayed message would have expired
+    let login_client = client.clone();
+    let mut connection = WssClient::new(config)
+        .with_login(move || login_client.fresh_ws_auth_message())
+        .spawn();
+    let handle = connection.handle();
+    let mut auth_failures: u32 = 0;
+
+    loop {
+        let event = tokio::select! {
+            event = connection.recv() => event,
+            _ = tx.closed() => return Ok(()),
+        };
+        let Some(event) = event else {
+            break;
+        };
+
+        let text = match event {
+            WssEvent::Connection(event) => {
+                info!("Bybit private WebSocket {}", event);
+                if tx.send(BybitPrivateEvent::Connection(event)).await.is_err() {
+                    return Ok(());
+                }
+                continue;
+            }
+            WssEvent::Text(text) => text,
+        };
+
+        let value: Value = match serde_json::from_str(&text) {
+            Ok(value) => value,
+            Err(e) => {
+                warn!("Failed to parse message: {}. Text: {}", e, text);
+                continue;
+            }
+        };
+
+        // Operation responses (auth, subscribe, pong) carry no topic
+        if value.get("topic").is_none() {
+            let success = value.get("success").and_then(Value::as_bool);
+            match (value.get("op").and_then(Value::as_str), success) {
+                (Some("auth"), Some(true)) => {
+                    auth_failures = 0;
+                    if tx.send(BybitPrivateEvent::Authenticated).await.is_err() {
+                        return Ok(());
+                    }
+                    handle.send(subscribe.clone()).await?;
+                }
+                (Some("auth"), _) => {
+                    auth_failures += 1;
+                    warn!("Bybit authentication refused: {}", text);
+                    if auth_failures >= MAX_AUTH_FAILURES {
+                        return Err(ClientError::Authentication(format!(
+                            "Bybit private stream refused {} authentications: {}",
+                            auth_failures, text
+                        ))
+                        .into());
+                    }
+                    // Usually an expired signature, sign again
+                    handle.send(client.fresh_ws_auth_message()?).await?;
+                }
+                (_, Some(false)) => warn!("Bybit operation failed: {}", text),
+                _ => debug!("Bybit operation response: {}", text),
+            }
+            continue;
+        }
+
+        match parse_private_event(value) {
+            Ok(Some(event)) => {
+                if tx.send(event).await.is_err() {
+                    error!("Receiver dropped. Shutting down private websocket client.");
+                    return Ok(());
+                }
+            }
+            Ok(None) => debug!("Unknown Bybit private topic: {}", text),
+            Err(e) => warn!("Failed to deserialize message: {}. Text: {}", e, text),
+        }
+    }
+
+    warn!("Private WebSocket client loop terminated.");
+    connection.finish().await
+}
+
+/// Typed event of a private topic message, `None` for unknown topics
+pub fn parse_private_event(
+    value: Value,
+) -> Result<Option<BybitPrivateEvent>, serde_json::Error> {
+    fn data<T: DeserializeOwned>(value: Value) -> Result<T, serde_json::Error> {
+        serde_json::from_value::<BybitPrivateStreamEvent<T>>(value)
+            .map(|event| event.data)
+    }
+
+    // Category topics such as `order.linear` share the data of `order`
+    let topic = value
+        .get("topic")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let event = match topic.split('.').next().unwrap_or_default() {
+        "order" => BybitPrivateEvent::Order(data(value)?),
+        "execution" => BybitPrivateEvent::Execution(data(value)?),
+        "position" => BybitPrivateEvent::Position(data(value)?),
+        "wallet" => BybitPrivateEvent::Wallet(data(value)?),
+        _ => return Ok(None),
+    };
+
+    Ok(Some(event))
+}
@@ -0,0 +1,338 @@
+// ix-cex/src/exchanges/bybit/bybit_trade_wss.rs
+use crate::client::private::timestamp_millis;
+use crate::client::wss_client::{
+    ConnectionEvent, WssClient, WssConfig, WssConnection, WssEvent, WssHandle,
+};
+use crate::exchanges::bybit::clients::BybitPrivateClient;
+use crate::exchanges::bybit::methods::orders::check_ret_code;
+use crate::exchanges::bybit::order_entry::OrderEntry;
+use crate::exchanges::bybit::requests::orders::{
+    AmendRequest, CancelRequest, OrderRequest,
+};
+use crate::exchanges::bybit::responses::{TradeResponse, TradeWsResponse};
+use ix_results::errors::{ExchangeError, Result};
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{oneshot, watch};
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+use tracing::{debug, error, info, warn};
+
+pub const BYBIT_TRADE_WS_URL: &str = "wss://stream.bybit.com/v5/trade";
+pub const BYBIT_TRADE_TESTNET_WS_URL: &str = "wss://stream-testnet.bybit.com/v5/trade";
+
+/// Bybit drops connections without a ping within 20 seconds
+const PING_INTERVAL: Duration = Duration::from_secs(20);
+
+/// Time allowed for the pong before the connection is considered stale
+const PONG_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Consecutive refused authentications before giving up
+const MAX_AUTH_FAILURES: u32 = 3;
+
+/// Time allowed for the authentication and then for the response of a
+/// request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Requests waiting for their response, by `reqId`
+type Pending = Arc<Mutex<HashMap<String, oneshot::Sender<TradeWsResponse>>>>;
+
+/// Trade WebSocket request, `args` holds the same body as the REST request
+pub fn trade_message<B: Serialize + ?Sized>(
+    req_id: &str,
+    op: &str,
+    request: &B,
+    timestamp: u64,
+    recv_window: u64,
+) -> Result<String> {
+    let message = json!({
+        "reqId": req_id,
+        "header": {
+            "X-BAPI-TIMESTAMP": timestamp.to_string(),
+            "X-BAPI-RECV-WINDOW": recv_window.to_string(),
+        },
+        "op": op,
+        "args": [serde_json::to_value(request)?],
+    });
+    Ok(message.to_string())
+}
+
+/// Order entry over the Bybit trade WebSocket.
+///
+/// The connection is authenticated on every (re)connect with the API key of
+/// the [`BybitPrivateClient`]. Requests wait for the authentication and are
+/// matched to their response by `reqId`, so they may be sent concurrently.
+/// A request in flight when the connection drops fails with a timeout, its
+/// order may or may not have been placed.
+pub struct BybitTradeClient {
+    handle: WssHandle,
+    pending: Pending,
+    authenticated: watch::Receiver<bool>,
+    next_id: AtomicU64,
+    /// Prefix of the `reqId`, unique per client
+    id_prefix: String,
+    recv_window: u64,
+    request_timeout: Duration,
+    task: JoinHandle<()>,
+}
+
+impl BybitTradeClient {
+    /// Connect to the trade WebSocket of the configured environment
+    pub fn connect(client: &BybitPrivateClient) -> Result<Self> {
+        let url = if client.testnet {
+            BYBIT_TRADE_TESTNET_WS_URL
+        } else {
+            BYBIT_TRADE_WS_URL
+        };
+        Self::connect_url(client, url)
+    }
+
+    /// Connect to the trade WebSocket at `url`
+    pub fn connect_url(client: &BybitPrivateClient, url: &str) -> Result<Self> {
+        // Fail here on missing credentials rather than in the task
+        client.fresh_ws_auth_message()?;
+
+        let config = WssConfig::new(url)
+            .with_ping(PING_INTERVAL, PONG_TIMEOUT)
+            .with_text_ping(json!({ "op": "ping" }).to_string());
+
+        let login_client = client.clone();
+        let connection = WssClient::new(config)
+            .with_login(move || login_client.fresh_ws_auth_message())
+            .spawn();
+
+        let handle = connection.handle();
+        let pending: Pending = Arc::new(Mutex::new(HashMap::new()));
+        let (authenticated_tx, authenticated) = watch::channel(false);
+        let task = tokio::spawn(dispatch(
+            connection,
+            client.clone(),
+            pending.clone(),
+            authenticated_tx,
+        ));
+
+        Ok(Self {
+            handle,
+            pending,
+            authenticated,
+            next_id: AtomicU64::new(1),
+            id_prefix: format!("ix-{}", timestamp_millis()),
+            recv_window: client.recv_window,
+            request_timeout: REQUEST_TIMEOUT,
+            task,
+        })
+    }
+
+    pub fn with_request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = request_timeout;
+        self
+    }
+
+    /// Whether the connection is currently authenticated
+    pub fn is_authenticated(&self) -> bool {
+        *self.authenticated.borrow()
+    }
+
+    /// Place an order, see [`BybitPrivateClient::place_order`]
+    pub async fn place_order(&self, request: &OrderRequest) -> Result<TradeWsResponse> {
+        info!(
+            "WS Bybit {} {} {} {}",
+            request.order_type, request.side, request.qty, request.symbol
+        );
+        self.request("order.create", request).await
+    }
+
+    /// Amend an open order, see [`BybitPrivateClient::amend_order`]
+    pub async fn amend_order(&self, request: &AmendRequest) -> Result<TradeWsResponse> {
+        info!("WS Bybit amend order {:?}", request.order);
+        self.request("order.amend", request).await
+    }
+
+    /// Cancel an open order, see [`BybitPrivateClient::cancel_order`]
+    pub async fn cancel_order(&self, request: &CancelRequest) -> Result<TradeWsResponse> {
+        info!("WS Bybit cancel order {:?}", request.order);
+        self.request("order.cancel", request).await
+    }
+
+    /// Send a request once authenticated and wait for its response
+    async fn request<B: Serialize + ?Sized>(
+        &self,
+        op: &str,
+        request: &B,
+    ) -> Result<TradeWsResponse> {
+        let mut authenticated = self.authenticated.clone();
+        timeout(self.request_timeout, authenticated.wait_for(|ok| *ok))
+            .await
+            .map_err(|_| {
+                ExchangeError::Timeout(
+                    "Bybit trade WebSocket not authenticated".to_string(),
+                )
+            })?
+            .map_err(|_| closed())?;
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let req_id = format!("{}-{}", self.id_prefix, id);
+        let message = trade_message(
+            &req_id,
+            op,
+            request,
+            timestamp_millis(),
+            self.recv_window,
+        )?;
+
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().unwrap().insert(req_id.clone(), tx);
+        debug!("Sending {} {}", op, req_id);
+        if self.handle.send(message).await.is_err() {
+            self.pending.lock().unwrap().remove(&req_id);
+            return Err(closed());
+        }
+
+        let response = match timeout(self.request_timeout, rx).await {
+            Ok(Ok(response)) => response,
+            // Pending requests are dropped when the connection is lost
+            Ok(Err(_)) => {
+                return Err(ExchangeError::Timeout(format!(
+                    "Bybit trade WebSocket disconnected before the response to {req_id}"
+                )));
+            }
+            Err(_) => {
+                self.pending.lock().unwrap().remove(&req_id);
+                return Err(ExchangeError::Timeout(format!(
+                    "No Bybit response to {req_id} within {:?}",
+                    self.request_timeout
+                )));
+            }
+        };
+
+        check_ret_code(response.ret_code, &response.ret_msg)?;
+        Ok(response)
+    }
+}
+
+impl Drop for BybitTradeClient {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[async_trait::async_trait]
+impl OrderEntry for BybitTradeClient {
+    async fn place_order(&self, request: &OrderRequest) -> Result<TradeResponse> {
+        BybitTradeClient::place_order(self, request)
+            .await
+            .map(TradeResponse::from)
+    }
+
+    async fn amend_order(&self, request: &AmendRequest) -> Result<TradeResponse> {
+        BybitTradeClient::amend_order(self, request)
+            .await
+            .map(TradeResponse::from)
+    }
+
+    async fn cancel_order(&self, request: &CancelRequest) -> Result<TradeResponse> {
+        BybitTradeClient::cancel_order(self, request)
+            .await
+            .map(TradeResponse::from)
+    }
+
+    fn transport(&self) -> &'static str {
+        "websocket"
+    }
+}
+
+fn closed() -> ExchangeError {
+    ExchangeError::ApiError {
+        exchange: "Bybit".to_string(),
+        message: "Trade WebSocket closed".to_string(),
+        code: None,
+    }
+}
+
+/// Track the authentication of the connection and hand every response to
+/// the request of its `reqId`
+async fn dispatch(
+    mut connection: WssConnection,
+    client: BybitPrivateClient,
+    pending: Pending,
+    authenticated: watch::Sender<bool>,
+) {
+    let handle = connection.handle();
+    let mut auth_failures: u32 = 0;
+
+    while let Some(event) = connection.recv().await {
+        let text = match event {
+            WssEvent::Connection(event) => {
+                info!("Bybit trade WebSocket {}", event);
+                authenticated.send_replace(false);
+                if matches!(event, ConnectionEvent::Disconnected { .. }) {
+                    // The responses of a lost connection never arrive
+                    pending.lock().unwrap().clear();
+                }
+                continue;
+            }
+            WssEvent::Text(text) => text,
+        };
+
+        let value: Value = match serde_json::from_str(&text) {
+            Ok(value) => value,
+            Err(e) => {
+                warn!("Failed to parse message: {}. Text: {}", e, text);
+                continue;
+            }
+        };
+
+        let ret_code = value.get("retCode").and_then(Value::as_i64);
+        let req_id = value
+            .get("reqId")
+            .and_then(Value::as_str)
+            .filter(|req_id| !req_id.is_empty());
+
+        match (value.get("op").and_then(Value::as_str), req_id) {
+            (Some("auth"), _) if ret_code == Some(0) => {
+                auth_failures = 0;
+                authenticated.send_replace(true);
+            }
+            (Some("auth"), _) => {
+                auth_failures += 1;
+                warn!("Bybit authentication refused: {}", text);
+                if auth_failures >= MAX_AUTH_FAILURES {
+                    error!(
+                        "Bybit trade WebSocket refused {} authentications",
+                        auth_failures
+                    );
+                    break;
+                }
+                // Usually an expired signature, sign again
+                let resent = match client.fresh_ws_auth_message() {
+                    Ok(message) => handle.send(message).await.is_ok(),
+                    Err(_) => false,
+                };
+                if !resent {
+                    break;
+                }
+            }
+            (_, Some(req_id)) => {
+                let sender = pending.lock().unwrap().remove(req_id);
+                match (sender, serde_json::from_value::<TradeWsResponse>(value)) {
+                    (Some(sender), Ok(response)) => {
+                        let _ = sender.send(response);
+                    }
+                    (None, _) => debug!("Response to an unknown request: {}", text),
+                    (_, Err(e)) => {
+                        warn!("Failed to deserialize response: {}. Text: {}", e, text)
+                    }
+                }
+            }
+            _ => debug!("Bybit operation response: {}", text),
+        }
+    }
+
+    // Waiting requests fail once the sender is dropped
+    authenticated.send_replace(false);
+    pending.lock().unwrap().clear();
+}
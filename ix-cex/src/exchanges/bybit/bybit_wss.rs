@@ -0,0 +1,276 @@
+// ix-cex/src/exchanges/bybit/bybit_wss.rs
+use crate::client::wss_client::{WssClient, WssConfig, WssEvent};
+use crate::exchanges::bybit::bybit_client::BybitClient;
+use crate::exchanges::bybit::responses::{
+    BybitStreamEvent, BybitStreamLiquidation, BybitStreamOrderbook, BybitStreamTrade,
+};
+use crate::exchanges::{
+    LiquidationStream, OrderbookStream, STREAM_CHANNEL_CAPACITY, TradeStream,
+    receiver_stream, timestamp_from_millis,
+};
+use crate::models::book::LevelBook;
+use crate::models::instruments::Instrument;
+use crate::models::orderbook::Orderbook;
+use crate::models::trades::{Liquidation, PublicTrade, TradeSide};
+use crate::results::errors::ExchangeError;
+use rust_decimal::prelude::ToPrimitive;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{debug, error, info, warn};
+use url::Url;
+
+pub const BYBIT_SPOT_WS_URL: &str = "wss://stream.bybit.com/v5/public/spot";
+pub const BYBIT_LINEAR_WS_URL: &str = "wss://stream.bybit.com/v5/public/linear";
+
+/// Bybit drops connections without a ping within 20 seconds
+const PING_INTERVAL: Duration = Duration::from_secs(20);
+
+/// Time allowed for the pong before the connection is considered stale
+const PONG_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Orderbook depths offered by the spot orderbook topic
+const SPOT_DEPTHS: [u32; 4] = [1, 50, 200, 1000];
+
+/// Levels per side published when no depth is requested
+const DEFAULT_DEPTH: u32 = 50;
+
+pub async fn run_websocket_client<T: DeserializeOwned>(
+    base_url: &str,
+    tx: mpsc::Sender<BybitStreamEvent<T>>,
+    topics: Vec<String>,
+) -> Result<(), ExchangeError> {
+    // let topics = [
+    //     "orderbook.50.BTCUSDT", // Orderbook snapshot + delta
+    //     "publicTrade.BTCUSDT",  // Public trades
+    // ];
+
+    Url::parse(base_url)?;
+
+    let subscribe = json!({ "op": "subscribe", "args": topics });
+    let config = WssConfig::new(base_url)
+        .with_ping(PING_INTERVAL, PONG_TIMEOUT)
+        .with_text_ping(json!({ "op": "ping" }).to_string());
+
+    let mut connection = WssClient::new(config)
+        .with_subscription(subscribe.to_string())
+        .spawn();
+
+    // Main message processing loop
+    while let Some(event) = connection.recv().await {
+        let text = match event {
+            WssEvent::Connection(event) => {
+                info!("Bybit WebSocket {}", event);
+                continue;
+            }
+            WssEvent::Text(text) => text,
+        };
+
+        let value: serde_json::Value = match serde_json::from_str(&text) {
+            Ok(value) => value,
+            Err(e) => {
+                warn!("Failed to parse message: {}. Text: {}", e, text);
+                continue;
+            }
+        };
+
+        // Operation responses (subscribe, pong) carry no topic
+        if value.get("topic").is_none() {
+            if value.get("success").and_then(|s| s.as_bool()) == Some(false) {
+                warn!("Bybit operation failed: {}", text);
+            } else {
+                debug!("Bybit operation response: {}", text);
+            }
+            continue;
+        }
+
+        match serde_json::from_value::<BybitStreamEvent<T>>(value) {
+            Ok(event) => {
+                if tx.send(event).await.is_err() {
+                    error!("Receiver dropped. Shutting down websocket client.");
+                    return Ok(());
+                }
+            }
+            Err(e) => warn!("Failed to deserialize message: {}. Text: {}", e, text),
+        }
+    }
+
+    warn!("WebSocket client loop terminated.");
+    connection.finish().await
+}
+
+impl BybitClient {
+    /// Stream the spot order book maintained from orderbook snapshots and deltas
+    pub fn subscribe_orderbook(
+        &self,
+        pair: Instrument,
+        depth: Option<u32>,
+    ) -> ix_results::errors::Result<OrderbookStream> {
+        let depth = depth.unwrap_or(DEFAULT_DEPTH);
+        let topic_depth = SPOT_DEPTHS
+            .into_iter()
+            .find(|d| *d >= depth)
+            .unwrap_or(SPOT_DEPTHS[SPOT_DEPTHS.len() - 1]);
+        let topic = format!(
+            "orderbook.{}.{}",
+            topic_depth,
+            pair.to_exchange_symbol("bybit")
+        );
+
+        let (event_tx, mut event_rx) = mpsc::channel::<
+            BybitStreamEvent<BybitStreamOrderbook>,
+        >(STREAM_CHANNEL_CAPACITY);
+        let (tx, rx) = mpsc::channel(STREAM_CHANNEL_CAPACITY);
+
+        tokio::spawn(async move {
+            if let Err(e) =
+                run_websocket_client(BYBIT_SPOT_WS_URL, event_tx, vec![topic]).await
+            {
+                error!("Bybit orderbook stream failed: {}", e);
+            }
+        });
+
+        tokio::spawn(async move {
+            let mut book = LevelBook::new();
+            while let Some(event) = event_rx.recv().await {
+                let orderbook = apply_orderbook_event(&mut book, &event, depth as usize)
+                    .with_registered_tick_size(&pair);
+                if tx.send(orderbook).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(receiver_stream(rx))
+    }
+
+    /// Stream spot public trades
+    pub fn subscribe_trades(
+        &self,
+        pair: Instrument,
+    ) -> ix_results::errors::Result<TradeStream> {
+        let topic = format!("publicTrade.{}", pair.to_exchange_symbol("bybit"));
+
+        let (event_tx, mut event_rx) = mpsc::channel::<
+            BybitStreamEvent<Vec<BybitStreamTrade>>,
+        >(STREAM_CHANNEL_CAPACITY);
+        let (tx, rx) = mpsc::channel(STREAM_CHANNEL_CAPACITY);
+
+        tokio::spawn(async move {
+            if let Err(e) =
+                run_websocket_client(BYBIT_SPOT_WS_URL, event_tx, vec![topic]).await
+            {
+                error!("Bybit trade stream failed: {}", e);
+            }
+        });
+
+        tokio::spawn(async move {
+            while let Some(event) = event_rx.recv().await {
+                for trade in &event.data {
+                    let Some(trade) = to_public_trade(trade) else {
+                        warn!("Unknown Bybit trade side: {}", trade.side);
+                        continue;
+                    };
+                    if tx.send(trade).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(receiver_stream(rx))
+    }
+
+    /// Stream liquidations of the linear perpetual
+    pub fn subscribe_liquidations(
+        &self,
+        pair: Instrument,
+    ) -> ix_results::errors::Result<LiquidationStream> {
+        let topic = format!("allLiquidation.{}", pair.to_exchange_symbol("bybit"));
+
+        let (event_tx, mut event_rx) = mpsc::channel::<
+            BybitStreamEvent<Vec<BybitStreamLiquidation>>,
+        >(STREAM_CHANNEL_CAPACITY);
+        let (tx, rx) = mpsc::channel(STREAM_CHANNEL_CAPACITY);
+
+        tokio::spawn(async move {
+            let result =
+                run_websocket_client(BYBIT_LINEAR_WS_URL, event_tx, vec![topic]).await;
+            if let Err(e) = result {
+                error!("Bybit liquidation stream failed: {}", e);
+            }
+        });
+
+        tokio::spawn(async move {
+            while let Some(event) = event_rx.recv().await {
+                for liquidation in &event.data {
+                    let Some(liquidation) = to_liquidation(liquidation) else {
+                        warn!("Unknown Bybit liquidation side: {}", liquidation.side);
+                        continue;
+                    };
+                    if tx.send(liquidation).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(receiver_stream(rx))
+    }
+}
+
+/// Apply an orderbook snapshot or delta and return the updated book.
+/// A delta with update id 1 is a snapshot sent after a service restart.
+pub fn apply_orderbook_event(
+    book: &mut LevelBook,
+    event: &BybitStreamEvent<BybitStreamOrderbook>,
+    depth: usize,
+) -> Orderbook {
+    let data = &event.data;
+
+    if event.event_type == "snapshot" || data.update_id == 1 {
+        book.clear();
+    }
+
+    for (price, qty) in &data.bids {
+        book.update_bid(*price, *qty);
+    }
+    for (price, qty) in &data.asks {
+        book.update_ask(*price, *qty);
+    }
+
+    book.to_orderbook(
+        &data.symbol,
+        "Bybit",
+        timestamp_from_millis(event.ts),
+        Some(depth),
+        Some(data.update_id),
+        Some(data.seq),
+    )
+}
+
+/// Normalize a public trade, the side is the taker side
+pub fn to_public_trade(trade: &BybitStreamTrade) -> Option<PublicTrade> {
+    Some(PublicTrade {
+        symbol: trade.symbol.clone(),
+        exchange: "Bybit".to_string(),
+        timestamp: timestamp_from_millis(trade.timestamp),
+        side: TradeSide::parse_from_str(&trade.side)?,
+        price: trade.price.to_f64().unwrap_or_default(),
+        amount: trade.size.to_f64().unwrap_or_default(),
+        trade_id: trade.trade_id.clone(),
+    })
+}
+
+/// Normalize a liquidation, the side is the side of the liquidated position
+pub fn to_liquidation(liquidation: &BybitStreamLiquidation) -> Option<Liquidation> {
+    Some(Liquidation {
+        symbol: liquidation.symbol.clone(),
+        exchange: "Bybit".to_string(),
+        timestamp: timestamp_from_millis(liquidation.timestamp),
+        side: TradeSide::parse_from_str(&liquidation.side)?,
+        price: liquidation.price.to_f64().unwrap_or_default(),
+        amount: liquidation.size.to_f64().unwrap_or_default(),
+    })
+}
@@ -1,20 +1,14 @@
 // private
 use crate::{
-    client::http_client::{HttpClient, RequestType, RetryConfig, RetryableHttpClient},
+    client::http_client::{RequestType, RetryableHttpClient},
+    client::private::{self, timestamp_millis},
     exchanges::bybit::configs::BybitConfig,
 };
 
-use std::{env, path::Path};
-
-use config::{Config, ConfigError};
-use hmac::{Hmac, Mac};
 use ix_results::errors::{ExchangeError, Result};
-use serde::Deserialize;
-use sha2::Sha256;
+use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
-use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::{debug, info};
-type HmacSha256 = Hmac<Sha256>;
 
 /// Bybit REST API client with private endpoint support
 #[derive(Clone)]
@@ -36,58 +30,25 @@ impl BybitPrivateClient {
 
     /// Create a new Bybit client with optional private credentials
     pub fn new() -> Result<Self> {
-
-        // --- Setup working directory
-        let manifest_dir = env!("CARGO_MANIFEST_DIR");
-        let workspace_root = Path::new(manifest_dir)
-            .parent()
-            .expect("Failed to get workspace root");
-
-        // --- File with Client Configuration (toml)
-        let client_file = workspace_root
-            .join("ix-cex")
-            .join("config")
-            .join("bybit.toml")
-            .to_str()
-            .unwrap()
-            .to_owned();
-
-        Self::from_config(&client_file)
-
+        Self::from_config(&private::config_path("bybit.toml"))
     }
 
     /// Create Bybit client from configuration file
     pub fn from_config(config_path: &str) -> Result<Self> {
-        let settings = Config::builder()
-            .add_source(config::File::with_name(config_path))
-            .build()
-            .map_err(|e: ConfigError| ExchangeError::Configuration {
-                message: format!("Failed to load Bybit config: {}", e),
-            })?;
-
-        let config: BybitConfig =
-            settings.try_deserialize().map_err(|e: ConfigError| {
-                ExchangeError::Configuration {
-                    message: format!("Failed to parse Bybit config: {}", e),
-                }
-            })?;
+        let config: BybitConfig = private::load_config(config_path, "Bybit")?;
 
         let base_url = if config.api.as_ref().and_then(|a| a.testnet).unwrap_or(false) {
             config.exchange.testnet_url
         } else {
             config.exchange.base_url
         };
-        let http_client = HttpClient::new(
-            "Bybit".to_string(),
-            base_url,
-            10, // 10 requests per second to stay under limits
-            config.collection.timeout_seconds,
-        )?;
-
-        let retry_client = RetryableHttpClient::new(http_client, RetryConfig::default());
 
         Ok(Self {
-            client: retry_client,
+            client: private::http_client(
+                "Bybit",
+                base_url,
+                config.collection.timeout_seconds,
+            )?,
             api_key: config.api.as_ref().map(|a| a.api_key.clone()),
             api_secret: config.api.as_ref().map(|a| a.api_secret.clone()),
             recv_window: config
@@ -111,13 +72,8 @@ impl BybitPrivateClient {
             "https://api.bybit.com"
         };
 
-        let http_client =
-            HttpClient::new("Bybit".to_string(), base_url.to_string(), 10, 30)?;
-
-        let retry_client = RetryableHttpClient::new(http_client, RetryConfig::default());
-
         Ok(Self {
-            client: retry_client,
+            client: private::http_client("Bybit", base_url.to_string(), 30)?,
             api_key: Some(api_key),
             api_secret: Some(api_secret),
             recv_window: 5000,
@@ -125,14 +81,6 @@ impl BybitPrivateClient {
         })
     }
 
-    /// Generate timestamp for API requests
-    fn get_timestamp() -> u64 {
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_millis() as u64
-    }
-
     /// Generate HMAC-SHA256 signature for authenticated requests
     fn create_signature(
         &self,
@@ -141,28 +89,19 @@ impl BybitPrivateClient {
         recv_window: u64,
         param_str: &str, // This is the string to be signed
     ) -> Result<String> {
-        // Get API_SECRET
-        let api_secret = self.api_secret.as_ref().ok_or_else(|| {
-            ExchangeError::Authentication(
-                "API secret is required for private endpoints".to_string(),
-            )
-        })?;
-
         // The string to sign is: timestamp + api_key + recv_window + param_str
         let full_param_str =
             format!("{}{}{}{}", timestamp, api_key, recv_window, param_str);
 
         debug!("String to sign: {}", full_param_str); // Essential for debugging
 
-        let mut mac = HmacSha256::new_from_slice(api_secret.as_bytes()).map_err(|e| {
-            ExchangeError::Authentication(format!("Invalid API secret: {}", e))
-        })?;
-
-        mac.update(full_param_str.as_bytes());
-        let result = mac.finalize();
-        let code_bytes = result.into_bytes();
+        self.sign(&full_param_str)
+    }
 
-        Ok(hex::encode(code_bytes))
+    /// Hex encoded HMAC-SHA256 of `payload` with the API secret, shared by
+    /// the REST requests and the private WebSocket authentication
+    pub(crate) fn sign(&self, payload: &str) -> Result<String> {
+        private::sign(self.api_secret.as_deref(), payload)
     }
 
     /// Make authenticated GET request to private endpoint
@@ -175,7 +114,7 @@ impl BybitPrivateClient {
     where
         T: for<'de> Deserialize<'de>,
     {
-        let timestamp = Self::get_timestamp();
+        let timestamp = timestamp_millis();
         let api_key = self.api_key.as_ref().ok_or_else(|| {
             ExchangeError::Authentication(
                 "API key is required for private endpoints".to_string(),
@@ -239,6 +178,46 @@ impl BybitPrivateClient {
             .await
     }
 
+    /// Make authenticated POST request with a JSON body, for the endpoints
+    /// taking nested or non string parameters
+    pub async fn request_private_json<T, B>(&self, endpoint: &str, body: &B) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+        B: Serialize + ?Sized,
+    {
+        let timestamp = timestamp_millis();
+        let api_key = self.api_key.as_ref().ok_or_else(|| {
+            ExchangeError::Authentication(
+                "API key is required for private endpoints".to_string(),
+            )
+        })?;
+
+        // The signed string is the body as sent
+        let body = serde_json::to_string(body)?;
+        let signature =
+            self.create_signature(timestamp, api_key, self.recv_window, &body)?;
+
+        let str_timestamp = timestamp.to_string();
+        let str_recv = self.recv_window.to_string();
+
+        let mut headers = HashMap::new();
+        headers.insert("X-BAPI-API-KEY", api_key.as_str());
+        headers.insert("X-BAPI-TIMESTAMP", &str_timestamp);
+        headers.insert("X-BAPI-SIGN", &signature);
+        headers.insert("X-BAPI-RECV-WINDOW", &str_recv);
+        headers.insert("Content-Type", "application/json");
+
+        info!("Making authenticated POST request to: {}", endpoint);
+        debug!("Body for signing: {}", body);
+
+        // Sent once, the POST endpoints place, amend and cancel orders and a
+        // resend after an unanswered attempt could repeat them
+        self.client
+            .client()
+            .post_body_with_headers(endpoint, &body, headers)
+            .await
+    }
+
     /// Make HTTP request with custom headers (for private endpoints)
     async fn client_with_headers<T>(
         &self,
@@ -257,10 +236,11 @@ impl BybitPrivateClient {
                     .get_with_headers_retry(endpoint, params, headers)
                     .await
             }
-            // POST
+            // POST, sent once as the order requests of `request_private_json`
             RequestType::Post => {
                 self.client
-                    .post_with_headers_retry(endpoint, params, headers)
+                    .client()
+                    .post_with_headers(endpoint, params, headers)
                     .await
             }
         }
@@ -10,3 +10,4 @@ pub mod account;
 pub mod instrument;
 pub mod orders;
 
+pub mod positions;
@@ -1,14 +1,21 @@
 // private
 use crate::exchanges::bybit::{
     clients::BybitPrivateClient,
-    responses::{OrderResponse, TradeResponse},
+    requests::orders::{
+        AmendRequest, BatchRequest, CancelRequest, Category, OrderRequest,
+        without_category,
+    },
+    requests::positions::PageQuery,
+    responses::{BatchResponse, OrderResponse, TradeResponse, orders::OrderBybit},
 };
 
 use crate::{
     client::http_client::RequestType, exchanges::bybit::responses::CancelResponse,
 };
 
+use futures::TryStreamExt;
 use ix_results::errors::{ExchangeError, Result};
+use serde::Serialize;
 use tracing::info;
 
 impl BybitPrivateClient {
@@ -42,19 +49,19 @@ impl BybitPrivateClient {
         let request_type = RequestType::Post;
 
         if let Some(symbol) = p_symbol {
-            params.push(("p_symbol", symbol));
+            params.push(("symbol", symbol));
         }
 
         if let Some(base_coin) = p_base_coin {
-            params.push(("p_base_coin", base_coin));
+            params.push(("baseCoin", base_coin));
         }
 
         if let Some(settle_coin) = p_settle_coin {
-            params.push(("p_settle_coin", settle_coin));
+            params.push(("settleCoin", settle_coin));
         }
 
         if let Some(order_filter) = p_order_filter {
-            params.push(("p_order_filter", order_filter));
+            params.push(("orderFilter", order_filter));
         }
 
         self
@@ -62,6 +69,52 @@ impl BybitPrivateClient {
             .await
     }
 
+    /// Get an Order by its orderLinkId, open or recently closed
+    pub async fn get_order_by_link_id(
+        &self,
+        p_category: &str,
+        p_order_link_id: &str,
+    ) -> Result<OrderResponse> {
+        info!("Fetching Bybit order {}", p_order_link_id);
+        let params = vec![("category", p_category), ("orderLinkId", p_order_link_id)];
+        let request_type = RequestType::Get;
+
+        self
+            .request_private("/v5/order/realtime", &params, request_type)
+            .await
+    }
+
+    /// Get an Order by its orderId, open or recently closed
+    pub async fn get_order_by_id(
+        &self,
+        p_category: &str,
+        p_order_id: &str,
+    ) -> Result<OrderResponse> {
+        info!("Fetching Bybit order {}", p_order_id);
+        let params = vec![("category", p_category), ("orderId", p_order_id)];
+        let request_type = RequestType::Get;
+
+        self
+            .request_private("/v5/order/realtime", &params, request_type)
+            .await
+    }
+
+    /// Get the Open Orders of a symbol or, for derivatives, of a settle coin,
+    /// following `next_page_cursor` until the last page
+    pub async fn get_open_orders(
+        &self,
+        p_category: Category,
+        p_symbol: Option<&str>,
+        p_settle_coin: Option<&str>,
+    ) -> Result<Vec<OrderBybit>> {
+        info!("Fetching Bybit open orders");
+        let mut query = PageQuery::new(p_category);
+        query.symbol = p_symbol.map(str::to_string);
+        query.settle_coin = p_settle_coin.map(str::to_string);
+
+        self.pages("/v5/order/realtime", query).try_concat().await
+    }
+
     /// Open a New Order
     pub async fn new_order(
         &self,
@@ -73,8 +126,6 @@ impl BybitPrivateClient {
     ) -> Result<TradeResponse> {
         info!("Post Bybit New Order");
 
-        let p_endpoint = "/v5/order/create".to_string();
-
         let p_params = vec![
             ("category", p_category),
             ("symbol", p_symbol),
@@ -83,22 +134,180 @@ impl BybitPrivateClient {
             ("qty", p_qty),
         ];
 
+        self.create_order(&p_params).await
+    }
+
+    async fn create_order(&self, p_params: &[(&str, &str)]) -> Result<TradeResponse> {
+        let p_endpoint = "/v5/order/create".to_string();
+
         let p_request_type = RequestType::Post;
         let response: TradeResponse = self
-            .request_private(&p_endpoint, &p_params, p_request_type)
+            .request_private(&p_endpoint, p_params, p_request_type)
+            .await?;
+
+        check_ret_code(response.ret_code, &response.ret_msg)?;
+        Ok(response)
+    }
+
+    /// Place an order
+    pub async fn place_order(&self, p_request: &OrderRequest) -> Result<TradeResponse> {
+        info!(
+            "Post Bybit {} {} {} {}",
+            p_request.order_type, p_request.side, p_request.qty, p_request.symbol
+        );
+
+        let response: TradeResponse = self
+            .request_private_json("/v5/order/create", p_request)
+            .await?;
+
+        check_ret_code(response.ret_code, &response.ret_msg)?;
+        Ok(response)
+    }
+
+    /// Amend the quantity, price, trigger or TP/SL of an open order
+    pub async fn amend_order(&self, p_request: &AmendRequest) -> Result<TradeResponse> {
+        info!("Post Bybit amend order {:?}", p_request.order);
+
+        let response: TradeResponse = self
+            .request_private_json("/v5/order/amend", p_request)
             .await?;
 
-        if response.ret_code != 0 {
-            return Err(ExchangeError::ApiError {
-                exchange: "Bybit".to_string(),
-                message: format!(
-                    "Bybit API Error\n Code: {:?} Message: {:?}",
-                    response.ret_code, response.ret_msg,
-                ),
-            });
+        check_ret_code(response.ret_code, &response.ret_msg)?;
+        Ok(response)
+    }
+
+    /// Cancel an open order
+    pub async fn cancel_order(&self, p_request: &CancelRequest) -> Result<TradeResponse> {
+        info!("Post Bybit cancel order {:?}", p_request.order);
+
+        let response: TradeResponse = self
+            .request_private_json("/v5/order/cancel", p_request)
+            .await?;
+
+        check_ret_code(response.ret_code, &response.ret_msg)?;
+        Ok(response)
+    }
+
+    /// Place up to 10 spot or 20 derivatives orders of one category, the
+    /// outcome of each order is in [`BatchResponse::orders`]
+    pub async fn batch_place_orders(
+        &self,
+        p_category: Category,
+        p_requests: &[OrderRequest],
+    ) -> Result<BatchResponse> {
+        info!("Post Bybit batch of {} orders", p_requests.len());
+
+        let body = batch_body(p_category, p_requests, |r| r.category)?;
+        let response: BatchResponse = self
+            .request_private_json("/v5/order/create-batch", &body)
+            .await?;
+
+        check_ret_code(response.ret_code, &response.ret_msg)?;
+        Ok(response)
+    }
+
+    /// Cancel up to 10 spot or 20 derivatives orders of one category
+    pub async fn batch_cancel_orders(
+        &self,
+        p_category: Category,
+        p_requests: &[CancelRequest],
+    ) -> Result<BatchResponse> {
+        info!("Post Bybit batch cancel of {} orders", p_requests.len());
+
+        let body = batch_body(p_category, p_requests, |r| r.category)?;
+        let response: BatchResponse = self
+            .request_private_json("/v5/order/cancel-batch", &body)
+            .await?;
+
+        check_ret_code(response.ret_code, &response.ret_msg)?;
+        Ok(response)
+    }
+
+    /// Get a page of the order history, newest first. `p_cursor` is the
+    /// `next_page_cursor` of the previous page.
+    pub async fn get_order_history(
+        &self,
+        p_category: Category,
+        p_symbol: Option<&str>,
+        p_limit: Option<u32>,
+        p_cursor: Option<&str>,
+    ) -> Result<OrderResponse> {
+        info!("Fetching Bybit order history");
+
+        let limit = p_limit.map(|limit| limit.to_string());
+        let mut params = vec![("category", p_category.as_str())];
+        if let Some(symbol) = p_symbol {
+            params.push(("symbol", symbol));
         }
+        if let Some(limit) = limit.as_deref() {
+            params.push(("limit", limit));
+        }
+        if let Some(cursor) = p_cursor.filter(|cursor| !cursor.is_empty()) {
+            params.push(("cursor", cursor));
+        }
+
+        let response: OrderResponse = self
+            .request_private("/v5/order/history", &params, RequestType::Get)
+            .await?;
 
+        check_ret_code(response.ret_code, &response.ret_msg)?;
         Ok(response)
     }
+
+    /// Every order of the history, following `next_page_cursor` until the
+    /// last page
+    pub async fn get_all_order_history(
+        &self,
+        p_category: Category,
+        p_symbol: Option<&str>,
+    ) -> Result<Vec<OrderBybit>> {
+        info!("Fetching Bybit order history");
+        let mut query = PageQuery::new(p_category).limit(HISTORY_PAGE_SIZE);
+        query.symbol = p_symbol.map(str::to_string);
+
+        self.pages("/v5/order/history", query).try_concat().await
+    }
 }
 
+/// Largest page of `/v5/order/history`
+const HISTORY_PAGE_SIZE: u32 = 50;
+
+pub(crate) fn check_ret_code(ret_code: i32, ret_msg: &str) -> Result<()> {
+    if ret_code != 0 {
+        return Err(ExchangeError::ApiError {
+            exchange: "Bybit".to_string(),
+            message: format!(
+                "Bybit API Error\n Code: {:?} Message: {:?}",
+                ret_code, ret_msg,
+            ),
+            code: Some(ret_code.into()),
+        });
+    }
+    Ok(())
+}
+
+/// Body of a batch endpoint, every request must be of `category`
+fn batch_body<T: Serialize>(
+    category: Category,
+    requests: &[T],
+    category_of: impl Fn(&T) -> Category,
+) -> Result<BatchRequest<serde_json::Value>> {
+    if requests.is_empty() {
+        return Err(ExchangeError::InvalidRequest("Empty batch".to_string()));
+    }
+    if let Some(request) = requests.iter().find(|r| category_of(r) != category) {
+        return Err(ExchangeError::InvalidRequest(format!(
+            "{} request in a {} batch",
+            category_of(request),
+            category
+        )));
+    }
+
+    Ok(BatchRequest {
+        category,
+        request: requests
+            .iter()
+            .map(without_category)
+            .collect::<Result<_>>()?,
+    })
+}
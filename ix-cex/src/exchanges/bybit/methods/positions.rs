@@ -0,0 +1,136 @@
+// private
+use crate::exchanges::bybit::{
+    clients::BybitPrivateClient,
+    requests::positions::{LeverageRequest, PageQuery, TradingStopRequest},
+    responses::{AckResponse, ClosedPnlBybit, ExecutionBybit, PageResponse, PositionBybit},
+};
+
+use super::orders::check_ret_code;
+use crate::client::http_client::RequestType;
+
+use futures::{Stream, TryStreamExt, stream};
+use ix_results::errors::Result;
+use serde::de::DeserializeOwned;
+use tracing::info;
+
+/// retCode of a leverage equal to the current one
+const LEVERAGE_NOT_MODIFIED: i32 = 110043;
+
+impl BybitPrivateClient {
+
+    /// Pages of a cursor paginated list endpoint, requested one at a time
+    /// as the stream is polled, until `next_page_cursor` is empty
+    pub fn pages<T>(
+        &self,
+        p_endpoint: &'static str,
+        p_query: PageQuery,
+    ) -> impl Stream<Item = Result<Vec<T>>> + '_
+    where
+        T: DeserializeOwned + 'static,
+    {
+        let params = p_query.params();
+
+        // None once the last page is read
+        stream::try_unfold(Some(String::new()), move |cursor| {
+            let params = params.clone();
+            async move {
+                let Some(cursor) = cursor else {
+                    return Ok(None);
+                };
+
+                let mut page_params: Vec<(&str, &str)> = params
+                    .iter()
+                    .map(|(key, value)| (*key, value.as_str()))
+                    .collect();
+                if !cursor.is_empty() {
+                    page_params.push(("cursor", cursor.as_str()));
+                }
+
+                let page: PageResponse<T> = self
+                    .request_private(p_endpoint, &page_params, RequestType::Get)
+                    .await?;
+                check_ret_code(page.ret_code, &page.ret_msg)?;
+
+                let next = page.result.next_page_cursor;
+                let next = (!next.is_empty() && next != cursor).then_some(next);
+                Ok(Some((page.result.list, next)))
+            }
+        })
+    }
+
+    /// Pages of the open positions
+    pub fn position_pages(
+        &self,
+        p_query: PageQuery,
+    ) -> impl Stream<Item = Result<Vec<PositionBybit>>> + '_ {
+        info!("Fetching Bybit positions");
+        self.pages("/v5/position/list", p_query)
+    }
+
+    /// Pages of the executions, newest first
+    pub fn execution_pages(
+        &self,
+        p_query: PageQuery,
+    ) -> impl Stream<Item = Result<Vec<ExecutionBybit>>> + '_ {
+        info!("Fetching Bybit executions");
+        self.pages("/v5/execution/list", p_query)
+    }
+
+    /// Pages of the closed PnL, newest first
+    pub fn closed_pnl_pages(
+        &self,
+        p_query: PageQuery,
+    ) -> impl Stream<Item = Result<Vec<ClosedPnlBybit>>> + '_ {
+        info!("Fetching Bybit closed PnL");
+        self.pages("/v5/position/closed-pnl", p_query)
+    }
+
+    /// Every open position, linear positions need a symbol or settle coin
+    pub async fn get_positions(&self, p_query: PageQuery) -> Result<Vec<PositionBybit>> {
+        self.position_pages(p_query).try_concat().await
+    }
+
+    /// Every execution, by default of the last 7 days
+    pub async fn get_executions(&self, p_query: PageQuery) -> Result<Vec<ExecutionBybit>> {
+        self.execution_pages(p_query).try_concat().await
+    }
+
+    /// Every closed PnL, by default of the last 7 days
+    pub async fn get_closed_pnl(&self, p_query: PageQuery) -> Result<Vec<ClosedPnlBybit>> {
+        self.closed_pnl_pages(p_query).try_concat().await
+    }
+
+    /// Set the leverage of a symbol, setting the current leverage succeeds
+    pub async fn set_leverage(&self, p_request: &LeverageRequest) -> Result<AckResponse> {
+        info!(
+            "Post Bybit leverage {}/{} of {}",
+            p_request.buy_leverage, p_request.sell_leverage, p_request.symbol
+        );
+        p_request.validate()?;
+
+        let response: AckResponse = self
+            .request_private_json("/v5/position/set-leverage", p_request)
+            .await?;
+
+        if response.ret_code != LEVERAGE_NOT_MODIFIED {
+            check_ret_code(response.ret_code, &response.ret_msg)?;
+        }
+        Ok(response)
+    }
+
+    /// Set the take profit, stop loss or trailing stop of a position
+    pub async fn set_trading_stop(
+        &self,
+        p_request: &TradingStopRequest,
+    ) -> Result<AckResponse> {
+        info!("Post Bybit trading stop of {}", p_request.symbol);
+        p_request.validate()?;
+
+        let response: AckResponse = self
+            .request_private_json("/v5/position/trading-stop", p_request)
+            .await?;
+
+        check_ret_code(response.ret_code, &response.ret_msg)?;
+        Ok(response)
+    }
+}
@@ -1,9 +1,27 @@
 pub mod bybit_client;
+pub mod bybit_wss;
+pub mod bybit_private_wss;
+pub mod bybit_trade_wss;
 pub mod responses;
 pub mod clients;
 pub mod configs;
 pub mod methods;
+pub mod order_entry;
+pub mod requests;
+pub mod trading;
 
 // Re-export
 pub use responses::orders::OrderBybit;
+pub use responses::{ClosedPnlBybit, ExecutionBybit, PositionBybit};
+pub use requests::orders::{
+    AmendRequest, CancelRequest, Category, MarketUnit, OrderRef, OrderRequest,
+    OrderType, Side, TimeInForce, TriggerBy,
+};
+pub use requests::positions::{
+    LeverageRequest, PageQuery, TpslMode, TradingStopRequest,
+};
+pub use bybit_private_wss::{BybitPrivateEvent, PrivateTopic};
+pub use bybit_trade_wss::BybitTradeClient;
+pub use order_entry::OrderEntry;
+pub use trading::BybitTradingClient;
 pub use methods::orders;
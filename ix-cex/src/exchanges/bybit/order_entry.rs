@@ -0,0 +1,43 @@
+// ix-cex/src/exchanges/bybit/order_entry.rs
+use crate::exchanges::bybit::clients::BybitPrivateClient;
+use crate::exchanges::bybit::requests::orders::{
+    AmendRequest, CancelRequest, OrderRequest,
+};
+use crate::exchanges::bybit::responses::TradeResponse;
+use ix_results::errors::Result;
+
+/// Transport of the Bybit order requests, REST with [`BybitPrivateClient`] or
+/// the trade WebSocket with
+/// [`BybitTradeClient`](crate::exchanges::bybit::bybit_trade_wss::BybitTradeClient).
+/// Both take the same requests and answer with the same response, an error
+/// when Bybit refuses the request.
+#[async_trait::async_trait]
+pub trait OrderEntry: Send + Sync {
+    async fn place_order(&self, request: &OrderRequest) -> Result<TradeResponse>;
+
+    async fn amend_order(&self, request: &AmendRequest) -> Result<TradeResponse>;
+
+    async fn cancel_order(&self, request: &CancelRequest) -> Result<TradeResponse>;
+
+    /// Name of the transport, for logs
+    fn transport(&self) -> &'static str;
+}
+
+#[async_trait::async_trait]
+impl OrderEntry for BybitPrivateClient {
+    async fn place_order(&self, request: &OrderRequest) -> Result<TradeResponse> {
+        BybitPrivateClient::place_order(self, request).await
+    }
+
+    async fn amend_order(&self, request: &AmendRequest) -> Result<TradeResponse> {
+        BybitPrivateClient::amend_order(self, request).await
+    }
+
+    async fn cancel_order(&self, request: &CancelRequest) -> Result<TradeResponse> {
+        BybitPrivateClient::cancel_order(self, request).await
+    }
+
+    fn transport(&self) -> &'static str {
+        "rest"
+    }
+}
@@ -0,0 +1,51 @@
+/// Enum of API values with `as_str`, `Display`, `FromStr` and serde
+macro_rules! api_enum {
+    (
+        $(#[$meta:meta])*
+        $name:ident { $($variant:ident => $value:literal),+ $(,)? }
+    ) => {
+        $(#[$meta])*
+        #[derive(
+            Debug, Clone, Copy, PartialEq, Eq, Hash, ::serde::Serialize, ::serde::Deserialize,
+        )]
+        pub enum $name {
+            $(
+                #[serde(rename = $value)]
+                $variant,
+            )+
+        }
+
+        impl $name {
+            /// Value of the API
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $($name::$variant => $value,)+
+                }
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl ::std::str::FromStr for $name {
+            type Err = ::ix_results::errors::ExchangeError;
+
+            fn from_str(s: &str) -> ::ix_results::errors::Result<Self> {
+                match s {
+                    $($value => Ok($name::$variant),)+
+                    _ => Err(::ix_results::errors::ExchangeError::InvalidRequest(format!(
+                        "Unknown {} {}",
+                        stringify!($name),
+                        s
+                    ))),
+                }
+            }
+        }
+    };
+}
+
+pub mod orders;
+pub mod positions;
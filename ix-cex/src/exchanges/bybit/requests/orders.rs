@@ -0,0 +1,443 @@
+//! Typed parameters of the Bybit v5 order endpoints
+//!
+//! Enums serialize to the strings of the API and decimals to strings, so the
+//! requests are posted as they are serialized.
+
+use ix_results::errors::{ExchangeError, Result};
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+api_enum! {
+    /// Product type
+    Category {
+        Spot => "spot",
+        Linear => "linear",
+        Inverse => "inverse",
+        Option => "option",
+    }
+}
+
+api_enum! {
+    Side {
+        Buy => "Buy",
+        Sell => "Sell",
+    }
+}
+
+api_enum! {
+    OrderType {
+        Market => "Market",
+        Limit => "Limit",
+    }
+}
+
+api_enum! {
+    TimeInForce {
+        GoodTillCancel => "GTC",
+        ImmediateOrCancel => "IOC",
+        FillOrKill => "FOK",
+        PostOnly => "PostOnly",
+    }
+}
+
+api_enum! {
+    /// Price triggering conditional, take profit and stop loss orders
+    TriggerBy {
+        LastPrice => "LastPrice",
+        IndexPrice => "IndexPrice",
+        MarkPrice => "MarkPrice",
+    }
+}
+
+api_enum! {
+    /// Unit of the `qty` of spot market orders
+    MarketUnit {
+        BaseCoin => "baseCoin",
+        QuoteCoin => "quoteCoin",
+    }
+}
+
+impl Side {
+    pub fn opposite(&self) -> Self {
+        match self {
+            Side::Buy => Side::Sell,
+            Side::Sell => Side::Buy,
+        }
+    }
+}
+
+/// Parameters of `/v5/order/create`, built with [`OrderRequest::builder`]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderRequest {
+    pub category: Category,
+    pub symbol: String,
+    pub side: Side,
+    pub order_type: OrderType,
+    pub qty: Decimal,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_in_force: Option<TimeInForce>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_link_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reduce_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub close_on_trigger: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position_idx: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub market_unit: Option<MarketUnit>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_price: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_by: Option<TriggerBy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub take_profit: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_loss: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tp_trigger_by: Option<TriggerBy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sl_trigger_by: Option<TriggerBy>,
+}
+
+impl OrderRequest {
+    pub fn builder() -> OrderRequestBuilder {
+        OrderRequestBuilder::new()
+    }
+
+    /// Market order
+    pub fn market(
+        category: Category,
+        symbol: impl Into<String>,
+        side: Side,
+        qty: Decimal,
+    ) -> OrderRequestBuilder {
+        Self::builder()
+            .category(category)
+            .symbol(symbol)
+            .side(side)
+            .order_type(OrderType::Market)
+            .qty(qty)
+    }
+
+    /// Limit order, good till cancelled unless set otherwise
+    pub fn limit(
+        category: Category,
+        symbol: impl Into<String>,
+        side: Side,
+        qty: Decimal,
+        price: Decimal,
+    ) -> OrderRequestBuilder {
+        Self::builder()
+            .category(category)
+            .symbol(symbol)
+            .side(side)
+            .order_type(OrderType::Limit)
+            .qty(qty)
+            .price(price)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct OrderRequestBuilder {
+    category: Option<Category>,
+    symbol: Option<String>,
+    side: Option<Side>,
+    order_type: Option<OrderType>,
+    qty: Option<Decimal>,
+    price: Option<Decimal>,
+    time_in_force: Option<TimeInForce>,
+    order_link_id: Option<String>,
+    reduce_only: Option<bool>,
+    close_on_trigger: Option<bool>,
+    position_idx: Option<i32>,
+    market_unit: Option<MarketUnit>,
+    trigger_price: Option<Decimal>,
+    trigger_by: Option<TriggerBy>,
+    take_profit: Option<Decimal>,
+    stop_loss: Option<Decimal>,
+    tp_trigger_by: Option<TriggerBy>,
+    sl_trigger_by: Option<TriggerBy>,
+}
+
+impl OrderRequestBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn category(mut self, category: Category) -> Self {
+        self.category = Some(category);
+        self
+    }
+
+    pub fn symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.symbol = Some(symbol.into());
+        self
+    }
+
+    pub fn side(mut self, side: Side) -> Self {
+        self.side = Some(side);
+        self
+    }
+
+    pub fn order_type(mut self, order_type: OrderType) -> Self {
+        self.order_type = Some(order_type);
+        self
+    }
+
+    pub fn qty(mut self, qty: Decimal) -> Self {
+        self.qty = Some(qty);
+        self
+    }
+
+    pub fn price(mut self, price: Decimal) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    pub fn time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.time_in_force = Some(time_in_force);
+        self
+    }
+
+    /// Client order id, at most 36 characters and unique among open orders
+    pub fn order_link_id(mut self, order_link_id: impl Into<String>) -> Self {
+        self.order_link_id = Some(order_link_id.into());
+        self
+    }
+
+    pub fn reduce_only(mut self, reduce_only: bool) -> Self {
+        self.reduce_only = Some(reduce_only);
+        self
+    }
+
+    pub fn close_on_trigger(mut self, close_on_trigger: bool) -> Self {
+        self.close_on_trigger = Some(close_on_trigger);
+        self
+    }
+
+    /// `0` in one-way mode, `1` buy and `2` sell side in hedge mode
+    pub fn position_idx(mut self, position_idx: i32) -> Self {
+        self.position_idx = Some(position_idx);
+        self
+    }
+
+    pub fn market_unit(mut self, market_unit: MarketUnit) -> Self {
+        self.market_unit = Some(market_unit);
+        self
+    }
+
+    /// Conditional order triggered at `trigger_price`
+    pub fn trigger(mut self, trigger_price: Decimal, trigger_by: TriggerBy) -> Self {
+        self.trigger_price = Some(trigger_price);
+        self.trigger_by = Some(trigger_by);
+        self
+    }
+
+    pub fn take_profit(mut self, take_profit: Decimal) -> Self {
+        self.take_profit = Some(take_profit);
+        self
+    }
+
+    pub fn stop_loss(mut self, stop_loss: Decimal) -> Self {
+        self.stop_loss = Some(stop_loss);
+        self
+    }
+
+    pub fn tp_trigger_by(mut self, tp_trigger_by: TriggerBy) -> Self {
+        self.tp_trigger_by = Some(tp_trigger_by);
+        self
+    }
+
+    pub fn sl_trigger_by(mut self, sl_trigger_by: TriggerBy) -> Self {
+        self.sl_trigger_by = Some(sl_trigger_by);
+        self
+    }
+
+    /// Check the parameters Bybit would reject
+    pub fn build(self) -> Result<OrderRequest> {
+        let invalid = |message: &str| ExchangeError::InvalidRequest(message.to_string());
+
+        let category = self.category.ok_or_else(|| invalid("Missing category"))?;
+        let symbol = self.symbol.ok_or_else(|| invalid("Missing symbol"))?;
+        let side = self.side.ok_or_else(|| invalid("Missing side"))?;
+        let order_type = self
+            .order_type
+            .ok_or_else(|| invalid("Missing order type"))?;
+        let qty = self.qty.ok_or_else(|| invalid("Missing qty"))?;
+
+        if qty <= Decimal::ZERO {
+            return Err(invalid("qty must be positive"));
+        }
+        if self.price.is_some_and(|price| price <= Decimal::ZERO) {
+            return Err(invalid("price must be positive"));
+        }
+        match order_type {
+            OrderType::Limit if self.price.is_none() => {
+                return Err(invalid("Limit orders need a price"));
+            }
+            OrderType::Market if self.time_in_force == Some(TimeInForce::PostOnly) => {
+                return Err(invalid("Market orders cannot be post only"));
+            }
+            _ => {}
+        }
+        if category == Category::Spot && self.reduce_only == Some(true) {
+            return Err(invalid("Spot orders cannot be reduce only"));
+        }
+        if self.market_unit.is_some()
+            && (category != Category::Spot || order_type != OrderType::Market)
+        {
+            return Err(invalid("market_unit only applies to spot market orders"));
+        }
+        if self
+            .order_link_id
+            .as_ref()
+            .is_some_and(|id| id.is_empty() || id.len() > 36)
+        {
+            return Err(invalid("order_link_id must have 1 to 36 characters"));
+        }
+
+        Ok(OrderRequest {
+            category,
+            symbol,
+            side,
+            order_type,
+            qty,
+            price: self.price,
+            time_in_force: self.time_in_force,
+            order_link_id: self.order_link_id,
+            reduce_only: self.reduce_only,
+            close_on_trigger: self.close_on_trigger,
+            position_idx: self.position_idx,
+            market_unit: self.market_unit,
+            trigger_price: self.trigger_price,
+            trigger_by: self.trigger_by,
+            take_profit: self.take_profit,
+            stop_loss: self.stop_loss,
+            tp_trigger_by: self.tp_trigger_by,
+            sl_trigger_by: self.sl_trigger_by,
+        })
+    }
+}
+
+/// Order of the amend and cancel requests, by exchange or client id
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderRef {
+    OrderId(String),
+    OrderLinkId(String),
+}
+
+impl Serialize for OrderRef {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(1))?;
+        match self {
+            OrderRef::OrderId(id) => map.serialize_entry("orderId", id)?,
+            OrderRef::OrderLinkId(id) => map.serialize_entry("orderLinkId", id)?,
+        }
+        map.end()
+    }
+}
+
+/// Parameters of `/v5/order/amend`, unset values are kept
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AmendRequest {
+    pub category: Category,
+    pub symbol: String,
+    #[serde(flatten)]
+    pub order: OrderRef,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qty: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_price: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub take_profit: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_loss: Option<Decimal>,
+}
+
+impl AmendRequest {
+    pub fn new(category: Category, symbol: impl Into<String>, order: OrderRef) -> Self {
+        Self {
+            category,
+            symbol: symbol.into(),
+            order,
+            qty: None,
+            price: None,
+            trigger_price: None,
+            take_profit: None,
+            stop_loss: None,
+        }
+    }
+
+    pub fn qty(mut self, qty: Decimal) -> Self {
+        self.qty = Some(qty);
+        self
+    }
+
+    pub fn price(mut self, price: Decimal) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    pub fn trigger_price(mut self, trigger_price: Decimal) -> Self {
+        self.trigger_price = Some(trigger_price);
+        self
+    }
+
+    pub fn take_profit(mut self, take_profit: Decimal) -> Self {
+        self.take_profit = Some(take_profit);
+        self
+    }
+
+    pub fn stop_loss(mut self, stop_loss: Decimal) -> Self {
+        self.stop_loss = Some(stop_loss);
+        self
+    }
+}
+
+/// Parameters of `/v5/order/cancel`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelRequest {
+    pub category: Category,
+    pub symbol: String,
+    #[serde(flatten)]
+    pub order: OrderRef,
+}
+
+impl CancelRequest {
+    pub fn new(category: Category, symbol: impl Into<String>, order: OrderRef) -> Self {
+        Self {
+            category,
+            symbol: symbol.into(),
+            order,
+        }
+    }
+}
+
+/// Body of the batch endpoints, the category is shared by every request
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct BatchRequest<T> {
+    pub category: Category,
+    pub request: Vec<T>,
+}
+
+/// Request without its category, for [`BatchRequest`]
+pub(crate) fn without_category<T: Serialize>(request: &T) -> Result<serde_json::Value> {
+    let mut value = serde_json::to_value(request)?;
+    if let Some(object) = value.as_object_mut() {
+        object.remove("category");
+    }
+    Ok(value)
+}
@@ -0,0 +1,263 @@
+//! Typed parameters of the Bybit v5 position, execution and closed PnL
+//! endpoints
+
+use ix_results::errors::{ExchangeError, Result};
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+use super::orders::{Category, TriggerBy};
+
+api_enum! {
+    /// Scope of the take profit and stop loss of a position
+    TpslMode {
+        Full => "Full",
+        Partial => "Partial",
+    }
+}
+
+/// Filters of the paginated list endpoints, `/v5/position/list`,
+/// `/v5/execution/list`, `/v5/position/closed-pnl`, `/v5/order/realtime` and
+/// `/v5/order/history`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageQuery {
+    pub category: Category,
+    pub symbol: Option<String>,
+    pub settle_coin: Option<String>,
+    pub order_id: Option<String>,
+    pub order_link_id: Option<String>,
+    /// Milliseconds
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    pub limit: Option<u32>,
+}
+
+impl PageQuery {
+    pub fn new(category: Category) -> Self {
+        Self {
+            category,
+            symbol: None,
+            settle_coin: None,
+            order_id: None,
+            order_link_id: None,
+            start_time: None,
+            end_time: None,
+            limit: None,
+        }
+    }
+
+    pub fn symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.symbol = Some(symbol.into());
+        self
+    }
+
+    pub fn settle_coin(mut self, settle_coin: impl Into<String>) -> Self {
+        self.settle_coin = Some(settle_coin.into());
+        self
+    }
+
+    pub fn order_id(mut self, order_id: impl Into<String>) -> Self {
+        self.order_id = Some(order_id.into());
+        self
+    }
+
+    pub fn order_link_id(mut self, order_link_id: impl Into<String>) -> Self {
+        self.order_link_id = Some(order_link_id.into());
+        self
+    }
+
+    pub fn start_time(mut self, start_time: u64) -> Self {
+        self.start_time = Some(start_time);
+        self
+    }
+
+    pub fn end_time(mut self, end_time: u64) -> Self {
+        self.end_time = Some(end_time);
+        self
+    }
+
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Query parameters, without the cursor
+    pub fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![("category", self.category.to_string())];
+        let optional = [
+            ("symbol", self.symbol.clone()),
+            ("settleCoin", self.settle_coin.clone()),
+            ("orderId", self.order_id.clone()),
+            ("orderLinkId", self.order_link_id.clone()),
+            ("startTime", self.start_time.map(|time| time.to_string())),
+            ("endTime", self.end_time.map(|time| time.to_string())),
+            ("limit", self.limit.map(|limit| limit.to_string())),
+        ];
+        params.extend(
+            optional
+                .into_iter()
+                .filter_map(|(key, value)| value.map(|value| (key, value))),
+        );
+        params
+    }
+}
+
+/// Parameters of `/v5/position/set-leverage`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeverageRequest {
+    pub category: Category,
+    pub symbol: String,
+    pub buy_leverage: Decimal,
+    pub sell_leverage: Decimal,
+}
+
+impl LeverageRequest {
+    /// Same leverage on both sides
+    pub fn new(category: Category, symbol: impl Into<String>, leverage: Decimal) -> Self {
+        Self {
+            category,
+            symbol: symbol.into(),
+            buy_leverage: leverage,
+            sell_leverage: leverage,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        derivatives_only(self.category)?;
+        if self.buy_leverage <= Decimal::ZERO || self.sell_leverage <= Decimal::ZERO {
+            return Err(ExchangeError::InvalidRequest(
+                "Leverage must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Parameters of `/v5/position/trading-stop`, a zero price removes the take
+/// profit, stop loss or trailing stop
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradingStopRequest {
+    pub category: Category,
+    pub symbol: String,
+    pub tpsl_mode: TpslMode,
+    /// 0 one-way mode, 1 buy side and 2 sell side of hedge mode
+    pub position_idx: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub take_profit: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_loss: Option<Decimal>,
+    /// Distance from the price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trailing_stop: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tp_trigger_by: Option<TriggerBy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sl_trigger_by: Option<TriggerBy>,
+    /// Quantity closed by the take profit, `Partial` mode only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tp_size: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sl_size: Option<Decimal>,
+}
+
+impl TradingStopRequest {
+    /// Take profit and stop loss of the whole one-way mode position
+    pub fn new(category: Category, symbol: impl Into<String>) -> Self {
+        Self {
+            category,
+            symbol: symbol.into(),
+            tpsl_mode: TpslMode::Full,
+            position_idx: 0,
+            take_profit: None,
+            stop_loss: None,
+            trailing_stop: None,
+            tp_trigger_by: None,
+            sl_trigger_by: None,
+            tp_size: None,
+            sl_size: None,
+        }
+    }
+
+    pub fn tpsl_mode(mut self, tpsl_mode: TpslMode) -> Self {
+        self.tpsl_mode = tpsl_mode;
+        self
+    }
+
+    pub fn position_idx(mut self, position_idx: i32) -> Self {
+        self.position_idx = position_idx;
+        self
+    }
+
+    pub fn take_profit(mut self, take_profit: Decimal) -> Self {
+        self.take_profit = Some(take_profit);
+        self
+    }
+
+    pub fn stop_loss(mut self, stop_loss: Decimal) -> Self {
+        self.stop_loss = Some(stop_loss);
+        self
+    }
+
+    pub fn trailing_stop(mut self, trailing_stop: Decimal) -> Self {
+        self.trailing_stop = Some(trailing_stop);
+        self
+    }
+
+    pub fn tp_trigger_by(mut self, tp_trigger_by: TriggerBy) -> Self {
+        self.tp_trigger_by = Some(tp_trigger_by);
+        self
+    }
+
+    pub fn sl_trigger_by(mut self, sl_trigger_by: TriggerBy) -> Self {
+        self.sl_trigger_by = Some(sl_trigger_by);
+        self
+    }
+
+    pub fn tp_size(mut self, tp_size: Decimal) -> Self {
+        self.tp_size = Some(tp_size);
+        self
+    }
+
+    pub fn sl_size(mut self, sl_size: Decimal) -> Self {
+        self.sl_size = Some(sl_size);
+        self
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |message: &str| ExchangeError::InvalidRequest(message.to_string());
+
+        derivatives_only(self.category)?;
+        if self.take_profit.is_none()
+            && self.stop_loss.is_none()
+            && self.trailing_stop.is_none()
+        {
+            return Err(invalid(
+                "Trading stop needs a take profit, stop loss or trailing stop",
+            ));
+        }
+        let prices = [self.take_profit, self.stop_loss, self.trailing_stop];
+        if prices
+            .iter()
+            .flatten()
+            .any(|price| price.is_sign_negative())
+        {
+            return Err(invalid("Trading stop prices must not be negative"));
+        }
+        let sizes = self.tp_size.is_some() || self.sl_size.is_some();
+        if sizes && self.tpsl_mode == TpslMode::Full {
+            return Err(invalid("TP/SL sizes need the Partial mode"));
+        }
+        Ok(())
+    }
+}
+
+/// Positions exist for linear and inverse contracts only
+fn derivatives_only(category: Category) -> Result<()> {
+    match category {
+        Category::Linear | Category::Inverse => Ok(()),
+        category => Err(ExchangeError::InvalidRequest(format!(
+            "No positions in category {category}"
+        ))),
+    }
+}
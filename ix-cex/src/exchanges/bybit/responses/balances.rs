@@ -9,6 +9,7 @@ pub struct CoinBalance {
     pub wallet_balance: String,
     pub free: Option<String>,
     pub locked: Option<String>,
+    #[serde(default)]
     pub spot_hedging_qty: String,
     pub borrow_amount: String,
     #[serde(default)]
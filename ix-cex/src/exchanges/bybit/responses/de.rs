@@ -0,0 +1,25 @@
+//! Deserializers of the numbers Bybit sends as strings
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer, de::Error};
+
+/// Decimal string, empty strings are zero
+pub fn decimal<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    if value.is_empty() {
+        return Ok(Decimal::ZERO);
+    }
+    value.parse::<Decimal>().map_err(Error::custom)
+}
+
+/// Integer string, e.g. a timestamp in milliseconds
+pub fn u64_str<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    value.parse::<u64>().map_err(Error::custom)
+}
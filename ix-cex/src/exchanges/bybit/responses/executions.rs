@@ -0,0 +1,53 @@
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use super::de;
+
+/// Bybit execution, one fill of an order
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionBybit {
+    pub symbol: String,
+    pub order_id: String,
+    pub order_link_id: String,
+    pub side: String,
+    #[serde(deserialize_with = "de::decimal")]
+    pub order_price: Decimal,
+    #[serde(deserialize_with = "de::decimal")]
+    pub order_qty: Decimal,
+    #[serde(deserialize_with = "de::decimal")]
+    pub leaves_qty: Decimal,
+    #[serde(default)]
+    pub create_type: String,
+    pub order_type: String,
+    #[serde(default)]
+    pub stop_order_type: String,
+    pub exec_id: String,
+    #[serde(deserialize_with = "de::decimal")]
+    pub exec_price: Decimal,
+    #[serde(deserialize_with = "de::decimal")]
+    pub exec_qty: Decimal,
+    #[serde(deserialize_with = "de::decimal")]
+    pub exec_value: Decimal,
+    #[serde(deserialize_with = "de::decimal")]
+    pub exec_fee: Decimal,
+    #[serde(default)]
+    pub fee_currency: String,
+    pub exec_type: String, // "Trade", "Funding", "AdlTrade", "BustTrade"
+    /// Milliseconds
+    #[serde(deserialize_with = "de::u64_str")]
+    pub exec_time: u64,
+    pub is_maker: bool,
+    #[serde(default, deserialize_with = "de::decimal")]
+    pub fee_rate: Decimal,
+    #[serde(default, deserialize_with = "de::decimal")]
+    pub mark_price: Decimal,
+    #[serde(default, deserialize_with = "de::decimal")]
+    pub index_price: Decimal,
+    #[serde(default, deserialize_with = "de::decimal")]
+    pub closed_size: Decimal,
+    #[serde(default)]
+    pub block_trade_id: String,
+    #[serde(default)]
+    pub seq: i64,
+}
@@ -1,17 +1,32 @@
 pub mod accounts;
 pub mod balances;
 pub mod connections;
+pub(crate) mod de;
+pub mod executions;
 pub mod instruments;
 pub mod orders;
 pub mod orderbook;
+pub mod pages;
+pub mod pnl;
+pub mod positions;
+pub mod streams;
 pub mod trades;
 pub mod wallets;
 
 pub use accounts::AccountInfoResponse;
 pub use balances::CoinBalance;
 pub use connections::ServerTimeResponse;
+pub use executions::ExecutionBybit;
 pub use instruments::InstrumentResponse;
-pub use orders::{CancelResponse, OrderResponse};
+pub use orders::{BatchResponse, CancelResponse, OrderResponse};
 pub use orderbook::BybitOrderbookResponse;
-pub use trades::TradeResponse;
-pub use wallets::WalletBalanceResponse;
+pub use pages::{AckResponse, PageResponse};
+pub use pnl::ClosedPnlBybit;
+pub use positions::PositionBybit;
+pub use streams::{
+    BybitPrivateStreamEvent, BybitStreamEvent, BybitStreamExecution,
+    BybitStreamLiquidation, BybitStreamOrder, BybitStreamOrderbook, BybitStreamPosition,
+    BybitStreamTrade,
+};
+pub use trades::{TradeResponse, TradeWsResponse};
+pub use wallets::{WalletBalance, WalletBalanceResponse};
@@ -1,5 +1,8 @@
+use rust_decimal::Decimal;
 use serde::Deserialize;
 
+use super::de;
+
 /// Bybit order response structure
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -7,10 +10,6 @@ pub struct OrderResponse {
     pub ret_code: i32,
     pub ret_msg: String,
     pub result: OrderResult,
-    #[serde(default)]
-    pub next_page_cursor: String,
-    #[serde(default)]
-    pub category: String, // "linear"
     pub ret_ext_info: serde_json::Value,
     pub time: u64, // 1684765770483
 }
@@ -18,7 +17,12 @@ pub struct OrderResponse {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderResult {
+    #[serde(default)]
+    pub category: String, // "linear"
     pub list: Vec<OrderBybit>,
+    /// Cursor of the next page, empty on the last one
+    #[serde(default)]
+    pub next_page_cursor: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -28,50 +32,64 @@ pub struct OrderBybit {
     pub order_link_id: String,
     pub block_trade_id: String,
     pub symbol: String,
-    pub price: String, // f64,
-    pub qty: String, // f64,
+    #[serde(deserialize_with = "de::decimal")]
+    pub price: Decimal,
+    #[serde(deserialize_with = "de::decimal")]
+    pub qty: Decimal,
     pub side: String,
     #[serde(default)]
     pub is_leverage: String,
-    pub position_idx: String, // i32,
+    pub position_idx: i32,
     pub order_status: String,
     pub cancel_type: String,
     pub reject_reason: String,
-    pub avg_price: String, // f64,
-    pub leaves_qty: String, // f64,
-    pub leaves_value: String, // i32,
-    pub cum_exec_qty: String, // f64,
-    pub cum_exec_value: String, // i32,
-    pub cum_exec_fee: String, // i32,
+    #[serde(deserialize_with = "de::decimal")]
+    pub avg_price: Decimal,
+    #[serde(deserialize_with = "de::decimal")]
+    pub leaves_qty: Decimal,
+    #[serde(deserialize_with = "de::decimal")]
+    pub leaves_value: Decimal,
+    #[serde(deserialize_with = "de::decimal")]
+    pub cum_exec_qty: Decimal,
+    #[serde(deserialize_with = "de::decimal")]
+    pub cum_exec_value: Decimal,
+    #[serde(deserialize_with = "de::decimal")]
+    pub cum_exec_fee: Decimal,
     pub time_in_force: String,
     pub order_type: String, // "Limit",
     pub stop_order_type: String, // "UNKNOWN",
-    #[serde(default)] 
+    #[serde(default)]
     pub order_iv: String, // "",
-    pub trigger_price: String, // "0.00",
-    pub take_profit: String, // f64, // "2500.00",
-    pub stop_loss: String, // f64, // "1500.00",
+    #[serde(deserialize_with = "de::decimal")]
+    pub trigger_price: Decimal,
+    #[serde(deserialize_with = "de::decimal")]
+    pub take_profit: Decimal,
+    #[serde(deserialize_with = "de::decimal")]
+    pub stop_loss: Decimal,
     pub tp_trigger_by: String, // "LastPrice",
     pub sl_trigger_by: String, // "LastPrice",
-    pub trigger_direction: String, // i32, // 0,
+    pub trigger_direction: i32,
     pub trigger_by: String, // "UNKNOWN",
-    #[serde(default)]
-    pub last_price_on_created: String, //  "",
-    pub reduce_only: bool, // false,
-    pub close_on_trigger: bool, // false,
+    #[serde(default, deserialize_with = "de::decimal")]
+    pub last_price_on_created: Decimal,
+    pub reduce_only: bool,
+    pub close_on_trigger: bool,
     pub smp_type: String, // "None",
-    pub smp_group: String, // i32, // 0,
+    pub smp_group: i32,
     #[serde(default)]
     pub smp_order_id: String, // "",
     pub tpsl_mode: String, // "Full",
-    #[serde(default)]
-    pub tp_limit_price: String, // "",
-    #[serde(default)]
-    pub sl_limit_price: String, // "",
+    #[serde(default, deserialize_with = "de::decimal")]
+    pub tp_limit_price: Decimal,
+    #[serde(default, deserialize_with = "de::decimal")]
+    pub sl_limit_price: Decimal,
     #[serde(default)]
     pub place_type: String, // "",
-    pub created_time: String, // u64, // "1684738540559",
-    pub updated_time: String, // u64, // "1684738540561"
+    /// Milliseconds
+    #[serde(deserialize_with = "de::u64_str")]
+    pub created_time: u64,
+    #[serde(deserialize_with = "de::u64_str")]
+    pub updated_time: u64,
 }
 
 /// Bybit cancel all order response structure
@@ -100,3 +118,64 @@ pub struct Cancel {
     pub order_link_id: String,
 }
 
+
+/// Bybit batch order response structure, `result` and `ret_ext_info` hold one
+/// entry per request in request order
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchResponse {
+    pub ret_code: i32,
+    pub ret_msg: String,
+    pub result: BatchResult,
+    pub ret_ext_info: BatchExtInfo,
+    pub time: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchResult {
+    pub list: Vec<BatchOrder>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchOrder {
+    #[serde(default)]
+    pub category: String,
+    #[serde(default)]
+    pub symbol: String,
+    #[serde(default)]
+    pub order_id: String,
+    #[serde(default)]
+    pub order_link_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchExtInfo {
+    #[serde(default)]
+    pub list: Vec<BatchStatus>,
+}
+
+/// Outcome of one request of a batch, code 0 on success
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchStatus {
+    pub code: i32,
+    pub msg: String,
+}
+
+impl BatchResponse {
+    /// Each order with its outcome, `Err` with the message of failed requests
+    pub fn orders(&self) -> Vec<std::result::Result<&BatchOrder, &str>> {
+        self.result
+            .list
+            .iter()
+            .zip(&self.ret_ext_info.list)
+            .map(|(order, status)| match status.code {
+                0 => Ok(order),
+                _ => Err(status.msg.as_str()),
+            })
+            .collect()
+    }
+}
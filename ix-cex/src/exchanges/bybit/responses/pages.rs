@@ -0,0 +1,37 @@
+use serde::Deserialize;
+
+/// Bybit response of a cursor paginated list endpoint
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageResponse<T> {
+    pub ret_code: i32,
+    pub ret_msg: String,
+    pub result: PageResult<T>,
+    #[serde(default)]
+    pub ret_ext_info: serde_json::Value,
+    pub time: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageResult<T> {
+    #[serde(default)]
+    pub category: String,
+    pub list: Vec<T>,
+    /// Cursor of the next page, empty on the last one
+    #[serde(default)]
+    pub next_page_cursor: String,
+}
+
+/// Bybit response of the endpoints returning no data
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AckResponse {
+    pub ret_code: i32,
+    pub ret_msg: String,
+    #[serde(default)]
+    pub result: serde_json::Value,
+    #[serde(default)]
+    pub ret_ext_info: serde_json::Value,
+    pub time: u64,
+}
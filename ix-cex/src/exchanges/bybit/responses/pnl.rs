@@ -0,0 +1,41 @@
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use super::de;
+
+/// Bybit closed PnL of a closing order
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClosedPnlBybit {
+    pub symbol: String,
+    pub order_id: String,
+    /// Side of the closing order
+    pub side: String,
+    #[serde(deserialize_with = "de::decimal")]
+    pub qty: Decimal,
+    #[serde(deserialize_with = "de::decimal")]
+    pub order_price: Decimal,
+    pub order_type: String,
+    pub exec_type: String,
+    #[serde(deserialize_with = "de::decimal")]
+    pub closed_size: Decimal,
+    #[serde(deserialize_with = "de::decimal")]
+    pub cum_entry_value: Decimal,
+    #[serde(deserialize_with = "de::decimal")]
+    pub avg_entry_price: Decimal,
+    #[serde(deserialize_with = "de::decimal")]
+    pub cum_exit_value: Decimal,
+    #[serde(deserialize_with = "de::decimal")]
+    pub avg_exit_price: Decimal,
+    #[serde(deserialize_with = "de::decimal")]
+    pub closed_pnl: Decimal,
+    #[serde(deserialize_with = "de::u64_str")]
+    pub fill_count: u64,
+    #[serde(deserialize_with = "de::decimal")]
+    pub leverage: Decimal,
+    /// Milliseconds
+    #[serde(deserialize_with = "de::u64_str")]
+    pub created_time: u64,
+    #[serde(deserialize_with = "de::u64_str")]
+    pub updated_time: u64,
+}
@@ -0,0 +1,62 @@
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use super::de;
+
+/// Bybit position, `side` is empty when the position is closed
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionBybit {
+    pub position_idx: i32,
+    #[serde(default)]
+    pub risk_id: i64,
+    pub symbol: String,
+    pub side: String, // "Buy", "Sell" or ""
+    #[serde(deserialize_with = "de::decimal")]
+    pub size: Decimal,
+    #[serde(default, deserialize_with = "de::decimal")]
+    pub avg_price: Decimal,
+    #[serde(deserialize_with = "de::decimal")]
+    pub position_value: Decimal,
+    #[serde(default)]
+    pub trade_mode: i32, // 0 cross, 1 isolated margin
+    #[serde(default)]
+    pub position_status: String, // "Normal", "Liq", "Adl"
+    #[serde(default, deserialize_with = "de::decimal")]
+    pub leverage: Decimal,
+    #[serde(default, deserialize_with = "de::decimal")]
+    pub position_balance: Decimal,
+    #[serde(deserialize_with = "de::decimal")]
+    pub mark_price: Decimal,
+    #[serde(default, deserialize_with = "de::decimal")]
+    pub liq_price: Decimal,
+    #[serde(default, deserialize_with = "de::decimal")]
+    pub bust_price: Decimal,
+    #[serde(rename = "positionIM", default, deserialize_with = "de::decimal")]
+    pub position_im: Decimal,
+    #[serde(rename = "positionMM", default, deserialize_with = "de::decimal")]
+    pub position_mm: Decimal,
+    #[serde(default)]
+    pub tpsl_mode: String, // "Full",
+    #[serde(default, deserialize_with = "de::decimal")]
+    pub take_profit: Decimal,
+    #[serde(default, deserialize_with = "de::decimal")]
+    pub stop_loss: Decimal,
+    #[serde(default, deserialize_with = "de::decimal")]
+    pub trailing_stop: Decimal,
+    #[serde(deserialize_with = "de::decimal")]
+    pub unrealised_pnl: Decimal,
+    #[serde(default, deserialize_with = "de::decimal")]
+    pub cur_realised_pnl: Decimal,
+    #[serde(default, deserialize_with = "de::decimal")]
+    pub cum_realised_pnl: Decimal,
+    #[serde(default)]
+    pub adl_rank_indicator: i32,
+    /// Milliseconds
+    #[serde(deserialize_with = "de::u64_str")]
+    pub created_time: u64,
+    #[serde(deserialize_with = "de::u64_str")]
+    pub updated_time: u64,
+    #[serde(default)]
+    pub seq: i64,
+}
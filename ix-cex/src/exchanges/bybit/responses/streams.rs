@@ -0,0 +1,102 @@
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use super::de;
+use super::executions::ExecutionBybit;
+use super::orders::OrderBybit;
+use super::positions::PositionBybit;
+
+/// Bybit public stream message envelope
+#[derive(Debug, Deserialize, Clone)]
+pub struct BybitStreamEvent<T> {
+    pub topic: String,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub ts: u64,
+    pub data: T,
+}
+
+/// Bybit orderbook stream data, `snapshot` or `delta`
+#[derive(Debug, Deserialize, Clone)]
+pub struct BybitStreamOrderbook {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "b")]
+    pub bids: Vec<(Decimal, Decimal)>,
+    #[serde(rename = "a")]
+    pub asks: Vec<(Decimal, Decimal)>,
+    #[serde(rename = "u")]
+    pub update_id: u64,
+    pub seq: u64,
+}
+
+/// Bybit public trade stream data
+#[derive(Debug, Deserialize, Clone)]
+pub struct BybitStreamTrade {
+    #[serde(rename = "T")]
+    pub timestamp: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "S")]
+    pub side: String,
+    #[serde(rename = "v")]
+    pub size: Decimal,
+    #[serde(rename = "p")]
+    pub price: Decimal,
+    #[serde(rename = "i")]
+    pub trade_id: String,
+}
+
+/// Bybit liquidation stream data
+#[derive(Debug, Deserialize, Clone)]
+pub struct BybitStreamLiquidation {
+    #[serde(rename = "T")]
+    pub timestamp: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "S")]
+    pub side: String,
+    #[serde(rename = "v")]
+    pub size: Decimal,
+    #[serde(rename = "p")]
+    pub price: Decimal,
+}
+
+/// Bybit private stream message envelope
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BybitPrivateStreamEvent<T> {
+    pub id: String,
+    pub topic: String,
+    /// Milliseconds
+    pub creation_time: u64,
+    pub data: T,
+}
+
+/// Bybit order stream data, the REST order with its category
+#[derive(Debug, Deserialize)]
+pub struct BybitStreamOrder {
+    pub category: String,
+    #[serde(flatten)]
+    pub order: OrderBybit,
+}
+
+/// Bybit execution stream data
+#[derive(Debug, Deserialize)]
+pub struct BybitStreamExecution {
+    pub category: String,
+    #[serde(flatten)]
+    pub execution: ExecutionBybit,
+}
+
+/// Bybit position stream data, the stream sends the average price as
+/// `entryPrice`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BybitStreamPosition {
+    pub category: String,
+    #[serde(default, deserialize_with = "de::decimal")]
+    pub entry_price: Decimal,
+    #[serde(flatten)]
+    pub position: PositionBybit,
+}
@@ -1,4 +1,5 @@
 use serde::Deserialize;
+use std::collections::HashMap;
 
 /// Bybit executed order (trade) response structure
 #[derive(Debug, Deserialize, Clone)]
@@ -6,11 +7,23 @@ use serde::Deserialize;
 pub struct TradeResponse {
     pub ret_code: i32,
     pub ret_msg: String,
-    //pub result: TradeResult,
+    /// Ids of the created order, empty when the order was rejected
+    #[serde(default)]
+    pub result: CreatedOrder,
     pub ret_ext_info: serde_json::Value,
     pub time: u64,
 }
 
+/// Bybit created order ids
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedOrder {
+    #[serde(default)]
+    pub order_id: String,
+    #[serde(default)]
+    pub order_link_id: String,
+}
+
 /// Bybit executed order (trade) result structure
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -25,3 +38,41 @@ pub struct TradeBybit {
     pub order_link: String,
 }
 
+
+/// Bybit trade WebSocket response, answering the request of the same `req_id`
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeWsResponse {
+    pub req_id: String,
+    pub ret_code: i32,
+    pub ret_msg: String,
+    pub op: String,
+    /// Ids of the order, empty when the request was rejected
+    #[serde(default)]
+    pub data: CreatedOrder,
+    #[serde(default)]
+    pub ret_ext_info: serde_json::Value,
+    /// Rate limit and `Timenow` headers
+    #[serde(default)]
+    pub header: HashMap<String, String>,
+    #[serde(default)]
+    pub conn_id: String,
+}
+
+impl From<TradeWsResponse> for TradeResponse {
+    fn from(response: TradeWsResponse) -> Self {
+        let time = response
+            .header
+            .get("Timenow")
+            .and_then(|time| time.parse().ok())
+            .unwrap_or_default();
+
+        Self {
+            ret_code: response.ret_code,
+            ret_msg: response.ret_msg,
+            result: response.data,
+            ret_ext_info: response.ret_ext_info,
+            time,
+        }
+    }
+}
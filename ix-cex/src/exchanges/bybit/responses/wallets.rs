@@ -42,8 +42,10 @@ pub struct WalletBalance {
     #[serde(default)]
     pub total_perp_upl: String,
     pub total_initial_margin: String,
+    #[serde(default)]
     pub total_initial_margin_by_mp: String,
     pub total_maintenance_margin: String,
+    #[serde(default)]
     pub total_maintenance_margin_by_mp: String,
     pub coin: Vec<CoinBalance>,
 }
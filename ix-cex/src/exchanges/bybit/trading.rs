@@ -0,0 +1,387 @@
+// ix-cex/src/exchanges/bybit/trading.rs
+use crate::exchanges::TradingClient;
+use crate::exchanges::bybit::clients::BybitPrivateClient;
+use crate::exchanges::bybit::methods::orders::check_ret_code;
+use crate::exchanges::bybit::order_entry::OrderEntry;
+use crate::exchanges::bybit::requests::orders::{
+    AmendRequest, CancelRequest, Category, MarketUnit, OrderRef, OrderRequest, Side,
+};
+use crate::exchanges::bybit::requests::positions::PageQuery;
+use crate::exchanges::bybit::responses::instruments::LotSize as BybitLotSize;
+use crate::exchanges::bybit::responses::orders::OrderBybit;
+use crate::exchanges::bybit::responses::{CoinBalance, ExecutionBybit, TradeResponse};
+use crate::exchanges::timestamp_from_millis;
+use crate::models::trades::TradeSide;
+use crate::models::trading::{
+    AmendOrder, Balance, Fill, LotSize, NewOrder, OrderAck, OrderKey, OrderQty,
+    OrderState, OrderStatus, OrderType,
+};
+use chrono::{DateTime, Utc};
+use ix_results::errors::{ExchangeError, Result};
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+/// [`TradingClient`] of one Bybit category.
+///
+/// Orders are sent over REST unless another [`OrderEntry`] is set with
+/// [`BybitTradingClient::with_order_entry`], e.g. the trade WebSocket, the
+/// other requests stay on REST.
+pub struct BybitTradingClient {
+    client: BybitPrivateClient,
+    entry: Box<dyn OrderEntry>,
+    category: Category,
+    account_type: String,
+    /// Settle coin of the derivative requests without a symbol
+    settle_coin: String,
+}
+
+impl BybitTradingClient {
+    pub fn new(client: BybitPrivateClient, category: Category) -> Self {
+        Self {
+            entry: Box::new(client.clone()),
+            client,
+            category,
+            account_type: "UNIFIED".to_string(),
+            settle_coin: "USDT".to_string(),
+        }
+    }
+
+    /// Send the orders with `entry` instead of REST
+    pub fn with_order_entry(mut self, entry: impl OrderEntry + 'static) -> Self {
+        self.entry = Box::new(entry);
+        self
+    }
+
+    /// Account of the balances, `UNIFIED` by default
+    pub fn with_account_type(mut self, account_type: impl Into<String>) -> Self {
+        self.account_type = account_type.into();
+        self
+    }
+
+    /// Settle coin of the derivative orders listed or cancelled without a
+    /// symbol, `USDT` by default
+    pub fn with_settle_coin(mut self, settle_coin: impl Into<String>) -> Self {
+        self.settle_coin = settle_coin.into();
+        self
+    }
+
+    pub fn client(&self) -> &BybitPrivateClient {
+        &self.client
+    }
+
+    pub fn category(&self) -> Category {
+        self.category
+    }
+
+    /// Request of a new order. Spot market orders may be sized in the quote
+    /// coin, the other orders in the base coin only.
+    pub fn order_request(&self, order: &NewOrder) -> Result<OrderRequest> {
+        order.validate()?;
+
+        let side = bybit_side(order.side);
+        let qty = order.qty.value();
+        let mut builder = match (order.order_type, order.price) {
+            (OrderType::Limit, Some(price)) => {
+                OrderRequest::limit(self.category, &order.symbol, side, qty, price)
+            }
+            _ => OrderRequest::market(self.category, &order.symbol, side, qty),
+        };
+
+        match (self.category, order.order_type, order.qty) {
+            (Category::Spot, OrderType::Market, OrderQty::Base(_)) => {
+                builder = builder.market_unit(MarketUnit::BaseCoin);
+            }
+            (Category::Spot, OrderType::Market, OrderQty::Quote(_)) => {
+                builder = builder.market_unit(MarketUnit::QuoteCoin);
+            }
+            (category, _, OrderQty::Quote(_)) => {
+                return Err(ExchangeError::InvalidRequest(format!(
+                    "Bybit {category} orders are sized in the base coin"
+                )));
+            }
+            _ => {}
+        }
+
+        if let Some(client_order_id) = &order.client_order_id {
+            builder = builder.order_link_id(client_order_id);
+        }
+        builder.build()
+    }
+
+    /// Request of an amend
+    pub fn amend_request(&self, amend: &AmendOrder) -> Result<AmendRequest> {
+        amend.validate()?;
+
+        let mut request =
+            AmendRequest::new(self.category, &amend.symbol, order_ref(&amend.order));
+        if let Some(qty) = amend.qty {
+            request = request.qty(qty);
+        }
+        if let Some(price) = amend.price {
+            request = request.price(price);
+        }
+        Ok(request)
+    }
+
+    /// Settle coin of the requests without a symbol, spot has none
+    fn settle_coin(&self, symbol: Option<&str>) -> Option<&str> {
+        match (self.category, symbol) {
+            (Category::Spot, _) | (_, Some(_)) => None,
+            _ => Some(&self.settle_coin),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl TradingClient for BybitTradingClient {
+    fn exchange_name(&self) -> &str {
+        "Bybit"
+    }
+
+    fn transport(&self) -> &'static str {
+        self.entry.transport()
+    }
+
+    async fn balances(&self) -> Result<Vec<Balance>> {
+        let response = self
+            .client
+            .get_wallet_balance(&self.account_type, None)
+            .await?;
+        check_ret_code(response.ret_code, &response.ret_msg)?;
+
+        response
+            .result
+            .list
+            .iter()
+            .flat_map(|wallet| &wallet.coin)
+            .map(Balance::try_from)
+            .collect()
+    }
+
+    async fn place_order(&self, order: &NewOrder) -> Result<OrderAck> {
+        let request = self.order_request(order)?;
+        self.entry.place_order(&request).await.map(order_ack)
+    }
+
+    async fn amend_order(&self, amend: &AmendOrder) -> Result<OrderAck> {
+        let request = self.amend_request(amend)?;
+        self.entry.amend_order(&request).await.map(order_ack)
+    }
+
+    async fn cancel_order(&self, symbol: &str, order: &OrderKey) -> Result<OrderAck> {
+        let request = CancelRequest::new(self.category, symbol, order_ref(order));
+        self.entry.cancel_order(&request).await.map(order_ack)
+    }
+
+    async fn cancel_all_orders(&self, symbol: Option<&str>) -> Result<()> {
+        let response = self
+            .client
+            .cancel_orders(
+                self.category.as_str(),
+                symbol,
+                None,
+                self.settle_coin(symbol),
+                None,
+            )
+            .await?;
+        check_ret_code(response.ret_code, &response.ret_msg)
+    }
+
+    async fn get_order(
+        &self,
+        _symbol: &str,
+        order: &OrderKey,
+    ) -> Result<Option<OrderState>> {
+        let category = self.category.as_str();
+        let response = match order {
+            OrderKey::OrderId(id) => self.client.get_order_by_id(category, id).await?,
+            OrderKey::ClientOrderId(id) => {
+                self.client.get_order_by_link_id(category, id).await?
+            }
+        };
+        check_ret_code(response.ret_code, &response.ret_msg)?;
+
+        response
+            .result
+            .list
+            .first()
+            .map(OrderState::try_from)
+            .transpose()
+    }
+
+    async fn open_orders(&self, symbol: Option<&str>) -> Result<Vec<OrderState>> {
+        self.client
+            .get_open_orders(self.category, symbol, self.settle_coin(symbol))
+            .await?
+            .iter()
+            .map(OrderState::try_from)
+            .collect()
+    }
+
+    async fn fills(
+        &self,
+        symbol: &str,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Fill>> {
+        let mut query = PageQuery::new(self.category).symbol(symbol);
+        if let Some(since) = since {
+            query = query.start_time(since.timestamp_millis().max(0) as u64);
+        }
+
+        // Newest first, funding and settlements are no fills
+        let mut fills = self
+            .client
+            .get_executions(query)
+            .await?
+            .iter()
+            .filter(|execution| execution.exec_type == "Trade")
+            .map(Fill::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        fills.reverse();
+        Ok(fills)
+    }
+
+    async fn lot_size(&self, symbol: &str) -> Result<LotSize> {
+        let response = self
+            .client
+            .get_instrument_info(self.category.as_str(), Some(symbol))
+            .await?;
+        check_ret_code(response.ret_code, &response.ret_msg)?;
+
+        let instrument = response.result.list.first().ok_or_else(|| {
+            ExchangeError::InvalidTradingPair {
+                pair: symbol.to_string(),
+            }
+        })?;
+        LotSize::try_from(&instrument.lot_size_filter)
+    }
+}
+
+/// Normalized status of a Bybit `orderStatus`, unknown ones are still open
+pub fn order_status(status: &str) -> OrderStatus {
+    match status {
+        "PartiallyFilled" => OrderStatus::PartiallyFilled,
+        "Filled" => OrderStatus::Filled,
+        "Cancelled" | "PartiallyFilledCanceled" | "Deactivated" => OrderStatus::Cancelled,
+        "Rejected" => OrderStatus::Rejected,
+        // New, Created, Untriggered, Triggered, Active
+        _ => OrderStatus::New,
+    }
+}
+
+impl TryFrom<&OrderBybit> for OrderState {
+    type Error = ExchangeError;
+
+    fn try_from(order: &OrderBybit) -> Result<Self> {
+        Ok(Self {
+            symbol: order.symbol.clone(),
+            order_id: order.order_id.clone(),
+            client_order_id: order.order_link_id.clone(),
+            side: trade_side(&order.side)?,
+            order_type: match order.order_type.as_str() {
+                "Limit" => OrderType::Limit,
+                _ => OrderType::Market,
+            },
+            qty: order.qty,
+            price: order.price,
+            status: order_status(&order.order_status),
+            filled_qty: order.cum_exec_qty,
+            avg_price: order.avg_price,
+            fee: order.cum_exec_fee,
+            reject_reason: match order.reject_reason.as_str() {
+                "EC_NoError" => String::new(),
+                reason => reason.to_string(),
+            },
+            updated: timestamp_from_millis(order.updated_time),
+        })
+    }
+}
+
+impl TryFrom<&ExecutionBybit> for Fill {
+    type Error = ExchangeError;
+
+    fn try_from(execution: &ExecutionBybit) -> Result<Self> {
+        Ok(Self {
+            symbol: execution.symbol.clone(),
+            order_id: execution.order_id.clone(),
+            trade_id: execution.exec_id.clone(),
+            side: trade_side(&execution.side)?,
+            price: execution.exec_price,
+            qty: execution.exec_qty,
+            fee: execution.exec_fee,
+            fee_coin: execution.fee_currency.clone(),
+            is_maker: execution.is_maker,
+            timestamp: timestamp_from_millis(execution.exec_time),
+        })
+    }
+}
+
+impl TryFrom<&CoinBalance> for Balance {
+    type Error = ExchangeError;
+
+    fn try_from(coin: &CoinBalance) -> Result<Self> {
+        let wallet_balance = decimal(&coin.wallet_balance)?;
+        let locked = decimal(coin.locked.as_deref().unwrap_or_default())?;
+
+        Ok(Self {
+            coin: coin.coin.clone(),
+            total: decimal(&coin.equity)?,
+            free: wallet_balance - locked,
+            locked,
+        })
+    }
+}
+
+impl TryFrom<&BybitLotSize> for LotSize {
+    type Error = ExchangeError;
+
+    fn try_from(lot: &BybitLotSize) -> Result<Self> {
+        Ok(Self {
+            base_precision: decimal(&lot.base_precision)?,
+            quote_precision: decimal(&lot.quote_precision)?,
+            min_order_qty: decimal(&lot.min_order_qty)?,
+            min_order_amt: decimal(&lot.min_order_amt)?,
+        })
+    }
+}
+
+fn bybit_side(side: TradeSide) -> Side {
+    match side {
+        TradeSide::Buy => Side::Buy,
+        TradeSide::Sell => Side::Sell,
+    }
+}
+
+fn order_ref(order: &OrderKey) -> OrderRef {
+    match order {
+        OrderKey::OrderId(id) => OrderRef::OrderId(id.clone()),
+        OrderKey::ClientOrderId(id) => OrderRef::OrderLinkId(id.clone()),
+    }
+}
+
+fn order_ack(response: TradeResponse) -> OrderAck {
+    OrderAck {
+        order_id: response.result.order_id,
+        client_order_id: response.result.order_link_id,
+    }
+}
+
+fn trade_side(side: &str) -> Result<TradeSide> {
+    TradeSide::parse_from_str(side).ok_or_else(|| ExchangeError::ApiError {
+        exchange: "Bybit".to_string(),
+        message: format!("Unknown side {side}"),
+        code: None,
+    })
+}
+
+/// Decimal string, empty strings are zero
+fn decimal(value: &str) -> Result<Decimal> {
+    if value.is_empty() {
+        return Ok(Decimal::ZERO);
+    }
+    Decimal::from_str(value).map_err(|e| ExchangeError::ApiError {
+        exchange: "Bybit".to_string(),
+        message: format!("Invalid decimal {value}: {e}"),
+        code: None,
+    })
+}
@@ -1,9 +1,11 @@
 use crate::client::http_client::{HttpClient, RetryConfig, RetryableHttpClient};
-use crate::models::orderbook::{Orderbook, PriceLevel, TradingPair};
+use crate::models::instruments::{Instrument, ListedInstrument};
+use crate::models::orderbook::{Orderbook, PriceLevel};
 use crate::exchanges::coinbase::responses::{orderbook, trades};
 
 use chrono::Utc;
 use ix_results::errors::{ExchangeError, Result};
+use rust_decimal::Decimal;
 use serde::Deserialize;
 use std::str::FromStr;
 use tracing::{debug, info};
@@ -34,7 +36,7 @@ impl CoinbaseClient {
     /// Get order book snapshot for a trading pair
     pub async fn get_orderbook(
         &self,
-        pair: TradingPair,
+        pair: Instrument,
         depth: Option<u32>,
     ) -> Result<Orderbook> {
         let product_id = pair.to_exchange_symbol("coinbase");
@@ -61,30 +63,33 @@ impl CoinbaseClient {
             response.pricebook.asks.len()
         );
 
-        self.convert_to_orderbook(response, product_id)
+        self.convert_to_orderbook(response, &pair)
     }
 
     /// Convert Coinbase response to our OrderBook format
-    fn convert_to_orderbook(
+    pub fn convert_to_orderbook(
         &self,
         response: orderbook::CoinbaseProductBookResponse,
-        symbol: String,
+        pair: &Instrument,
     ) -> Result<Orderbook> {
+        let symbol = pair.to_exchange_symbol("coinbase");
         let mut v_bids = Vec::new();
         let mut v_asks = Vec::new();
 
         // Convert bids
         for bid in response.pricebook.bids {
             let price =
-                f64::from_str(&bid.price).map_err(|e| ExchangeError::ApiError {
+                Decimal::from_str(&bid.price).map_err(|e| ExchangeError::ApiError {
                     exchange: "Coinbase".to_string(),
                     message: format!("Invalid bid price '{}': {}", bid.price, e),
+                    code: None,
                 })?;
 
             let quantity =
-                f64::from_str(&bid.size).map_err(|e| ExchangeError::ApiError {
+                Decimal::from_str(&bid.size).map_err(|e| ExchangeError::ApiError {
                     exchange: "Coinbase".to_string(),
                     message: format!("Invalid bid size '{}': {}", bid.size, e),
+                    code: None,
                 })?;
 
             // orderbook.bids.push(PriceLevel { price, quantity });
@@ -94,15 +99,17 @@ impl CoinbaseClient {
         // Convert asks
         for ask in response.pricebook.asks {
             let price =
-                f64::from_str(&ask.price).map_err(|e| ExchangeError::ApiError {
+                Decimal::from_str(&ask.price).map_err(|e| ExchangeError::ApiError {
                     exchange: "Coinbase".to_string(),
                     message: format!("Invalid ask price '{}': {}", ask.price, e),
+                    code: None,
                 })?;
 
             let quantity =
-                f64::from_str(&ask.size).map_err(|e| ExchangeError::ApiError {
+                Decimal::from_str(&ask.size).map_err(|e| ExchangeError::ApiError {
                     exchange: "Coinbase".to_string(),
                     message: format!("Invalid ask size '{}': {}", ask.size, e),
+                    code: None,
                 })?;
 
             // orderbook.asks.push(PriceLevel { price, quantity });
@@ -118,13 +125,15 @@ impl CoinbaseClient {
             v_asks,
             None,
             None,
-        );
+        )
+        .with_registered_tick_size(pair);
 
         // Validate the orderbook
         if !orderbook.is_valid() {
             return Err(ExchangeError::ApiError {
                 exchange: "Coinbase".to_string(),
                 message: "Received invalid orderbook data".to_string(),
+                code: None,
             });
         }
 
@@ -150,6 +159,23 @@ impl CoinbaseClient {
         Ok(response.products)
     }
 
+    /// List the spot products currently enabled, with their product ids
+    pub async fn list_instruments(&self) -> Result<Vec<ListedInstrument>> {
+        let products = self.get_products().await?;
+
+        Ok(products
+            .into_iter()
+            .filter(|product| product.product_type == "SPOT" && !product.is_disabled)
+            .map(|product| {
+                let instrument =
+                    Instrument::spot(&product.base_currency_id, &product.quote_currency_id);
+                // Prices are quoted in quote increments
+                let tick_size = Decimal::from_str(&product.quote_increment).ok();
+                ListedInstrument::new(instrument, product.product_id).with_tick_size(tick_size)
+            })
+            .collect())
+    }
+
     /// Get specific product information
     pub async fn get_product(&self, product_id: &str) -> Result<CoinbaseProduct> {
         info!("Fetching Coinbase product info for {}", product_id);
@@ -0,0 +1,443 @@
+// ix-cex/src/exchanges/coinbase/coinbase_wss.rs
+use crate::client::wss_client::{ConnectionEvent, WssClient, WssConfig, WssEvent};
+use crate::exchanges::coinbase::coinbase_client::CoinbaseClient;
+use crate::exchanges::coinbase::responses::streams::{
+    CoinbaseHeartbeatEvent, CoinbaseLevel2Event, CoinbaseStreamEvent,
+    CoinbaseStreamTrade, CoinbaseTradesEvent,
+};
+use crate::exchanges::{
+    LiquidationStream, OrderbookStream, STREAM_CHANNEL_CAPACITY, TradeStream,
+    receiver_stream,
+};
+use crate::models::book::LevelBook;
+use crate::models::instruments::Instrument;
+use crate::models::orderbook::Orderbook;
+use crate::models::trades::{PublicTrade, TradeSide};
+use crate::results::errors::ExchangeError;
+use rust_decimal::prelude::ToPrimitive;
+use serde_json::json;
+use std::collections::HashMap;
+use tokio::sync::mpsc;
+use tracing::{debug, error, info, warn};
+use url::Url;
+
+pub const COINBASE_WS_URL: &str = "wss://advanced-trade-ws.coinbase.com";
+
+/// Levels per side published when no depth is requested
+const DEFAULT_DEPTH: u32 = 50;
+
+/// Coinbase Advanced Trade market data channels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoinbaseChannel {
+    Level2,
+    MarketTrades,
+    Heartbeats,
+}
+
+impl CoinbaseChannel {
+    /// Name used in subscribe messages
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CoinbaseChannel::Level2 => "level2",
+            CoinbaseChannel::MarketTrades => "market_trades",
+            CoinbaseChannel::Heartbeats => "heartbeats",
+        }
+    }
+}
+
+/// Normalized market data produced by the Coinbase WebSocket client
+#[derive(Debug, Clone)]
+pub enum CoinbaseWssEvent {
+    Orderbook(Orderbook),
+    Trade(PublicTrade),
+}
+
+/// Result of handling one Coinbase message
+#[derive(Debug, Default)]
+pub struct CoinbaseFeedUpdate {
+    pub events: Vec<CoinbaseWssEvent>,
+    /// Expected and received sequence numbers when the message did not follow
+    /// the previous one. The books were dropped and level2 must be resubscribed
+    pub sequence_gap: Option<(u64, u64)>,
+}
+
+/// Coinbase message handling state: order books per product, the last
+/// sequence number of the connection and the last trade id per product.
+///
+/// `sequence_num` is shared by every channel of a connection, so a gap on
+/// any channel may hide level2 updates and invalidates all books. Books stay
+/// unpublished until a new level2 snapshot arrives.
+#[derive(Debug)]
+pub struct CoinbaseFeed {
+    depth: usize,
+    books: HashMap<String, LevelBook>,
+    last_sequence: Option<u64>,
+    last_trade_ids: HashMap<String, u64>,
+}
+
+impl CoinbaseFeed {
+    pub fn new(depth: usize) -> Self {
+        Self {
+            depth,
+            books: HashMap::new(),
+            last_sequence: None,
+            last_trade_ids: HashMap::new(),
+        }
+    }
+
+    /// Forget the connection state, sequence numbers restart on a new
+    /// connection. Trade ids are kept to skip the replayed trade snapshot.
+    pub fn reset(&mut self) {
+        self.books.clear();
+        self.last_sequence = None;
+    }
+
+    /// Check if a level2 snapshot was applied for the product
+    pub fn is_synced(&self, product_id: &str) -> bool {
+        self.books.contains_key(product_id)
+    }
+
+    /// Handle one raw message
+    pub fn on_message(
+        &mut self,
+        text: &str,
+    ) -> Result<CoinbaseFeedUpdate, serde_json::Error> {
+        let value: serde_json::Value = serde_json::from_str(text)?;
+        let mut update = CoinbaseFeedUpdate::default();
+
+        let channel = match value.get("channel").and_then(|c| c.as_str()) {
+            Some(channel) => channel.to_string(),
+            None => {
+                warn!("Coinbase message without channel: {}", text);
+                return Ok(update);
+            }
+        };
+
+        if let Some(sequence) = value.get("sequence_num").and_then(|s| s.as_u64()) {
+            update.sequence_gap = self.check_sequence(sequence);
+        }
+
+        match channel.as_str() {
+            "l2_data" => {
+                let event: CoinbaseStreamEvent<CoinbaseLevel2Event> =
+                    serde_json::from_value(value)?;
+                for orderbook in self.apply_level2(&event) {
+                    update.events.push(CoinbaseWssEvent::Orderbook(orderbook));
+                }
+            }
+            "market_trades" => {
+                let event: CoinbaseStreamEvent<CoinbaseTradesEvent> =
+                    serde_json::from_value(value)?;
+                for trade in self.new_trades(&event) {
+                    update.events.push(CoinbaseWssEvent::Trade(trade));
+                }
+            }
+            "heartbeats" => {
+                let event: CoinbaseStreamEvent<CoinbaseHeartbeatEvent> =
+                    serde_json::from_value(value)?;
+                if let Some(heartbeat) = event.events.first() {
+                    debug!("Coinbase heartbeat {}", heartbeat.heartbeat_counter);
+                }
+            }
+            "subscriptions" => debug!("Coinbase subscriptions: {}", text),
+            other => debug!("Ignoring Coinbase channel {}", other),
+        }
+
+        Ok(update)
+    }
+
+    /// Track the connection sequence, dropping the books on a gap
+    fn check_sequence(&mut self, sequence: u64) -> Option<(u64, u64)> {
+        match self.last_sequence {
+            // Duplicate or out of order, keep the highest seen
+            Some(last) if sequence <= last => None,
+            Some(last) if sequence != last + 1 => {
+                self.last_sequence = Some(sequence);
+                self.books.clear();
+                Some((last + 1, sequence))
+            }
+            _ => {
+                self.last_sequence = Some(sequence);
+                None
+            }
+        }
+    }
+
+    /// Apply the level2 events of a message, returning the updated book after
+    /// each event. A snapshot event replaces the book, updates for a product
+    /// without a snapshot are dropped.
+    fn apply_level2(
+        &mut self,
+        event: &CoinbaseStreamEvent<CoinbaseLevel2Event>,
+    ) -> Vec<Orderbook> {
+        let mut orderbooks = Vec::with_capacity(event.events.len());
+
+        for l2_event in &event.events {
+            let book = if l2_event.event_type == "snapshot" {
+                let book = self.books.entry(l2_event.product_id.clone()).or_default();
+                book.clear();
+                book
+            } else {
+                match self.books.get_mut(&l2_event.product_id) {
+                    Some(book) => book,
+                    None => continue,
+                }
+            };
+
+            for update in &l2_event.updates {
+                match update.side.as_str() {
+                    "bid" => book.update_bid(update.price_level, update.new_quantity),
+                    "offer" | "ask" => {
+                        book.update_ask(update.price_level, update.new_quantity)
+                    }
+                    side => warn!("Unknown Coinbase level2 side: {}", side),
+                }
+            }
+
+            orderbooks.push(book.to_orderbook(
+                &l2_event.product_id,
+                "Coinbase",
+                event.timestamp,
+                Some(self.depth),
+                None,
+                Some(event.sequence_num),
+            ));
+        }
+
+        orderbooks
+    }
+
+    /// Normalize trades not seen before, trade ids increase per product
+    fn new_trades(
+        &mut self,
+        event: &CoinbaseStreamEvent<CoinbaseTradesEvent>,
+    ) -> Vec<PublicTrade> {
+        let mut trades = Vec::new();
+
+        // Snapshots list the most recent trade first
+        let mut event_trades: Vec<&CoinbaseStreamTrade> =
+            event.events.iter().flat_map(|e| &e.trades).collect();
+        event_trades.sort_by_key(|trade| trade.time);
+
+        for trade in event_trades {
+            if let Ok(trade_id) = trade.trade_id.parse::<u64>() {
+                let last = self
+                    .last_trade_ids
+                    .entry(trade.product_id.clone())
+                    .or_default();
+                if trade_id <= *last {
+                    continue;
+                }
+                *last = trade_id;
+            }
+
+            match to_public_trade(trade) {
+                Some(trade) => trades.push(trade),
+                None => warn!("Unknown Coinbase trade side: {}", trade.side),
+            }
+        }
+
+        trades
+    }
+}
+
+/// Coinbase Advanced Trade WebSocket client.
+///
+/// Subscribes the requested channels plus `heartbeats` on one connection,
+/// keeps a level2 book per product and resubscribes level2 whenever the
+/// connection sequence skips a message.
+pub struct CoinbaseWssClient {
+    product_ids: Vec<String>,
+    channels: Vec<CoinbaseChannel>,
+    depth: usize,
+}
+
+impl CoinbaseWssClient {
+    pub fn new(product_ids: Vec<String>) -> Self {
+        Self {
+            product_ids,
+            channels: vec![CoinbaseChannel::Heartbeats],
+            depth: DEFAULT_DEPTH as usize,
+        }
+    }
+
+    /// Maintain level2 books, publishing `depth` levels per side
+    pub fn with_level2(mut self, depth: usize) -> Self {
+        self.channels.push(CoinbaseChannel::Level2);
+        self.depth = depth;
+        self
+    }
+
+    /// Publish market trades
+    pub fn with_market_trades(mut self) -> Self {
+        self.channels.push(CoinbaseChannel::MarketTrades);
+        self
+    }
+
+    fn channel_message(&self, kind: &str, channel: CoinbaseChannel) -> String {
+        json!({
+            "type": kind,
+            "product_ids": self.product_ids,
+            "channel": channel.as_str(),
+        })
+        .to_string()
+    }
+
+    /// Connect and forward normalized events to `tx` until it is closed
+    pub async fn run(
+        self,
+        tx: mpsc::Sender<CoinbaseWssEvent>,
+    ) -> Result<(), ExchangeError> {
+        Url::parse(COINBASE_WS_URL)?;
+
+        let mut client = WssClient::new(WssConfig::new(COINBASE_WS_URL));
+        for channel in &self.channels {
+            client =
+                client.with_subscription(self.channel_message("subscribe", *channel));
+        }
+
+        let mut connection = client.spawn();
+        let handle = connection.handle();
+        let mut feed = CoinbaseFeed::new(self.depth);
+
+        // Main message processing loop
+        while let Some(event) = connection.recv().await {
+            let text = match event {
+                WssEvent::Connection(event) => {
+                    info!("Coinbase WebSocket {}", event);
+                    if let ConnectionEvent::Connected { .. } = event {
+                        feed.reset();
+                    }
+                    continue;
+                }
+                WssEvent::Text(text) => text,
+            };
+
+            let update = match feed.on_message(&text) {
+                Ok(update) => update,
+                Err(e) => {
+                    warn!("Failed to deserialize message: {}. Text: {}", e, text);
+                    continue;
+                }
+            };
+
+            if let Some((expected, received)) = update.sequence_gap {
+                warn!(
+                    "Coinbase sequence gap: expected {}, received {}",
+                    expected, received
+                );
+                if self.channels.contains(&CoinbaseChannel::Level2) {
+                    let level2 = CoinbaseChannel::Level2;
+                    handle
+                        .send(self.channel_message("unsubscribe", level2))
+                        .await?;
+                    handle
+                        .send(self.channel_message("subscribe", level2))
+                        .await?;
+                }
+            }
+
+            for event in update.events {
+                if tx.send(event).await.is_err() {
+                    error!("Receiver dropped. Shutting down websocket client.");
+                    return Ok(());
+                }
+            }
+        }
+
+        warn!("WebSocket client loop terminated.");
+        connection.finish().await
+    }
+}
+
+impl CoinbaseClient {
+    /// Stream the order book maintained from the level2 channel
+    pub fn subscribe_orderbook(
+        &self,
+        pair: Instrument,
+        depth: Option<u32>,
+    ) -> ix_results::errors::Result<OrderbookStream> {
+        let product_id = pair.to_exchange_symbol("coinbase");
+        let depth = depth.unwrap_or(DEFAULT_DEPTH) as usize;
+
+        let (event_tx, mut event_rx) = mpsc::channel(STREAM_CHANNEL_CAPACITY);
+        let (tx, rx) = mpsc::channel(STREAM_CHANNEL_CAPACITY);
+
+        let client = CoinbaseWssClient::new(vec![product_id]).with_level2(depth);
+
+        tokio::spawn(async move {
+            if let Err(e) = client.run(event_tx).await {
+                error!("Coinbase level2 stream failed: {}", e);
+            }
+        });
+
+        tokio::spawn(async move {
+            while let Some(event) = event_rx.recv().await {
+                if let CoinbaseWssEvent::Orderbook(orderbook) = event
+                    && tx
+                        .send(orderbook.with_registered_tick_size(&pair))
+                        .await
+                        .is_err()
+                {
+                    return;
+                }
+            }
+        });
+
+        Ok(receiver_stream(rx))
+    }
+
+    /// Stream public trades from the market_trades channel
+    pub fn subscribe_trades(
+        &self,
+        pair: Instrument,
+    ) -> ix_results::errors::Result<TradeStream> {
+        let product_id = pair.to_exchange_symbol("coinbase");
+
+        let (event_tx, mut event_rx) = mpsc::channel(STREAM_CHANNEL_CAPACITY);
+        let (tx, rx) = mpsc::channel(STREAM_CHANNEL_CAPACITY);
+
+        let client = CoinbaseWssClient::new(vec![product_id]).with_market_trades();
+
+        tokio::spawn(async move {
+            if let Err(e) = client.run(event_tx).await {
+                error!("Coinbase market_trades stream failed: {}", e);
+            }
+        });
+
+        tokio::spawn(async move {
+            while let Some(event) = event_rx.recv().await {
+                if let CoinbaseWssEvent::Trade(trade) = event
+                    && tx.send(trade).await.is_err()
+                {
+                    return;
+                }
+            }
+        });
+
+        Ok(receiver_stream(rx))
+    }
+
+    /// Coinbase spot markets have no liquidations
+    pub fn subscribe_liquidations(
+        &self,
+        _pair: Instrument,
+    ) -> ix_results::errors::Result<LiquidationStream> {
+        Err(ix_results::errors::ExchangeError::UnsupportedExchange {
+            exchange: "Coinbase liquidations".to_string(),
+        })
+    }
+}
+
+/// Normalize a market trade
+pub fn to_public_trade(trade: &CoinbaseStreamTrade) -> Option<PublicTrade> {
+    Some(PublicTrade {
+        symbol: trade.product_id.clone(),
+        exchange: "Coinbase".to_string(),
+        timestamp: trade.time,
+        side: TradeSide::parse_from_str(&trade.side)?,
+        price: trade.price.to_f64().unwrap_or_default(),
+        amount: trade.size.to_f64().unwrap_or_default(),
+        trade_id: trade.trade_id.clone(),
+    })
+}
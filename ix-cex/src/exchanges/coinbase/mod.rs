@@ -1,2 +1,3 @@
 pub mod coinbase_client;
+pub mod coinbase_wss;
 pub mod responses;
@@ -1,2 +1,3 @@
 pub mod orderbook;
+pub mod streams;
 pub mod trades;
@@ -0,0 +1,58 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+/// Coinbase Advanced Trade stream message envelope
+#[derive(Debug, Deserialize, Clone)]
+pub struct CoinbaseStreamEvent<T> {
+    pub channel: String,
+    #[serde(default)]
+    pub client_id: String,
+    pub timestamp: DateTime<Utc>,
+    pub sequence_num: u64,
+    pub events: Vec<T>,
+}
+
+/// Coinbase `level2` channel event, `snapshot` or `update`
+#[derive(Debug, Deserialize, Clone)]
+pub struct CoinbaseLevel2Event {
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub product_id: String,
+    pub updates: Vec<CoinbaseLevel2Update>,
+}
+
+/// Coinbase `level2` price level update, quantities are absolute
+#[derive(Debug, Deserialize, Clone)]
+pub struct CoinbaseLevel2Update {
+    pub side: String,
+    pub event_time: DateTime<Utc>,
+    pub price_level: Decimal,
+    pub new_quantity: Decimal,
+}
+
+/// Coinbase `market_trades` channel event
+#[derive(Debug, Deserialize, Clone)]
+pub struct CoinbaseTradesEvent {
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub trades: Vec<CoinbaseStreamTrade>,
+}
+
+/// Coinbase `market_trades` trade
+#[derive(Debug, Deserialize, Clone)]
+pub struct CoinbaseStreamTrade {
+    pub trade_id: String,
+    pub product_id: String,
+    pub price: Decimal,
+    pub size: Decimal,
+    pub side: String,
+    pub time: DateTime<Utc>,
+}
+
+/// Coinbase `heartbeats` channel event
+#[derive(Debug, Deserialize, Clone)]
+pub struct CoinbaseHeartbeatEvent {
+    pub current_time: String,
+    pub heartbeat_counter: u64,
+}
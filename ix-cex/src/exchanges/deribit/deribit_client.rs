@@ -0,0 +1,339 @@
+use crate::client::http_client::{HttpClient, RetryConfig, RetryableHttpClient};
+use crate::exchanges::timestamp_from_millis;
+use crate::models::instruments::{Instrument, ListedInstrument};
+use crate::models::orderbook::{Orderbook, PriceLevel};
+use crate::models::trades::{PublicTrade, TradeSide};
+
+use ix_results::errors::{ExchangeError, Result};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use tracing::{debug, info};
+
+/// Depths accepted by the order book endpoint
+const BOOK_DEPTHS: [u32; 8] = [1, 5, 10, 20, 50, 100, 1000, 10000];
+
+/// Maximum number of trades returned by the trades endpoint
+const MAX_TRADES: u32 = 1000;
+
+/// Deribit v2 REST API client
+#[derive(Clone)]
+pub struct DeribitClient {
+    client: RetryableHttpClient,
+}
+
+impl DeribitClient {
+    /// Create a new Deribit client
+    pub fn new() -> Result<Self> {
+        let http_client = HttpClient::new(
+            "Deribit".to_string(),
+            "https://www.deribit.com".to_string(),
+            10, // Non-matching engine requests are limited to 20 per second
+            30, // 30 second timeout
+        )?;
+
+        let retry_client = RetryableHttpClient::new(http_client, RetryConfig::default());
+
+        Ok(Self {
+            client: retry_client,
+        })
+    }
+
+    /// Get order book snapshot for a trading pair
+    pub async fn get_orderbook(
+        &self,
+        pair: Instrument,
+        depth: Option<u32>,
+    ) -> Result<Orderbook> {
+        let instrument_name = pair.to_exchange_symbol("deribit");
+        let depth = depth.unwrap_or(1000);
+        let depth_str = BOOK_DEPTHS
+            .into_iter()
+            .find(|d| *d >= depth)
+            .unwrap_or(BOOK_DEPTHS[BOOK_DEPTHS.len() - 1])
+            .to_string();
+
+        info!(
+            "Fetching Deribit orderbook for {} with depth {}",
+            instrument_name, depth_str
+        );
+
+        let params = vec![
+            ("instrument_name", instrument_name.as_str()),
+            ("depth", depth_str.as_str()),
+        ];
+
+        let response: DeribitResponse<DeribitOrderbookData> = self
+            .client
+            .get_with_params_retry("/api/v2/public/get_order_book", &params)
+            .await?;
+
+        let data = response.into_result()?;
+
+        debug!(
+            "Received Deribit orderbook with {} bids, {} asks",
+            data.bids.len(),
+            data.asks.len()
+        );
+
+        self.convert_to_orderbook(data, &pair, depth as usize)
+    }
+
+    /// Convert Deribit response to our OrderBook format
+    pub fn convert_to_orderbook(
+        &self,
+        data: DeribitOrderbookData,
+        pair: &Instrument,
+        depth: usize,
+    ) -> Result<Orderbook> {
+        let to_levels = |levels: &[(Decimal, Decimal)]| -> Vec<PriceLevel> {
+            levels
+                .iter()
+                .take(depth)
+                .map(|(price, quantity)| PriceLevel::new(*price, *quantity))
+                .collect()
+        };
+
+        // Final value
+        let orderbook = Orderbook::new(
+            data.instrument_name,
+            "Deribit".to_string(),
+            timestamp_from_millis(data.timestamp),
+            to_levels(&data.bids),
+            to_levels(&data.asks),
+            Some(data.change_id),
+            None,
+        )
+        .with_registered_tick_size(pair);
+
+        // Validate the orderbook
+        if !orderbook.is_valid() {
+            return Err(ExchangeError::ApiError {
+                exchange: "Deribit".to_string(),
+                message: "Received invalid orderbook data".to_string(),
+                code: None,
+            });
+        }
+
+        info!(
+            "Successfully converted Deribit orderbook: {} bids, {} asks, spread: {:?}",
+            orderbook.bids.len(),
+            orderbook.asks.len(),
+            orderbook.spread()
+        );
+
+        Ok(orderbook)
+    }
+
+    /// Get the most recent public trades, newest first
+    pub async fn get_recent_trades(
+        &self,
+        pair: Instrument,
+        limit: Option<u32>,
+    ) -> Result<Vec<PublicTrade>> {
+        let instrument_name = pair.to_exchange_symbol("deribit");
+        let count_str = limit.unwrap_or(100).min(MAX_TRADES).to_string();
+
+        info!("Fetching Deribit recent trades for {}", instrument_name);
+
+        let params = vec![
+            ("instrument_name", instrument_name.as_str()),
+            ("count", count_str.as_str()),
+            ("sorting", "desc"),
+        ];
+
+        let response: DeribitResponse<DeribitTradesData> = self
+            .client
+            .get_with_params_retry(
+                "/api/v2/public/get_last_trades_by_instrument",
+                &params,
+            )
+            .await?;
+
+        response
+            .into_result()?
+            .trades
+            .iter()
+            .map(|trade| trade.to_public_trade())
+            .collect()
+    }
+
+    /// Get instrument metadata for a spot trading pair
+    pub async fn get_instrument(&self, pair: Instrument) -> Result<DeribitInstrument> {
+        let instrument_name = pair.to_exchange_symbol("deribit");
+        info!("Fetching Deribit instrument {}", instrument_name);
+
+        let params = vec![("instrument_name", instrument_name.as_str())];
+
+        let response: DeribitResponse<DeribitInstrument> = self
+            .client
+            .get_with_params_retry("/api/v2/public/get_instrument", &params)
+            .await?;
+
+        response.into_result()
+    }
+
+    /// Get all active instruments of a currency (`BTC`, `ETH`, `USDC`, `any`)
+    /// and kind (`spot`, `future`, `option`)
+    pub async fn get_instruments(
+        &self,
+        currency: &str,
+        kind: &str,
+    ) -> Result<Vec<DeribitInstrument>> {
+        info!("Fetching Deribit {} {} instruments", currency, kind);
+
+        let params = vec![("currency", currency), ("kind", kind)];
+
+        let response: DeribitResponse<Vec<DeribitInstrument>> = self
+            .client
+            .get_with_params_retry("/api/v2/public/get_instruments", &params)
+            .await?;
+
+        response.into_result()
+    }
+
+    /// List the active spot pairs and perpetuals, with their names
+    pub async fn list_instruments(&self) -> Result<Vec<ListedInstrument>> {
+        let mut listed = Vec::new();
+
+        for instrument in self.get_instruments("any", "spot").await? {
+            let tick_size = Some(instrument.tick_size);
+            listed.push(
+                ListedInstrument::new(
+                    Instrument::spot(
+                        &instrument.base_currency,
+                        &instrument.quote_currency,
+                    ),
+                    instrument.instrument_name,
+                )
+                .with_tick_size(tick_size),
+            );
+        }
+
+        for instrument in self.get_instruments("any", "future").await? {
+            if instrument.settlement_period.as_deref() == Some("perpetual") {
+                let tick_size = Some(instrument.tick_size);
+                listed.push(
+                    ListedInstrument::new(
+                        Instrument::perpetual(
+                            &instrument.base_currency,
+                            &instrument.quote_currency,
+                        ),
+                        instrument.instrument_name,
+                    )
+                    .with_tick_size(tick_size),
+                );
+            }
+        }
+
+        Ok(listed)
+    }
+}
+
+/// Deribit JSON-RPC response envelope
+#[derive(Debug, Deserialize)]
+pub struct DeribitResponse<T> {
+    pub result: Option<T>,
+    pub error: Option<DeribitError>,
+}
+
+impl<T> DeribitResponse<T> {
+    /// Result of a successful response
+    pub fn into_result(self) -> Result<T> {
+        if let Some(error) = self.error {
+            return Err(ExchangeError::ApiError {
+                exchange: "Deribit".to_string(),
+                message: format!("Deribit API error {}: {}", error.code, error.message),
+                code: Some(error.code),
+            });
+        }
+
+        self.result.ok_or_else(|| ExchangeError::ApiError {
+            exchange: "Deribit".to_string(),
+            message: "No result found in response".to_string(),
+            code: None,
+        })
+    }
+}
+
+/// Deribit JSON-RPC error
+#[derive(Debug, Deserialize)]
+pub struct DeribitError {
+    pub code: i64,
+    pub message: String,
+}
+
+/// Deribit order book data, levels are `[price, amount]`
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeribitOrderbookData {
+    pub instrument_name: String,
+    pub timestamp: u64,
+    pub change_id: u64,
+    pub bids: Vec<(Decimal, Decimal)>,
+    pub asks: Vec<(Decimal, Decimal)>,
+}
+
+/// Deribit last trades result
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeribitTradesData {
+    pub trades: Vec<DeribitTrade>,
+    pub has_more: bool,
+}
+
+/// Deribit public trade
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeribitTrade {
+    pub trade_id: String,
+    pub trade_seq: u64,
+    pub instrument_name: String,
+    pub timestamp: u64,
+    pub price: f64,
+    pub amount: f64,
+    pub direction: String,
+}
+
+impl DeribitTrade {
+    /// Normalize the trade, the direction is the taker side
+    pub fn to_public_trade(&self) -> Result<PublicTrade> {
+        let side = TradeSide::parse_from_str(&self.direction).ok_or_else(|| {
+            ExchangeError::ApiError {
+                exchange: "Deribit".to_string(),
+                message: format!("Invalid trade direction '{}'", self.direction),
+                code: None,
+            }
+        })?;
+
+        Ok(PublicTrade {
+            symbol: self.instrument_name.clone(),
+            exchange: "Deribit".to_string(),
+            timestamp: timestamp_from_millis(self.timestamp),
+            side,
+            price: self.price,
+            amount: self.amount,
+            trade_id: self.trade_id.clone(),
+        })
+    }
+}
+
+/// Deribit instrument metadata
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeribitInstrument {
+    pub instrument_name: String,
+    pub kind: String,
+    pub base_currency: String,
+    pub quote_currency: String,
+    pub settlement_currency: Option<String>,
+    /// `perpetual`, `day`, `week` or `month` for futures
+    pub settlement_period: Option<String>,
+    pub tick_size: Decimal,
+    pub min_trade_amount: f64,
+    pub contract_size: f64,
+    pub is_active: bool,
+    pub creation_timestamp: u64,
+    pub expiration_timestamp: u64,
+}
+
+impl Default for DeribitClient {
+    fn default() -> Self {
+        Self::new().expect("Failed to create default Deribit client")
+    }
+}
@@ -0,0 +1 @@
+pub mod deribit_client;
@@ -1,8 +1,10 @@
 use crate::client::http_client::{HttpClient, RetryConfig, RetryableHttpClient};
-use crate::models::orderbook::{Orderbook, PriceLevel, TradingPair};
+use crate::models::instruments::{Instrument, ListedInstrument};
+use crate::models::orderbook::{Orderbook, PriceLevel};
 
 use chrono::Utc;
 use ix_results::errors::{ExchangeError, Result};
+use rust_decimal::Decimal;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::str::FromStr;
@@ -34,7 +36,7 @@ impl KrakenClient {
     /// Get order book snapshot for a trading pair
     pub async fn get_orderbook(
         &self,
-        pair: TradingPair,
+        pair: Instrument,
         count: Option<u32>,
     ) -> Result<Orderbook> {
         let product_id = pair.to_exchange_symbol("kraken");
@@ -62,6 +64,7 @@ impl KrakenClient {
             return Err(ExchangeError::ApiError {
                 exchange: "Kraken".to_string(),
                 message: format!("Kraken API errors: {:?}", response.error),
+                code: None,
             });
         }
 
@@ -79,17 +82,19 @@ impl KrakenClient {
                 .ok_or_else(|| ExchangeError::ApiError {
                     exchange: "Kraken".to_string(),
                     message: "No orderbook data found in response".to_string(),
+                    code: None,
                 })?;
 
-        self.convert_to_orderbook(orderbook_data.clone(), product_id)
+        self.convert_to_orderbook(orderbook_data.clone(), &pair)
     }
 
     /// Convert Kraken response to our OrderBook format
-    fn convert_to_orderbook(
+    pub fn convert_to_orderbook(
         &self,
         data: KrakenOrderbookData,
-        symbol: String,
+        pair: &Instrument,
     ) -> Result<Orderbook> {
+        let symbol = pair.to_exchange_symbol("kraken");
         let mut ob_ts: u64 = 0;
         let mut v_bids = Vec::new();
         let mut v_asks = Vec::new();
@@ -102,14 +107,17 @@ impl KrakenClient {
                 ob_ts += delta_ts;
             }
 
-            let price = f64::from_str(&bid.0).map_err(|e| ExchangeError::ApiError {
-                exchange: "Kraken".to_string(),
-                message: format!("Invalid bid price '{}': {}", bid.0, e),
-            })?;
+            let price =
+                Decimal::from_str(&bid.0).map_err(|e| ExchangeError::ApiError {
+                    exchange: "Kraken".to_string(),
+                    message: format!("Invalid bid price '{}': {}", bid.0, e),
+                    code: None,
+                })?;
             let quantity =
-                f64::from_str(&bid.1).map_err(|e| ExchangeError::ApiError {
+                Decimal::from_str(&bid.1).map_err(|e| ExchangeError::ApiError {
                     exchange: "Kraken".to_string(),
                     message: format!("Invalid bid volume '{}': {}", bid.1, e),
+                    code: None,
                 })?;
             v_bids.push(PriceLevel { price, quantity });
         }
@@ -120,14 +128,17 @@ impl KrakenClient {
                 ob_ts += delta_ts;
             }
 
-            let price = f64::from_str(&ask.0).map_err(|e| ExchangeError::ApiError {
-                exchange: "Kraken".to_string(),
-                message: format!("Invalid ask price '{}': {}", ask.0, e),
-            })?;
+            let price =
+                Decimal::from_str(&ask.0).map_err(|e| ExchangeError::ApiError {
+                    exchange: "Kraken".to_string(),
+                    message: format!("Invalid ask price '{}': {}", ask.0, e),
+                    code: None,
+                })?;
             let quantity =
-                f64::from_str(&ask.1).map_err(|e| ExchangeError::ApiError {
+                Decimal::from_str(&ask.1).map_err(|e| ExchangeError::ApiError {
                     exchange: "Kraken".to_string(),
                     message: format!("Invalid ask volume '{}': {}", ask.1, e),
+                    code: None,
                 })?;
             v_asks.push(PriceLevel { price, quantity });
         }
@@ -141,12 +152,14 @@ impl KrakenClient {
             v_asks,
             None,
             None,
-        );
+        )
+        .with_registered_tick_size(pair);
 
         if !orderbook.is_valid() {
             return Err(ExchangeError::ApiError {
                 exchange: "Kraken".to_string(),
                 message: "Received invalid orderbook data".to_string(),
+                code: None,
             });
         }
 
@@ -177,6 +190,7 @@ impl KrakenClient {
             return Err(ExchangeError::ApiError {
                 exchange: "Kraken".to_string(),
                 message: format!("Kraken API errors: {:?}", response.error),
+                code: None,
             });
         }
 
@@ -194,6 +208,7 @@ impl KrakenClient {
             return Err(ExchangeError::ApiError {
                 exchange: "Kraken".to_string(),
                 message: format!("Kraken API errors: {:?}", response.error),
+                code: None,
             });
         }
 
@@ -211,16 +226,70 @@ impl KrakenClient {
             return Err(ExchangeError::ApiError {
                 exchange: "Kraken".to_string(),
                 message: format!("Kraken API errors: {:?}", response.error),
+                code: None,
             });
         }
 
         Ok(response.result)
     }
 
+    /// List the spot pairs currently online, with their REST names.
+    /// The assets are taken from the websocket name, e.g. `XBT/USDT`.
+    pub async fn list_instruments(&self) -> Result<Vec<ListedInstrument>> {
+        let asset_pairs = self.get_asset_pairs().await?;
+
+        Ok(asset_pairs
+            .into_values()
+            .filter(|asset_pair| asset_pair.status == "online")
+            .filter_map(|asset_pair| {
+                let tick_size = asset_pair.price_tick();
+                let wsname = asset_pair.wsname?;
+                let (base, quote) = wsname.split_once('/')?;
+                Some(
+                    ListedInstrument::new(
+                        Instrument::spot(base, quote),
+                        asset_pair.altname,
+                    )
+                    .with_tick_size(Some(tick_size)),
+                )
+            })
+            .collect())
+    }
+
+    /// Get asset pair information for a single trading pair
+    pub async fn get_asset_pair(&self, pair: Instrument) -> Result<KrakenAssetPair> {
+        let pair_name = pair.to_exchange_symbol("kraken");
+        info!("Fetching Kraken asset pair {}", pair_name);
+
+        let params = vec![("pair", pair_name.as_str())];
+        let response: KrakenAssetPairsResponse = self
+            .client
+            .get_with_params_retry("/0/public/AssetPairs", &params)
+            .await?;
+
+        if !response.error.is_empty() {
+            return Err(ExchangeError::ApiError {
+                exchange: "Kraken".to_string(),
+                message: format!("Kraken API errors: {:?}", response.error),
+                code: None,
+            });
+        }
+
+        response
+            .result
+            .into_values()
+            .next()
+            .ok_or_else(|| ExchangeError::ApiError {
+                exchange: "Kraken".to_string(),
+                message: format!("No asset pair data found for {}", pair_name),
+                code: None,
+            })
+    }
+
     /// Get ticker information
     pub async fn get_ticker(
         &self,
-        pair: TradingPair,
+        pair: Instrument,
     ) -> Result<HashMap<String, KrakenTicker>> {
         let pair_name = pair.to_exchange_symbol("kraken");
         info!("Fetching Kraken ticker for {}", pair_name);
@@ -235,6 +304,7 @@ impl KrakenClient {
             return Err(ExchangeError::ApiError {
                 exchange: "Kraken".to_string(),
                 message: format!("Kraken API errors: {:?}", response.error),
+                code: None,
             });
         }
 
@@ -250,16 +320,16 @@ struct KrakenDepthResponse {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct KrakenPriceLevel(
-    String, // price
-    String, // volume
-    u64,    // timestamp
+pub struct KrakenPriceLevel(
+    pub String, // price
+    pub String, // volume
+    pub u64,    // timestamp
 );
 
 #[derive(Debug, Clone, Deserialize)]
-struct KrakenOrderbookData {
-    bids: Vec<KrakenPriceLevel>,
-    asks: Vec<KrakenPriceLevel>,
+pub struct KrakenOrderbookData {
+    pub bids: Vec<KrakenPriceLevel>,
+    pub asks: Vec<KrakenPriceLevel>,
 }
 
 /// Kraken server time response
@@ -325,6 +395,16 @@ pub struct KrakenAssetPair {
     pub short_position_limit: Option<u32>,
 }
 
+impl KrakenAssetPair {
+    /// Minimum price increment, the pair precision when no tick size is set
+    pub fn price_tick(&self) -> Decimal {
+        self.tick_size
+            .as_deref()
+            .and_then(|tick_size| Decimal::from_str(tick_size).ok())
+            .unwrap_or_else(|| Decimal::new(1, self.pair_decimals))
+    }
+}
+
 /// Kraken ticker response
 #[derive(Debug, Deserialize)]
 struct KrakenTickerResponse {
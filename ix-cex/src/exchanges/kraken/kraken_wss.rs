@@ -0,0 +1,472 @@
+// ix-cex/src/exchanges/kraken/kraken_wss.rs
+use crate::client::wss_client::{ConnectionEvent, WssClient, WssConfig, WssEvent};
+use crate::exchanges::kraken::kraken_client::KrakenClient;
+use crate::exchanges::{
+    LiquidationStream, OrderbookStream, STREAM_CHANNEL_CAPACITY, TradeStream,
+    receiver_stream,
+};
+use crate::models::book::LevelBook;
+use crate::models::instruments::Instrument;
+use crate::models::orderbook::Orderbook;
+use crate::models::trades::{PublicTrade, TradeSide};
+use crate::results::errors::ExchangeError;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use tokio::sync::mpsc;
+use tracing::{debug, error, info, warn};
+use url::Url;
+
+pub const KRAKEN_WS_URL: &str = "wss://ws.kraken.com/v2";
+
+/// Book depths offered by the book channel
+const BOOK_DEPTHS: [u32; 5] = [10, 25, 100, 500, 1000];
+
+/// Levels per side published when no depth is requested
+const DEFAULT_DEPTH: u32 = 25;
+
+/// Levels per side covered by the book checksum
+const CHECKSUM_DEPTH: usize = 10;
+
+/// Parse a v2 message, returning it only when it carries channel data.
+/// Method responses, heartbeats and status messages are logged and dropped.
+fn channel_message(text: &str) -> Option<serde_json::Value> {
+    let value: serde_json::Value = match serde_json::from_str(text) {
+        Ok(value) => value,
+        Err(e) => {
+            warn!("Failed to parse message: {}. Text: {}", e, text);
+            return None;
+        }
+    };
+
+    // Method responses (subscribe, pong) carry no channel
+    if let Some(method) = value.get("method") {
+        if value.get("success").and_then(|s| s.as_bool()) == Some(false) {
+            warn!("Kraken {} failed: {}", method, text);
+        } else {
+            debug!("Kraken {} response: {}", method, text);
+        }
+        return None;
+    }
+
+    match value.get("channel").and_then(|c| c.as_str()) {
+        Some("heartbeat") | Some("status") | None => {
+            debug!("Kraken control message: {}", text);
+            None
+        }
+        Some(_) => Some(value),
+    }
+}
+
+pub async fn run_websocket_client<T: DeserializeOwned>(
+    tx: mpsc::Sender<KrakenStreamEvent<T>>,
+    subscription: serde_json::Value,
+) -> Result<(), ExchangeError> {
+    // let subscription = json!({
+    //     "method": "subscribe",
+    //     "params": { "channel": "book", "symbol": ["BTC/USD"], "depth": 10 },
+    // });
+    Url::parse(KRAKEN_WS_URL)?;
+
+    let mut connection = WssClient::new(WssConfig::new(KRAKEN_WS_URL))
+        .with_subscription(subscription.to_string())
+        .spawn();
+
+    // Main message processing loop
+    while let Some(event) = connection.recv().await {
+        let text = match event {
+            WssEvent::Connection(event) => {
+                info!("Kraken WebSocket {}", event);
+                continue;
+            }
+            WssEvent::Text(text) => text,
+        };
+
+        let Some(value) = channel_message(&text) else {
+            continue;
+        };
+
+        match serde_json::from_value::<KrakenStreamEvent<T>>(value) {
+            Ok(event) => {
+                if tx.send(event).await.is_err() {
+                    error!("Receiver dropped. Shutting down websocket client.");
+                    return Ok(());
+                }
+            }
+            Err(e) => warn!("Failed to deserialize message: {}. Text: {}", e, text),
+        }
+    }
+
+    warn!("WebSocket client loop terminated.");
+    connection.finish().await
+}
+
+/// Run the book channel for a single symbol, validating every message
+/// against its checksum and resubscribing when it does not match
+pub async fn run_book_client(
+    tx: mpsc::Sender<Orderbook>,
+    mut book: KrakenBook,
+    ws_symbol: String,
+    channel_depth: u32,
+) -> Result<(), ExchangeError> {
+    Url::parse(KRAKEN_WS_URL)?;
+
+    let book_message = |method: &str| {
+        json!({
+            "method": method,
+            "params": {
+                "channel": "book",
+                "symbol": [ws_symbol],
+                "depth": channel_depth,
+            },
+        })
+        .to_string()
+    };
+
+    let mut connection = WssClient::new(WssConfig::new(KRAKEN_WS_URL))
+        .with_subscription(book_message("subscribe"))
+        .spawn();
+    let handle = connection.handle();
+
+    // Main message processing loop
+    while let Some(event) = connection.recv().await {
+        let text = match event {
+            WssEvent::Connection(event) => {
+                info!("Kraken WebSocket {}", event);
+                if let ConnectionEvent::Connected { .. } = event {
+                    book.reset();
+                }
+                continue;
+            }
+            WssEvent::Text(text) => text,
+        };
+
+        let Some(value) = channel_message(&text) else {
+            continue;
+        };
+
+        let event =
+            match serde_json::from_value::<KrakenStreamEvent<KrakenStreamBook>>(value) {
+                Ok(event) => event,
+                Err(e) => {
+                    warn!("Failed to deserialize message: {}. Text: {}", e, text);
+                    continue;
+                }
+            };
+
+        let update = book.apply(&event);
+
+        if let Some((expected, computed)) = update.checksum_mismatch {
+            warn!(
+                "Kraken {} checksum mismatch: expected {}, computed {}. Resubscribing",
+                ws_symbol, expected, computed
+            );
+            handle.send(book_message("unsubscribe")).await?;
+            handle.send(book_message("subscribe")).await?;
+        }
+
+        for orderbook in update.orderbooks {
+            if tx.send(orderbook).await.is_err() {
+                error!("Receiver dropped. Shutting down websocket client.");
+                return Ok(());
+            }
+        }
+    }
+
+    warn!("WebSocket client loop terminated.");
+    connection.finish().await
+}
+
+impl KrakenClient {
+    /// Stream the order book maintained from the v2 book channel. The pair
+    /// precisions needed for the checksum are fetched from the REST API first.
+    pub fn subscribe_orderbook(
+        &self,
+        pair: Instrument,
+        depth: Option<u32>,
+    ) -> ix_results::errors::Result<OrderbookStream> {
+        let depth = depth.unwrap_or(DEFAULT_DEPTH);
+        let channel_depth = BOOK_DEPTHS
+            .into_iter()
+            .find(|d| *d >= depth)
+            .unwrap_or(BOOK_DEPTHS[BOOK_DEPTHS.len() - 1]);
+        let symbol = pair.to_exchange_symbol("kraken");
+
+        let (tx, rx) = mpsc::channel(STREAM_CHANNEL_CAPACITY);
+        let client = self.clone();
+
+        tokio::spawn(async move {
+            let asset_pair = match client.get_asset_pair(pair.clone()).await {
+                Ok(asset_pair) => asset_pair,
+                Err(e) => {
+                    error!("Failed to fetch Kraken precisions for {}: {}", symbol, e);
+                    return;
+                }
+            };
+
+            let book = KrakenBook::new(
+                &symbol,
+                channel_depth as usize,
+                depth as usize,
+                asset_pair.pair_decimals,
+                asset_pair.lot_decimals,
+            );
+
+            if let Err(e) =
+                run_book_client(tx, book, pair.to_string(), channel_depth).await
+            {
+                error!("Kraken book stream failed: {}", e);
+            }
+        });
+
+        Ok(receiver_stream(rx))
+    }
+
+    /// Stream public trades from the v2 trade channel
+    pub fn subscribe_trades(
+        &self,
+        pair: Instrument,
+    ) -> ix_results::errors::Result<TradeStream> {
+        let symbol = pair.to_exchange_symbol("kraken");
+
+        let subscription = json!({
+            "method": "subscribe",
+            "params": {
+                "channel": "trade",
+                "symbol": [pair.to_string()],
+                "snapshot": false,
+            },
+        });
+
+        let (event_tx, mut event_rx) = mpsc::channel::<
+            KrakenStreamEvent<KrakenStreamTrade>,
+        >(STREAM_CHANNEL_CAPACITY);
+        let (tx, rx) = mpsc::channel(STREAM_CHANNEL_CAPACITY);
+
+        tokio::spawn(async move {
+            if let Err(e) = run_websocket_client(event_tx, subscription).await {
+                error!("Kraken trade stream failed: {}", e);
+            }
+        });
+
+        tokio::spawn(async move {
+            while let Some(event) = event_rx.recv().await {
+                for trade in &event.data {
+                    let Some(trade) = to_public_trade(trade, &symbol) else {
+                        warn!("Unknown Kraken trade side: {}", trade.side);
+                        continue;
+                    };
+                    if tx.send(trade).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(receiver_stream(rx))
+    }
+
+    /// Kraken spot markets have no liquidations
+    pub fn subscribe_liquidations(
+        &self,
+        _pair: Instrument,
+    ) -> ix_results::errors::Result<LiquidationStream> {
+        Err(ix_results::errors::ExchangeError::UnsupportedExchange {
+            exchange: "Kraken liquidations".to_string(),
+        })
+    }
+}
+
+/// Outcome of applying a book message
+#[derive(Debug, Default)]
+pub struct KrakenBookUpdate {
+    /// Updated book after each entry that passed the checksum
+    pub orderbooks: Vec<Orderbook>,
+    /// Checksum sent by Kraken and the one computed locally, when they differ
+    pub checksum_mismatch: Option<(u32, u32)>,
+}
+
+/// Order book of a single symbol maintained from the v2 book channel.
+///
+/// The book is kept at the subscribed depth, as levels falling out of it
+/// are not deleted by Kraken. After a checksum mismatch the book is cleared
+/// and updates are ignored until the next snapshot.
+#[derive(Debug, Clone)]
+pub struct KrakenBook {
+    symbol: String,
+    channel_depth: usize,
+    depth: usize,
+    price_precision: u32,
+    qty_precision: u32,
+    book: LevelBook,
+    synced: bool,
+}
+
+impl KrakenBook {
+    pub fn new(
+        symbol: &str,
+        channel_depth: usize,
+        depth: usize,
+        price_precision: u32,
+        qty_precision: u32,
+    ) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            channel_depth,
+            depth,
+            price_precision,
+            qty_precision,
+            book: LevelBook::new(),
+            synced: false,
+        }
+    }
+
+    /// Drop the book, it is rebuilt from the next snapshot
+    pub fn reset(&mut self) {
+        self.book.clear();
+        self.synced = false;
+    }
+
+    pub fn is_synced(&self) -> bool {
+        self.synced
+    }
+
+    /// Checksum of the current book
+    pub fn checksum(&self) -> u32 {
+        book_checksum(&self.book, self.price_precision, self.qty_precision)
+    }
+
+    /// Apply a snapshot or update message
+    pub fn apply(
+        &mut self,
+        event: &KrakenStreamEvent<KrakenStreamBook>,
+    ) -> KrakenBookUpdate {
+        let mut update = KrakenBookUpdate::default();
+
+        if event.event_type == "snapshot" {
+            self.reset();
+            self.synced = true;
+        } else if !self.synced {
+            debug!("Dropping Kraken book update received before the snapshot");
+            return update;
+        }
+
+        for data in &event.data {
+            for level in &data.bids {
+                self.book.update_bid(level.price, level.qty);
+            }
+            for level in &data.asks {
+                self.book.update_ask(level.price, level.qty);
+            }
+            self.book.truncate(self.channel_depth);
+
+            let computed = self.checksum();
+            if computed != data.checksum {
+                self.reset();
+                update.checksum_mismatch = Some((data.checksum, computed));
+                return update;
+            }
+
+            let orderbook = self.book.to_orderbook(
+                &self.symbol,
+                "Kraken",
+                data.timestamp.unwrap_or_else(Utc::now),
+                Some(self.depth),
+                None,
+                None,
+            );
+
+            // The pair precision is the tick of the price grid
+            update
+                .orderbooks
+                .push(orderbook.with_tick_size(Decimal::new(1, self.price_precision)));
+        }
+
+        update
+    }
+}
+
+/// CRC32 checksum of the top 10 levels of a book, as computed by Kraken.
+///
+/// Asks from best to worst then bids from best to worst, each level is the
+/// price followed by the quantity, formatted at the pair precision with the
+/// decimal point and leading zeros removed.
+pub fn book_checksum(book: &LevelBook, price_precision: u32, qty_precision: u32) -> u32 {
+    let mut payload = String::new();
+
+    let levels = book
+        .asks()
+        .take(CHECKSUM_DEPTH)
+        .chain(book.bids().take(CHECKSUM_DEPTH));
+
+    for (price, qty) in levels {
+        payload.push_str(&checksum_field(*price, price_precision));
+        payload.push_str(&checksum_field(*qty, qty_precision));
+    }
+
+    crc32fast::hash(payload.as_bytes())
+}
+
+fn checksum_field(value: Decimal, precision: u32) -> String {
+    let mut value = value;
+    value.rescale(precision);
+    value
+        .to_string()
+        .replace('.', "")
+        .trim_start_matches('0')
+        .to_string()
+}
+
+/// Normalize a trade, the side is the taker side
+pub fn to_public_trade(trade: &KrakenStreamTrade, symbol: &str) -> Option<PublicTrade> {
+    Some(PublicTrade {
+        symbol: symbol.to_string(),
+        exchange: "Kraken".to_string(),
+        timestamp: trade.timestamp,
+        side: TradeSide::parse_from_str(&trade.side)?,
+        price: trade.price.to_f64().unwrap_or_default(),
+        amount: trade.qty.to_f64().unwrap_or_default(),
+        trade_id: trade.trade_id.to_string(),
+    })
+}
+
+/// Kraken v2 stream message envelope
+#[derive(Debug, Deserialize, Clone)]
+pub struct KrakenStreamEvent<T> {
+    pub channel: String,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub data: Vec<T>,
+}
+
+/// Kraken v2 `book` channel data, `snapshot` or `update`
+#[derive(Debug, Deserialize, Clone)]
+pub struct KrakenStreamBook {
+    pub symbol: String,
+    pub bids: Vec<KrakenStreamLevel>,
+    pub asks: Vec<KrakenStreamLevel>,
+    pub checksum: u32,
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+/// Kraken v2 price level, quantities are absolute
+#[derive(Debug, Deserialize, Clone)]
+pub struct KrakenStreamLevel {
+    pub price: Decimal,
+    pub qty: Decimal,
+}
+
+/// Kraken v2 `trade` channel data
+#[derive(Debug, Deserialize, Clone)]
+pub struct KrakenStreamTrade {
+    pub symbol: String,
+    pub side: String,
+    pub price: Decimal,
+    pub qty: Decimal,
+    pub ord_type: String,
+    pub trade_id: u64,
+    pub timestamp: DateTime<Utc>,
+}
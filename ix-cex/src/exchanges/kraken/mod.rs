@@ -1 +1,2 @@
 pub mod kraken_client;
+pub mod kraken_wss;
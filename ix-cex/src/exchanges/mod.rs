@@ -1,18 +1,48 @@
 pub mod binance;
 pub use binance::binance_client::BinanceClient;
+pub use binance::binance_private::BinancePrivateClient;
+pub use binance::local_orderbook::LocalOrderbook;
 
 pub mod bybit;
 pub use bybit::bybit_client::BybitClient;
 pub use bybit::clients::BybitPrivateClient;
+pub use bybit::trading::BybitTradingClient;
 
 pub mod coinbase;
 pub use coinbase::coinbase_client::CoinbaseClient;
 
+pub mod deribit;
+pub use deribit::deribit_client::DeribitClient;
+
 pub mod kraken;
 pub use kraken::kraken_client::KrakenClient;
 
-use crate::models::orderbook::{Orderbook, TradingPair};
+pub mod okx;
+pub use okx::okx_client::OkxClient;
+
+use crate::models::instruments::{Instrument, ListedInstrument};
+
+use crate::models::orderbook::Orderbook;
+use crate::models::trades::{Liquidation, PublicTrade};
+use crate::models::trading::{
+    AmendOrder, Balance, Fill, LotSize, NewOrder, OrderAck, OrderKey, OrderState,
+};
+use chrono::{DateTime, TimeZone, Utc};
+use futures::stream::BoxStream;
 use ix_results::errors::Result;
+use tokio::sync::mpsc;
+
+/// Capacity of the channels between websocket tasks and returned streams
+pub(crate) const STREAM_CHANNEL_CAPACITY: usize = 1000;
+
+/// Stream of normalized order books, one item per applied update
+pub type OrderbookStream = BoxStream<'static, Orderbook>;
+
+/// Stream of normalized public trades
+pub type TradeStream = BoxStream<'static, PublicTrade>;
+
+/// Stream of normalized liquidations
+pub type LiquidationStream = BoxStream<'static, Liquidation>;
 
 /// Trait for exchange clients
 #[async_trait::async_trait]
@@ -20,24 +50,113 @@ pub trait ExchangeClient {
     /// Get order book snapshot for a trading pair
     async fn get_orderbook(
         &self,
-        pair: TradingPair,
+        pair: Instrument,
         limit: Option<u32>,
     ) -> Result<Orderbook>;
 
+    /// List the instruments traded on the exchange with their symbols, as
+    /// returned by its instrument info endpoint
+    async fn list_instruments(&self) -> Result<Vec<ListedInstrument>>;
+
     /// Get the exchange name
     fn exchange_name(&self) -> &str;
 }
 
+/// Trait for exchange clients with WebSocket market data.
+///
+/// Every subscription opens its own connection, which is closed once the
+/// returned stream is dropped. A stream ends when its connection terminates.
+#[async_trait::async_trait]
+pub trait StreamingExchangeClient: ExchangeClient {
+    /// Subscribe to order book updates, publishing `depth` levels per side
+    async fn subscribe_orderbook(
+        &self,
+        pair: Instrument,
+        depth: Option<u32>,
+    ) -> Result<OrderbookStream>;
+
+    /// Subscribe to public trades
+    async fn subscribe_trades(&self, pair: Instrument) -> Result<TradeStream>;
+
+    /// Subscribe to liquidations of the perpetual contract for the pair
+    async fn subscribe_liquidations(&self, pair: Instrument) -> Result<LiquidationStream>;
+}
+
+/// Trait for exchange clients trading on the account of their API key.
+///
+/// Requests refused by the exchange are errors. An order request failing with
+/// a retryable error may still have been placed, its client id tells.
+#[async_trait::async_trait]
+pub trait TradingClient: Send + Sync {
+    /// Get the exchange name
+    fn exchange_name(&self) -> &str;
+
+    /// Name of the transport of the orders, for logs
+    fn transport(&self) -> &'static str {
+        "rest"
+    }
+
+    /// Balances of the coins held
+    async fn balances(&self) -> Result<Vec<Balance>>;
+
+    /// Place an order, the ids once accepted, an error when refused
+    async fn place_order(&self, order: &NewOrder) -> Result<OrderAck>;
+
+    /// Amend an open order, the ids of the amended one, which on Binance
+    /// replaces it, an error when it is closed or the amend refused
+    async fn amend_order(&self, amend: &AmendOrder) -> Result<OrderAck>;
+
+    /// Cancel an open order, its ids, an error when it is already closed
+    async fn cancel_order(&self, symbol: &str, order: &OrderKey) -> Result<OrderAck>;
+
+    /// Cancel the open orders of `symbol`, or of every symbol
+    async fn cancel_all_orders(&self, symbol: Option<&str>) -> Result<()>;
+
+    /// State of an open or recently closed order, `None` when unknown
+    async fn get_order(&self, symbol: &str, order: &OrderKey)
+    -> Result<Option<OrderState>>;
+
+    /// Open orders of `symbol`, or of every symbol
+    async fn open_orders(&self, symbol: Option<&str>) -> Result<Vec<OrderState>>;
+
+    /// Fills of `symbol` since `since`, by default of the last 7 days, oldest
+    /// first
+    async fn fills(&self, symbol: &str, since: Option<DateTime<Utc>>) -> Result<Vec<Fill>>;
+
+    /// Lot size rules of `symbol`
+    async fn lot_size(&self, symbol: &str) -> Result<LotSize>;
+}
+
+/// Convert the receiving end of a channel into a stream
+pub(crate) fn receiver_stream<T: Send + 'static>(
+    rx: mpsc::Receiver<T>,
+) -> BoxStream<'static, T> {
+    Box::pin(futures::stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|item| (item, rx))
+    }))
+}
+
+/// Convert a millisecond epoch timestamp, falling back to the current time
+pub(crate) fn timestamp_from_millis(ms: u64) -> DateTime<Utc> {
+    Utc.timestamp_millis_opt(ms as i64)
+        .single()
+        .unwrap_or_else(Utc::now)
+}
+
 #[async_trait::async_trait]
 impl ExchangeClient for BinanceClient {
     async fn get_orderbook(
         &self,
-        pair: TradingPair,
+        pair: Instrument,
         limit: Option<u32>,
     ) -> Result<Orderbook> {
         self.get_orderbook(pair, limit).await
     }
 
+    async fn list_instruments(&self) -> Result<Vec<ListedInstrument>> {
+        self.list_instruments().await
+    }
+
     fn exchange_name(&self) -> &str {
         "Binance"
     }
@@ -47,12 +166,16 @@ impl ExchangeClient for BinanceClient {
 impl ExchangeClient for CoinbaseClient {
     async fn get_orderbook(
         &self,
-        pair: TradingPair,
+        pair: Instrument,
         limit: Option<u32>,
     ) -> Result<Orderbook> {
         self.get_orderbook(pair, limit).await
     }
 
+    async fn list_instruments(&self) -> Result<Vec<ListedInstrument>> {
+        self.list_instruments().await
+    }
+
     fn exchange_name(&self) -> &str {
         "Coinbase"
     }
@@ -62,12 +185,16 @@ impl ExchangeClient for CoinbaseClient {
 impl ExchangeClient for KrakenClient {
     async fn get_orderbook(
         &self,
-        pair: TradingPair,
+        pair: Instrument,
         limit: Option<u32>,
     ) -> Result<Orderbook> {
         self.get_orderbook(pair, limit).await
     }
 
+    async fn list_instruments(&self) -> Result<Vec<ListedInstrument>> {
+        self.list_instruments().await
+    }
+
     fn exchange_name(&self) -> &str {
         "Kraken"
     }
@@ -77,13 +204,131 @@ impl ExchangeClient for KrakenClient {
 impl ExchangeClient for BybitClient {
     async fn get_orderbook(
         &self,
-        pair: TradingPair,
+        pair: Instrument,
         limit: Option<u32>,
     ) -> Result<Orderbook> {
         self.get_orderbook(pair, limit).await
     }
 
+    async fn list_instruments(&self) -> Result<Vec<ListedInstrument>> {
+        self.list_instruments().await
+    }
+
     fn exchange_name(&self) -> &str {
         "Bybit"
     }
 }
+
+#[async_trait::async_trait]
+impl ExchangeClient for OkxClient {
+    async fn get_orderbook(
+        &self,
+        pair: Instrument,
+        limit: Option<u32>,
+    ) -> Result<Orderbook> {
+        self.get_orderbook(pair, limit).await
+    }
+
+    async fn list_instruments(&self) -> Result<Vec<ListedInstrument>> {
+        self.list_instruments().await
+    }
+
+    fn exchange_name(&self) -> &str {
+        "OKX"
+    }
+}
+
+#[async_trait::async_trait]
+impl ExchangeClient for DeribitClient {
+    async fn get_orderbook(
+        &self,
+        pair: Instrument,
+        limit: Option<u32>,
+    ) -> Result<Orderbook> {
+        self.get_orderbook(pair, limit).await
+    }
+
+    async fn list_instruments(&self) -> Result<Vec<ListedInstrument>> {
+        self.list_instruments().await
+    }
+
+    fn exchange_name(&self) -> &str {
+        "Deribit"
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamingExchangeClient for BinanceClient {
+    async fn subscribe_orderbook(
+        &self,
+        pair: Instrument,
+        depth: Option<u32>,
+    ) -> Result<OrderbookStream> {
+        self.subscribe_orderbook(pair, depth)
+    }
+
+    async fn subscribe_trades(&self, pair: Instrument) -> Result<TradeStream> {
+        self.subscribe_trades(pair)
+    }
+
+    async fn subscribe_liquidations(&self, pair: Instrument) -> Result<LiquidationStream> {
+        self.subscribe_liquidations(pair)
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamingExchangeClient for CoinbaseClient {
+    async fn subscribe_orderbook(
+        &self,
+        pair: Instrument,
+        depth: Option<u32>,
+    ) -> Result<OrderbookStream> {
+        self.subscribe_orderbook(pair, depth)
+    }
+
+    async fn subscribe_trades(&self, pair: Instrument) -> Result<TradeStream> {
+        self.subscribe_trades(pair)
+    }
+
+    async fn subscribe_liquidations(&self, pair: Instrument) -> Result<LiquidationStream> {
+        self.subscribe_liquidations(pair)
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamingExchangeClient for KrakenClient {
+    async fn subscribe_orderbook(
+        &self,
+        pair: Instrument,
+        depth: Option<u32>,
+    ) -> Result<OrderbookStream> {
+        self.subscribe_orderbook(pair, depth)
+    }
+
+    async fn subscribe_trades(&self, pair: Instrument) -> Result<TradeStream> {
+        self.subscribe_trades(pair)
+    }
+
+    async fn subscribe_liquidations(&self, pair: Instrument) -> Result<LiquidationStream> {
+        self.subscribe_liquidations(pair)
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamingExchangeClient for BybitClient {
+    async fn subscribe_orderbook(
+        &self,
+        pair: Instrument,
+        depth: Option<u32>,
+    ) -> Result<OrderbookStream> {
+        self.subscribe_orderbook(pair, depth)
+    }
+
+    async fn subscribe_trades(&self, pair: Instrument) -> Result<TradeStream> {
+        self.subscribe_trades(pair)
+    }
+
+    async fn subscribe_liquidations(&self, pair: Instrument) -> Result<LiquidationStream> {
+        self.subscribe_liquidations(pair)
+    }
+}
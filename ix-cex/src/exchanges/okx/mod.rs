@@ -0,0 +1 @@
+pub mod okx_client;
@@ -0,0 +1,358 @@
+use crate::client::http_client::{HttpClient, RetryConfig, RetryableHttpClient};
+use crate::exchanges::timestamp_from_millis;
+use crate::models::instruments::{Instrument, ListedInstrument};
+use crate::models::orderbook::{Orderbook, PriceLevel};
+use crate::models::trades::{PublicTrade, TradeSide};
+
+use ix_results::errors::{ExchangeError, Result};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::str::FromStr;
+use tracing::{debug, info, warn};
+
+/// Maximum number of levels per side returned by the books endpoint
+const MAX_BOOK_DEPTH: u32 = 400;
+
+/// Maximum number of trades returned by the trades endpoint
+const MAX_TRADES: u32 = 500;
+
+/// OKX v5 REST API client
+#[derive(Clone)]
+pub struct OkxClient {
+    client: RetryableHttpClient,
+}
+
+impl OkxClient {
+    /// Create a new OKX client
+    pub fn new() -> Result<Self> {
+        let http_client = HttpClient::new(
+            "OKX".to_string(),
+            "https://www.okx.com".to_string(),
+            10, // Market data endpoints allow 20 requests per 2 seconds
+            30, // 30 second timeout
+        )?;
+
+        let retry_client = RetryableHttpClient::new(http_client, RetryConfig::default());
+
+        Ok(Self {
+            client: retry_client,
+        })
+    }
+
+    /// Get order book snapshot for a trading pair
+    pub async fn get_orderbook(
+        &self,
+        pair: Instrument,
+        depth: Option<u32>,
+    ) -> Result<Orderbook> {
+        let inst_id = pair.to_exchange_symbol("okx");
+        let depth_str = depth
+            .unwrap_or(MAX_BOOK_DEPTH)
+            .min(MAX_BOOK_DEPTH)
+            .to_string();
+
+        info!(
+            "Fetching OKX orderbook for {} with depth {}",
+            inst_id, depth_str
+        );
+
+        let params = vec![("instId", inst_id.as_str()), ("sz", depth_str.as_str())];
+
+        let response: OkxResponse<OkxOrderbookData> = self
+            .client
+            .get_with_params_retry("/api/v5/market/books", &params)
+            .await?;
+
+        let data = response.into_first()?;
+
+        debug!(
+            "Received OKX orderbook with {} bids, {} asks",
+            data.bids.len(),
+            data.asks.len()
+        );
+
+        self.convert_to_orderbook(data, &pair)
+    }
+
+    /// Convert OKX response to our OrderBook format
+    pub fn convert_to_orderbook(
+        &self,
+        data: OkxOrderbookData,
+        pair: &Instrument,
+    ) -> Result<Orderbook> {
+        let symbol = pair.to_exchange_symbol("okx");
+        let v_bids = convert_levels(&data.bids, "bid")?;
+        let v_asks = convert_levels(&data.asks, "ask")?;
+
+        let timestamp = u64::from_str(&data.ts).map_err(|e| ExchangeError::ApiError {
+            exchange: "OKX".to_string(),
+            message: format!("Invalid orderbook timestamp '{}': {}", data.ts, e),
+            code: None,
+        })?;
+
+        // Final value
+        let orderbook = Orderbook::new(
+            symbol,
+            "OKX".to_string(),
+            timestamp_from_millis(timestamp),
+            v_bids,
+            v_asks,
+            None,
+            None,
+        )
+        .with_registered_tick_size(pair);
+
+        // Validate the orderbook
+        if !orderbook.is_valid() {
+            return Err(ExchangeError::ApiError {
+                exchange: "OKX".to_string(),
+                message: "Received invalid orderbook data".to_string(),
+                code: None,
+            });
+        }
+
+        info!(
+            "Successfully converted OKX orderbook: {} bids, {} asks, spread: {:?}",
+            orderbook.bids.len(),
+            orderbook.asks.len(),
+            orderbook.spread()
+        );
+
+        Ok(orderbook)
+    }
+
+    /// Get the most recent public trades, newest first
+    pub async fn get_recent_trades(
+        &self,
+        pair: Instrument,
+        limit: Option<u32>,
+    ) -> Result<Vec<PublicTrade>> {
+        let inst_id = pair.to_exchange_symbol("okx");
+        let limit_str = limit.unwrap_or(100).min(MAX_TRADES).to_string();
+
+        info!("Fetching OKX recent trades for {}", inst_id);
+
+        let params = vec![("instId", inst_id.as_str()), ("limit", limit_str.as_str())];
+
+        let response: OkxResponse<OkxTrade> = self
+            .client
+            .get_with_params_retry("/api/v5/market/trades", &params)
+            .await?;
+
+        response
+            .into_data()?
+            .iter()
+            .map(|trade| trade.to_public_trade())
+            .collect()
+    }
+
+    /// Get instrument metadata for a spot trading pair
+    pub async fn get_instrument(&self, pair: Instrument) -> Result<OkxInstrument> {
+        let inst_id = pair.to_exchange_symbol("okx");
+        info!("Fetching OKX instrument {}", inst_id);
+
+        let params = vec![("instType", "SPOT"), ("instId", inst_id.as_str())];
+
+        let response: OkxResponse<OkxInstrument> = self
+            .client
+            .get_with_params_retry("/api/v5/public/instruments", &params)
+            .await?;
+
+        response.into_first()
+    }
+
+    /// Get all instruments of a type (`SPOT`, `SWAP`, `FUTURES`, `OPTION`)
+    pub async fn get_instruments(&self, inst_type: &str) -> Result<Vec<OkxInstrument>> {
+        info!("Fetching OKX {} instruments", inst_type);
+
+        let params = vec![("instType", inst_type)];
+
+        let response: OkxResponse<OkxInstrument> = self
+            .client
+            .get_with_params_retry("/api/v5/public/instruments", &params)
+            .await?;
+
+        response.into_data()
+    }
+
+    /// List the live spot and perpetual swap instruments, with their ids
+    pub async fn list_instruments(&self) -> Result<Vec<ListedInstrument>> {
+        let mut listed = Vec::new();
+
+        for instrument in self.get_instruments("SPOT").await? {
+            if instrument.state == "live" {
+                let tick_size = Decimal::from_str(&instrument.tick_sz).ok();
+                listed.push(
+                    ListedInstrument::new(
+                        Instrument::spot(&instrument.base_ccy, &instrument.quote_ccy),
+                        instrument.inst_id,
+                    )
+                    .with_tick_size(tick_size),
+                );
+            }
+        }
+
+        // Swaps have no base and quote, they come from the underlying
+        for instrument in self.get_instruments("SWAP").await? {
+            if instrument.state != "live" {
+                continue;
+            }
+            if let Some((base, quote)) = instrument.uly.split_once('-') {
+                let tick_size = Decimal::from_str(&instrument.tick_sz).ok();
+                listed.push(
+                    ListedInstrument::new(
+                        Instrument::perpetual(base, quote),
+                        instrument.inst_id,
+                    )
+                    .with_tick_size(tick_size),
+                );
+            }
+        }
+
+        Ok(listed)
+    }
+}
+
+/// Convert `[price, size, deprecated, orders]` levels
+fn convert_levels(levels: &[Vec<String>], side: &str) -> Result<Vec<PriceLevel>> {
+    let mut v_levels = Vec::with_capacity(levels.len());
+
+    for level in levels {
+        if level.len() < 2 {
+            warn!(
+                "Invalid {} format from OKX: expected at least 2 elements, got {}",
+                side,
+                level.len()
+            );
+            continue;
+        }
+
+        let price =
+            Decimal::from_str(&level[0]).map_err(|e| ExchangeError::ApiError {
+                exchange: "OKX".to_string(),
+                message: format!("Invalid {} price '{}': {}", side, level[0], e),
+                code: None,
+            })?;
+
+        let quantity =
+            Decimal::from_str(&level[1]).map_err(|e| ExchangeError::ApiError {
+                exchange: "OKX".to_string(),
+                message: format!("Invalid {} size '{}': {}", side, level[1], e),
+                code: None,
+            })?;
+
+        v_levels.push(PriceLevel { price, quantity });
+    }
+
+    Ok(v_levels)
+}
+
+/// OKX v5 response envelope, `code` is "0" on success
+#[derive(Debug, Deserialize)]
+pub struct OkxResponse<T> {
+    pub code: String,
+    pub msg: String,
+    pub data: Vec<T>,
+}
+
+impl<T> OkxResponse<T> {
+    /// Data of a successful response
+    pub fn into_data(self) -> Result<Vec<T>> {
+        if self.code != "0" {
+            return Err(ExchangeError::ApiError {
+                exchange: "OKX".to_string(),
+                message: format!("OKX API error {}: {}", self.code, self.msg),
+                code: self.code.parse().ok(),
+            });
+        }
+
+        Ok(self.data)
+    }
+
+    /// First data entry of a successful response
+    pub fn into_first(self) -> Result<T> {
+        self.into_data()?
+            .into_iter()
+            .next()
+            .ok_or_else(|| ExchangeError::ApiError {
+                exchange: "OKX".to_string(),
+                message: "No data found in response".to_string(),
+                code: None,
+            })
+    }
+}
+
+/// OKX order book data
+#[derive(Debug, Clone, Deserialize)]
+pub struct OkxOrderbookData {
+    pub asks: Vec<Vec<String>>,
+    pub bids: Vec<Vec<String>>,
+    pub ts: String,
+}
+
+/// OKX public trade
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OkxTrade {
+    pub inst_id: String,
+    pub trade_id: String,
+    pub px: String,
+    pub sz: String,
+    pub side: String,
+    pub ts: String,
+}
+
+impl OkxTrade {
+    /// Normalize the trade, the side is the taker side
+    pub fn to_public_trade(&self) -> Result<PublicTrade> {
+        let invalid = |field: &str, value: &str| ExchangeError::ApiError {
+            exchange: "OKX".to_string(),
+            message: format!("Invalid trade {} '{}'", field, value),
+            code: None,
+        };
+
+        Ok(PublicTrade {
+            symbol: self.inst_id.clone(),
+            exchange: "OKX".to_string(),
+            timestamp: timestamp_from_millis(
+                u64::from_str(&self.ts).map_err(|_| invalid("ts", &self.ts))?,
+            ),
+            side: TradeSide::parse_from_str(&self.side)
+                .ok_or_else(|| invalid("side", &self.side))?,
+            price: f64::from_str(&self.px).map_err(|_| invalid("px", &self.px))?,
+            amount: f64::from_str(&self.sz).map_err(|_| invalid("sz", &self.sz))?,
+            trade_id: self.trade_id.clone(),
+        })
+    }
+}
+
+/// OKX instrument metadata
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OkxInstrument {
+    pub inst_type: String,
+    pub inst_id: String,
+    #[serde(default)]
+    pub base_ccy: String,
+    #[serde(default)]
+    pub quote_ccy: String,
+    #[serde(default)]
+    pub settle_ccy: String,
+    /// Underlying of derivatives, e.g. `BTC-USDT`
+    #[serde(default)]
+    pub uly: String,
+    #[serde(default)]
+    pub ct_val: String,
+    pub tick_sz: String,
+    pub lot_sz: String,
+    pub min_sz: String,
+    pub state: String,
+    #[serde(default)]
+    pub list_time: String,
+}
+
+impl Default for OkxClient {
+    fn default() -> Self {
+        Self::new().expect("Failed to create default OKX client")
+    }
+}
@@ -1,7 +1,8 @@
 //! # Centralized Exchange Client
 //!
-//! Query Centralized Exchange APIs to get order book data.
-//! Currently supports Binance, Coinbase, and Kraken exchanges for BTC/USDC and SOL/USDC pairs.
+//! Query Centralized Exchange APIs to get order book data, and trade on
+//! Bybit and Binance spot.
+//! Currently supports Binance, Bybit, Coinbase, Kraken, OKX and Deribit exchanges.
 //!
 //! ## Features
 //!
@@ -19,8 +20,9 @@ pub mod results;
 
 // Re-export commonly used types
 pub use exchanges::{
-    BinanceClient, BybitClient, BybitPrivateClient, CoinbaseClient, ExchangeClient,
-    KrakenClient,
+    BinanceClient, BinancePrivateClient, BybitClient, BybitPrivateClient,
+    BybitTradingClient, CoinbaseClient, DeribitClient, ExchangeClient, KrakenClient,
+    OkxClient, StreamingExchangeClient, TradingClient,
 };
 pub use ix_results::errors::{ExchangeError, Result};
 //pub use models::{Orderbook, OrderbookSummary, PriceLevel};
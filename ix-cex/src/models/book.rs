@@ -0,0 +1,119 @@
+use crate::models::orderbook::{Orderbook, PriceLevel};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+
+/// Price levels of an order book maintained from incremental updates.
+///
+/// Quantities are absolute, a zero quantity removes the level.
+#[derive(Debug, Clone, Default)]
+pub struct LevelBook {
+    bids: BTreeMap<Decimal, Decimal>,
+    asks: BTreeMap<Decimal, Decimal>,
+}
+
+impl LevelBook {
+    /// Create an empty book
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a book from a normalized order book snapshot
+    pub fn from_orderbook(orderbook: &Orderbook) -> Self {
+        let mut book = Self::new();
+
+        for level in &orderbook.bids {
+            book.update_bid(level.price, level.quantity);
+        }
+
+        for level in &orderbook.asks {
+            book.update_ask(level.price, level.quantity);
+        }
+
+        book
+    }
+
+    /// Remove all levels
+    pub fn clear(&mut self) {
+        self.bids.clear();
+        self.asks.clear();
+    }
+
+    /// Check if both sides are empty
+    pub fn is_empty(&self) -> bool {
+        self.bids.is_empty() && self.asks.is_empty()
+    }
+
+    /// Set the quantity of a bid level
+    pub fn update_bid(&mut self, price: Decimal, qty: Decimal) {
+        Self::update_level(&mut self.bids, price, qty);
+    }
+
+    /// Set the quantity of an ask level
+    pub fn update_ask(&mut self, price: Decimal, qty: Decimal) {
+        Self::update_level(&mut self.asks, price, qty);
+    }
+
+    /// Bid levels from the best (highest) price
+    pub fn bids(&self) -> impl Iterator<Item = (&Decimal, &Decimal)> {
+        self.bids.iter().rev()
+    }
+
+    /// Ask levels from the best (lowest) price
+    pub fn asks(&self) -> impl Iterator<Item = (&Decimal, &Decimal)> {
+        self.asks.iter()
+    }
+
+    /// Drop the levels beyond `depth` on each side
+    pub fn truncate(&mut self, depth: usize) {
+        while self.bids.len() > depth {
+            self.bids.pop_first();
+        }
+        while self.asks.len() > depth {
+            self.asks.pop_last();
+        }
+    }
+
+    /// Build the normalized order book with at most `depth` levels per side
+    pub fn to_orderbook(
+        &self,
+        symbol: &str,
+        exchange: &str,
+        timestamp: DateTime<Utc>,
+        depth: Option<usize>,
+        last_update_id: Option<u64>,
+        sequence: Option<u64>,
+    ) -> Orderbook {
+        let depth = depth.unwrap_or(usize::MAX);
+
+        let bids = self
+            .bids()
+            .take(depth)
+            .map(|(price, qty)| PriceLevel::new(*price, *qty))
+            .collect();
+
+        let asks = self
+            .asks()
+            .take(depth)
+            .map(|(price, qty)| PriceLevel::new(*price, *qty))
+            .collect();
+
+        Orderbook::new(
+            symbol.to_string(),
+            exchange.to_string(),
+            timestamp,
+            bids,
+            asks,
+            last_update_id,
+            sequence,
+        )
+    }
+
+    fn update_level(side: &mut BTreeMap<Decimal, Decimal>, price: Decimal, qty: Decimal) {
+        if qty.is_zero() {
+            side.remove(&price);
+        } else {
+            side.insert(price, qty);
+        }
+    }
+}
@@ -0,0 +1,485 @@
+use config::{Config, ConfigError};
+use ix_results::errors::{ExchangeError, Result};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{OnceLock, RwLock};
+use tracing::{debug, info, warn};
+
+/// Environment variable overriding the directory of the exchange configs
+pub const CONFIG_DIR_ENV: &str = "IX_CEX_CONFIG_DIR";
+
+/// Quote assets recognized when splitting symbols without a separator,
+/// longer ones first so that `USDT` wins over `USD`
+const KNOWN_QUOTES: [&str; 9] = [
+    "FDUSD", "USDT", "USDC", "USDE", "EUR", "USD", "BTC", "ETH", "SOL",
+];
+
+/// Suffixes marking a perpetual contract symbol
+const PERPETUAL_SUFFIXES: [&str; 3] = ["-PERPETUAL", "-SWAP", "-PERP"];
+
+static GLOBAL_REGISTRY: OnceLock<RwLock<InstrumentRegistry>> = OnceLock::new();
+
+/// Kind of market an instrument trades on
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum InstrumentKind {
+    #[default]
+    Spot,
+    Perpetual,
+}
+
+impl std::fmt::Display for InstrumentKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            InstrumentKind::Spot => "spot",
+            InstrumentKind::Perpetual => "perpetual",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// Tradable instrument, identified by its assets and market kind.
+///
+/// Exchange symbols are resolved through the global [`InstrumentRegistry`]
+/// first, then through the symbol convention of each exchange.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Instrument {
+    pub base: String,
+    pub quote: String,
+    pub kind: InstrumentKind,
+}
+
+impl Instrument {
+    pub fn new(base: &str, quote: &str, kind: InstrumentKind) -> Self {
+        Self {
+            base: normalize_asset(base),
+            quote: normalize_asset(quote),
+            kind,
+        }
+    }
+
+    pub fn spot(base: &str, quote: &str) -> Self {
+        Self::new(base, quote, InstrumentKind::Spot)
+    }
+
+    pub fn perpetual(base: &str, quote: &str) -> Self {
+        Self::new(base, quote, InstrumentKind::Perpetual)
+    }
+
+    /// Convert to exchange-specific symbol format
+    pub fn to_exchange_symbol(&self, exchange: &str) -> String {
+        let registry = InstrumentRegistry::global()
+            .read()
+            .unwrap_or_else(|e| e.into_inner());
+
+        match registry.symbol(exchange, self) {
+            Some(symbol) => symbol.to_string(),
+            None => self.default_symbol(exchange),
+        }
+    }
+
+    /// Minimum price increment of the instrument on an exchange, if known
+    pub fn tick_size(&self, exchange: &str) -> Option<Decimal> {
+        let registry = InstrumentRegistry::global()
+            .read()
+            .unwrap_or_else(|e| e.into_inner());
+
+        registry.tick_size(exchange, self)
+    }
+
+    /// Symbol following the naming convention of the exchange
+    pub fn default_symbol(&self, exchange: &str) -> String {
+        let (base, quote) = (&self.base, &self.quote);
+
+        match (exchange.to_lowercase().as_str(), self.kind) {
+            ("coinbase", _) => format!("{base}-{quote}"),
+            ("okx", InstrumentKind::Spot) => format!("{base}-{quote}"),
+            ("okx", InstrumentKind::Perpetual) => format!("{base}-{quote}-SWAP"),
+            ("deribit", InstrumentKind::Spot) => format!("{base}_{quote}"),
+            // Inverse perpetuals are named after the base only
+            ("deribit", InstrumentKind::Perpetual) if quote == "USD" => {
+                format!("{base}-PERPETUAL")
+            }
+            ("deribit", InstrumentKind::Perpetual) => format!("{base}_{quote}-PERPETUAL"),
+            _ => format!("{base}{quote}"),
+        }
+    }
+
+    /// Parse from string, accepting `BASE/QUOTE`, `BASE-QUOTE`, `BASE_QUOTE`
+    /// and `BASEQUOTE` forms. A `-PERP`, `-SWAP` or `-PERPETUAL` suffix marks
+    /// a perpetual contract.
+    pub fn parse_from_str(s: &str) -> Option<Self> {
+        let upper = s.trim().to_uppercase();
+
+        let (symbol, kind) = match PERPETUAL_SUFFIXES
+            .iter()
+            .find_map(|suffix| upper.strip_suffix(suffix))
+        {
+            Some(symbol) => (symbol, InstrumentKind::Perpetual),
+            None => (upper.as_str(), InstrumentKind::Spot),
+        };
+
+        if let Some((base, quote)) = symbol.split_once(['/', '-', '_']) {
+            if base.is_empty() || quote.is_empty() {
+                return None;
+            }
+            return Some(Self::new(base, quote, kind));
+        }
+
+        // Inverse perpetuals named after the base only, e.g. BTC-PERPETUAL
+        if kind == InstrumentKind::Perpetual && !symbol.is_empty() {
+            let quoted = KNOWN_QUOTES
+                .iter()
+                .any(|quote| symbol.len() > quote.len() && symbol.ends_with(quote));
+            if !quoted {
+                return Some(Self::new(symbol, "USD", kind));
+            }
+        }
+
+        KNOWN_QUOTES.iter().find_map(|quote| {
+            let base = symbol.strip_suffix(quote)?;
+            (!base.is_empty()).then(|| Self::new(base, quote, kind))
+        })
+    }
+}
+
+impl FromStr for Instrument {
+    type Err = ExchangeError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse_from_str(s).ok_or_else(|| ExchangeError::InvalidTradingPair {
+            pair: s.to_string(),
+        })
+    }
+}
+
+impl std::fmt::Display for Instrument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            InstrumentKind::Spot => write!(f, "{}/{}", self.base, self.quote),
+            InstrumentKind::Perpetual => write!(f, "{}/{}-PERP", self.base, self.quote),
+        }
+    }
+}
+
+/// Instrument listed by an exchange, as returned by its instrument info
+/// endpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListedInstrument {
+    pub instrument: Instrument,
+    pub symbol: String,
+    /// Minimum price increment, when the listing reports it
+    pub tick_size: Option<Decimal>,
+}
+
+impl ListedInstrument {
+    pub fn new(instrument: Instrument, symbol: impl Into<String>) -> Self {
+        Self {
+            instrument,
+            symbol: symbol.into(),
+            tick_size: None,
+        }
+    }
+
+    /// Set the tick size, ignoring values that are not positive
+    pub fn with_tick_size(mut self, tick_size: Option<Decimal>) -> Self {
+        self.tick_size = tick_size.filter(|tick| *tick > Decimal::ZERO);
+        self
+    }
+}
+
+impl From<(Instrument, String)> for ListedInstrument {
+    fn from((instrument, symbol): (Instrument, String)) -> Self {
+        Self::new(instrument, symbol)
+    }
+}
+
+/// Use a single name for assets listed under aliases
+fn normalize_asset(asset: &str) -> String {
+    match asset.trim().to_uppercase().as_str() {
+        "XBT" => "BTC".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Instruments section of an exchange config file
+#[derive(Debug, Deserialize)]
+struct InstrumentsConfig {
+    exchange: InstrumentsExchangeConfig,
+    pairs: Option<PairsConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InstrumentsExchangeConfig {
+    name: String,
+}
+
+/// `[pairs]` section, `symbols` are exchange symbols parsed into spot
+/// instruments, `instruments` map an instrument to its symbol explicitly
+#[derive(Debug, Default, Deserialize)]
+pub struct PairsConfig {
+    #[serde(default)]
+    pub symbols: Vec<String>,
+    /// Tick sizes of `symbols`, by exchange symbol
+    #[serde(default)]
+    pub tick_sizes: HashMap<String, Decimal>,
+    #[serde(default)]
+    pub instruments: Vec<InstrumentConfig>,
+}
+
+/// Explicit `[[pairs.instruments]]` entry
+#[derive(Debug, Deserialize)]
+pub struct InstrumentConfig {
+    pub base: String,
+    pub quote: String,
+    #[serde(default)]
+    pub kind: InstrumentKind,
+    /// Exchange symbol, the exchange convention is used when missing
+    pub symbol: Option<String>,
+    pub tick_size: Option<Decimal>,
+}
+
+/// Per exchange mapping between instruments and exchange symbols
+#[derive(Debug, Clone, Default)]
+pub struct InstrumentRegistry {
+    symbols: HashMap<String, HashMap<Instrument, String>>,
+    instruments: HashMap<String, HashMap<(String, InstrumentKind), Instrument>>,
+    tick_sizes: HashMap<String, HashMap<Instrument, Decimal>>,
+}
+
+impl InstrumentRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry shared by the whole process, loaded from the exchange
+    /// configs on first use
+    pub fn global() -> &'static RwLock<InstrumentRegistry> {
+        GLOBAL_REGISTRY.get_or_init(|| {
+            let registry = Self::from_default_config().unwrap_or_else(|e| {
+                warn!("Failed to load instrument configs: {}", e);
+                Self::new()
+            });
+            RwLock::new(registry)
+        })
+    }
+
+    /// Add the mappings of another registry to the global one
+    pub fn install(other: InstrumentRegistry) {
+        let mut registry = Self::global().write().unwrap_or_else(|e| e.into_inner());
+        registry.merge(other);
+    }
+
+    /// Directory of the exchange configs, `IX_CEX_CONFIG_DIR` or the
+    /// `config` directory of this crate
+    pub fn default_config_dir() -> PathBuf {
+        match std::env::var(CONFIG_DIR_ENV) {
+            Ok(dir) => PathBuf::from(dir),
+            Err(_) => Path::new(env!("CARGO_MANIFEST_DIR")).join("config"),
+        }
+    }
+
+    /// Load every exchange config of the default config directory
+    pub fn from_default_config() -> Result<Self> {
+        let mut registry = Self::new();
+        registry.load_config_dir(Self::default_config_dir())?;
+        Ok(registry)
+    }
+
+    /// Load every `*.toml` exchange config of a directory, except the template
+    pub fn load_config_dir(&mut self, dir: impl AsRef<Path>) -> Result<usize> {
+        let entries = std::fs::read_dir(dir.as_ref()).map_err(|e| {
+            ExchangeError::Configuration {
+                message: format!(
+                    "Failed to read config directory {}: {}",
+                    dir.as_ref().display(),
+                    e
+                ),
+            }
+        })?;
+
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .filter(|path| path.file_stem().is_some_and(|stem| stem != "template"))
+            .collect();
+        paths.sort();
+
+        let mut loaded = 0;
+        for path in paths {
+            loaded += self.load_config(&path)?;
+        }
+
+        Ok(loaded)
+    }
+
+    /// Load the `[pairs]` section of an exchange config file, returning the
+    /// number of instruments registered
+    pub fn load_config(&mut self, path: impl AsRef<Path>) -> Result<usize> {
+        let path = path.as_ref();
+
+        let settings = Config::builder()
+            .add_source(config::File::from(path))
+            .build()
+            .map_err(|e: ConfigError| ExchangeError::Configuration {
+                message: format!("Failed to load {}: {}", path.display(), e),
+            })?;
+
+        let config: InstrumentsConfig =
+            settings.try_deserialize().map_err(|e: ConfigError| {
+                ExchangeError::Configuration {
+                    message: format!("Failed to parse {}: {}", path.display(), e),
+                }
+            })?;
+
+        let exchange = config.exchange.name;
+        let pairs = config.pairs.unwrap_or_default();
+        let mut loaded = 0;
+
+        for symbol in &pairs.symbols {
+            match Instrument::parse_from_str(symbol) {
+                Some(instrument) => {
+                    if let Some(tick_size) = pairs.tick_sizes.get(symbol) {
+                        self.set_tick_size(&exchange, &instrument, *tick_size);
+                    }
+                    self.register(&exchange, instrument, symbol);
+                    loaded += 1;
+                }
+                None => warn!("Unrecognized {} symbol in config: {}", exchange, symbol),
+            }
+        }
+
+        for entry in &pairs.instruments {
+            let instrument = Instrument::new(&entry.base, &entry.quote, entry.kind);
+            let symbol = match &entry.symbol {
+                Some(symbol) => symbol.clone(),
+                None => instrument.default_symbol(&exchange),
+            };
+            if let Some(tick_size) = entry.tick_size {
+                self.set_tick_size(&exchange, &instrument, tick_size);
+            }
+            self.register(&exchange, instrument, &symbol);
+            loaded += 1;
+        }
+
+        debug!(
+            "Loaded {} instruments for {} from {}",
+            loaded,
+            exchange,
+            path.display()
+        );
+
+        Ok(loaded)
+    }
+
+    /// Register the symbol of an instrument on an exchange
+    pub fn register(&mut self, exchange: &str, instrument: Instrument, symbol: &str) {
+        let exchange = exchange.to_lowercase();
+
+        self.instruments
+            .entry(exchange.clone())
+            .or_default()
+            .insert((symbol.to_uppercase(), instrument.kind), instrument.clone());
+
+        self.symbols
+            .entry(exchange)
+            .or_default()
+            .insert(instrument, symbol.to_string());
+    }
+
+    /// Set the minimum price increment of an instrument on an exchange,
+    /// values that are not positive are ignored
+    pub fn set_tick_size(
+        &mut self,
+        exchange: &str,
+        instrument: &Instrument,
+        tick: Decimal,
+    ) {
+        if tick <= Decimal::ZERO {
+            return;
+        }
+
+        self.tick_sizes
+            .entry(exchange.to_lowercase())
+            .or_default()
+            .insert(instrument.clone(), tick);
+    }
+
+    /// Register instruments listed by an exchange, e.g. from its
+    /// instrument info endpoint, with their tick sizes
+    pub fn extend(
+        &mut self,
+        exchange: &str,
+        listed: impl IntoIterator<Item = impl Into<ListedInstrument>>,
+    ) -> usize {
+        let mut count = 0;
+        for listed in listed {
+            let listed = listed.into();
+            if let Some(tick_size) = listed.tick_size {
+                self.set_tick_size(exchange, &listed.instrument, tick_size);
+            }
+            self.register(exchange, listed.instrument, &listed.symbol);
+            count += 1;
+        }
+
+        info!("Registered {} instruments for {}", count, exchange);
+        count
+    }
+
+    /// Add all mappings of another registry, replacing existing ones
+    pub fn merge(&mut self, other: InstrumentRegistry) {
+        for (exchange, symbols) in other.symbols {
+            for (instrument, symbol) in symbols {
+                self.register(&exchange, instrument, &symbol);
+            }
+        }
+
+        for (exchange, tick_sizes) in other.tick_sizes {
+            for (instrument, tick_size) in tick_sizes {
+                self.set_tick_size(&exchange, &instrument, tick_size);
+            }
+        }
+    }
+
+    /// Symbol of an instrument on an exchange, if registered
+    pub fn symbol(&self, exchange: &str, instrument: &Instrument) -> Option<&str> {
+        self.symbols
+            .get(&exchange.to_lowercase())?
+            .get(instrument)
+            .map(String::as_str)
+    }
+
+    /// Minimum price increment of an instrument on an exchange, if registered
+    pub fn tick_size(&self, exchange: &str, instrument: &Instrument) -> Option<Decimal> {
+        self.tick_sizes
+            .get(&exchange.to_lowercase())?
+            .get(instrument)
+            .copied()
+    }
+
+    /// Instrument traded under a symbol on an exchange, if registered
+    pub fn instrument(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        kind: InstrumentKind,
+    ) -> Option<&Instrument> {
+        self.instruments
+            .get(&exchange.to_lowercase())?
+            .get(&(symbol.to_uppercase(), kind))
+    }
+
+    /// Instruments registered for an exchange, sorted by name
+    pub fn instruments(&self, exchange: &str) -> Vec<Instrument> {
+        let mut instruments: Vec<Instrument> = self
+            .symbols
+            .get(&exchange.to_lowercase())
+            .map(|symbols| symbols.keys().cloned().collect())
+            .unwrap_or_default();
+        instruments.sort_by_key(|instrument| instrument.to_string());
+        instruments
+    }
+}
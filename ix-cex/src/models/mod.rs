@@ -1,2 +1,6 @@
+pub mod book;
 pub mod exchanges;
+pub mod instruments;
 pub mod orderbook;
+pub mod trades;
+pub mod trading;
@@ -1,17 +1,21 @@
 use chrono::{DateTime, Utc};
 use clickhouse::Row;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
+use thiserror::Error;
+
+use super::instruments::Instrument;
 
 /// Represents a single price level in the order book
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PriceLevel {
-    pub price: f64,
-    pub quantity: f64,
+    pub price: Decimal,
+    pub quantity: Decimal,
 }
 
 impl PriceLevel {
-    pub fn new(price: f64, quantity: f64) -> Self {
+    pub fn new(price: Decimal, quantity: Decimal) -> Self {
         Self { price, quantity }
     }
 }
@@ -19,8 +23,8 @@ impl PriceLevel {
 impl Default for PriceLevel {
     fn default() -> Self {
         Self {
-            price: 0.0,
-            quantity: 0.01,
+            price: Decimal::ZERO,
+            quantity: Decimal::new(1, 2),
         }
     }
 }
@@ -53,10 +57,34 @@ pub struct Orderbook {
     pub asks: Vec<PriceLevel>,
     pub last_update_id: Option<u64>,
     pub sequence: Option<u64>,
+    /// Minimum price increment of the instrument, when known
+    #[serde(default)]
+    pub tick_size: Option<Decimal>,
+}
+
+impl TryFrom<PriceLevelInput> for PriceLevel {
+    type Error = rust_decimal::Error;
+
+    fn try_from(input: PriceLevelInput) -> Result<Self, Self::Error> {
+        Ok(PriceLevel::new(
+            Decimal::from_str(&input.price)?,
+            Decimal::from_str(&input.quantity)?,
+        ))
+    }
+}
+
+/// Error converting an [`OrderbookInput`]
+#[derive(Debug, Error)]
+pub enum OrderbookInputError {
+    #[error("Invalid timestamp: {0}")]
+    Timestamp(#[from] chrono::ParseError),
+
+    #[error("Invalid price level: {0}")]
+    PriceLevel(#[from] rust_decimal::Error),
 }
 
 impl TryFrom<OrderbookInput> for Orderbook {
-    type Error = chrono::ParseError;
+    type Error = OrderbookInputError;
 
     fn try_from(input: OrderbookInput) -> Result<Self, Self::Error> {
         let timestamp =
@@ -65,24 +93,14 @@ impl TryFrom<OrderbookInput> for Orderbook {
         let bids = input
             .bids
             .into_iter()
-            .map(|level| {
-                PriceLevel::new(
-                    f64::from_str(&level.price).unwrap(),
-                    f64::from_str(&level.quantity).unwrap(),
-                )
-            })
-            .collect();
+            .map(PriceLevel::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
 
         let asks = input
             .asks
             .into_iter()
-            .map(|level| {
-                PriceLevel::new(
-                    f64::from_str(&level.price).unwrap(),
-                    f64::from_str(&level.quantity).unwrap(),
-                )
-            })
-            .collect();
+            .map(PriceLevel::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
 
         Ok(Orderbook::new(
             input.symbol,
@@ -106,6 +124,7 @@ impl Default for Orderbook {
             asks: vec![PriceLevel::default()],
             last_update_id: Some(1234),
             sequence: Some(4321),
+            tick_size: None,
         }
     }
 }
@@ -129,6 +148,33 @@ impl Orderbook {
             asks,
             last_update_id,
             sequence,
+            tick_size: None,
+        }
+    }
+
+    /// Set the minimum price increment used by the price arithmetic
+    pub fn with_tick_size(mut self, tick_size: Decimal) -> Self {
+        if tick_size > Decimal::ZERO {
+            self.tick_size = Some(tick_size);
+        }
+        self
+    }
+
+    /// Set the tick size registered for the instrument on the exchange of
+    /// the book, the book is unchanged when none is registered
+    pub fn with_registered_tick_size(self, instrument: &Instrument) -> Self {
+        match instrument.tick_size(&self.exchange) {
+            Some(tick_size) => self.with_tick_size(tick_size),
+            None => self,
+        }
+    }
+
+    /// Round a price to the nearest multiple of the tick size, prices are
+    /// returned unchanged when the tick size is unknown
+    pub fn round_to_tick(&self, price: Decimal) -> Decimal {
+        match self.tick_size {
+            Some(tick) => (price / tick).round() * tick,
+            None => price,
         }
     }
 
@@ -142,32 +188,58 @@ impl Orderbook {
         self.asks.first()
     }
 
-    /// Calculate the bid-ask spread
-    pub fn spread(&self) -> Option<f64> {
+    /// Calculate the bid-ask spread, on the tick grid when the tick size is known
+    pub fn spread(&self) -> Option<Decimal> {
         match (self.best_bid(), self.best_ask()) {
-            (Some(bid), Some(ask)) => Some(ask.price - bid.price),
+            (Some(bid), Some(ask)) => Some(self.round_to_tick(ask.price - bid.price)),
             _ => None,
         }
     }
 
-    /// Calculate the mid price
-    pub fn mid_price(&self) -> Option<f64> {
+    /// Calculate the bid-ask spread as a number of ticks
+    pub fn spread_in_ticks(&self) -> Option<Decimal> {
+        let tick = self.tick_size?;
+        self.spread().map(|spread| (spread / tick).round())
+    }
+
+    /// Calculate the mid price.
+    ///
+    /// The mid of two prices on the tick grid falls on a tick or half way
+    /// between two, so with a known tick size it is kept on the half-tick grid.
+    pub fn mid_price(&self) -> Option<Decimal> {
         match (self.best_bid(), self.best_ask()) {
-            (Some(bid), Some(ask)) => Some((bid.price + ask.price) / 2.0),
+            (Some(bid), Some(ask)) => {
+                let mid = (bid.price + ask.price) / Decimal::TWO;
+                Some(match self.tick_size {
+                    Some(tick) => {
+                        let half_tick = tick / Decimal::TWO;
+                        (mid / half_tick).round() * half_tick
+                    }
+                    None => mid,
+                })
+            }
             _ => None,
         }
     }
 
-    /// Get total liquidity within a certain percentage of the mid price
-    pub fn liquidity_within_percentage(&self, percentage: f64) -> (f64, f64) {
+    /// Get total liquidity within a certain percentage of the mid price.
+    ///
+    /// With a known tick size the band edges are moved inwards to the tick
+    /// grid, so only levels that can be quoted inside the band are counted.
+    pub fn liquidity_within_percentage(&self, percentage: Decimal) -> (Decimal, Decimal) {
         let mid = match self.mid_price() {
             Some(mid) => mid,
-            None => return (0.0, 0.0),
+            None => return (Decimal::ZERO, Decimal::ZERO),
         };
 
-        let threshold = mid * percentage / 100.0;
-        let bid_threshold = mid - threshold;
-        let ask_threshold = mid + threshold;
+        let threshold = mid * percentage / Decimal::ONE_HUNDRED;
+        let mut bid_threshold = mid - threshold;
+        let mut ask_threshold = mid + threshold;
+
+        if let Some(tick) = self.tick_size {
+            bid_threshold = (bid_threshold / tick).ceil() * tick;
+            ask_threshold = (ask_threshold / tick).floor() * tick;
+        }
 
         let bid_liquidity = self
             .bids
@@ -213,12 +285,12 @@ impl Orderbook {
     }
 
     /// Get total volume on bid side
-    pub fn bid_volume(&self) -> f64 {
+    pub fn bid_volume(&self) -> Decimal {
         self.bids.iter().map(|level| level.quantity).sum()
     }
 
     /// Get total volume on ask side
-    pub fn ask_volume(&self) -> f64 {
+    pub fn ask_volume(&self) -> Decimal {
         self.asks.iter().map(|level| level.quantity).sum()
     }
 
@@ -252,124 +324,20 @@ impl Orderbook {
     }
 }
 
-/// Supported trading pairs
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
-pub enum TradingPair {
-    BtcUsdt,
-    BtcUsdc,
-    EthUsdt,
-    EthUsdc,
-    SolUsdt,
-    SolUsdc,
-    LinkUsdc,
-    LinkUsdt,
-    UniUsdt,
-    UniUsdc,
-}
-
-impl TradingPair {
-    /// Convert to exchange-specific symbol format
-    pub fn to_exchange_symbol(&self, exchange: &str) -> String {
-        match (self, exchange.to_lowercase().as_str()) {
-            (TradingPair::BtcUsdt, "binance") => "BTCUSDT".to_string(),
-            (TradingPair::SolUsdt, "binance") => "SOLUSDT".to_string(),
-            (TradingPair::EthUsdt, "binance") => "ETHUSDT".to_string(),
-            (TradingPair::UniUsdt, "binance") => "UNIUSDT".to_string(),
-            (TradingPair::LinkUsdt, "binance") => "LINKUSDT".to_string(),
-            (TradingPair::BtcUsdc, "binance") => "BTCUSDC".to_string(),
-            (TradingPair::SolUsdc, "binance") => "SOLUSDC".to_string(),
-            (TradingPair::EthUsdc, "binance") => "ETHUSDC".to_string(),
-            (TradingPair::UniUsdc, "binance") => "UNIUSDC".to_string(),
-            (TradingPair::LinkUsdc, "binance") => "LINKUSDC".to_string(),
-
-            (TradingPair::BtcUsdt, "bybit") => "BTCUSDT".to_string(),
-            (TradingPair::SolUsdt, "bybit") => "SOLUSDT".to_string(),
-            (TradingPair::EthUsdt, "bybit") => "ETHUSDT".to_string(),
-            (TradingPair::UniUsdt, "bybit") => "UNIUSDT".to_string(),
-            (TradingPair::LinkUsdt, "bybit") => "LINKUSDT".to_string(),
-            (TradingPair::BtcUsdc, "bybit") => "BTCUSDC".to_string(),
-            (TradingPair::SolUsdc, "bybit") => "SOLUSDC".to_string(),
-            (TradingPair::EthUsdc, "bybit") => "ETHUSDC".to_string(),
-            (TradingPair::UniUsdc, "bybit") => "UNIUSDC".to_string(),
-            (TradingPair::LinkUsdc, "bybit") => "LINKUSDC".to_string(),
-
-            (TradingPair::BtcUsdt, "coinbase") => "BTC-USDT".to_string(),
-            (TradingPair::SolUsdt, "coinbase") => "SOL-USDT".to_string(),
-            (TradingPair::EthUsdt, "coinbase") => "ETH-USDT".to_string(),
-            (TradingPair::UniUsdt, "coinbase") => "UNI-USDT".to_string(),
-            (TradingPair::LinkUsdt, "coinbase") => "LINK-USDT".to_string(),
-            (TradingPair::BtcUsdc, "coinbase") => "BTC-USDC".to_string(),
-            (TradingPair::SolUsdc, "coinbase") => "SOL-USDC".to_string(),
-            (TradingPair::EthUsdc, "coinbase") => "ETH-USDC".to_string(),
-            (TradingPair::UniUsdc, "coinbase") => "UNI-USDC".to_string(),
-            (TradingPair::LinkUsdc, "coinbase") => "LINK-USDC".to_string(),
-
-            (TradingPair::BtcUsdt, "kraken") => "BTCUSDT".to_string(),
-            (TradingPair::SolUsdt, "kraken") => "SOLUSDT".to_string(),
-            (TradingPair::EthUsdt, "kraken") => "ETHUSDT".to_string(),
-            (TradingPair::UniUsdt, "kraken") => "UNIUSDT".to_string(),
-            (TradingPair::LinkUsdt, "kraken") => "LINKUSDT".to_string(),
-            (TradingPair::BtcUsdc, "kraken") => "BTCUSDC".to_string(),
-            (TradingPair::SolUsdc, "kraken") => "SOLUSDC".to_string(),
-            (TradingPair::EthUsdc, "kraken") => "ETHUSDC".to_string(),
-            (TradingPair::UniUsdc, "kraken") => "UNIUSDC".to_string(),
-            (TradingPair::LinkUsdc, "kraken") => "LINKUSDC".to_string(),
-            _ => format!("{self:?}"), // Fallback
-        }
-    }
-
-    /// Parse from string
-    pub fn parse_from_str(s: &str) -> Option<Self> {
-        match s.to_uppercase().as_str() {
-            "BTCUSDT" | "BTC-USDT" | "BTC/USDT" => Some(TradingPair::BtcUsdt),
-            "SOLUSDT" | "SOL-USDT" | "SOL/USDT" => Some(TradingPair::SolUsdt),
-            "ETHUSDT" | "ETH-USDT" | "ETH/USDT" => Some(TradingPair::EthUsdt),
-            "UNIUSDT" | "UNI-USDT" | "UNI/USDT" => Some(TradingPair::UniUsdt),
-            "LINKUSDT" | "LINK-USDT" | "LINK/USDT" => Some(TradingPair::LinkUsdt),
-
-            "BTCUSDC" | "BTC-USDC" | "BTC/USDC" => Some(TradingPair::BtcUsdc),
-            "SOLUSDC" | "SOL-USDC" | "SOL/USDC" => Some(TradingPair::SolUsdc),
-            "ETHUSDC" | "ETH-USDC" | "ETH/USDC" => Some(TradingPair::EthUsdc),
-            "UNIUSDC" | "UNI-USDC" | "UNI/USDC" => Some(TradingPair::UniUsdc),
-            "LINKUSDC" | "LINK-USDC" | "LINK/USDC" => Some(TradingPair::LinkUsdc),
-            _ => None,
-        }
-    }
-}
-
-impl std::fmt::Display for TradingPair {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let s = match self {
-            TradingPair::BtcUsdt => "BTC/USDT",
-            TradingPair::SolUsdt => "SOL/USDT",
-            TradingPair::EthUsdt => "ETH/USDT",
-            TradingPair::UniUsdt => "UNI/USDT",
-            TradingPair::LinkUsdt => "LINK/USDT",
-
-            TradingPair::BtcUsdc => "BTC/USDC",
-            TradingPair::SolUsdc => "SOL/USDC",
-            TradingPair::EthUsdc => "ETH/USDC",
-            TradingPair::UniUsdc => "UNI/USDC",
-            TradingPair::LinkUsdc => "LINK/USDC",
-        };
-        write!(f, "{s}")
-    }
-}
-
 /// Summary statistics for an order book
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderbookSummary {
     pub symbol: String,
     pub exchange: String,
     pub timestamp: DateTime<Utc>,
-    pub best_bid: Option<f64>,
-    pub best_ask: Option<f64>,
-    pub spread: Option<f64>,
-    pub mid_price: Option<f64>,
+    pub best_bid: Option<Decimal>,
+    pub best_ask: Option<Decimal>,
+    pub spread: Option<Decimal>,
+    pub mid_price: Option<Decimal>,
     pub bid_count: usize,
     pub ask_count: usize,
-    pub total_bid_volume: f64,
-    pub total_ask_volume: f64,
+    pub total_bid_volume: Decimal,
+    pub total_ask_volume: Decimal,
 }
 
 impl From<&Orderbook> for OrderbookSummary {
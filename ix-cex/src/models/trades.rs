@@ -0,0 +1,53 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Side of the aggressor in a public trade, or of the liquidated order
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+impl TradeSide {
+    /// Parse from string
+    pub fn parse_from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "buy" | "b" | "bid" => Some(TradeSide::Buy),
+            "sell" | "s" | "ask" | "offer" => Some(TradeSide::Sell),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for TradeSide {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            TradeSide::Buy => "Buy",
+            TradeSide::Sell => "Sell",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// Public trade normalized across exchanges
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PublicTrade {
+    pub symbol: String,
+    pub exchange: String,
+    pub timestamp: DateTime<Utc>,
+    pub side: TradeSide,
+    pub price: f64,
+    pub amount: f64,
+    pub trade_id: String,
+}
+
+/// Liquidation event normalized across exchanges
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Liquidation {
+    pub symbol: String,
+    pub exchange: String,
+    pub timestamp: DateTime<Utc>,
+    pub side: TradeSide,
+    pub price: f64,
+    pub amount: f64,
+}
@@ -0,0 +1,311 @@
+//! Private trading models normalized across exchanges, the requests and
+//! answers of [`TradingClient`](crate::exchanges::TradingClient)
+
+use chrono::{DateTime, Utc};
+use ix_results::errors::{ExchangeError, Result};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::trades::TradeSide;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum OrderType {
+    Market,
+    Limit,
+}
+
+impl fmt::Display for OrderType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            OrderType::Market => "Market",
+            OrderType::Limit => "Limit",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// Quantity of an order, in the base coin or, for market orders of the
+/// exchanges supporting it, in the quote coin
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OrderQty {
+    Base(Decimal),
+    Quote(Decimal),
+}
+
+impl OrderQty {
+    pub fn value(&self) -> Decimal {
+        match self {
+            OrderQty::Base(qty) | OrderQty::Quote(qty) => *qty,
+        }
+    }
+}
+
+/// Order of the amend, cancel and status requests, by exchange or client id
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OrderKey {
+    OrderId(String),
+    ClientOrderId(String),
+}
+
+/// New order, built with [`NewOrder::market`] or [`NewOrder::limit`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewOrder {
+    pub symbol: String,
+    pub side: TradeSide,
+    pub order_type: OrderType,
+    pub qty: OrderQty,
+    /// Limit orders only
+    pub price: Option<Decimal>,
+    /// Id chosen by the client, the exchanges refuse a second open order
+    /// with the same id
+    pub client_order_id: Option<String>,
+}
+
+impl NewOrder {
+    pub fn market(symbol: impl Into<String>, side: TradeSide, qty: OrderQty) -> Self {
+        Self {
+            symbol: symbol.into(),
+            side,
+            order_type: OrderType::Market,
+            qty,
+            price: None,
+            client_order_id: None,
+        }
+    }
+
+    /// Limit order good till cancelled, sized in the base coin
+    pub fn limit(
+        symbol: impl Into<String>,
+        side: TradeSide,
+        qty: Decimal,
+        price: Decimal,
+    ) -> Self {
+        Self {
+            symbol: symbol.into(),
+            side,
+            order_type: OrderType::Limit,
+            qty: OrderQty::Base(qty),
+            price: Some(price),
+            client_order_id: None,
+        }
+    }
+
+    pub fn client_order_id(mut self, client_order_id: impl Into<String>) -> Self {
+        self.client_order_id = Some(client_order_id.into());
+        self
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |message: &str| ExchangeError::InvalidRequest(message.to_string());
+
+        if self.symbol.is_empty() {
+            return Err(invalid("Order needs a symbol"));
+        }
+        if self.qty.value() <= Decimal::ZERO {
+            return Err(invalid("Order quantity must be positive"));
+        }
+        match (self.order_type, self.price, self.qty) {
+            (OrderType::Limit, None, _) => Err(invalid("Limit order needs a price")),
+            (OrderType::Limit, Some(price), _) if price <= Decimal::ZERO => {
+                Err(invalid("Limit price must be positive"))
+            }
+            (OrderType::Limit, _, OrderQty::Quote(_)) => {
+                Err(invalid("Limit order is sized in the base coin"))
+            }
+            (OrderType::Market, Some(_), _) => {
+                Err(invalid("Market order takes no price"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// New quantity or price of an open order, unset values are kept
+#[derive(Debug, Clone, PartialEq)]
+pub struct AmendOrder {
+    pub symbol: String,
+    pub order: OrderKey,
+    /// Total quantity of the order, filled part included
+    pub qty: Option<Decimal>,
+    pub price: Option<Decimal>,
+}
+
+impl AmendOrder {
+    pub fn new(symbol: impl Into<String>, order: OrderKey) -> Self {
+        Self {
+            symbol: symbol.into(),
+            order,
+            qty: None,
+            price: None,
+        }
+    }
+
+    pub fn qty(mut self, qty: Decimal) -> Self {
+        self.qty = Some(qty);
+        self
+    }
+
+    pub fn price(mut self, price: Decimal) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |message: &str| ExchangeError::InvalidRequest(message.to_string());
+
+        match (self.qty, self.price) {
+            (None, None) => Err(invalid("Amend needs a quantity or a price")),
+            (Some(qty), _) if qty <= Decimal::ZERO => {
+                Err(invalid("Order quantity must be positive"))
+            }
+            (_, Some(price)) if price <= Decimal::ZERO => {
+                Err(invalid("Limit price must be positive"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Ids of the order of an accepted request
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OrderAck {
+    pub order_id: String,
+    /// Empty when the exchange does not report it
+    pub client_order_id: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum OrderStatus {
+    /// Accepted and not filled, including untriggered conditional orders
+    New,
+    PartiallyFilled,
+    Filled,
+    /// Cancelled or expired, possibly partially filled
+    Cancelled,
+    Rejected,
+}
+
+impl OrderStatus {
+    /// The order no longer changes
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Rejected
+        )
+    }
+}
+
+impl fmt::Display for OrderStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            OrderStatus::New => "New",
+            OrderStatus::PartiallyFilled => "PartiallyFilled",
+            OrderStatus::Filled => "Filled",
+            OrderStatus::Cancelled => "Cancelled",
+            OrderStatus::Rejected => "Rejected",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// State of an order
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderState {
+    pub symbol: String,
+    pub order_id: String,
+    /// Empty for orders sent without one
+    pub client_order_id: String,
+    pub side: TradeSide,
+    pub order_type: OrderType,
+    /// In the unit the order was sent in, the quote coin for the market
+    /// orders sized in it
+    pub qty: Decimal,
+    /// Zero for market orders
+    pub price: Decimal,
+    pub status: OrderStatus,
+    /// Filled base coin quantity
+    pub filled_qty: Decimal,
+    /// Zero until filled
+    pub avg_price: Decimal,
+    /// Zero on the exchanges reporting fees on the fills only
+    pub fee: Decimal,
+    /// Empty unless rejected
+    pub reject_reason: String,
+    pub updated: DateTime<Utc>,
+}
+
+/// Execution of an order
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fill {
+    pub symbol: String,
+    pub order_id: String,
+    pub trade_id: String,
+    pub side: TradeSide,
+    pub price: Decimal,
+    /// Base coin quantity
+    pub qty: Decimal,
+    pub fee: Decimal,
+    pub fee_coin: String,
+    pub is_maker: bool,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Balance of a coin of the account
+#[derive(Debug, Clone, PartialEq)]
+pub struct Balance {
+    pub coin: String,
+    /// Equity of the coin, unrealised PnL included on margin accounts
+    pub total: Decimal,
+    /// Available for new orders
+    pub free: Decimal,
+    /// Held by open orders
+    pub locked: Decimal,
+}
+
+/// Lot size rules of a spot instrument
+#[derive(Debug, Clone, PartialEq)]
+pub struct LotSize {
+    /// Step of base coin quantities
+    pub base_precision: Decimal,
+    /// Step of quote coin quantities
+    pub quote_precision: Decimal,
+    pub min_order_qty: Decimal,
+    /// Smallest order value in the quote coin
+    pub min_order_amt: Decimal,
+}
+
+impl LotSize {
+    /// Quantity of a market order worth `notional` quote coins, `None` below
+    /// the minimum order size. Market buys are sized in the quote coin,
+    /// sells in the base coin at `price`, both rounded down to their step.
+    pub fn market_qty(
+        &self,
+        side: TradeSide,
+        notional: Decimal,
+        price: Decimal,
+    ) -> Option<OrderQty> {
+        match side {
+            TradeSide::Buy => {
+                let qty = round_down(notional, self.quote_precision);
+                (qty > Decimal::ZERO && qty >= self.min_order_amt)
+                    .then_some(OrderQty::Quote(qty))
+            }
+            TradeSide::Sell if price > Decimal::ZERO => {
+                let qty = round_down(notional / price, self.base_precision);
+                (qty > Decimal::ZERO
+                    && qty >= self.min_order_qty
+                    && qty * price >= self.min_order_amt)
+                    .then_some(OrderQty::Base(qty))
+            }
+            TradeSide::Sell => None,
+        }
+    }
+}
+
+fn round_down(value: Decimal, step: Decimal) -> Decimal {
+    if step <= Decimal::ZERO {
+        return value;
+    }
+    ((value / step).floor() * step).normalize()
+}
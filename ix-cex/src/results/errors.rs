@@ -20,6 +20,18 @@ pub enum ExchangeError {
     #[error("Channel send error")]
     ChannelSendError,
 
+    #[error("WebSocket reconnect attempts exhausted for {url} after {attempts} attempts")]
+    RetriesExhausted { url: String, attempts: u32 },
+
+    #[error(transparent)]
+    Exchange(Box<ix_results::errors::ExchangeError>),
+
     #[error("An IO error occurred: {0}")]
     IoError(#[from] std::io::Error),
 }
+
+impl From<ix_results::errors::ExchangeError> for ExchangeError {
+    fn from(e: ix_results::errors::ExchangeError) -> Self {
+        ExchangeError::Exchange(Box::new(e))
+    }
+}
@@ -2,6 +2,7 @@
 mod tests {
 
     use ix_cex::BybitPrivateClient;
+    use ix_cex::exchanges::bybit::{Category, PageQuery};
 
     #[tokio::test]
     async fn test_bybit_get_wallet_balance() {
@@ -50,4 +51,25 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn test_bybit_get_positions_and_executions() {
+        let client = BybitPrivateClient::new().unwrap();
+        let query = PageQuery::new(Category::Linear).settle_coin("USDT");
+
+        match client.get_positions(query.clone().limit(200)).await {
+            Ok(positions) => println!("Bybit positions: {:?}", positions),
+            Err(e) => println!("Expected network error in test environment: {e:?}"),
+        }
+
+        match client.get_executions(query.clone().limit(100)).await {
+            Ok(executions) => println!("Bybit executions: {}", executions.len()),
+            Err(e) => println!("Expected network error in test environment: {e:?}"),
+        }
+
+        match client.get_closed_pnl(query.limit(100)).await {
+            Ok(closed_pnl) => println!("Bybit closed PnL: {}", closed_pnl.len()),
+            Err(e) => println!("Expected network error in test environment: {e:?}"),
+        }
+    }
 }
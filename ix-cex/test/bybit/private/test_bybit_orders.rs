@@ -0,0 +1,203 @@
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+
+    use ix_cex::exchanges::bybit::responses::{BatchResponse, OrderResponse};
+    use ix_cex::exchanges::bybit::{
+        AmendRequest, CancelRequest, Category, MarketUnit, OrderRef, OrderRequest,
+        OrderType, Side, TimeInForce, TriggerBy,
+    };
+    use rust_decimal::Decimal;
+    use serde_json::json;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    #[test]
+    fn test_enum_values() {
+        assert_eq!(Category::Spot.to_string(), "spot");
+        assert_eq!(TimeInForce::GoodTillCancel.as_str(), "GTC");
+        assert_eq!(MarketUnit::QuoteCoin.as_str(), "quoteCoin");
+        assert_eq!("linear".parse::<Category>().unwrap(), Category::Linear);
+        assert_eq!(
+            "PostOnly".parse::<TimeInForce>().unwrap(),
+            TimeInForce::PostOnly
+        );
+        assert_eq!(Side::Buy.opposite(), Side::Sell);
+        assert!("buy".parse::<Side>().is_err());
+    }
+
+    #[test]
+    fn test_limit_order_body() {
+        let request = OrderRequest::limit(
+            Category::Linear,
+            "BTCUSDT",
+            Side::Buy,
+            dec("0.010"),
+            dec("60000.5"),
+        )
+        .time_in_force(TimeInForce::PostOnly)
+        .order_link_id("ix-1")
+        .take_profit(dec("65000"))
+        .tp_trigger_by(TriggerBy::MarkPrice)
+        .build()
+        .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "category": "linear",
+                "symbol": "BTCUSDT",
+                "side": "Buy",
+                "orderType": "Limit",
+                "qty": "0.010",
+                "price": "60000.5",
+                "timeInForce": "PostOnly",
+                "orderLinkId": "ix-1",
+                "takeProfit": "65000",
+                "tpTriggerBy": "MarkPrice",
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_orders() {
+        let market =
+            || OrderRequest::market(Category::Spot, "SOLUSDT", Side::Sell, dec("1"));
+
+        assert!(market().build().is_ok());
+        assert!(
+            OrderRequest::builder()
+                .category(Category::Spot)
+                .side(Side::Buy)
+                .build()
+                .is_err()
+        );
+        assert!(
+            OrderRequest::market(Category::Spot, "SOLUSDT", Side::Sell, Decimal::ZERO)
+                .build()
+                .is_err()
+        );
+        assert!(
+            market()
+                .time_in_force(TimeInForce::PostOnly)
+                .build()
+                .is_err()
+        );
+        assert!(market().reduce_only(true).build().is_err());
+        assert!(market().order_link_id("x".repeat(37)).build().is_err());
+        assert!(
+            OrderRequest::builder()
+                .category(Category::Linear)
+                .symbol("BTCUSDT")
+                .side(Side::Buy)
+                .order_type(OrderType::Limit)
+                .qty(dec("1"))
+                .build()
+                .is_err()
+        );
+        assert!(
+            OrderRequest::market(Category::Linear, "BTCUSDT", Side::Buy, dec("1"))
+                .market_unit(MarketUnit::BaseCoin)
+                .build()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_amend_and_cancel_bodies() {
+        let amend = AmendRequest::new(
+            Category::Linear,
+            "ETHUSDT",
+            OrderRef::OrderLinkId("ix-2".to_string()),
+        )
+        .price(dec("3000"));
+        assert_eq!(
+            serde_json::to_value(&amend).unwrap(),
+            json!({
+                "category": "linear",
+                "symbol": "ETHUSDT",
+                "orderLinkId": "ix-2",
+                "price": "3000",
+            })
+        );
+
+        let cancel = CancelRequest::new(
+            Category::Spot,
+            "SOLUSDT",
+            OrderRef::OrderId("1234".to_string()),
+        );
+        assert_eq!(
+            serde_json::to_value(&cancel).unwrap(),
+            json!({"category": "spot", "symbol": "SOLUSDT", "orderId": "1234"})
+        );
+    }
+
+    #[test]
+    fn test_parse_order_numbers() {
+        let response: OrderResponse = serde_json::from_str(
+            r#"{
+            "retCode": 0,
+            "retMsg": "OK",
+            "result": {
+                "category": "spot",
+                "nextPageCursor": "page2",
+                "list": [{
+                    "orderId": "1", "orderLinkId": "ix-1", "blockTradeId": "",
+                    "symbol": "SOLUSDT", "price": "150.25", "qty": "2.5",
+                    "side": "Buy", "isLeverage": "0", "positionIdx": 0,
+                    "orderStatus": "PartiallyFilled", "cancelType": "UNKNOWN",
+                    "rejectReason": "EC_NoError", "avgPrice": "150.2",
+                    "leavesQty": "1.5", "leavesValue": "225.375",
+                    "cumExecQty": "1", "cumExecValue": "150.2",
+                    "cumExecFee": "0.001", "timeInForce": "GTC",
+                    "orderType": "Limit", "stopOrderType": "", "orderIv": "",
+                    "triggerPrice": "0", "takeProfit": "", "stopLoss": "",
+                    "tpTriggerBy": "", "slTriggerBy": "", "triggerDirection": 0,
+                    "triggerBy": "", "lastPriceOnCreated": "", "reduceOnly": false,
+                    "closeOnTrigger": false, "smpType": "None", "smpGroup": 0,
+                    "smpOrderId": "", "tpslMode": "", "tpLimitPrice": "",
+                    "slLimitPrice": "", "placeType": "",
+                    "createdTime": "1752000000000", "updatedTime": "1752000001000"
+                }]
+            },
+            "retExtInfo": {},
+            "time": 1752000002000
+        }"#,
+        )
+        .unwrap();
+
+        assert_eq!(response.result.next_page_cursor, "page2");
+        let order = &response.result.list[0];
+        assert_eq!(order.price, dec("150.25"));
+        assert_eq!(order.qty, dec("2.5"));
+        assert_eq!(order.cum_exec_qty, dec("1"));
+        assert_eq!(order.cum_exec_fee, dec("0.001"));
+        assert_eq!(order.take_profit, Decimal::ZERO);
+        assert_eq!(order.created_time, 1_752_000_000_000);
+    }
+
+    #[test]
+    fn test_batch_outcomes() {
+        let response: BatchResponse = serde_json::from_value(json!({
+            "retCode": 0,
+            "retMsg": "OK",
+            "result": {"list": [
+                {"category": "spot", "symbol": "SOLUSDT", "orderId": "1", "orderLinkId": "a"},
+                {"category": "spot", "symbol": "SOLUSDT", "orderId": "", "orderLinkId": "b"}
+            ]},
+            "retExtInfo": {"list": [
+                {"code": 0, "msg": "OK"},
+                {"code": 170140, "msg": "Order value exceeded lower limit"}
+            ]},
+            "time": 1752000000000u64
+        }))
+        .unwrap();
+
+        let orders = response.orders();
+        assert_eq!(orders[0].unwrap().order_id, "1");
+        assert_eq!(orders[1].unwrap_err(), "Order value exceeded lower limit");
+    }
+}
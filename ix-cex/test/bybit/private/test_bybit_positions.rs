@@ -0,0 +1,271 @@
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+
+    use ix_cex::exchanges::bybit::responses::{
+        ClosedPnlBybit, ExecutionBybit, PageResponse, PositionBybit,
+    };
+    use ix_cex::exchanges::bybit::{
+        Category, LeverageRequest, PageQuery, TpslMode, TradingStopRequest, TriggerBy,
+    };
+    use rust_decimal::Decimal;
+    use serde_json::json;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    #[test]
+    fn test_page_query_params() {
+        let params = PageQuery::new(Category::Linear)
+            .symbol("BTCUSDT")
+            .start_time(1_700_000_000_000)
+            .limit(100)
+            .params();
+
+        assert_eq!(
+            params,
+            vec![
+                ("category", "linear".to_string()),
+                ("symbol", "BTCUSDT".to_string()),
+                ("startTime", "1700000000000".to_string()),
+                ("limit", "100".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_positions() {
+        let body = json!({
+            "retCode": 0,
+            "retMsg": "OK",
+            "result": {
+                "list": [{
+                    "positionIdx": 0,
+                    "riskId": 1,
+                    "riskLimitValue": "2000000",
+                    "symbol": "BTCUSDT",
+                    "side": "Buy",
+                    "size": "0.010",
+                    "avgPrice": "60250.5",
+                    "positionValue": "602.505",
+                    "tradeMode": 0,
+                    "autoAddMargin": 0,
+                    "positionStatus": "Normal",
+                    "leverage": "10",
+                    "markPrice": "60400",
+                    "liqPrice": "",
+                    "bustPrice": "",
+                    "positionIM": "60.2505",
+                    "positionMM": "3.0125",
+                    "positionBalance": "0",
+                    "tpslMode": "Full",
+                    "takeProfit": "62000",
+                    "stopLoss": "0",
+                    "trailingStop": "0",
+                    "unrealisedPnl": "1.495",
+                    "curRealisedPnl": "-0.331",
+                    "cumRealisedPnl": "-12.5",
+                    "adlRankIndicator": 2,
+                    "createdTime": "1676538056258",
+                    "updatedTime": "1697673600012",
+                    "seq": 4688002127i64
+                }],
+                "nextPageCursor": "",
+                "category": "linear"
+            },
+            "retExtInfo": {},
+            "time": 1697684980172u64
+        });
+
+        let response: PageResponse<PositionBybit> = serde_json::from_value(body).unwrap();
+        assert_eq!(response.result.category, "linear");
+        assert!(response.result.next_page_cursor.is_empty());
+
+        let position = &response.result.list[0];
+        assert_eq!(position.size, dec("0.010"));
+        assert_eq!(position.position_im, dec("60.2505"));
+        assert_eq!(position.liq_price, Decimal::ZERO);
+        assert_eq!(position.take_profit, dec("62000"));
+        assert_eq!(position.updated_time, 1_697_673_600_012);
+    }
+
+    #[test]
+    fn test_parse_executions() {
+        let body = json!({
+            "retCode": 0,
+            "retMsg": "OK",
+            "result": {
+                "nextPageCursor": "132766%3A2%2C132766%3A2",
+                "category": "linear",
+                "list": [{
+                    "symbol": "ETHUSDT",
+                    "orderType": "Market",
+                    "underlyingPrice": "",
+                    "orderLinkId": "ix-abc",
+                    "side": "Sell",
+                    "indexPrice": "",
+                    "orderId": "8c065341-7b52-4ca9-ac2c-37e31ac55c94",
+                    "stopOrderType": "UNKNOWN",
+                    "leavesQty": "0",
+                    "execTime": "1672282722429",
+                    "feeCurrency": "",
+                    "isMaker": false,
+                    "execFee": "0.071409",
+                    "feeRate": "0.0006",
+                    "execId": "e0cbe81d-0f18-5866-9415-cf319b5dab3b",
+                    "tradeIv": "",
+                    "blockTradeId": "",
+                    "markPrice": "1183.54",
+                    "execPrice": "1190.15",
+                    "markIv": "",
+                    "orderQty": "0.1",
+                    "orderPrice": "1236.9",
+                    "execValue": "119.015",
+                    "execType": "Trade",
+                    "execQty": "0.1",
+                    "closedSize": "",
+                    "seq": 4688002127i64
+                }]
+            },
+            "retExtInfo": {},
+            "time": 1672283754510u64
+        });
+
+        let response: PageResponse<ExecutionBybit> =
+            serde_json::from_value(body).unwrap();
+        assert_eq!(response.result.next_page_cursor, "132766%3A2%2C132766%3A2");
+
+        let execution = &response.result.list[0];
+        assert_eq!(execution.order_link_id, "ix-abc");
+        assert_eq!(execution.exec_price, dec("1190.15"));
+        assert_eq!(execution.exec_fee, dec("0.071409"));
+        assert_eq!(execution.closed_size, Decimal::ZERO);
+        assert_eq!(execution.exec_time, 1_672_282_722_429);
+        assert!(!execution.is_maker);
+    }
+
+    #[test]
+    fn test_parse_closed_pnl() {
+        let body = json!({
+            "retCode": 0,
+            "retMsg": "OK",
+            "result": {
+                "nextPageCursor": "",
+                "category": "linear",
+                "list": [{
+                    "symbol": "ETHPERP",
+                    "orderType": "Market",
+                    "leverage": "3",
+                    "updatedTime": "1672214887236",
+                    "side": "Sell",
+                    "orderId": "5a373bfe-188d-4913-9c81-d57ab5be8068",
+                    "closedPnl": "-47.4065323",
+                    "avgEntryPrice": "1194.97516667",
+                    "qty": "3",
+                    "cumEntryValue": "3584.9255",
+                    "createdTime": "1672214887231",
+                    "orderPrice": "1122.95",
+                    "closedSize": "3",
+                    "avgExitPrice": "1180.59833333",
+                    "execType": "Trade",
+                    "fillCount": "4",
+                    "cumExitValue": "3541.795"
+                }]
+            },
+            "retExtInfo": {},
+            "time": 1672284129153u64
+        });
+
+        let response: PageResponse<ClosedPnlBybit> =
+            serde_json::from_value(body).unwrap();
+        let pnl = &response.result.list[0];
+        assert_eq!(pnl.closed_pnl, dec("-47.4065323"));
+        assert_eq!(pnl.avg_exit_price, dec("1180.59833333"));
+        assert_eq!(pnl.fill_count, 4);
+        assert_eq!(pnl.created_time, 1_672_214_887_231);
+    }
+
+    #[test]
+    fn test_leverage_body() {
+        let request = LeverageRequest::new(Category::Linear, "BTCUSDT", dec("5"));
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "category": "linear",
+                "symbol": "BTCUSDT",
+                "buyLeverage": "5",
+                "sellLeverage": "5"
+            })
+        );
+    }
+
+    #[test]
+    fn test_trading_stop_body() {
+        let request = TradingStopRequest::new(Category::Linear, "BTCUSDT")
+            .tpsl_mode(TpslMode::Partial)
+            .take_profit(dec("65000"))
+            .tp_size(dec("0.005"))
+            .stop_loss(dec("58000"))
+            .sl_trigger_by(TriggerBy::MarkPrice);
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "category": "linear",
+                "symbol": "BTCUSDT",
+                "tpslMode": "Partial",
+                "positionIdx": 0,
+                "takeProfit": "65000",
+                "stopLoss": "58000",
+                "slTriggerBy": "MarkPrice",
+                "tpSize": "0.005"
+            })
+        );
+    }
+
+    #[test]
+    fn test_validation() {
+        let stop = TradingStopRequest::new(Category::Linear, "BTCUSDT");
+        assert!(stop.validate().is_err());
+        assert!(stop.clone().stop_loss(dec("58000")).validate().is_ok());
+        assert!(stop.clone().take_profit(dec("-1")).validate().is_err());
+        assert!(
+            stop.clone()
+                .take_profit(dec("65000"))
+                .tp_size(dec("0.005"))
+                .validate()
+                .is_err()
+        );
+        assert!(
+            TradingStopRequest::new(Category::Spot, "BTCUSDT")
+                .stop_loss(dec("58000"))
+                .validate()
+                .is_err()
+        );
+
+        assert!(
+            LeverageRequest::new(Category::Inverse, "BTCUSD", dec("2"))
+                .validate()
+                .is_ok()
+        );
+        assert!(
+            LeverageRequest::new(Category::Linear, "BTCUSDT", Decimal::ZERO)
+                .validate()
+                .is_err()
+        );
+        assert!(
+            LeverageRequest::new(Category::Option, "BTC-28JUN24-60000-C", dec("2"))
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_tpsl_mode_values() {
+        assert_eq!(TpslMode::Full.as_str(), "Full");
+        assert_eq!("Partial".parse::<TpslMode>().unwrap(), TpslMode::Partial);
+        assert!("partial".parse::<TpslMode>().is_err());
+    }
+}
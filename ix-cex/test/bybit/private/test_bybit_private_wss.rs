@@ -0,0 +1,356 @@
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use futures_util::{SinkExt, StreamExt};
+    use ix_cex::BybitPrivateClient;
+    use ix_cex::client::wss_client::ConnectionEvent;
+    use ix_cex::exchanges::bybit::bybit_private_wss::{
+        parse_private_event, run_private_websocket_client,
+    };
+    use ix_cex::exchanges::bybit::{BybitPrivateEvent, PrivateTopic};
+    use rust_decimal::Decimal;
+    use serde_json::{Value, json};
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio::sync::mpsc;
+    use tokio::time::timeout;
+    use tokio_tungstenite::tungstenite::protocol::Message;
+    use tokio_tungstenite::{WebSocketStream, accept_async};
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    fn test_client() -> BybitPrivateClient {
+        BybitPrivateClient::with_credentials(
+            "test-key".to_string(),
+            "test-secret".to_string(),
+            false,
+        )
+        .unwrap()
+    }
+
+    fn order_message() -> Value {
+        serde_json::from_str(
+            r#"{
+                "id": "5923240c6880ab-c59f-420b-9adb-3639adc9dd90",
+                "topic": "order",
+                "creationTime": 1672364262474,
+                "data": [{
+                    "symbol": "BTCUSDT",
+                    "orderId": "fd4300ae-7847-404e-b947-b46980a4d140",
+                    "side": "Buy",
+                    "orderType": "Market",
+                    "cancelType": "UNKNOWN",
+                    "price": "61000",
+                    "qty": "0.010",
+                    "orderIv": "",
+                    "timeInForce": "IOC",
+                    "orderStatus": "Filled",
+                    "orderLinkId": "ix-abc",
+                    "lastPriceOnCreated": "60100",
+                    "reduceOnly": false,
+                    "leavesQty": "0",
+                    "leavesValue": "0",
+                    "cumExecQty": "0.010",
+                    "cumExecValue": "601.2",
+                    "avgPrice": "60120",
+                    "blockTradeId": "",
+                    "positionIdx": 0,
+                    "cumExecFee": "0.33066",
+                    "closedPnl": "0",
+                    "createdTime": "1672364262444",
+                    "updatedTime": "1672364262457",
+                    "rejectReason": "EC_NoError",
+                    "stopOrderType": "",
+                    "tpslMode": "",
+                    "triggerPrice": "",
+                    "takeProfit": "",
+                    "stopLoss": "",
+                    "tpTriggerBy": "",
+                    "slTriggerBy": "",
+                    "tpLimitPrice": "",
+                    "slLimitPrice": "",
+                    "triggerDirection": 0,
+                    "triggerBy": "",
+                    "closeOnTrigger": false,
+                    "category": "linear",
+                    "placeType": "",
+                    "smpType": "None",
+                    "smpGroup": 0,
+                    "smpOrderId": "",
+                    "feeCurrency": ""
+                }]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    /// Next operation sent by the client, skipping its pings
+    async fn read_op(ws: &mut WebSocketStream<TcpStream>) -> Value {
+        loop {
+            if let Some(Ok(Message::Text(text))) = ws.next().await {
+                let value: Value = serde_json::from_str(&text).unwrap();
+                if value["op"] != "ping" {
+                    return value;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_ws_auth_message() {
+        let message: Value = serde_json::from_str(
+            &test_client().ws_auth_message(1_700_000_005_000).unwrap(),
+        )
+        .unwrap();
+
+        // HMAC-SHA256 of "GET/realtime1700000005000" with "test-secret"
+        assert_eq!(
+            message,
+            json!({
+                "op": "auth",
+                "args": [
+                    "test-key",
+                    1_700_000_005_000u64,
+                    "4343ac53a3dafc0ec96562c63a7899f56be48a0e2ab052e07ae410e8f5472338"
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_order_event() {
+        match parse_private_event(order_message()).unwrap() {
+            Some(BybitPrivateEvent::Order(orders)) => {
+                assert_eq!(orders[0].category, "linear");
+                assert_eq!(orders[0].order.order_link_id, "ix-abc");
+                assert_eq!(orders[0].order.order_status, "Filled");
+                assert_eq!(orders[0].order.cum_exec_qty, dec("0.010"));
+                assert_eq!(orders[0].order.trigger_price, Decimal::ZERO);
+            }
+            other => panic!("expected orders, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_execution_and_position_events() {
+        let execution = json!({
+            "id": "592324803b2785-26fa-4214-9963-bdd4727f07be",
+            "topic": "execution.linear",
+            "creationTime": 1672364174455u64,
+            "data": [{
+                "category": "linear",
+                "symbol": "XRPUSDT",
+                "execFee": "0.005061",
+                "execId": "7e2ae69c-4edf-5800-a352-893d52b446aa",
+                "execPrice": "0.3374",
+                "execQty": "25",
+                "execType": "Trade",
+                "execValue": "8.435",
+                "isMaker": false,
+                "feeRate": "0.0006",
+                "tradeIv": "",
+                "markIv": "",
+                "blockTradeId": "",
+                "markPrice": "0.3391",
+                "indexPrice": "",
+                "underlyingPrice": "",
+                "leavesQty": "0",
+                "orderId": "f6e324ff-99c2-4e89-9739-3086e47f9381",
+                "orderLinkId": "",
+                "orderPrice": "0.3207",
+                "orderQty": "25",
+                "orderType": "Market",
+                "stopOrderType": "UNKNOWN",
+                "side": "Sell",
+                "execTime": "1672364174443",
+                "isLeverage": "0",
+                "closedSize": "",
+                "seq": 4688002127i64
+            }]
+        });
+        match parse_private_event(execution).unwrap() {
+            Some(BybitPrivateEvent::Execution(executions)) => {
+                assert_eq!(executions[0].execution.exec_qty, dec("25"));
+                assert_eq!(executions[0].execution.exec_time, 1_672_364_174_443);
+            }
+            other => panic!("expected executions, got {other:?}"),
+        }
+
+        let position = json!({
+            "id": "59232430b58efe-5fc5-4470-9337-4ce293b68edd",
+            "topic": "position",
+            "creationTime": 1672364174455u64,
+            "data": [{
+                "positionIdx": 0,
+                "tradeMode": 0,
+                "riskId": 41,
+                "riskLimitValue": "200000",
+                "symbol": "XRPUSDT",
+                "side": "Buy",
+                "size": "75",
+                "entryPrice": "0.3615",
+                "leverage": "10",
+                "positionValue": "27.1125",
+                "positionBalance": "0",
+                "markPrice": "0.3374",
+                "positionIM": "2.72589075",
+                "positionMM": "0.28576575",
+                "takeProfit": "0",
+                "stopLoss": "0",
+                "trailingStop": "0",
+                "unrealisedPnl": "-1.8075",
+                "cumRealisedPnl": "0.64782276",
+                "createdTime": "1672121182216",
+                "updatedTime": "1672364174449",
+                "tpslMode": "Full",
+                "liqPrice": "",
+                "bustPrice": "",
+                "category": "linear",
+                "positionStatus": "Normal",
+                "adlRankIndicator": 2,
+                "seq": 4688002127i64
+            }]
+        });
+        match parse_private_event(position).unwrap() {
+            Some(BybitPrivateEvent::Position(positions)) => {
+                assert_eq!(positions[0].entry_price, dec("0.3615"));
+                assert_eq!(positions[0].position.size, dec("75"));
+                assert_eq!(positions[0].position.unrealised_pnl, dec("-1.8075"));
+            }
+            other => panic!("expected positions, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_wallet_and_unknown_events() {
+        let wallet = json!({
+            "id": "5923242c464be9-25ca-483d-a743-c60101fc656f",
+            "topic": "wallet",
+            "creationTime": 1672364262482u64,
+            "data": [{
+                "accountIMRate": "0.016",
+                "accountMMRate": "0.003",
+                "totalEquity": "12837.78330098",
+                "totalWalletBalance": "12840.4045924",
+                "totalMarginBalance": "12837.78330188",
+                "totalAvailableBalance": "12632.05767702",
+                "totalPerpUPL": "-2.62129051",
+                "totalInitialMargin": "205.72562486",
+                "totalMaintenanceMargin": "39.42876721",
+                "coin": [{
+                    "coin": "USDT",
+                    "equity": "12836.91",
+                    "usdValue": "12837.78",
+                    "walletBalance": "12839.53",
+                    "availableToWithdraw": "12632.05",
+                    "availableToBorrow": "",
+                    "borrowAmount": "0",
+                    "accruedInterest": "0",
+                    "totalOrderIM": "",
+                    "totalPositionIM": "205.72",
+                    "totalPositionMM": "39.42",
+                    "unrealisedPnl": "-2.62",
+                    "cumRealisedPnl": "-1.23",
+                    "bonus": "0",
+                    "collateralSwitch": true,
+                    "marginCollateral": true,
+                    "locked": "",
+                    "spotHedgingQty": "0"
+                }],
+                "accountLTV": "0",
+                "accountType": "UNIFIED"
+            }]
+        });
+        match parse_private_event(wallet).unwrap() {
+            Some(BybitPrivateEvent::Wallet(wallets)) => {
+                assert_eq!(wallets[0].account_type, "UNIFIED");
+                assert_eq!(wallets[0].coin[0].coin, "USDT");
+            }
+            other => panic!("expected wallets, got {other:?}"),
+        }
+
+        let greeks = json!({
+            "id": "1",
+            "topic": "greeks",
+            "creationTime": 1672364262482u64,
+            "data": []
+        });
+        assert!(parse_private_event(greeks).unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_private_stream_signs_again_when_refused() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        // Refuse the first authentication, accept the second, then check the
+        // subscription and publish an order
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = accept_async(stream).await.unwrap();
+
+            let first = read_op(&mut ws).await;
+            assert_eq!(first["op"], "auth");
+            assert_eq!(first["args"][0], "test-key");
+            let refused =
+                json!({ "success": false, "ret_msg": "Request expired", "op": "auth" });
+            ws.send(Message::Text(refused.to_string())).await.unwrap();
+
+            let second = read_op(&mut ws).await;
+            assert_eq!(second["op"], "auth");
+            let accepted = json!({ "success": true, "ret_msg": "", "op": "auth" });
+            ws.send(Message::Text(accepted.to_string())).await.unwrap();
+
+            let subscribe = read_op(&mut ws).await;
+            assert_eq!(
+                subscribe,
+                json!({ "op": "subscribe", "args": ["order", "wallet"] })
+            );
+            ws.send(Message::Text(order_message().to_string()))
+                .await
+                .unwrap();
+            ws
+        });
+
+        let (tx, mut events) = mpsc::channel(100);
+        let client = tokio::spawn(async move {
+            run_private_websocket_client(
+                test_client(),
+                &url,
+                tx,
+                vec![PrivateTopic::Order, PrivateTopic::Wallet],
+            )
+            .await
+        });
+
+        let mut next = async || {
+            timeout(Duration::from_secs(5), events.recv())
+                .await
+                .expect("timed out waiting for event")
+                .expect("stream stopped")
+        };
+        assert!(matches!(
+            next().await,
+            BybitPrivateEvent::Connection(ConnectionEvent::Connected { attempt: 1 })
+        ));
+        assert!(matches!(next().await, BybitPrivateEvent::Authenticated));
+        match next().await {
+            BybitPrivateEvent::Order(orders) => {
+                assert_eq!(orders[0].order.order_link_id, "ix-abc")
+            }
+            other => panic!("expected orders, got {other:?}"),
+        }
+
+        drop(events);
+        let _ws = server.await.unwrap();
+        timeout(Duration::from_secs(5), client)
+            .await
+            .expect("client did not stop")
+            .unwrap()
+            .unwrap();
+    }
+}
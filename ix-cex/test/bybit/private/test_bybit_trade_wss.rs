@@ -0,0 +1,234 @@
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use futures_util::{SinkExt, StreamExt};
+    use ix_cex::BybitPrivateClient;
+    use ix_cex::exchanges::bybit::bybit_trade_wss::trade_message;
+    use ix_cex::exchanges::bybit::responses::{TradeResponse, TradeWsResponse};
+    use ix_cex::exchanges::bybit::{
+        BybitTradeClient, CancelRequest, Category, OrderEntry, OrderRef, OrderRequest,
+        Side,
+    };
+    use ix_results::errors::ExchangeError;
+    use rust_decimal::Decimal;
+    use serde_json::{Value, json};
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio::time::timeout;
+    use tokio_tungstenite::tungstenite::protocol::Message;
+    use tokio_tungstenite::{WebSocketStream, accept_async};
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    fn test_client() -> BybitPrivateClient {
+        BybitPrivateClient::with_credentials(
+            "test-key".to_string(),
+            "test-secret".to_string(),
+            false,
+        )
+        .unwrap()
+    }
+
+    fn market_order(link_id: &str) -> OrderRequest {
+        OrderRequest::market(Category::Linear, "BTCUSDT", Side::Buy, dec("0.001"))
+            .order_link_id(link_id)
+            .build()
+            .unwrap()
+    }
+
+    /// Next operation sent by the client, skipping its pings
+    async fn read_op(ws: &mut WebSocketStream<TcpStream>) -> Value {
+        loop {
+            if let Some(Ok(Message::Text(text))) = ws.next().await {
+                let value: Value = serde_json::from_str(&text).unwrap();
+                if value["op"] != "ping" {
+                    return value;
+                }
+            }
+        }
+    }
+
+    /// Accept one connection and answer its authentication
+    async fn authenticated_server(listener: TcpListener) -> WebSocketStream<TcpStream> {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = accept_async(stream).await.unwrap();
+
+        let auth = read_op(&mut ws).await;
+        assert_eq!(auth["op"], "auth");
+        assert_eq!(auth["args"][0], "test-key");
+        let accepted =
+            json!({ "retCode": 0, "retMsg": "OK", "op": "auth", "connId": "c1" });
+        ws.send(Message::Text(accepted.to_string())).await.unwrap();
+        ws
+    }
+
+    fn response(request: &Value, ret_code: i32, order_id: &str) -> Message {
+        let data = if ret_code == 0 {
+            json!({
+                "orderId": order_id,
+                "orderLinkId": request["args"][0]["orderLinkId"]
+            })
+        } else {
+            json!({})
+        };
+        let response = json!({
+            "reqId": request["reqId"],
+            "retCode": ret_code,
+            "retMsg": if ret_code == 0 { "OK" } else { "Insufficient balance" },
+            "op": request["op"],
+            "data": data,
+            "retExtInfo": {},
+            "header": {
+                "X-Bapi-Limit": "10",
+                "X-Bapi-Limit-Status": "9",
+                "Timenow": "1711001595209"
+            },
+            "connId": "c1"
+        });
+        Message::Text(response.to_string())
+    }
+
+    #[test]
+    fn test_trade_message() {
+        let message: Value = serde_json::from_str(
+            &trade_message(
+                "req-1",
+                "order.create",
+                &market_order("ix-1"),
+                1_711_001_595_207,
+                5000,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            message,
+            json!({
+                "reqId": "req-1",
+                "header": {
+                    "X-BAPI-TIMESTAMP": "1711001595207",
+                    "X-BAPI-RECV-WINDOW": "5000"
+                },
+                "op": "order.create",
+                "args": [{
+                    "category": "linear",
+                    "symbol": "BTCUSDT",
+                    "side": "Buy",
+                    "orderType": "Market",
+                    "qty": "0.001",
+                    "orderLinkId": "ix-1"
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn test_response_as_rest_response() {
+        let response: TradeWsResponse = serde_json::from_value(json!({
+            "reqId": "req-1",
+            "retCode": 0,
+            "retMsg": "OK",
+            "op": "order.cancel",
+            "data": { "orderId": "1321003749386327552", "orderLinkId": "ix-1" },
+            "retExtInfo": {},
+            "header": { "Timenow": "1711001595209" },
+            "connId": "c1"
+        }))
+        .unwrap();
+
+        let response = TradeResponse::from(response);
+        assert_eq!(response.result.order_id, "1321003749386327552");
+        assert_eq!(response.result.order_link_id, "ix-1");
+        assert_eq!(response.time, 1_711_001_595_209);
+    }
+
+    #[tokio::test]
+    async fn test_responses_are_matched_by_request_id() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        // Answer two orders in reverse order, then refuse a cancel
+        let server = tokio::spawn(async move {
+            let mut ws = authenticated_server(listener).await;
+            let first = read_op(&mut ws).await;
+            let second = read_op(&mut ws).await;
+            assert_eq!(first["op"], "order.create");
+            assert_ne!(first["reqId"], second["reqId"]);
+            let order_id = |request: &Value| {
+                format!("id-{}", request["args"][0]["orderLinkId"].as_str().unwrap())
+            };
+            ws.send(response(&second, 0, &order_id(&second)))
+                .await
+                .unwrap();
+            ws.send(response(&first, 0, &order_id(&first)))
+                .await
+                .unwrap();
+
+            let cancel = read_op(&mut ws).await;
+            assert_eq!(cancel["op"], "order.cancel");
+            assert_eq!(cancel["args"][0]["orderId"], "id-ix-a");
+            ws.send(response(&cancel, 110001, "")).await.unwrap();
+            ws
+        });
+
+        let trade = BybitTradeClient::connect_url(&test_client(), &url).unwrap();
+        let (order_a, order_b) = (market_order("ix-a"), market_order("ix-b"));
+        let (a, b) =
+            tokio::join!(trade.place_order(&order_a), trade.place_order(&order_b));
+        assert_eq!(a.unwrap().data.order_id, "id-ix-a");
+        assert_eq!(b.unwrap().data.order_id, "id-ix-b");
+        assert!(trade.is_authenticated());
+
+        let cancel = CancelRequest::new(
+            Category::Linear,
+            "BTCUSDT",
+            OrderRef::OrderId("id-ix-a".to_string()),
+        );
+        let entry: &dyn OrderEntry = &trade;
+        assert_eq!(entry.transport(), "websocket");
+        match entry.cancel_order(&cancel).await {
+            Err(ExchangeError::ApiError { message, code, .. }) => {
+                assert_eq!(code, Some(110001));
+                assert!(message.contains("110001"), "unexpected message: {message}")
+            }
+            other => panic!("expected an API error, got {other:?}"),
+        }
+
+        let _ws = server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unanswered_request_times_out() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        // Read the order but never answer it
+        let server = tokio::spawn(async move {
+            let mut ws = authenticated_server(listener).await;
+            read_op(&mut ws).await;
+            ws
+        });
+
+        let trade = BybitTradeClient::connect_url(&test_client(), &url)
+            .unwrap()
+            .with_request_timeout(Duration::from_millis(300));
+        let result = timeout(
+            Duration::from_secs(5),
+            trade.place_order(&market_order("ix-c")),
+        )
+        .await
+        .expect("request did not time out");
+
+        match result {
+            Err(e @ ExchangeError::Timeout(_)) => assert!(e.is_retryable()),
+            other => panic!("expected a timeout, got {other:?}"),
+        }
+
+        let _ws = server.await.unwrap();
+    }
+}
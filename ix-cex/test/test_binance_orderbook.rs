@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod tests {
+
+    use chrono::Utc;
+    use ix_cex::BinanceClient;
+    use ix_cex::exchanges::binance::local_orderbook::{
+        DiffOutcome, LocalOrderbook, SyncState,
+    };
+    use ix_cex::exchanges::binance::models::{DiffDepth, Level};
+    use ix_cex::models::instruments::{Instrument, InstrumentRegistry};
+    use ix_cex::models::orderbook::{Orderbook, PriceLevel};
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    fn level(price: &str, qty: &str) -> Level {
+        Level {
+            price: Decimal::from_str(price).unwrap(),
+            qty: Decimal::from_str(qty).unwrap(),
+        }
+    }
+
+    fn diff(first: u64, last: u64, bids: Vec<Level>, asks: Vec<Level>) -> DiffDepth {
+        DiffDepth {
+            event_type: "depthUpdate".to_string(),
+            event_time: 1_752_000_000_000,
+            symbol: "SOLUSDC".to_string(),
+            first_update_id: first,
+            final_update_id: last,
+            bids,
+            asks,
+        }
+    }
+
+    fn snapshot(last_update_id: u64) -> Orderbook {
+        Orderbook::new(
+            "SOLUSDC".to_string(),
+            "Binance".to_string(),
+            Utc::now(),
+            vec![
+                PriceLevel::new(dec("151.52"), dec("95.568")),
+                PriceLevel::new(dec("151.51"), dec("85.869")),
+            ],
+            vec![
+                PriceLevel::new(dec("151.53"), dec("131.551")),
+                PriceLevel::new(dec("151.54"), dec("89.835")),
+            ],
+            Some(last_update_id),
+            None,
+        )
+    }
+
+    fn local_orderbook() -> LocalOrderbook {
+        let client = BinanceClient::new().unwrap();
+        let mut book = LocalOrderbook::new(client, Instrument::spot("SOL", "USDC"), 10);
+        book.seed(&snapshot(100)).unwrap();
+        book
+    }
+
+    #[test]
+    fn test_seed_from_snapshot() {
+        let book = local_orderbook();
+        let orderbook = book.to_orderbook();
+
+        assert_eq!(book.state(), SyncState::Synced);
+        assert_eq!(book.last_update_id(), Some(100));
+        assert_eq!(orderbook.best_bid().unwrap().price, dec("151.52"));
+        assert_eq!(orderbook.best_ask().unwrap().price, dec("151.53"));
+        assert!(orderbook.is_valid());
+    }
+
+    #[test]
+    fn test_apply_diff_sequence() {
+        let mut book = local_orderbook();
+
+        // Overlaps the snapshot: U <= lastUpdateId + 1 <= u
+        let first = diff(95, 105, vec![level("151.52000000", "0.00000000")], vec![]);
+        assert_eq!(book.apply_diff(&first), DiffOutcome::Applied);
+
+        let second = diff(106, 110, vec![], vec![level("151.525", "3.5")]);
+        assert_eq!(book.apply_diff(&second), DiffOutcome::Applied);
+
+        let orderbook = book.to_orderbook();
+        assert_eq!(book.last_update_id(), Some(110));
+        assert_eq!(orderbook.best_bid().unwrap().price, dec("151.51"));
+        assert_eq!(orderbook.best_ask().unwrap().price, dec("151.525"));
+        assert_eq!(orderbook.asks.len(), 3);
+    }
+
+    #[test]
+    fn test_stale_diff_is_dropped() {
+        let mut book = local_orderbook();
+        let stale = diff(90, 100, vec![level("151.52", "0")], vec![]);
+
+        assert_eq!(book.apply_diff(&stale), DiffOutcome::Stale);
+        assert_eq!(book.to_orderbook().best_bid().unwrap().price, dec("151.52"));
+    }
+
+    #[test]
+    fn test_gap_is_detected() {
+        let mut book = local_orderbook();
+        let gap = diff(105, 110, vec![level("151.50", "1")], vec![]);
+
+        assert_eq!(
+            book.apply_diff(&gap),
+            DiffOutcome::Gap {
+                expected: 101,
+                received: 105
+            }
+        );
+        assert_eq!(book.last_update_id(), Some(100));
+    }
+
+    #[test]
+    fn test_diffs_buffered_until_snapshot() {
+        let client = BinanceClient::new().unwrap();
+        let mut book = LocalOrderbook::new(client, Instrument::spot("SOL", "USDC"), 10);
+
+        // Unsynced diffs are only buffered, the snapshot is requested once
+        assert!(book.on_diff(diff(101, 104, vec![], vec![])).is_none());
+        assert!(
+            book.on_diff(diff(105, 110, vec![level("151.50", "1")], vec![]))
+                .is_none()
+        );
+        assert_eq!(book.state(), SyncState::Syncing);
+        assert_eq!(book.buffered(), 2);
+
+        // Snapshot older than the first buffered event asks for another one
+        assert!(book.on_snapshot(&snapshot(90)).unwrap().is_none());
+        assert_eq!(book.state(), SyncState::Syncing);
+        assert_eq!(book.buffered(), 2);
+
+        let orderbook = book.on_snapshot(&snapshot(102)).unwrap().unwrap();
+        assert_eq!(book.state(), SyncState::Synced);
+        assert_eq!(book.last_update_id(), Some(110));
+        assert_eq!(book.buffered(), 0);
+        assert_eq!(orderbook.bids.len(), 3);
+
+        // Synced diffs are applied directly
+        let next = book.on_diff(diff(111, 112, vec![], vec![level("151.53", "0")]));
+        assert_eq!(next.unwrap().best_ask().unwrap().price, dec("151.54"));
+    }
+
+    #[test]
+    fn test_registered_tick_size() {
+        let pair = Instrument::spot("TICKL", "USDC");
+        let mut registry = InstrumentRegistry::new();
+        registry.set_tick_size("binance", &pair, dec("0.01"));
+        InstrumentRegistry::install(registry);
+
+        let client = BinanceClient::new().unwrap();
+        let mut book = LocalOrderbook::new(client, pair, 10);
+        book.seed(&snapshot(100)).unwrap();
+
+        let orderbook = book.to_orderbook();
+        assert_eq!(orderbook.tick_size, Some(dec("0.01")));
+        assert_eq!(orderbook.spread_in_ticks(), Some(Decimal::ONE));
+    }
+}
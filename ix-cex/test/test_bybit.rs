@@ -0,0 +1,11 @@
+#[cfg(test)]
+mod tests {
+
+    use ix_cex::BybitClient;
+
+    #[tokio::test]
+    async fn test_bybit_client_creation() {
+        let client = BybitClient::new();
+        assert!(client.is_ok());
+    }
+}
@@ -0,0 +1,248 @@
+#[cfg(test)]
+mod tests {
+
+    use ix_cex::exchanges::binance::binance_client::BinanceDepthResponse;
+    use ix_cex::exchanges::bybit::responses::BybitOrderbookResponse;
+    use ix_cex::exchanges::coinbase::responses::orderbook::CoinbaseProductBookResponse;
+    use ix_cex::exchanges::deribit::deribit_client::DeribitOrderbookData;
+    use ix_cex::exchanges::kraken::kraken_client::KrakenOrderbookData;
+    use ix_cex::exchanges::okx::okx_client::OkxOrderbookData;
+    use ix_cex::models::instruments::{
+        Instrument, InstrumentKind, InstrumentRegistry, ListedInstrument,
+    };
+    use ix_cex::models::orderbook::Orderbook;
+    use ix_cex::{
+        BinanceClient, BybitClient, CoinbaseClient, DeribitClient, KrakenClient,
+        OkxClient,
+    };
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn test_parse_symbol_forms() {
+        let sol_usdt = Instrument::spot("SOL", "USDT");
+
+        for symbol in ["SOLUSDT", "SOL-USDT", "sol/usdt", "SOL_USDT"] {
+            assert_eq!(Instrument::parse_from_str(symbol), Some(sol_usdt.clone()));
+        }
+
+        assert_eq!(
+            Instrument::parse_from_str("XBTUSDC"),
+            Some(Instrument::spot("BTC", "USDC"))
+        );
+        assert_eq!(
+            Instrument::parse_from_str("SOL-USDT-SWAP"),
+            Some(Instrument::perpetual("SOL", "USDT"))
+        );
+        assert_eq!(
+            Instrument::parse_from_str("BTC-PERPETUAL"),
+            Some(Instrument::perpetual("BTC", "USD"))
+        );
+        assert_eq!(Instrument::parse_from_str("SOL"), None);
+
+        // Display parses back to the same instrument
+        let perp = Instrument::perpetual("ETH", "USDC");
+        assert_eq!(perp.to_string(), "ETH/USDC-PERP");
+        assert_eq!(perp.to_string().parse::<Instrument>().unwrap(), perp);
+    }
+
+    #[test]
+    fn test_default_symbols() {
+        let sol_usdc = Instrument::spot("SOL", "USDC");
+
+        assert_eq!(sol_usdc.default_symbol("binance"), "SOLUSDC");
+        assert_eq!(sol_usdc.default_symbol("Coinbase"), "SOL-USDC");
+        assert_eq!(sol_usdc.default_symbol("okx"), "SOL-USDC");
+        assert_eq!(sol_usdc.default_symbol("deribit"), "SOL_USDC");
+
+        let btc_perp = Instrument::perpetual("BTC", "USD");
+        assert_eq!(btc_perp.default_symbol("deribit"), "BTC-PERPETUAL");
+        assert_eq!(
+            Instrument::perpetual("SOL", "USDT").default_symbol("okx"),
+            "SOL-USDT-SWAP"
+        );
+    }
+
+    #[test]
+    fn test_registry_from_config() {
+        let path = std::env::temp_dir()
+            .join(format!("ix_cex_instruments_{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            r#"
+[exchange]
+name = "kraken"
+
+[pairs]
+symbols = ["LINKUSD", "ETH/USDC"]
+tick_sizes = { LINKUSD = "0.001" }
+
+[[pairs.instruments]]
+base = "XBT"
+quote = "USDT"
+symbol = "XBTUSDT"
+tick_size = "0.1"
+
+[[pairs.instruments]]
+base = "SOL"
+quote = "USD"
+kind = "perpetual"
+symbol = "PF_SOLUSD"
+"#,
+        )
+        .unwrap();
+
+        let mut registry = InstrumentRegistry::new();
+        let loaded = registry.load_config(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, 4);
+        assert_eq!(
+            registry.symbol("Kraken", &Instrument::spot("BTC", "USDT")),
+            Some("XBTUSDT")
+        );
+        assert_eq!(
+            registry.instrument("kraken", "PF_SOLUSD", InstrumentKind::Perpetual),
+            Some(&Instrument::perpetual("SOL", "USD"))
+        );
+        assert_eq!(registry.instruments("kraken").len(), 4);
+        assert_eq!(
+            registry.tick_size("kraken", &Instrument::spot("LINK", "USD")),
+            Some(Decimal::new(1, 3))
+        );
+        assert_eq!(
+            registry.tick_size("kraken", &Instrument::spot("BTC", "USDT")),
+            Some(Decimal::new(1, 1))
+        );
+        assert_eq!(
+            registry.tick_size("kraken", &Instrument::spot("ETH", "USDC")),
+            None
+        );
+        assert!(registry.instruments("binance").is_empty());
+    }
+
+    #[test]
+    fn test_registry_extend_and_merge() {
+        let mut listed = InstrumentRegistry::new();
+        listed.extend(
+            "bybit",
+            vec![
+                ListedInstrument::new(Instrument::spot("SOL", "USDT"), "SOLUSDT")
+                    .with_tick_size(Some(Decimal::new(1, 2))),
+                ListedInstrument::new(Instrument::perpetual("SOL", "USDT"), "SOLUSDT")
+                    .with_tick_size(Some(Decimal::new(1, 3))),
+            ],
+        );
+
+        // Spot and perpetual share the symbol, the kind tells them apart
+        assert_eq!(
+            listed.instrument("bybit", "solusdt", InstrumentKind::Perpetual),
+            Some(&Instrument::perpetual("SOL", "USDT"))
+        );
+
+        let mut registry = InstrumentRegistry::new();
+        registry.merge(listed);
+        assert_eq!(registry.instruments("bybit").len(), 2);
+
+        // Tick sizes follow the instrument, not the shared symbol
+        assert_eq!(
+            registry.tick_size("Bybit", &Instrument::perpetual("SOL", "USDT")),
+            Some(Decimal::new(1, 3))
+        );
+        assert_eq!(
+            registry.tick_size("bybit", &Instrument::spot("SOL", "USDT")),
+            Some(Decimal::new(1, 2))
+        );
+    }
+
+    #[test]
+    fn test_repository_configs_load() {
+        let mut registry = InstrumentRegistry::new();
+        registry
+            .load_config_dir(InstrumentRegistry::default_config_dir())
+            .unwrap();
+
+        assert_eq!(
+            registry.symbol("coinbase", &Instrument::spot("BTC", "USD")),
+            Some("BTC-USD")
+        );
+        assert!(
+            registry
+                .instruments("binance")
+                .contains(&Instrument::spot("SOL", "USDC"))
+        );
+    }
+
+    /// Order book of `pair` converted from a REST response of the exchange
+    type Convert = fn(&Instrument) -> Orderbook;
+
+    #[test]
+    fn test_orderbook_tick_size() {
+        let cases: [(&str, Instrument, Convert); 6] = [
+            ("binance", Instrument::spot("TICKA", "USDC"), |pair| {
+                let text = r#"{"lastUpdateId":100,"bids":[["151.52","95.568"]],
+                    "asks":[["151.53","131.551"]]}"#;
+                let response: BinanceDepthResponse = serde_json::from_str(text).unwrap();
+                let client = BinanceClient::new().unwrap();
+                client.convert_to_orderbook(response, pair).unwrap()
+            }),
+            ("bybit", Instrument::spot("TICKB", "USDT"), |pair| {
+                let text = r#"{"retCode":0,"retMsg":"OK","result":{"s":"TICKBUSDT",
+                    "b":[["151.52","95.568"]],"a":[["151.53","131.551"]],
+                    "ts":1752000000000},"retExtInfo":{},"time":1752000000000}"#;
+                let response: BybitOrderbookResponse =
+                    serde_json::from_str(text).unwrap();
+                let client = BybitClient::new().unwrap();
+                client.convert_to_orderbook(response, pair).unwrap()
+            }),
+            ("coinbase", Instrument::spot("TICKC", "USD"), |pair| {
+                let text = r#"{"pricebook":{"product_id":"TICKC-USD",
+                    "bids":[{"price":"151.52","size":"95.568"}],
+                    "asks":[{"price":"151.53","size":"131.551"}],
+                    "time":"2025-07-08T18:40:00.000000Z"}}"#;
+                let response: CoinbaseProductBookResponse =
+                    serde_json::from_str(text).unwrap();
+                let client = CoinbaseClient::new().unwrap();
+                client.convert_to_orderbook(response, pair).unwrap()
+            }),
+            ("deribit", Instrument::spot("TICKD", "USDC"), |pair| {
+                let text = r#"{"instrument_name":"TICKD_USDC","timestamp":1752000000000,
+                    "change_id":100,"bids":[[151.52,95.568]],"asks":[[151.53,131.551]]}"#;
+                let data: DeribitOrderbookData = serde_json::from_str(text).unwrap();
+                let client = DeribitClient::new().unwrap();
+                client.convert_to_orderbook(data, pair, 10).unwrap()
+            }),
+            ("kraken", Instrument::spot("TICKK", "USDC"), |pair| {
+                let text = r#"{"bids":[["151.52","95.568",1752000000]],
+                    "asks":[["151.53","131.551",1752000000]]}"#;
+                let data: KrakenOrderbookData = serde_json::from_str(text).unwrap();
+                let client = KrakenClient::new().unwrap();
+                client.convert_to_orderbook(data, pair).unwrap()
+            }),
+            ("okx", Instrument::spot("TICKO", "USDT"), |pair| {
+                let text = r#"{"bids":[["151.52","95.568","0","4"]],
+                    "asks":[["151.53","131.551","0","3"]],"ts":"1752000000000"}"#;
+                let data: OkxOrderbookData = serde_json::from_str(text).unwrap();
+                let client = OkxClient::new().unwrap();
+                client.convert_to_orderbook(data, pair).unwrap()
+            }),
+        ];
+
+        // The converters read the global registry, it is installed into once
+        // for every case
+        let mut registry = InstrumentRegistry::new();
+        for (exchange, pair, _) in &cases {
+            registry.set_tick_size(exchange, pair, Decimal::new(1, 2));
+        }
+        InstrumentRegistry::install(registry);
+
+        for (exchange, pair, convert) in &cases {
+            let orderbook = convert(pair);
+            assert_eq!(orderbook.tick_size, Some(Decimal::new(1, 2)), "{exchange}");
+            assert_eq!(
+                orderbook.spread_in_ticks(),
+                Some(Decimal::ONE),
+                "{exchange}"
+            );
+        }
+    }
+}
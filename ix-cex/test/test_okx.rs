@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+
+    use ix_cex::OkxClient;
+    use ix_cex::exchanges::okx::okx_client::{OkxResponse, OkxTrade};
+    use ix_cex::models::instruments::Instrument;
+    use ix_cex::models::trades::TradeSide;
+
+    #[tokio::test]
+    async fn test_okx_client_creation() {
+        let client = OkxClient::new();
+        assert!(client.is_ok());
+    }
+
+    #[test]
+    fn test_okx_trades_response() {
+        let text = r#"{"code":"0","msg":"","data":[{"instId":"SOL-USDT","side":"sell",
+            "sz":"0.25","px":"151.52","source":"0","tradeId":"130639474","ts":"1752000000000"}]}"#;
+
+        let response: OkxResponse<OkxTrade> = serde_json::from_str(text).unwrap();
+        let trade = response.into_data().unwrap()[0].to_public_trade().unwrap();
+
+        assert_eq!(trade.exchange, "OKX");
+        assert_eq!(trade.side, TradeSide::Sell);
+        assert_eq!(trade.price, 151.52);
+        assert_eq!(trade.timestamp.timestamp_millis(), 1_752_000_000_000);
+
+        let text = r#"{"code":"51001","msg":"Instrument ID does not exist","data":[]}"#;
+        let response: OkxResponse<OkxTrade> = serde_json::from_str(text).unwrap();
+        assert_eq!(response.into_data().unwrap_err().api_code(), Some(51001));
+    }
+
+    #[tokio::test]
+    async fn test_get_orderbook() {
+        let client = OkxClient::new().unwrap();
+        let result = client
+            .get_orderbook(Instrument::spot("SOL", "USDT"), Some(10))
+            .await;
+
+        // This test might fail if no internet connection
+        match result {
+            Ok(orderbook) => {
+                assert_eq!(orderbook.symbol, "SOL-USDT");
+                assert!(orderbook.bids.len() <= 10);
+            }
+            Err(e) => {
+                println!("Expected network error in test environment: {e:?}");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_instrument() {
+        let client = OkxClient::new().unwrap();
+        let result = client.get_instrument(Instrument::spot("SOL", "USDT")).await;
+
+        // This test might fail if no internet connection
+        match result {
+            Ok(instrument) => {
+                assert_eq!(instrument.inst_id, "SOL-USDT");
+                println!("OKX SOL-USDT tick size: {}", instrument.tick_sz);
+            }
+            Err(e) => {
+                println!("Expected network error in test environment: {e:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,295 @@
+#[cfg(test)]
+mod tests {
+
+    use ix_cex::exchanges::binance::{binance_wss, models::Trade};
+    use ix_cex::exchanges::bybit::bybit_wss;
+    use ix_cex::exchanges::bybit::responses::{
+        BybitStreamEvent, BybitStreamOrderbook, BybitStreamTrade,
+    };
+    use ix_cex::exchanges::coinbase::coinbase_wss::{
+        CoinbaseFeed, CoinbaseFeedUpdate, CoinbaseWssEvent,
+    };
+    use ix_cex::exchanges::kraken::kraken_wss::{
+        KrakenBook, KrakenBookUpdate, KrakenStreamBook, KrakenStreamEvent,
+    };
+    use ix_cex::models::book::LevelBook;
+    use ix_cex::models::orderbook::Orderbook;
+    use ix_cex::models::trades::TradeSide;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    #[test]
+    fn test_binance_trade_normalized() {
+        let text = r#"{"e":"trade","E":1752000000001,"s":"SOLUSDT","t":12345,
+            "p":"151.52000000","q":"2.50000000","T":1752000000000,"m":true,"M":true}"#;
+
+        let trade: Trade = serde_json::from_str(text).unwrap();
+        let public_trade = binance_wss::to_public_trade(&trade);
+
+        assert_eq!(public_trade.exchange, "Binance");
+        assert_eq!(public_trade.side, TradeSide::Sell);
+        assert_eq!(public_trade.price, 151.52);
+        assert_eq!(public_trade.amount, 2.5);
+        assert_eq!(public_trade.trade_id, "12345");
+        assert_eq!(public_trade.timestamp.timestamp_millis(), 1_752_000_000_000);
+    }
+
+    #[test]
+    fn test_bybit_orderbook_snapshot_and_delta() {
+        let snapshot = r#"{"topic":"orderbook.50.SOLUSDT","type":"snapshot","ts":1752000000000,
+            "data":{"s":"SOLUSDT","b":[["151.52","95.5"],["151.51","85.8"]],
+            "a":[["151.53","131.5"],["151.54","89.8"]],"u":100,"seq":7000},"cts":1752000000000}"#;
+        let delta = r#"{"topic":"orderbook.50.SOLUSDT","type":"delta","ts":1752000000100,
+            "data":{"s":"SOLUSDT","b":[["151.52","0"]],"a":[["151.53","10"]],
+            "u":101,"seq":7001},"cts":1752000000100}"#;
+
+        let mut book = LevelBook::new();
+
+        let event: BybitStreamEvent<BybitStreamOrderbook> =
+            serde_json::from_str(snapshot).unwrap();
+        let orderbook = bybit_wss::apply_orderbook_event(&mut book, &event, 50);
+        assert_eq!(orderbook.bids.len(), 2);
+        assert_eq!(orderbook.best_bid().unwrap().price, dec("151.52"));
+
+        let event: BybitStreamEvent<BybitStreamOrderbook> =
+            serde_json::from_str(delta).unwrap();
+        let orderbook = bybit_wss::apply_orderbook_event(&mut book, &event, 50);
+        assert_eq!(orderbook.bids.len(), 1);
+        assert_eq!(orderbook.best_bid().unwrap().price, dec("151.51"));
+        assert_eq!(orderbook.best_ask().unwrap().quantity, dec("10.0"));
+        assert_eq!(orderbook.last_update_id, Some(101));
+        assert_eq!(orderbook.sequence, Some(7001));
+    }
+
+    #[test]
+    fn test_bybit_trade_normalized() {
+        let text = r#"{"topic":"publicTrade.SOLUSDT","type":"snapshot","ts":1752000000000,
+            "data":[{"T":1752000000000,"s":"SOLUSDT","S":"Buy","v":"1.25","p":"151.53",
+            "L":"PlusTick","i":"2290000000012345678","BT":false}]}"#;
+
+        let event: BybitStreamEvent<Vec<BybitStreamTrade>> =
+            serde_json::from_str(text).unwrap();
+        let trade = bybit_wss::to_public_trade(&event.data[0]).unwrap();
+
+        assert_eq!(trade.exchange, "Bybit");
+        assert_eq!(trade.side, TradeSide::Buy);
+        assert_eq!(trade.price, 151.53);
+        assert_eq!(trade.amount, 1.25);
+    }
+
+    fn coinbase_l2(sequence: u64, kind: &str, updates: &str) -> String {
+        format!(
+            r#"{{"channel":"l2_data","client_id":"","timestamp":"2025-07-08T18:40:00.123456Z",
+            "sequence_num":{sequence},"events":[{{"type":"{kind}","product_id":"SOL-USDT",
+            "updates":[{updates}]}}]}}"#
+        )
+    }
+
+    fn coinbase_level(side: &str, price: &str, qty: &str) -> String {
+        format!(
+            r#"{{"side":"{side}","event_time":"2025-07-08T18:40:00.1Z","price_level":"{price}","new_quantity":"{qty}"}}"#
+        )
+    }
+
+    fn coinbase_orderbooks(update: CoinbaseFeedUpdate) -> Vec<Orderbook> {
+        update
+            .events
+            .into_iter()
+            .filter_map(|event| match event {
+                CoinbaseWssEvent::Orderbook(orderbook) => Some(orderbook),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_coinbase_level2_snapshot_and_update() {
+        let mut feed = CoinbaseFeed::new(25);
+
+        let snapshot = coinbase_l2(
+            0,
+            "snapshot",
+            &[
+                coinbase_level("bid", "151.52", "95.5"),
+                coinbase_level("offer", "151.53", "131.5"),
+            ]
+            .join(","),
+        );
+        let orderbooks = coinbase_orderbooks(feed.on_message(&snapshot).unwrap());
+        assert_eq!(orderbooks.len(), 1);
+        assert_eq!(orderbooks[0].best_ask().unwrap().price, dec("151.53"));
+
+        let update = coinbase_l2(
+            1,
+            "update",
+            &[
+                coinbase_level("offer", "151.53", "0"),
+                coinbase_level("offer", "151.55", "3"),
+            ]
+            .join(","),
+        );
+        let orderbooks = coinbase_orderbooks(feed.on_message(&update).unwrap());
+        assert_eq!(orderbooks[0].best_ask().unwrap().price, dec("151.55"));
+        assert_eq!(orderbooks[0].sequence, Some(1));
+        assert_eq!(orderbooks[0].exchange, "Coinbase");
+    }
+
+    #[test]
+    fn test_coinbase_sequence_gap_drops_books() {
+        let mut feed = CoinbaseFeed::new(25);
+
+        let snapshot =
+            coinbase_l2(0, "snapshot", &coinbase_level("bid", "151.52", "95.5"));
+        feed.on_message(&snapshot).unwrap();
+        assert!(feed.is_synced("SOL-USDT"));
+
+        let heartbeat = r#"{"channel":"heartbeats","client_id":"","timestamp":"2025-07-08T18:40:01Z",
+            "sequence_num":1,"events":[{"current_time":"2025-07-08 18:40:01","heartbeat_counter":7}]}"#;
+        let update = feed.on_message(heartbeat).unwrap();
+        assert!(update.sequence_gap.is_none());
+
+        // Sequence 2 is missing
+        let update = coinbase_l2(3, "update", &coinbase_level("bid", "151.51", "1"));
+        let update = feed.on_message(&update).unwrap();
+        assert_eq!(update.sequence_gap, Some((2, 3)));
+        assert!(update.events.is_empty());
+        assert!(!feed.is_synced("SOL-USDT"));
+
+        // Resubscribed snapshot resyncs the book
+        let snapshot = coinbase_l2(4, "snapshot", &coinbase_level("bid", "151.50", "2"));
+        let orderbooks = coinbase_orderbooks(feed.on_message(&snapshot).unwrap());
+        assert_eq!(orderbooks[0].best_bid().unwrap().price, dec("151.50"));
+        assert!(feed.is_synced("SOL-USDT"));
+    }
+
+    #[test]
+    fn test_coinbase_trades_deduplicated() {
+        let mut feed = CoinbaseFeed::new(25);
+
+        let trades = |sequence: u64, kind: &str, ids: &[u64]| {
+            let trades: Vec<String> = ids
+                .iter()
+                .map(|id| {
+                    format!(
+                        r#"{{"trade_id":"{id}","product_id":"SOL-USDT","price":"151.53",
+                        "size":"0.5","side":"BUY","time":"2025-07-08T18:40:0{}Z"}}"#,
+                        id % 10
+                    )
+                })
+                .collect();
+            format!(
+                r#"{{"channel":"market_trades","client_id":"","timestamp":"2025-07-08T18:40:10Z",
+                "sequence_num":{sequence},"events":[{{"type":"{kind}","trades":[{}]}}]}}"#,
+                trades.join(",")
+            )
+        };
+
+        // Snapshot lists the most recent trade first
+        let update = feed.on_message(&trades(0, "snapshot", &[3, 2, 1])).unwrap();
+        assert_eq!(update.events.len(), 3);
+
+        // Replayed snapshot after a reconnect only yields the new trade
+        feed.reset();
+        let update = feed.on_message(&trades(0, "snapshot", &[4, 3, 2])).unwrap();
+        assert_eq!(update.events.len(), 1);
+
+        match &update.events[0] {
+            CoinbaseWssEvent::Trade(trade) => {
+                assert_eq!(trade.trade_id, "4");
+                assert_eq!(trade.side, TradeSide::Buy);
+                assert_eq!(trade.symbol, "SOL-USDT");
+            }
+            other => panic!("expected trade, got {other:?}"),
+        }
+    }
+
+    fn kraken_book(kind: &str, bids: &str, asks: &str, checksum: u32) -> String {
+        format!(
+            r#"{{"channel":"book","type":"{kind}","data":[{{"symbol":"SOL/USDT",
+            "bids":[{bids}],"asks":[{asks}],"checksum":{checksum},
+            "timestamp":"2025-07-08T18:40:00.223456Z"}}]}}"#
+        )
+    }
+
+    fn kraken_apply(book: &mut KrakenBook, text: &str) -> KrakenBookUpdate {
+        let event: KrakenStreamEvent<KrakenStreamBook> =
+            serde_json::from_str(text).unwrap();
+        book.apply(&event)
+    }
+
+    #[test]
+    fn test_kraken_book_checksum_validated() {
+        let mut book = KrakenBook::new("SOLUSDT", 10, 10, 2, 8);
+
+        // Asks best first then bids best first, price then quantity without
+        // the decimal point and leading zeros
+        let checksum = crc32fast::hash(b"1515313150000000151529550000000151518580000000");
+        let snapshot = kraken_book(
+            "snapshot",
+            r#"{"price":151.52,"qty":95.5},{"price":151.51,"qty":85.8}"#,
+            r#"{"price":151.53,"qty":131.5}"#,
+            checksum,
+        );
+        let update = kraken_apply(&mut book, &snapshot);
+        assert!(update.checksum_mismatch.is_none());
+        assert_eq!(update.orderbooks[0].bids.len(), 2);
+        assert_eq!(book.checksum(), checksum);
+
+        let checksum =
+            crc32fast::hash(b"151555000000015152955000000015151858000000015150400000000");
+        let update = kraken_book(
+            "update",
+            r#"{"price":151.5,"qty":4.0}"#,
+            r#"{"price":151.53,"qty":0.0},{"price":151.55,"qty":0.5}"#,
+            checksum,
+        );
+        let update = kraken_apply(&mut book, &update);
+        assert!(update.checksum_mismatch.is_none());
+
+        let orderbook = &update.orderbooks[0];
+        assert_eq!(orderbook.best_ask().unwrap().price, dec("151.55"));
+        assert_eq!(orderbook.tick_size, Some(dec("0.01")));
+        assert_eq!(orderbook.spread_in_ticks(), Some(dec("3")));
+        assert_eq!(orderbook.bids.len(), 3);
+        assert_eq!(orderbook.symbol, "SOLUSDT");
+        assert_eq!(orderbook.exchange, "Kraken");
+    }
+
+    #[test]
+    fn test_kraken_checksum_mismatch_resets_book() {
+        let mut book = KrakenBook::new("SOLUSDT", 10, 10, 2, 8);
+
+        let checksum = crc32fast::hash(b"1515313150000000151529550000000");
+        let snapshot = kraken_book(
+            "snapshot",
+            r#"{"price":151.52,"qty":95.5}"#,
+            r#"{"price":151.53,"qty":131.5}"#,
+            checksum,
+        );
+        kraken_apply(&mut book, &snapshot);
+        assert!(book.is_synced());
+
+        let update = kraken_book("update", r#"{"price":151.51,"qty":1.0}"#, "", 42);
+        let update = kraken_apply(&mut book, &update);
+        assert_eq!(
+            update.checksum_mismatch.map(|(expected, _)| expected),
+            Some(42)
+        );
+        assert!(update.orderbooks.is_empty());
+        assert!(!book.is_synced());
+
+        // Updates are ignored until the snapshot of the resubscription
+        let update = kraken_book("update", r#"{"price":151.51,"qty":1.0}"#, "", 0);
+        let update = kraken_apply(&mut book, &update);
+        assert!(update.checksum_mismatch.is_none());
+        assert!(update.orderbooks.is_empty());
+
+        let update = kraken_apply(&mut book, &snapshot);
+        assert_eq!(update.orderbooks.len(), 1);
+        assert!(book.is_synced());
+    }
+}
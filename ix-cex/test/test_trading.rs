@@ -0,0 +1,352 @@
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+
+    use ix_cex::client::http_client::error_code;
+    use ix_cex::exchanges::binance::binance_client::BinanceSymbol;
+    use ix_cex::exchanges::binance::binance_private::{
+        cancel_replace_params, order_params, order_status,
+    };
+    use ix_cex::exchanges::binance::models::{BinanceAccountTrade, BinanceOrder};
+    use ix_cex::exchanges::bybit::responses::OrderResponse;
+    use ix_cex::exchanges::bybit::{Category, MarketUnit, OrderRef, trading};
+    use ix_cex::models::trades::TradeSide;
+    use ix_cex::models::trading::{
+        AmendOrder, Fill, LotSize, NewOrder, OrderKey, OrderQty, OrderState, OrderStatus,
+        OrderType,
+    };
+    use ix_cex::{
+        BinancePrivateClient, BybitPrivateClient, BybitTradingClient, ExchangeError,
+    };
+    use rust_decimal::Decimal;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    fn bybit(category: Category) -> BybitTradingClient {
+        let client = BybitPrivateClient::with_credentials(
+            "key".to_string(),
+            "secret".to_string(),
+            true,
+        )
+        .unwrap();
+        BybitTradingClient::new(client, category)
+    }
+
+    fn binance_order(body: &str) -> BinanceOrder {
+        serde_json::from_str(body).unwrap()
+    }
+
+    const BINANCE_LIMIT_ORDER: &str = r#"{
+        "symbol": "SOLUSDT", "orderId": 28, "orderListId": -1,
+        "clientOrderId": "ix-1", "price": "150.00000000",
+        "origQty": "2.00000000", "executedQty": "0.50000000",
+        "cummulativeQuoteQty": "74.90000000", "status": "PARTIALLY_FILLED",
+        "timeInForce": "GTC", "type": "LIMIT", "side": "BUY",
+        "stopPrice": "0.00000000", "icebergQty": "0.00000000",
+        "time": 1752000000000, "updateTime": 1752000001000,
+        "isWorking": true, "workingTime": 1752000000000,
+        "origQuoteOrderQty": "0.00000000",
+        "selfTradePreventionMode": "EXPIRE_MAKER"
+    }"#;
+
+    #[test]
+    fn test_new_order_validation() {
+        let market =
+            NewOrder::market("SOLUSDT", TradeSide::Buy, OrderQty::Quote(dec("10")));
+        assert!(market.validate().is_ok());
+
+        let zero =
+            NewOrder::market("SOLUSDT", TradeSide::Buy, OrderQty::Base(Decimal::ZERO));
+        assert!(matches!(
+            zero.validate(),
+            Err(ExchangeError::InvalidRequest(_))
+        ));
+
+        let mut limit = NewOrder::limit("SOLUSDT", TradeSide::Sell, dec("1"), dec("150"));
+        assert!(limit.validate().is_ok());
+        limit.qty = OrderQty::Quote(dec("150"));
+        assert!(limit.validate().is_err());
+
+        let amend = AmendOrder::new("SOLUSDT", OrderKey::OrderId("1".to_string()));
+        assert!(amend.validate().is_err());
+        assert!(amend.price(dec("151")).validate().is_ok());
+    }
+
+    #[test]
+    fn test_status_is_terminal() {
+        assert!(!OrderStatus::New.is_terminal());
+        assert!(!OrderStatus::PartiallyFilled.is_terminal());
+        assert!(OrderStatus::Filled.is_terminal());
+        assert!(OrderStatus::Cancelled.is_terminal());
+        assert_eq!(OrderStatus::PartiallyFilled.to_string(), "PartiallyFilled");
+    }
+
+    #[test]
+    fn test_bybit_order_request() {
+        let spot = bybit(Category::Spot);
+        let order =
+            NewOrder::market("SOLUSDT", TradeSide::Buy, OrderQty::Quote(dec("25")))
+                .client_order_id("ix-1");
+        let request = spot.order_request(&order).unwrap();
+        assert_eq!(request.qty, dec("25"));
+        assert_eq!(request.market_unit, Some(MarketUnit::QuoteCoin));
+        assert_eq!(request.order_link_id.as_deref(), Some("ix-1"));
+
+        let sell =
+            NewOrder::market("SOLUSDT", TradeSide::Sell, OrderQty::Base(dec("0.5")));
+        let request = spot.order_request(&sell).unwrap();
+        assert_eq!(request.market_unit, Some(MarketUnit::BaseCoin));
+
+        // Contracts are sized in the base coin only
+        let linear = bybit(Category::Linear);
+        assert!(linear.order_request(&order).is_err());
+        let limit = NewOrder::limit("BTCUSDT", TradeSide::Buy, dec("0.01"), dec("60000"));
+        let request = linear.order_request(&limit).unwrap();
+        assert_eq!(request.price, Some(dec("60000")));
+        assert_eq!(request.market_unit, None);
+
+        let amend =
+            AmendOrder::new("SOLUSDT", OrderKey::ClientOrderId("ix-1".to_string()))
+                .qty(dec("3"));
+        let request = spot.amend_request(&amend).unwrap();
+        assert_eq!(request.order, OrderRef::OrderLinkId("ix-1".to_string()));
+        assert_eq!(request.qty, Some(dec("3")));
+        assert_eq!(request.price, None);
+    }
+
+    #[test]
+    fn test_bybit_order_state() {
+        assert_eq!(trading::order_status("Untriggered"), OrderStatus::New);
+        assert_eq!(
+            trading::order_status("PartiallyFilledCanceled"),
+            OrderStatus::Cancelled
+        );
+        assert_eq!(trading::order_status("Deactivated"), OrderStatus::Cancelled);
+
+        let response: OrderResponse = serde_json::from_str(
+            r#"{
+            "retCode": 0,
+            "retMsg": "OK",
+            "result": {
+                "category": "spot",
+                "list": [{
+                    "orderId": "1", "orderLinkId": "ix-1", "blockTradeId": "",
+                    "symbol": "SOLUSDT", "price": "0", "qty": "25",
+                    "side": "Buy", "isLeverage": "0", "positionIdx": 0,
+                    "orderStatus": "Filled", "cancelType": "UNKNOWN",
+                    "rejectReason": "EC_NoError", "avgPrice": "150.2",
+                    "leavesQty": "0", "leavesValue": "0",
+                    "cumExecQty": "0.166", "cumExecValue": "24.93",
+                    "cumExecFee": "0.000166", "timeInForce": "IOC",
+                    "orderType": "Market", "stopOrderType": "", "orderIv": "",
+                    "triggerPrice": "0", "takeProfit": "", "stopLoss": "",
+                    "tpTriggerBy": "", "slTriggerBy": "", "triggerDirection": 0,
+                    "triggerBy": "", "lastPriceOnCreated": "", "reduceOnly": false,
+                    "closeOnTrigger": false, "smpType": "None", "smpGroup": 0,
+                    "smpOrderId": "", "tpslMode": "", "tpLimitPrice": "",
+                    "slLimitPrice": "", "placeType": "",
+                    "createdTime": "1752000000000", "updatedTime": "1752000001000"
+                }]
+            },
+            "retExtInfo": {},
+            "time": 1752000002000
+        }"#,
+        )
+        .unwrap();
+
+        let state = OrderState::try_from(&response.result.list[0]).unwrap();
+        assert_eq!(state.client_order_id, "ix-1");
+        assert_eq!(state.side, TradeSide::Buy);
+        assert_eq!(state.order_type, OrderType::Market);
+        assert_eq!(state.status, OrderStatus::Filled);
+        assert_eq!(state.qty, dec("25"));
+        assert_eq!(state.filled_qty, dec("0.166"));
+        assert_eq!(state.fee, dec("0.000166"));
+        assert_eq!(state.reject_reason, "");
+        assert_eq!(state.updated.timestamp_millis(), 1_752_000_001_000);
+    }
+
+    #[test]
+    fn test_binance_signature() {
+        // Example of the Binance API documentation
+        let client = BinancePrivateClient::with_credentials(
+            "vmPUZE6mv9SD5VNHk4HlWFsOr6aKE2zvsw0MuIgwCIPy6utIco14y7Ju91duEh8A"
+                .to_string(),
+            "NhqPtmdSJYdKjVHjA7PZj4Mge3R5YNiP1e3UZjInClVN65XAbvqqM6A7H5fATj0j"
+                .to_string(),
+            false,
+        )
+        .unwrap();
+
+        let params = [
+            ("symbol", "LTCBTC"),
+            ("side", "BUY"),
+            ("type", "LIMIT"),
+            ("timeInForce", "GTC"),
+            ("quantity", "1"),
+            ("price", "0.1"),
+        ];
+        let query = client.signed_query(&params, 1_499_827_319_559).unwrap();
+        assert_eq!(
+            query,
+            "symbol=LTCBTC&side=BUY&type=LIMIT&timeInForce=GTC&quantity=1&price=0.1\
+             &recvWindow=5000&timestamp=1499827319559\
+             &signature=c8db56825ae71d6d79447849e617115f4a920fa2acdcab2b053c4b2838bd6b71"
+        );
+
+        let unsigned = BinancePrivateClient {
+            api_secret: None,
+            ..client
+        };
+        assert!(matches!(
+            unsigned.signed_query(&params, 1_499_827_319_559),
+            Err(ExchangeError::Authentication(_))
+        ));
+    }
+
+    #[test]
+    fn test_binance_order_params() {
+        let market =
+            NewOrder::market("SOLUSDT", TradeSide::Buy, OrderQty::Quote(dec("25.50")))
+                .client_order_id("ix-1");
+        let params = order_params(&market).unwrap();
+        assert!(params.contains(&("side", "BUY".to_string())));
+        assert!(params.contains(&("type", "MARKET".to_string())));
+        assert!(params.contains(&("quoteOrderQty", "25.5".to_string())));
+        assert!(params.contains(&("newClientOrderId", "ix-1".to_string())));
+        assert!(!params.iter().any(|(key, _)| *key == "price"));
+
+        let limit = NewOrder::limit("SOLUSDT", TradeSide::Sell, dec("2"), dec("150.10"));
+        let params = order_params(&limit).unwrap();
+        assert!(params.contains(&("quantity", "2".to_string())));
+        assert!(params.contains(&("price", "150.1".to_string())));
+        assert!(params.contains(&("timeInForce", "GTC".to_string())));
+    }
+
+    #[test]
+    fn test_binance_cancel_replace_params() {
+        let order = binance_order(BINANCE_LIMIT_ORDER);
+        let amend = AmendOrder::new("SOLUSDT", OrderKey::OrderId("28".to_string()))
+            .price(dec("149.5"));
+        let params = cancel_replace_params(&order, &amend).unwrap();
+
+        // The 0.5 filled are not ordered again
+        assert!(params.contains(&("quantity", "1.5".to_string())));
+        assert!(params.contains(&("price", "149.5".to_string())));
+        assert!(params.contains(&("cancelOrderId", "28".to_string())));
+        assert!(params.contains(&("newClientOrderId", "ix-1".to_string())));
+
+        let below_filled = amend.clone().qty(dec("0.5"));
+        assert!(cancel_replace_params(&order, &below_filled).is_err());
+
+        let mut market = order.clone();
+        market.order_type = "MARKET".to_string();
+        assert!(cancel_replace_params(&market, &amend).is_err());
+    }
+
+    #[test]
+    fn test_binance_order_state() {
+        assert_eq!(order_status("EXPIRED"), OrderStatus::Cancelled);
+        assert_eq!(order_status("NEW"), OrderStatus::New);
+
+        let state = OrderState::try_from(&binance_order(BINANCE_LIMIT_ORDER)).unwrap();
+        assert_eq!(state.order_id, "28");
+        assert_eq!(state.side, TradeSide::Buy);
+        assert_eq!(state.order_type, OrderType::Limit);
+        assert_eq!(state.status, OrderStatus::PartiallyFilled);
+        assert_eq!(state.qty, dec("2"));
+        assert_eq!(state.filled_qty, dec("0.5"));
+        assert_eq!(state.avg_price, dec("149.8"));
+
+        // Market buys sized in the quote coin keep their quote quantity
+        let market = binance_order(
+            r#"{
+            "symbol": "SOLUSDT", "orderId": 29, "clientOrderId": "ix-2",
+            "price": "0.00000000", "origQty": "0.16600000",
+            "executedQty": "0.16600000", "cummulativeQuoteQty": "24.93320000",
+            "status": "FILLED", "timeInForce": "GTC", "type": "MARKET",
+            "side": "BUY", "time": 1752000000000, "updateTime": 1752000000000,
+            "isWorking": true, "origQuoteOrderQty": "25.00000000"
+        }"#,
+        );
+        let state = OrderState::try_from(&market).unwrap();
+        assert_eq!(state.qty, dec("25"));
+        assert_eq!(state.status, OrderStatus::Filled);
+        assert_eq!(state.avg_price, dec("150.2"));
+    }
+
+    #[test]
+    fn test_binance_fill() {
+        let trade: BinanceAccountTrade = serde_json::from_str(
+            r#"{
+            "symbol": "SOLUSDT", "id": 28457, "orderId": 100234,
+            "orderListId": -1, "price": "150.2", "qty": "0.5",
+            "quoteQty": "75.1", "commission": "0.0005",
+            "commissionAsset": "SOL", "time": 1752000000000,
+            "isBuyer": false, "isMaker": true, "isBestMatch": true
+        }"#,
+        )
+        .unwrap();
+
+        let fill = Fill::from(&trade);
+        assert_eq!(fill.trade_id, "28457");
+        assert_eq!(fill.order_id, "100234");
+        assert_eq!(fill.side, TradeSide::Sell);
+        assert_eq!(fill.fee_coin, "SOL");
+        assert!(fill.is_maker);
+    }
+
+    #[test]
+    fn test_binance_lot_size() {
+        let symbol: BinanceSymbol = serde_json::from_str(
+            r#"{
+            "symbol": "SOLUSDT", "status": "TRADING",
+            "baseAsset": "SOL", "baseAssetPrecision": 8,
+            "quoteAsset": "USDT", "quotePrecision": 8,
+            "filters": [
+                {"filterType": "PRICE_FILTER", "minPrice": "0.01000000",
+                 "maxPrice": "10000.00000000", "tickSize": "0.01000000"},
+                {"filterType": "LOT_SIZE", "minQty": "0.00100000",
+                 "maxQty": "9000000.00000000", "stepSize": "0.00100000"},
+                {"filterType": "NOTIONAL", "minNotional": "5.00000000",
+                 "applyMinToMarket": true, "maxNotional": "9000000.00000000",
+                 "applyMaxToMarket": false, "avgPriceMins": 5}
+            ]
+        }"#,
+        )
+        .unwrap();
+
+        let lot_size = LotSize::try_from(&symbol).unwrap();
+        assert_eq!(lot_size.base_precision, dec("0.001"));
+        assert_eq!(lot_size.quote_precision, dec("0.00000001"));
+        assert_eq!(lot_size.min_order_qty, dec("0.001"));
+        assert_eq!(lot_size.min_order_amt, dec("5"));
+
+        assert_eq!(
+            lot_size.market_qty(TradeSide::Sell, dec("100"), dec("150")),
+            Some(OrderQty::Base(dec("0.666")))
+        );
+    }
+
+    #[test]
+    fn test_binance_error_code() {
+        let body = r#"{"code":-2013,"msg":"Order does not exist."}"#;
+        assert_eq!(error_code(body), Some(-2013));
+        assert_eq!(error_code("Bad Gateway"), None);
+        assert_eq!(error_code(r#"{"code":"51000","msg":""}"#), None);
+
+        let error = ExchangeError::ApiError {
+            exchange: "Binance".to_string(),
+            message: format!("Client error (400 Bad Request): {body}"),
+            code: error_code(body),
+        };
+        assert_eq!(error.api_code(), Some(-2013));
+        assert_eq!(
+            ExchangeError::InvalidRequest(body.to_string()).api_code(),
+            None
+        );
+    }
+}
@@ -0,0 +1,194 @@
+#[cfg(test)]
+mod tests {
+
+    use futures_util::{SinkExt, StreamExt};
+    use ix_cex::client::http_client::RetryConfig;
+    use ix_cex::client::wss_client::{
+        ConnectionEvent, WssClient, WssConfig, WssConnection, WssEvent,
+    };
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration;
+    use tokio::net::TcpListener;
+    use tokio::time::timeout;
+    use tokio_tungstenite::accept_async;
+    use tokio_tungstenite::tungstenite::protocol::Message;
+
+    fn fast_retry() -> RetryConfig {
+        RetryConfig {
+            max_retries: 5,
+            initial_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(50),
+            backoff_factor: 2.0,
+        }
+    }
+
+    async fn next_event(connection: &mut WssConnection) -> WssEvent {
+        timeout(Duration::from_secs(5), connection.recv())
+            .await
+            .expect("timed out waiting for event")
+            .expect("connection stopped")
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_replays_subscriptions() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        // Echo the subscription back then close, twice
+        let server = tokio::spawn(async move {
+            for _ in 0..2 {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut ws = accept_async(stream).await.unwrap();
+                if let Some(Ok(Message::Text(text))) = ws.next().await {
+                    ws.send(Message::Text(format!("ack {text}"))).await.unwrap();
+                }
+                ws.close(None).await.unwrap();
+            }
+        });
+
+        let config = WssConfig::new(url).with_retry_config(fast_retry());
+        let mut connection = WssClient::new(config).with_subscription("sub").spawn();
+
+        assert_eq!(
+            next_event(&mut connection).await,
+            WssEvent::Connection(ConnectionEvent::Connected { attempt: 1 })
+        );
+        assert_eq!(
+            next_event(&mut connection).await,
+            WssEvent::Text("ack sub".to_string())
+        );
+        assert!(matches!(
+            next_event(&mut connection).await,
+            WssEvent::Connection(ConnectionEvent::Disconnected { .. })
+        ));
+        assert!(matches!(
+            next_event(&mut connection).await,
+            WssEvent::Connection(ConnectionEvent::Reconnecting { .. })
+        ));
+        assert!(matches!(
+            next_event(&mut connection).await,
+            WssEvent::Connection(ConnectionEvent::Connected { .. })
+        ));
+        assert_eq!(
+            next_event(&mut connection).await,
+            WssEvent::Text("ack sub".to_string())
+        );
+
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_login_is_built_for_every_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        // Echo the first two messages back then close, twice
+        let server = tokio::spawn(async move {
+            for _ in 0..2 {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut ws = accept_async(stream).await.unwrap();
+                for _ in 0..2 {
+                    if let Some(Ok(Message::Text(text))) = ws.next().await {
+                        ws.send(Message::Text(format!("ack {text}"))).await.unwrap();
+                    }
+                }
+                ws.close(None).await.unwrap();
+            }
+        });
+
+        let logins = Arc::new(AtomicU32::new(0));
+        let counter = logins.clone();
+        let config = WssConfig::new(url).with_retry_config(fast_retry());
+        let mut connection = WssClient::new(config)
+            .with_login(move || {
+                Ok(format!("login {}", counter.fetch_add(1, Ordering::SeqCst)))
+            })
+            .with_subscription("sub")
+            .spawn();
+
+        for login in ["ack login 0", "ack login 1"] {
+            assert!(matches!(
+                next_event(&mut connection).await,
+                WssEvent::Connection(ConnectionEvent::Connected { .. })
+            ));
+            assert_eq!(
+                next_event(&mut connection).await,
+                WssEvent::Text(login.to_string())
+            );
+            assert_eq!(
+                next_event(&mut connection).await,
+                WssEvent::Text("ack sub".to_string())
+            );
+            if login.ends_with('0') {
+                assert!(matches!(
+                    next_event(&mut connection).await,
+                    WssEvent::Connection(ConnectionEvent::Disconnected { .. })
+                ));
+                assert!(matches!(
+                    next_event(&mut connection).await,
+                    WssEvent::Connection(ConnectionEvent::Reconnecting { .. })
+                ));
+            }
+        }
+        assert_eq!(logins.load(Ordering::SeqCst), 2);
+
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_stale_connection_is_dropped() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        // Accept the connection but never read, so pings are not answered
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let _ws = accept_async(stream).await.unwrap();
+            tokio::time::sleep(Duration::from_secs(2)).await;
+        });
+
+        let config = WssConfig::new(url)
+            .with_retry_config(fast_retry())
+            .with_ping(Duration::from_millis(50), Duration::from_millis(100));
+        let mut connection = WssClient::new(config).spawn();
+
+        assert!(matches!(
+            next_event(&mut connection).await,
+            WssEvent::Connection(ConnectionEvent::Connected { .. })
+        ));
+
+        match next_event(&mut connection).await {
+            WssEvent::Connection(ConnectionEvent::Disconnected { reason }) => {
+                assert!(reason.contains("stale"), "unexpected reason: {reason}");
+            }
+            other => panic!("expected disconnect, got {other:?}"),
+        }
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_retries() {
+        // Bind then drop to get a port nobody listens on
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let config = WssConfig::new(url).with_retry_config(RetryConfig {
+            max_retries: 2,
+            ..fast_retry()
+        });
+        let connection = WssClient::new(config).spawn();
+
+        let result = timeout(Duration::from_secs(5), async {
+            let mut connection = connection;
+            while connection.recv().await.is_some() {}
+            connection.finish().await
+        })
+        .await
+        .expect("supervisor did not give up");
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,295 @@
+// src/bin/cli_database.rs
+
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::env;
+use std::path::PathBuf;
+
+use ix_execution::engine::{DEFAULT_DEPTHS, FeatureEngine};
+use ix_execution::liquidations::{LiquidationRow, read_tables::read_liquidation_rows};
+use ix_execution::migrations::Migrator;
+use ix_execution::orderbooks::{
+    OrderbookLevelRow,
+    read_tables::{read_orderbook_levels, read_orderbooks},
+};
+use ix_execution::queries::select::{ReadRow, SelectQuery};
+use ix_execution::storage::{ParquetRow, ParquetStore};
+use ix_execution::trades::{TradeRow, read_tables::read_trade_rows};
+use ix_execution::{ClickHouseClient, WriteRow, WriterConfig};
+
+#[derive(Parser)]
+#[command(name = "cli_database")]
+#[command(about = "Manage the schema of the operations database")]
+#[command(version = "0.0.1")]
+struct Cli {
+    /// ClickHouse HTTP url, defaults to $CLICKHOUSE_URL
+    #[arg(long)]
+    url: Option<String>,
+
+    /// Database name, defaults to $CLICKHOUSE_DB
+    #[arg(long)]
+    database: Option<String>,
+
+    /// Enable verbose logging
+    #[arg(short, long)]
+    verbose: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Apply the pending migrations
+    Migrate {
+        /// Only list the migrations that would be applied
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// List the migrations and when they were applied
+    Status,
+    /// Write hourly Parquet files of a table
+    Export {
+        #[arg(long, value_enum)]
+        dataset: Dataset,
+        /// Start of the range (RFC 3339), rounded down to the hour
+        #[arg(long)]
+        from: DateTime<Utc>,
+        /// End of the range (RFC 3339), rounded up to the hour
+        #[arg(long)]
+        to: DateTime<Utc>,
+        /// Only these exchanges, repeatable
+        #[arg(long)]
+        exchange: Vec<String>,
+        /// Only these symbols, repeatable
+        #[arg(long)]
+        symbol: Vec<String>,
+        /// Root directory of the files
+        #[arg(long, default_value = "parquet")]
+        dir: PathBuf,
+    },
+    /// Load Parquet files into a table
+    Import {
+        #[arg(long, value_enum)]
+        dataset: Dataset,
+        /// Root directory of the files
+        #[arg(long, default_value = "parquet")]
+        dir: PathBuf,
+        /// Files to load, all files of the dataset by default
+        files: Vec<PathBuf>,
+    },
+    /// Compute the features of the stored order books
+    Features {
+        /// Start of the range (RFC 3339)
+        #[arg(long)]
+        from: DateTime<Utc>,
+        /// End of the range (RFC 3339)
+        #[arg(long)]
+        to: DateTime<Utc>,
+        /// Only these exchanges, repeatable
+        #[arg(long)]
+        exchange: Vec<String>,
+        /// Only these symbols, repeatable
+        #[arg(long)]
+        symbol: Vec<String>,
+        /// Levels per side, repeatable, 1, 5 and 10 by default
+        #[arg(long)]
+        depth: Vec<u16>,
+    },
+}
+
+/// Table stored in Parquet files
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Dataset {
+    /// `orderbook_levels`
+    Orderbooks,
+    /// `publictrades`
+    Trades,
+    /// `liquidations`
+    Liquidations,
+}
+
+async fn export<T: ReadRow + ParquetRow>(
+    client: &ClickHouseClient,
+    store: &ParquetStore,
+    query: SelectQuery<T>,
+    exchanges: Vec<String>,
+    symbols: Vec<String>,
+    range: (DateTime<Utc>, DateTime<Utc>),
+) -> anyhow::Result<()> {
+    let hour = Duration::hours(1);
+    let from = range.0.duration_trunc(hour)?;
+    let to = range.1.duration_round_up(hour)?;
+
+    let query = query
+        .exchanges(exchanges)
+        .symbols(symbols)
+        .from(from)
+        .to(to);
+    for path in store.export(client, &query).await? {
+        println!("exported {}", path.display());
+    }
+    Ok(())
+}
+
+async fn import<T>(
+    client: &ClickHouseClient,
+    store: &ParquetStore,
+    table: &str,
+    files: Vec<PathBuf>,
+) -> anyhow::Result<()>
+where
+    T: ParquetRow + WriteRow + Send + 'static,
+{
+    let files = if files.is_empty() {
+        store.files::<T>()?
+    } else {
+        files
+    };
+
+    let writer = client.batch_writer::<T>(table, WriterConfig::default())?;
+    let count = store.import(&writer, &files).await?;
+    writer.shutdown().await?;
+
+    println!(
+        "imported {} rows from {} files into {}",
+        count,
+        files.len(),
+        table
+    );
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(if cli.verbose {
+            tracing::Level::DEBUG
+        } else {
+            tracing::Level::INFO
+        })
+        .finish();
+    tracing::subscriber::set_global_default(subscriber)?;
+
+    let ch_url = cli.url.unwrap_or_else(|| {
+        env::var("CLICKHOUSE_URL").unwrap_or_else(|_| "http://localhost:8123".to_string())
+    });
+    let ch_db = cli.database.unwrap_or_else(|| {
+        env::var("CLICKHOUSE_DB").unwrap_or_else(|_| "operations".to_string())
+    });
+
+    let client = ClickHouseClient::builder()
+        .url(ch_url)
+        .database(ch_db)
+        .build()
+        .await?;
+    let migrator = Migrator::new(&client);
+
+    match cli.command {
+        Command::Migrate { dry_run: true } => {
+            migrator.init().await?;
+            for migration in migrator.pending().await? {
+                println!("pending {:>4} {}", migration.version, migration.name);
+            }
+        }
+        Command::Migrate { dry_run: false } => {
+            let applied = migrator.migrate().await?;
+            println!("applied {} migrations {:?}", applied.len(), applied);
+        }
+        Command::Status => {
+            migrator.init().await?;
+            for status in migrator.status().await? {
+                let applied_at = status
+                    .applied
+                    .map(|applied| applied.applied_at.to_rfc3339())
+                    .unwrap_or_else(|| "pending".to_string());
+                println!(
+                    "{:>4} {:<32} {}",
+                    status.migration.version, status.migration.name, applied_at
+                );
+            }
+        }
+        Command::Export {
+            dataset,
+            from,
+            to,
+            exchange,
+            symbol,
+            dir,
+        } => {
+            let store = ParquetStore::new(dir);
+            let range = (from, to);
+            match dataset {
+                Dataset::Orderbooks => {
+                    let query = read_orderbook_levels();
+                    export(&client, &store, query, exchange, symbol, range).await?
+                }
+                Dataset::Trades => {
+                    let query = read_trade_rows();
+                    export(&client, &store, query, exchange, symbol, range).await?
+                }
+                Dataset::Liquidations => {
+                    let query = read_liquidation_rows();
+                    export(&client, &store, query, exchange, symbol, range).await?
+                }
+            }
+        }
+        Command::Features {
+            from,
+            to,
+            exchange,
+            symbol,
+            depth,
+        } => {
+            let engine = if depth.is_empty() {
+                FeatureEngine::new(DEFAULT_DEPTHS)
+            } else {
+                FeatureEngine::new(depth)
+            };
+
+            let orderbooks = read_orderbooks()
+                .exchanges(exchange)
+                .symbols(symbol)
+                .from(from)
+                .to(to)
+                .fetch_orderbooks(&client)
+                .await?;
+
+            let writer = client.features_writer(WriterConfig::default())?;
+            let count = engine
+                .run(futures::stream::iter(orderbooks), &writer)
+                .await?;
+            writer.shutdown().await?;
+            println!("computed {} feature rows", count);
+        }
+        Command::Import {
+            dataset,
+            dir,
+            files,
+        } => {
+            let store = ParquetStore::new(dir);
+            match dataset {
+                Dataset::Orderbooks => {
+                    import::<OrderbookLevelRow>(
+                        &client,
+                        &store,
+                        "orderbook_levels",
+                        files,
+                    )
+                    .await?
+                }
+                Dataset::Trades => {
+                    import::<TradeRow>(&client, &store, "publictrades", files).await?
+                }
+                Dataset::Liquidations => {
+                    import::<LiquidationRow>(&client, &store, "liquidations", files)
+                        .await?
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -1,24 +1,35 @@
 // src/bin/datacollector.rs
 
-use atelier_data::{
-    exchanges::bybit::{ws_decoder::BybitWssEvent, WssExchange},
-    stream_data,
-};
-use std::{
-    env,
-    thread::sleep,
-    time::{Duration, Instant},
-};
+use futures::{stream::select_all, StreamExt};
+use std::env;
+use tokio::sync::watch;
 
 use ix_execution::{
-    liquidations::LiquidationNew, queries, queries::trades::ClickhouseTradeData, ClickHouseClient,
+    engine::{DEFAULT_DEPTHS, FeatureEngine},
+    liquidations::LiquidationRow, orderbooks::OrderbookRow, trades::TradeRow,
+    migrations::Migrator, ClickHouseClient, DatabaseError, WriterConfig,
 };
 
 use ix_cex::{
-    exchanges::{BinanceClient, BybitClient, CoinbaseClient, ExchangeClient, KrakenClient},
-    models::{exchanges::Exchange, orderbook::TradingPair},
+    exchanges::{
+        BinanceClient, BybitClient, CoinbaseClient, KrakenClient, StreamingExchangeClient,
+    },
+    models::{exchanges::Exchange, instruments::Instrument},
 };
 
+fn streaming_client(
+    exchange: &Exchange,
+) -> Option<Box<dyn StreamingExchangeClient + Send + Sync>> {
+    match exchange {
+        Exchange::Binance => Some(Box::new(BinanceClient::new().unwrap())),
+        Exchange::Coinbase => Some(Box::new(CoinbaseClient::new().unwrap())),
+        Exchange::Kraken => Some(Box::new(KrakenClient::new().unwrap())),
+        Exchange::Bybit => Some(Box::new(BybitClient::new().unwrap())),
+        // REST only
+        Exchange::Okx | Exchange::Deribit => None,
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
 
@@ -27,147 +38,207 @@ async fn main() -> anyhow::Result<()> {
     let ch_url = env::var("CLICKHOUSE_URL").unwrap_or_else(|_| "http://database:8123".to_string());
     let ch_db = env::var("CLICKHOUSE_DB").unwrap_or_else(|_| "operations".to_string());
 
-    // --- LIQUIDATIONS Datacollector --- //
-    let ch_lq_client = ClickHouseClient::builder()
-        .url(ch_url.clone())
-        .database(ch_db.clone())
-        .build()
-        .await
-        .unwrap();
-
-    let streams_task = tokio::spawn(async move {
-        println!("started streams_task");
-
-        let symbols = vec![
-            "SOLUSDT".to_string(),
-            "LINKUSDT".to_string(),
-        ];
-
-        let streams = vec!["allLiquidation".to_string(), "publicTrade".to_string()];
-        let source = WssExchange::Bybit;
-        let mut rx = stream_data(symbols, streams, source)
-            .await
-            .expect("failed to open Bybit WS");
-
-        while let Some(recv_event) = rx.recv().await {
-            println!("\nrecv_event");
-            match recv_event {
-                BybitWssEvent::LiquidationData(event_data) => {
-                    let i_liq = LiquidationNew {
-                        ts: event_data.liquidation_ts,
-                        symbol: event_data.symbol.clone(),
-                        side: event_data.side,
-                        amount: event_data.amount,
-                        price: event_data.price,
-                        exchange: "Bybit".to_string(),
-                    };
-
-                    println!(
-                        "\n ---- allLiquidation event received... {:?} ----\n ",
-                        event_data.symbol
-                    );
-
-                    let liquidation_query =
-                        queries::liquidations::write_tables::q_insert_liquidations(
-                            &i_liq,
-                        )
-                        .unwrap();
-                    println!("\n ---- liquidation_query {:?} ---- \n", liquidation_query);
-
-                    let ch_lq_result = ch_lq_client.write_table(&liquidation_query).await;
-                    println!("\n ---- ch_lq_result {:?} ---- \n", ch_lq_result);
-                }
-
-                BybitWssEvent::TradeData(event_data) => {
-                    let i_trade = ClickhouseTradeData {
-                        timestamp: event_data.trade_ts,
-                        symbol: event_data.symbol.clone(),
-                        side: event_data.side,
-                        amount: event_data.amount.parse().expect("failed to parse amount to f64"),
-                        price: event_data.price.parse().expect("failed to parse price to f64"),
-                        exchange: "Bybit".to_string(),
-                    };
-
-                    println!("\n ---- publicTrade event received... {:?} ---- ", event_data.symbol);
-
-                    let trade_query =
-                        queries::trades::write_tables::q_insert_trades(&i_trade).unwrap();
-                    println!("\n ---- trade_query {:?} --- \n", trade_query);
-
-                    let ch_pt_result = ch_lq_client.write_table(&trade_query).await;
-                    println!("\n ---- ch_pt_result {:?} ---- \n", ch_pt_result);
-
-                }
+    let v_exchanges = vec![
+        Exchange::Binance,
+        Exchange::Bybit,
+        Exchange::Coinbase,
+        Exchange::Kraken,
+    ];
+
+    let v_pairs = vec![
+        Instrument::spot("SOL", "USDT"),
+        Instrument::spot("LINK", "USDT"),
+    ];
+
+    let depth = 25;
+
+    // --- Subscriptions --- //
+    let mut v_orderbooks = Vec::new();
+    let mut v_trades = Vec::new();
+    let mut v_liquidations = Vec::new();
+
+    for i_exchange in &v_exchanges {
+        let Some(exchange_client) = streaming_client(i_exchange) else {
+            println!("skipping {:?}: no streaming client", i_exchange);
+            continue;
+        };
+
+        for i_pair in v_pairs.clone() {
+            println!("subscribing {:?} {:?}", i_exchange, i_pair);
+
+            v_orderbooks.push(
+                exchange_client
+                    .subscribe_orderbook(i_pair.clone(), Some(depth))
+                    .await?,
+            );
+
+            v_trades.push(exchange_client.subscribe_trades(i_pair.clone()).await?);
+
+            match exchange_client.subscribe_liquidations(i_pair.clone()).await {
+                Ok(stream) => v_liquidations.push(stream),
+                Err(e) => println!("skipping liquidations for {:?}: {}", i_exchange, e),
             }
         }
-    });
+    }
 
-    // --- ORDERBOOKS Dataollector --- //
-    let ch_ob_client = ClickHouseClient::builder()
+    let ch_client = ClickHouseClient::builder()
         .url(ch_url.clone())
         .database(ch_db.clone())
         .build()
         .await
         .unwrap();
 
+    // Bring the schema up to date before writing
+    let applied = Migrator::new(&ch_client).migrate().await?;
+    println!("applied migrations {:?}", applied);
+
+    // Stops the collection tasks, their writers flush on the way out
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+    // --- ORDERBOOKS Datacollector --- //
+    // Snapshots go to `orderbooks` (arrays), `orderbook_levels` (levels) or both
+    let ob_layout = env::var("ORDERBOOK_LAYOUT").unwrap_or_else(|_| "both".to_string());
+    let ob_writer = match ob_layout.as_str() {
+        "arrays" | "both" => Some(ch_client.orderbooks_writer(WriterConfig::default())?),
+        _ => None,
+    };
+    let obl_writer = match ob_layout.as_str() {
+        "levels" | "both" => {
+            Some(ch_client.orderbook_levels_writer(WriterConfig::default())?)
+        }
+        _ => None,
+    };
+    if ob_writer.is_none() && obl_writer.is_none() {
+        anyhow::bail!("unknown ORDERBOOK_LAYOUT {:?}, use arrays, levels or both", ob_layout);
+    }
+
+    // Features of every snapshot, FEATURE_DEPTHS is a comma separated list
+    let feature_engine = match env::var("FEATURE_DEPTHS") {
+        Ok(depths) => FeatureEngine::new(
+            depths
+                .split(',')
+                .map(|depth| depth.trim().parse::<u16>())
+                .collect::<Result<Vec<_>, _>>()?,
+        ),
+        Err(_) => FeatureEngine::new(DEFAULT_DEPTHS),
+    };
+    let ft_writer = ch_client.features_writer(WriterConfig::default())?;
+    let mut ob_shutdown = shutdown_rx.clone();
+
     let orderbook_task = tokio::spawn(async move {
-        let interval = Duration::from_millis(800);
-        let mut next_time = Instant::now() + interval;
+        println!("started orderbook_task");
 
-        loop {
+        let mut orderbooks = select_all(v_orderbooks);
 
-            let v_exchanges = vec![
-                Exchange::Kraken,
-                Exchange::Binance,
-                Exchange::Bybit,
-                Exchange::Coinbase
-            ];
+        loop {
+            tokio::select! {
+                Some(r_orderbook) = orderbooks.next() => {
+                    if let Some(writer) = &ob_writer {
+                        let row = OrderbookRow::from(&r_orderbook);
+                        if let Err(e) = writer.write(row).await {
+                            println!("\n ---- ch_ob_result {:?} ---- \n", e);
+                            break;
+                        }
+                    }
+                    if let Some(writer) = &obl_writer
+                        && let Err(e) = writer.write_orderbook(&r_orderbook).await
+                    {
+                        println!("\n ---- ch_obl_result {:?} ---- \n", e);
+                        break;
+                    }
+                    if let Err(e) = feature_engine.write(&r_orderbook, &ft_writer).await {
+                        println!("\n ---- ch_ft_result {:?} ---- \n", e);
+                        break;
+                    }
+                }
+                _ = ob_shutdown.changed() => break,
+                else => break,
+            }
+        }
 
-            let v_pairs = vec![
-                TradingPair::SolUsdt,
-                TradingPair::LinkUsdt,
-            ];
+        if let Some(writer) = ob_writer {
+            let ch_ob_result = writer.shutdown().await;
+            println!("\n ---- ch_ob_result {:?} ---- \n", ch_ob_result);
+        }
+        if let Some(writer) = obl_writer {
+            let ch_obl_result = writer.shutdown().await;
+            println!("\n ---- ch_obl_result {:?} ---- \n", ch_obl_result);
+        }
+        let ch_ft_result = ft_writer.shutdown().await;
+        println!("\n ---- ch_ft_result {:?} ---- \n", ch_ft_result);
+    });
 
-            let depth = 25;
+    // --- TRADES Datacollector --- //
+    let pt_writer = ch_client.trades_writer(WriterConfig::default())?;
+    let mut pt_shutdown = shutdown_rx.clone();
 
-            for i_exchange in v_exchanges {
+    let trades_task = tokio::spawn(async move {
+        println!("started trades_task");
 
-                println!("exchange {:?}", i_exchange);
+        let mut trades = select_all(v_trades);
 
-                let exchange_client: Box<dyn ExchangeClient + Send + Sync> =
-                    match i_exchange {
-                        Exchange::Binance => Box::new(BinanceClient::new().unwrap()),
-                        Exchange::Coinbase => Box::new(CoinbaseClient::new().unwrap()),
-                        Exchange::Kraken => Box::new(KrakenClient::new().unwrap()),
-                        Exchange::Bybit => Box::new(BybitClient::new().unwrap()),
+        loop {
+            tokio::select! {
+                Some(r_trade) = trades.next() => {
+                    // A trade that does not fit the table is skipped, not stored as 0
+                    let row = match TradeRow::try_from(&r_trade) {
+                        Ok(row) => row,
+                        Err(e) => {
+                            println!("skipped trade {:?}: {}", r_trade, e);
+                            continue;
+                        }
                     };
+                    if let Err(e) = pt_writer.write(row).await {
+                        if let DatabaseError::InvalidRow(_) = e {
+                            println!("skipped trade {:?}: {}", r_trade, e);
+                            continue;
+                        }
+                        println!("\n ---- ch_pt_result {:?} ---- \n", e);
+                        break;
+                    }
+                }
+                _ = pt_shutdown.changed() => break,
+                else => break,
+            }
+        }
 
-                for i_pair in v_pairs.clone() {
-                    println!("\nOrderbook query for pair: {:?}", i_pair);
-
-                    let r_orderbook = exchange_client
-                        .get_orderbook(i_pair.clone(), Some(depth))
-                        .await
-                        .unwrap();
+        let ch_pt_result = pt_writer.shutdown().await;
+        println!("\n ---- ch_pt_result {:?} ---- \n", ch_pt_result);
+    });
 
-                    let ob_query: String =
-                        queries::orderbooks::write_tables::q_insert_orderbook(&r_orderbook)
-                            .unwrap();
-                    println!("\n ---- ob_query {:?} ---- \n", ob_query);
+    // --- LIQUIDATIONS Datacollector --- //
+    let lq_writer = ch_client.liquidations_writer(WriterConfig::default())?;
+    let mut lq_shutdown = shutdown_rx.clone();
 
-                    let ch_ob_result = ch_ob_client.write_table(&ob_query).await;
-                    println!("\n ---- ch_ob_result {:?} ---- \n", ch_ob_result);
+    let liquidations_task = tokio::spawn(async move {
+        println!("started liquidations_task");
 
-                    sleep(next_time - Instant::now());
-                    next_time += interval;
+        let mut liquidations = select_all(v_liquidations);
 
+        loop {
+            tokio::select! {
+                Some(r_liquidation) = liquidations.next() => {
+                    let row = LiquidationRow::from(&r_liquidation);
+                    if let Err(e) = lq_writer.write(row).await {
+                        println!("\n ---- ch_lq_result {:?} ---- \n", e);
+                        break;
+                    }
                 }
+                _ = lq_shutdown.changed() => break,
+                else => break,
             }
         }
+
+        let ch_lq_result = lq_writer.shutdown().await;
+        println!("\n ---- ch_lq_result {:?} ---- \n", ch_lq_result);
     });
 
-    // Wait for both tasks
-    tokio::try_join!(streams_task, orderbook_task)?;
+    tokio::signal::ctrl_c().await?;
+    println!("shutting down, flushing writers");
+    shutdown_tx.send(true)?;
+
+    // Wait for all tasks
+    tokio::try_join!(orderbook_task, trades_task, liquidations_task)?;
     Ok(())
 
 }
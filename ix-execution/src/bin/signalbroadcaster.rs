@@ -1,8 +1,58 @@
+use chrono::Duration as ChronoDuration;
+use ix_execution::ClickHouseClient;
+use ix_execution::broadcast::{self, CHANNEL_CAPACITY, SignalFeed};
+use std::env;
+use tokio::net::TcpListener;
+use tokio::sync::broadcast as channel;
+use tokio::time::{Duration, interval};
+
+// === SYSTEM PARAMETERS ===
+const POLL_INTERVAL_MILLIS: u64 = 500; // Reads of the signals table
+const LOOKBACK_SECONDS: i64 = 30; // Window covering late batches
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    println!("yes");
+    let ch_url = env::var("CLICKHOUSE_URL")
+        .unwrap_or_else(|_| "http://localhost:8123".to_string());
+    let ch_db = env::var("CLICKHOUSE_DB").unwrap_or_else(|_| "operations".to_string());
+    let addr = env::var("SIGNAL_BROADCAST_ADDR")
+        .unwrap_or_else(|_| "127.0.0.1:9010".to_string());
 
-    Ok(())
-}
+    let ch_client = ClickHouseClient::builder()
+        .url(ch_url.clone())
+        .database(ch_db.clone())
+        .build()
+        .await?;
+
+    let (sender, _) = channel::channel(CHANNEL_CAPACITY);
+    let listener = TcpListener::bind(&addr).await?;
+
+    println!("🔗 ClickHouse: {} (database: {})", ch_url, ch_db);
+    println!("📡 Broadcasting signals on ws://{addr}/ws and http://{addr}/sse");
 
+    let server = tokio::spawn(broadcast::serve(listener, sender.clone()));
+
+    let mut feed = SignalFeed::new(ChronoDuration::seconds(LOOKBACK_SECONDS));
+    let mut ticks = interval(Duration::from_millis(POLL_INTERVAL_MILLIS));
+
+    loop {
+        tokio::select! {
+            _ = ticks.tick() => {
+                match feed.poll(&ch_client, &sender).await {
+                    Ok(0) => {}
+                    Ok(count) => println!(
+                        "📨 {} signals to {} subscribers",
+                        count,
+                        sender.receiver_count()
+                    ),
+                    Err(e) => println!("❌ Signals not read: {:?}", e),
+                }
+            }
+            _ = tokio::signal::ctrl_c() => {
+                println!("shutting down");
+                server.abort();
+                return Ok(());
+            }
+        }
+    }
+}
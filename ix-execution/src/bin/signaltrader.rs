@@ -1,36 +1,157 @@
-// src/bin/trader.rs
+use chrono::{Duration as ChronoDuration, Utc};
+use ix_cex::exchanges::bybit::{BybitTradeClient, Category};
+use ix_cex::{BinancePrivateClient, BybitPrivateClient, BybitTradingClient, TradingClient};
+use ix_execution::broadcast::{CHANNEL_CAPACITY, SignalFeed};
+use ix_execution::execution::{ExecutionConfig, ExecutionEngine};
+use ix_execution::risk::{RiskConfig, RiskManager};
+use ix_execution::{ClickHouseClient, WriterConfig};
+use rust_decimal::Decimal;
+use std::env;
+use std::str::FromStr;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::{Duration, interval};
 
-use ix_cex::BybitPrivateClient;
-// use atelier_data::orderbooks::Orderbook;
+// === SYSTEM PARAMETERS ===
+const POLL_INTERVAL_MILLIS: u64 = 500; // Reads of the signals table
+const LOOKBACK_SECONDS: i64 = 30; // Window covering late batches
+const ORDER_UPDATE_SECONDS: u64 = 2; // Status requests of the open orders
+const RESUME_HOURS: i64 = 24; // Orders loaded at startup
 
 #[tokio::main]
-async fn main() {
+async fn main() -> anyhow::Result<()> {
+    let ch_url = env::var("CLICKHOUSE_URL")
+        .unwrap_or_else(|_| "http://localhost:8123".to_string());
+    let ch_db = env::var("CLICKHOUSE_DB").unwrap_or_else(|_| "operations".to_string());
 
-    // --- Get Balance
-    let client = BybitPrivateClient::new().unwrap();
-    let p_account_type = "UNIFIED";
-    let p_coin = Some("USD");
-    let wallet_balance = client.get_wallet_balance(p_account_type, p_coin).await;
-    let wb = wallet_balance.unwrap().result.list[0].total_equity.clone();
+    let mut config = ExecutionConfig::default();
+    if let Ok(fraction) = env::var("RISK_FRACTION") {
+        config.risk_fraction = Decimal::from_str(&fraction)?;
+    }
+    if let Ok(coin) = env::var("SIGNALTRADER_COIN") {
+        config.coin = coin;
+    }
+    if let Ok(account) = env::var("SIGNALTRADER_ACCOUNT") {
+        config.account = account;
+    }
 
-    println!("\n ---- Wallet Balance: {:?} ", wb);
+    // Risk config path from the first argument, the environment or the default
+    let risk_path = env::args().nth(1).unwrap_or_else(RiskConfig::path_from_env);
+    let risk_config = RiskConfig::from_file(&risk_path)?;
+    let risk = RiskManager::new(
+        config.account.clone(),
+        risk_config.limits(&config.account)?.clone(),
+    );
 
-    // --- 
+    let ch_client = ClickHouseClient::builder()
+        .url(ch_url.clone())
+        .database(ch_db.clone())
+        .build()
+        .await?;
+    let writer = ch_client.orders_writer(WriterConfig::default())?;
+    let rejections = ch_client.risk_rejections_writer(WriterConfig::default())?;
+    let risk_state = ch_client.risk_state_writer(WriterConfig::default())?;
 
-    // --- Open a trade
-    let client = BybitPrivateClient::new().unwrap();
-    let p_category = "spot";
-    let p_symbol = "SOLUSDT";
-    let p_side = "Sell";
+    let client = trading_client()?;
+    config.exchange = client.exchange_name().to_string();
+    let mut engine = ExecutionEngine::new(
+        client,
+        ch_client.clone(),
+        writer,
+        config,
+        risk,
+        rejections,
+        risk_state,
+    );
+    engine
+        .resume(Utc::now() - ChronoDuration::hours(RESUME_HOURS))
+        .await?;
+    // The kill switch stays engaged across restarts until reset
+    if env::var("SIGNALTRADER_RESET_KILL_SWITCH").is_ok() {
+        engine.reset_kill_switch().await?;
+    }
 
-    let p_order_type = "Market";
-    let p_qty = "5.0";
-    let open_trade = client
-        .new_order(p_category, p_symbol, p_side, p_order_type, p_qty)
-        .await;
+    println!("🔗 ClickHouse: {} (database: {})", ch_url, ch_db);
+    println!(
+        "💼 Trading {} signals with {} of the {} equity, orders over {}",
+        engine.config().exchange,
+        engine.config().risk_fraction,
+        engine.config().coin,
+        engine.transport()
+    );
+    println!(
+        "🛡️ Risk limits of {} from {}: {:?}",
+        engine.config().account,
+        risk_path,
+        engine.risk().limits()
+    );
 
-    let ot = open_trade.unwrap();
+    let (sender, mut signals) = broadcast::channel(CHANNEL_CAPACITY);
+    let mut feed = SignalFeed::new(ChronoDuration::seconds(LOOKBACK_SECONDS));
+    let mut poll_ticks = interval(Duration::from_millis(POLL_INTERVAL_MILLIS));
+    let mut order_ticks = interval(Duration::from_secs(ORDER_UPDATE_SECONDS));
 
-    println!("\n ---- Opened Trade: {:?} ", ot);
+    loop {
+        tokio::select! {
+            _ = poll_ticks.tick() => {
+                if let Err(e) = feed.poll(&ch_client, &sender).await {
+                    println!("❌ Signals not read: {:?}", e);
+                }
+            }
+            signal = signals.recv() => match signal {
+                Ok(signal) => match engine.on_signal(&signal).await {
+                    Ok(Some(order)) => println!(
+                        "📤 {} {} {} {}: {}",
+                        order.side,
+                        order.qty,
+                        order.symbol,
+                        order.order_link_id,
+                        order.status
+                    ),
+                    Ok(None) => {}
+                    Err(e) => println!("❌ Signal not executed: {:?}", e),
+                },
+                Err(RecvError::Lagged(skipped)) => {
+                    println!("⚠️ {} signals skipped", skipped);
+                }
+                Err(RecvError::Closed) => break,
+            },
+            _ = order_ticks.tick() => {
+                if let Err(e) = engine.update_orders().await {
+                    println!("❌ Orders not updated: {:?}", e);
+                }
+            }
+            _ = tokio::signal::ctrl_c() => {
+                println!("shutting down");
+                break;
+            }
+        }
+    }
+
+    engine.shutdown().await?;
+    Ok(())
 }
 
+/// Trading client of the exchange of `SIGNALTRADER_EXCHANGE`, Bybit by default
+fn trading_client() -> anyhow::Result<Box<dyn TradingClient>> {
+    match env::var("SIGNALTRADER_EXCHANGE").as_deref() {
+        Ok("bybit") | Err(_) => {
+            let client = BybitPrivateClient::new()?;
+            let category = match env::var("BYBIT_CATEGORY") {
+                Ok(category) => category.parse()?,
+                Err(_) => Category::Spot,
+            };
+            let trading = BybitTradingClient::new(client.clone(), category);
+
+            // Orders over REST unless the trade WebSocket is asked for
+            match env::var("BYBIT_ORDER_TRANSPORT").as_deref() {
+                Ok("websocket") => Ok(Box::new(
+                    trading.with_order_entry(BybitTradeClient::connect(&client)?),
+                )),
+                Ok("rest") | Err(_) => Ok(Box::new(trading)),
+                Ok(other) => anyhow::bail!("Unknown BYBIT_ORDER_TRANSPORT {other}"),
+            }
+        }
+        Ok("binance") => Ok(Box::new(BinancePrivateClient::new()?)),
+        Ok(other) => anyhow::bail!("Unknown SIGNALTRADER_EXCHANGE {other}"),
+    }
+}
@@ -0,0 +1,199 @@
+//! Signal broadcasting
+//!
+//! A [`SignalFeed`] polls the `signals` table and publishes every new row once
+//! on a broadcast channel. [`router`] serves the channel to local subscribers,
+//! as WebSocket text messages on `/ws` and as server-sent `signal` events on
+//! `/sse`, one JSON [`SignalRow`] per message with the timestamp in UTC
+//! microseconds. Both endpoints take optional `exchange` and `symbol` query
+//! filters, e.g. `/sse?symbol=SOLUSDT`.
+
+use std::collections::HashSet;
+use std::convert::Infallible;
+
+use axum::Router;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Query, State};
+use axum::response::Response;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::routing::get;
+use chrono::{DateTime, Duration, Utc};
+use futures::Stream;
+use serde::Deserialize;
+use tokio::net::TcpListener;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::{debug, warn};
+
+use crate::signals::SignalRow;
+use crate::signals::read_tables::read_signals;
+use crate::{ClickHouseClient, DatabaseResult};
+
+/// Signals kept in the channel for slow subscribers
+pub const CHANNEL_CAPACITY: usize = 1024;
+
+/// Publishes the rows of the `signals` table that were not seen before
+///
+/// Rows are written in batches and can arrive after newer ones, each poll reads
+/// the `lookback` window and skips the rows already published.
+#[derive(Debug)]
+pub struct SignalFeed {
+    lookback: Duration,
+    seen: HashSet<(i64, String, String)>,
+    primed: bool,
+}
+
+impl SignalFeed {
+    pub fn new(lookback: Duration) -> Self {
+        Self {
+            lookback,
+            seen: HashSet::new(),
+            primed: false,
+        }
+    }
+
+    /// Rows of `rows` not returned before. Rows older than `since` will not be
+    /// read again and are forgotten.
+    pub fn unseen(
+        &mut self,
+        rows: Vec<SignalRow>,
+        since: DateTime<Utc>,
+    ) -> Vec<SignalRow> {
+        let since = since.timestamp_micros();
+        self.seen.retain(|(timestamp, _, _)| *timestamp >= since);
+
+        rows.into_iter()
+            .filter(|row| {
+                self.seen.insert((
+                    row.timestamp.timestamp_micros(),
+                    row.exchange.clone(),
+                    row.symbol.clone(),
+                ))
+            })
+            .collect()
+    }
+
+    /// Read the window and publish the new rows, returns how many were sent.
+    /// The rows found by the first poll are history and only marked as seen.
+    pub async fn poll(
+        &mut self,
+        client: &ClickHouseClient,
+        sender: &broadcast::Sender<SignalRow>,
+    ) -> DatabaseResult<usize> {
+        let since = Utc::now() - self.lookback;
+        let rows = read_signals().from(since).fetch_all(client).await?;
+        let rows = self.unseen(rows, since);
+
+        if !self.primed {
+            self.primed = true;
+            debug!("Skipped {} signals already stored", rows.len());
+            return Ok(0);
+        }
+
+        let count = rows.len();
+        for row in rows {
+            // Fails only while nobody is subscribed
+            let _ = sender.send(row);
+        }
+        Ok(count)
+    }
+}
+
+/// Subscriber filters, a missing filter matches every signal
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SignalFilter {
+    pub exchange: Option<String>,
+    pub symbol: Option<String>,
+}
+
+impl SignalFilter {
+    pub fn matches(&self, row: &SignalRow) -> bool {
+        self.exchange
+            .as_ref()
+            .is_none_or(|exchange| exchange.eq_ignore_ascii_case(&row.exchange))
+            && self.symbol.as_ref().is_none_or(|symbol| {
+                crate::queries::format_symbol_for_clickhouse(symbol) == row.symbol
+            })
+    }
+}
+
+/// Next signal passing `filter`, `None` once the channel is closed
+async fn next_signal(
+    receiver: &mut broadcast::Receiver<SignalRow>,
+    filter: &SignalFilter,
+) -> Option<SignalRow> {
+    loop {
+        match receiver.recv().await {
+            Ok(row) if filter.matches(&row) => return Some(row),
+            Ok(_) => {}
+            Err(RecvError::Lagged(skipped)) => {
+                warn!("Subscriber lagged, {} signals skipped", skipped);
+            }
+            Err(RecvError::Closed) => return None,
+        }
+    }
+}
+
+/// Routes of the WebSocket and SSE endpoints
+pub fn router(sender: broadcast::Sender<SignalRow>) -> Router {
+    Router::new()
+        .route("/ws", get(websocket))
+        .route("/sse", get(server_sent_events))
+        .with_state(sender)
+}
+
+/// Serve [`router`] until the listener fails
+pub async fn serve(
+    listener: TcpListener,
+    sender: broadcast::Sender<SignalRow>,
+) -> DatabaseResult<()> {
+    Ok(axum::serve(listener, router(sender)).await?)
+}
+
+async fn websocket(
+    upgrade: WebSocketUpgrade,
+    State(sender): State<broadcast::Sender<SignalRow>>,
+    Query(filter): Query<SignalFilter>,
+) -> Response {
+    let receiver = sender.subscribe();
+    upgrade.on_upgrade(move |socket| forward(socket, receiver, filter))
+}
+
+async fn forward(
+    mut socket: WebSocket,
+    mut receiver: broadcast::Receiver<SignalRow>,
+    filter: SignalFilter,
+) {
+    loop {
+        tokio::select! {
+            signal = next_signal(&mut receiver, &filter) => {
+                let Some(row) = signal else { break };
+                let Ok(text) = serde_json::to_string(&row) else { continue };
+                if socket.send(Message::Text(text.into())).await.is_err() {
+                    break;
+                }
+            }
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+async fn server_sent_events(
+    State(sender): State<broadcast::Sender<SignalRow>>,
+    Query(filter): Query<SignalFilter>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let events = futures::stream::unfold(
+        (sender.subscribe(), filter),
+        |(mut receiver, filter)| async move {
+            let row = next_signal(&mut receiver, &filter).await?;
+            let event = Event::default()
+                .event("signal")
+                .json_data(&row)
+                .unwrap_or_else(|_| Event::default().comment("unserializable signal"));
+            Some((Ok(event), (receiver, filter)))
+        },
+    );
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
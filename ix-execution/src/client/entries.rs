@@ -5,6 +5,7 @@
 
 use chrono::{DateTime, Utc};
 use clickhouse::Row;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -64,8 +65,8 @@ pub struct OrderbookInput {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PriceLevelInput {
-    pub price: f64,
-    pub quantity: f64,
+    pub price: Decimal,
+    pub quantity: Decimal,
 }
 
 impl TryFrom<OrderbookInput> for Orderbook {
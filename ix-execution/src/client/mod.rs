@@ -5,3 +5,6 @@
 
 pub mod entries;
 pub use entries::*;
+
+pub mod writer;
+pub use writer::*;
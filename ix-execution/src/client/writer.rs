@@ -0,0 +1,418 @@
+//! Batched row-binary inserts
+//!
+//! A [`BatchWriter`] owns a `clickhouse` [`Inserter`] in a background task and
+//! receives rows over a bounded channel. Rows are sent to ClickHouse in one
+//! `INSERT` per batch, a batch ends when it reaches the row or byte limit, or
+//! when the flush period elapses. Writers wait while the channel is full, and
+//! pending rows are flushed when the writer is shut down.
+//!
+//! A row that fails to serialize aborts the whole pending `INSERT`, so rows are
+//! checked with [`WriteRow::check`] and rejected before they are queued. The
+//! rows of a batch ClickHouse did not accept are dropped and counted, see
+//! [`BatchWriter::dropped_rows`], and the next [`BatchWriter::flush`] fails.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use clickhouse::Row;
+use clickhouse::inserter::{Inserter, Quantities};
+use ix_cex::models::orderbook::Orderbook;
+use serde::Serialize;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info};
+
+use crate::queries::decimal::{self, scale8};
+use crate::{ClickHouseClient, DatabaseError, DatabaseResult};
+use crate::{
+    features::FeatureRow,
+    liquidations::LiquidationRow,
+    orderbooks::{OrderbookLevelRow, OrderbookRow},
+    orders::OrderRow,
+    rejections::RiskRejectionRow,
+    risk_state::RiskStateRow,
+    signals::SignalRow,
+    trades::TradeRow,
+};
+
+/// Row written by a [`BatchWriter`]
+pub trait WriteRow: Row + Serialize {
+    /// Fail on values the row can't be serialized with
+    fn check(&self) -> DatabaseResult<()> {
+        Ok(())
+    }
+}
+
+impl WriteRow for OrderbookRow {}
+
+impl WriteRow for LiquidationRow {}
+
+impl WriteRow for SignalRow {}
+
+impl WriteRow for OrderbookLevelRow {
+    fn check(&self) -> DatabaseResult<()> {
+        decimal::check(&self.price, scale8::SCALE)?;
+        decimal::check(&self.amount, scale8::SCALE)
+    }
+}
+
+impl WriteRow for TradeRow {
+    fn check(&self) -> DatabaseResult<()> {
+        decimal::check(&self.amount, decimal::SCALE)?;
+        decimal::check(&self.price, decimal::SCALE)
+    }
+}
+
+impl WriteRow for FeatureRow {
+    fn check(&self) -> DatabaseResult<()> {
+        for value in [
+            &self.spread,
+            &self.midprice,
+            &self.w_midprice,
+            &self.vwap,
+            &self.imb,
+            &self.tav,
+        ] {
+            decimal::check(value, decimal::SCALE)?;
+        }
+        Ok(())
+    }
+}
+
+impl WriteRow for OrderRow {
+    fn check(&self) -> DatabaseResult<()> {
+        for value in [&self.qty, &self.filled_qty, &self.avg_price, &self.fee] {
+            decimal::check(value, decimal::SCALE)?;
+        }
+        Ok(())
+    }
+}
+
+impl WriteRow for RiskRejectionRow {
+    fn check(&self) -> DatabaseResult<()> {
+        decimal::check(&self.notional, decimal::SCALE)
+    }
+}
+
+impl WriteRow for RiskStateRow {
+    fn check(&self) -> DatabaseResult<()> {
+        decimal::check(&self.day_start_equity, decimal::SCALE)
+    }
+}
+
+/// Limits of a [`BatchWriter`]
+#[derive(Debug, Clone)]
+pub struct WriterConfig {
+    /// Rows per `INSERT`
+    pub max_rows: u64,
+    /// Uncompressed bytes per `INSERT`
+    pub max_bytes: u64,
+    /// Longest time a row waits before its batch is sent
+    pub period: Duration,
+    /// Rows buffered in the channel before writers have to wait
+    pub capacity: usize,
+}
+
+impl Default for WriterConfig {
+    fn default() -> Self {
+        Self {
+            max_rows: 10_000,
+            max_bytes: 16 * 1024 * 1024,
+            period: Duration::from_secs(1),
+            capacity: 10_000,
+        }
+    }
+}
+
+enum WriterCommand<T> {
+    Row(T),
+    Flush(oneshot::Sender<DatabaseResult<Quantities>>),
+}
+
+/// Batched writer of one table
+pub struct BatchWriter<T> {
+    table: String,
+    sender: mpsc::Sender<WriterCommand<T>>,
+    task: JoinHandle<Quantities>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl<T> BatchWriter<T>
+where
+    T: WriteRow + Send + 'static,
+{
+    fn spawn(
+        client: &ClickHouseClient,
+        table: &str,
+        config: WriterConfig,
+    ) -> DatabaseResult<Self> {
+        let inserter = client
+            .client()
+            .inserter::<T>(table)?
+            .with_max_rows(config.max_rows)
+            .with_max_bytes(config.max_bytes)
+            .with_period(Some(config.period));
+
+        let (sender, receiver) = mpsc::channel(config.capacity.max(1));
+        let dropped = Arc::new(AtomicU64::new(0));
+        let task = tokio::spawn(run_inserter(
+            inserter,
+            receiver,
+            table.to_string(),
+            dropped.clone(),
+        ));
+
+        Ok(Self {
+            table: table.to_string(),
+            sender,
+            task,
+            dropped,
+        })
+    }
+
+    /// Queue a row, waits while the channel is full. A row that fails
+    /// [`WriteRow::check`] is rejected and the pending batch is kept.
+    pub async fn write(&self, row: T) -> DatabaseResult<()> {
+        row.check()?;
+
+        self.sender
+            .send(WriterCommand::Row(row))
+            .await
+            .map_err(|_| self.stopped())
+    }
+
+    /// Send the pending rows now. Fails when they, or rows of a batch sent
+    /// since the last flush, were not inserted.
+    pub async fn flush(&self) -> DatabaseResult<Quantities> {
+        let (reply, response) = oneshot::channel();
+
+        self.sender
+            .send(WriterCommand::Flush(reply))
+            .await
+            .map_err(|_| self.stopped())?;
+
+        response.await.map_err(|_| self.stopped())?
+    }
+
+    /// Flush the pending rows and stop the writer, returns the inserted totals
+    pub async fn shutdown(self) -> DatabaseResult<Quantities> {
+        drop(self.sender);
+
+        self.task.await.map_err(|e| {
+            DatabaseError::OperationFailed(format!(
+                "Writer for {} panicked: {e}",
+                self.table
+            ))
+        })
+    }
+
+    /// Name of the table written to
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+
+    /// Rows dropped so far because their batch was not inserted
+    pub fn dropped_rows(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    fn stopped(&self) -> DatabaseError {
+        DatabaseError::OperationFailed(format!("Writer for {} stopped", self.table))
+    }
+}
+
+impl BatchWriter<OrderbookLevelRow> {
+    /// Queue every level of a snapshot
+    pub async fn write_orderbook(&self, orderbook: &Orderbook) -> DatabaseResult<()> {
+        for row in OrderbookLevelRow::from_orderbook(orderbook) {
+            self.write(row).await?;
+        }
+        Ok(())
+    }
+}
+
+async fn run_inserter<T>(
+    mut inserter: Inserter<T>,
+    mut receiver: mpsc::Receiver<WriterCommand<T>>,
+    table: String,
+    dropped: Arc<AtomicU64>,
+) -> Quantities
+where
+    T: WriteRow,
+{
+    let mut inserted = Quantities::ZERO;
+    // Rows dropped since the last flush and the last error, for its reply
+    let mut failure: Option<(u64, String)> = None;
+    let drop_rows = |failure: &mut Option<(u64, String)>, rows: u64, error: String| {
+        error!("Dropped {} rows of {}: {}", rows, table, error);
+        dropped.fetch_add(rows, Ordering::Relaxed);
+        let lost = failure.take().map_or(0, |(lost, _)| lost);
+        *failure = Some((lost + rows, error));
+    };
+
+    loop {
+        let time_left = inserter.time_left().unwrap_or(Duration::MAX);
+
+        tokio::select! {
+            command = receiver.recv() => match command {
+                Some(WriterCommand::Row(row)) => {
+                    // Rows are checked before they are queued, a failure here
+                    // has already dropped the pending batch
+                    let pending = inserter.pending().rows;
+                    if let Err(e) = inserter.write(&row) {
+                        drop_rows(&mut failure, pending + 1, format!("Failed to serialize row: {e}"));
+                        continue;
+                    }
+                }
+                Some(WriterCommand::Flush(reply)) => {
+                    let pending = inserter.pending().rows;
+                    let quantities = match inserter.force_commit().await {
+                        Ok(quantities) => {
+                            add_quantities(&mut inserted, &quantities);
+                            quantities
+                        }
+                        Err(e) => {
+                            drop_rows(&mut failure, pending, format!("Failed to insert batch: {e}"));
+                            Quantities::ZERO
+                        }
+                    };
+                    let result = match failure.take() {
+                        None => Ok(quantities),
+                        Some((rows, error)) => Err(DatabaseError::OperationFailed(format!(
+                            "{rows} rows of {table} dropped since the last flush, {error}"
+                        ))),
+                    };
+                    let _ = reply.send(result);
+                    continue;
+                }
+                None => break,
+            },
+            _ = tokio::time::sleep(time_left) => {}
+        }
+
+        let pending = inserter.pending().rows;
+        match inserter.commit().await {
+            Ok(quantities) => {
+                if quantities.rows > 0 {
+                    debug!("Inserted {} rows into {}", quantities.rows, table);
+                }
+                add_quantities(&mut inserted, &quantities);
+            }
+            Err(e) => drop_rows(
+                &mut failure,
+                pending,
+                format!("Failed to insert batch: {e}"),
+            ),
+        }
+    }
+
+    // All writers are gone, send what is left
+    let pending = inserter.pending().clone();
+    match inserter.end().await {
+        Ok(_) => add_quantities(&mut inserted, &pending),
+        Err(e) => drop_rows(
+            &mut failure,
+            pending.rows,
+            format!("Failed to insert last batch: {e}"),
+        ),
+    }
+
+    info!(
+        "Writer for {} stopped, {} rows inserted",
+        table, inserted.rows
+    );
+    inserted
+}
+
+fn add_quantities(total: &mut Quantities, quantities: &Quantities) {
+    total.bytes += quantities.bytes;
+    total.rows += quantities.rows;
+    total.transactions += quantities.transactions;
+}
+
+impl ClickHouseClient {
+    /// Batched writer of rows into `table`, must be called within a tokio runtime
+    pub fn batch_writer<T>(
+        &self,
+        table: &str,
+        config: WriterConfig,
+    ) -> DatabaseResult<BatchWriter<T>>
+    where
+        T: WriteRow + Send + 'static,
+    {
+        BatchWriter::spawn(self, table, config)
+    }
+
+    /// Batched writer of the `orderbooks` table
+    pub fn orderbooks_writer(
+        &self,
+        config: WriterConfig,
+    ) -> DatabaseResult<BatchWriter<OrderbookRow>> {
+        self.batch_writer("orderbooks", config)
+    }
+
+    /// Batched writer of the `orderbook_levels` table
+    pub fn orderbook_levels_writer(
+        &self,
+        config: WriterConfig,
+    ) -> DatabaseResult<BatchWriter<OrderbookLevelRow>> {
+        self.batch_writer("orderbook_levels", config)
+    }
+
+    /// Batched writer of the `publictrades` table
+    pub fn trades_writer(
+        &self,
+        config: WriterConfig,
+    ) -> DatabaseResult<BatchWriter<TradeRow>> {
+        self.batch_writer("publictrades", config)
+    }
+
+    /// Batched writer of the `liquidations` table
+    pub fn liquidations_writer(
+        &self,
+        config: WriterConfig,
+    ) -> DatabaseResult<BatchWriter<LiquidationRow>> {
+        self.batch_writer("liquidations", config)
+    }
+
+    /// Batched writer of the `signals` table
+    pub fn signals_writer(
+        &self,
+        config: WriterConfig,
+    ) -> DatabaseResult<BatchWriter<SignalRow>> {
+        self.batch_writer("signals", config)
+    }
+
+    /// Batched writer of the `features` table
+    pub fn features_writer(
+        &self,
+        config: WriterConfig,
+    ) -> DatabaseResult<BatchWriter<FeatureRow>> {
+        self.batch_writer("features", config)
+    }
+
+    /// Batched writer of the `orders` table
+    pub fn orders_writer(
+        &self,
+        config: WriterConfig,
+    ) -> DatabaseResult<BatchWriter<OrderRow>> {
+        self.batch_writer("orders", config)
+    }
+
+    /// Batched writer of the `risk_rejections` table
+    pub fn risk_rejections_writer(
+        &self,
+        config: WriterConfig,
+    ) -> DatabaseResult<BatchWriter<RiskRejectionRow>> {
+        self.batch_writer("risk_rejections", config)
+    }
+
+    /// Batched writer of the `risk_state` table
+    pub fn risk_state_writer(
+        &self,
+        config: WriterConfig,
+    ) -> DatabaseResult<BatchWriter<RiskStateRow>> {
+        self.batch_writer("risk_state", config)
+    }
+}
@@ -0,0 +1,126 @@
+//! Orderbook feature engine
+//!
+//! [`FeatureEngine`] computes the `features` of each snapshot at a set of
+//! depths, the number of levels per side the volume based features use. The
+//! spread and the midprice only depend on the top of the book.
+
+use futures::{Stream, StreamExt};
+use ix_cex::models::orderbook::{Orderbook, PriceLevel};
+use rust_decimal::Decimal;
+use tracing::debug;
+
+use crate::features::{FeatureData, FeatureRow};
+use crate::{BatchWriter, DatabaseResult};
+
+/// Depths computed by default
+pub const DEFAULT_DEPTHS: [u16; 3] = [1, 5, 10];
+
+/// Computes the features of orderbook snapshots
+#[derive(Debug, Clone)]
+pub struct FeatureEngine {
+    depths: Vec<u16>,
+}
+
+impl Default for FeatureEngine {
+    fn default() -> Self {
+        Self::new(DEFAULT_DEPTHS)
+    }
+}
+
+impl FeatureEngine {
+    /// Engine of the given depths, zero is ignored
+    pub fn new(depths: impl IntoIterator<Item = u16>) -> Self {
+        let mut depths: Vec<u16> = depths.into_iter().filter(|d| *d > 0).collect();
+        depths.sort_unstable();
+        depths.dedup();
+        Self { depths }
+    }
+
+    pub fn depths(&self) -> &[u16] {
+        &self.depths
+    }
+
+    /// Features of a snapshot, one per depth. Empty when a side of the book is
+    /// empty or has no volume.
+    pub fn compute(&self, orderbook: &Orderbook) -> Vec<FeatureData> {
+        self.depths
+            .iter()
+            .filter_map(|depth| depth_features(orderbook, *depth))
+            .collect()
+    }
+
+    /// Compute the features of a snapshot and queue them
+    pub async fn write(
+        &self,
+        orderbook: &Orderbook,
+        writer: &BatchWriter<FeatureRow>,
+    ) -> DatabaseResult<usize> {
+        let features = self.compute(orderbook);
+        for feature in &features {
+            writer.write(FeatureRow::from(feature)).await?;
+        }
+        Ok(features.len())
+    }
+
+    /// Write the features of every snapshot of a stream, returns the rows sent
+    pub async fn run<S>(
+        &self,
+        orderbooks: S,
+        writer: &BatchWriter<FeatureRow>,
+    ) -> DatabaseResult<u64>
+    where
+        S: Stream<Item = Orderbook>,
+    {
+        let mut orderbooks = std::pin::pin!(orderbooks);
+        let mut count = 0;
+
+        while let Some(orderbook) = orderbooks.next().await {
+            count += self.write(&orderbook, writer).await? as u64;
+        }
+
+        debug!("Computed {} feature rows", count);
+        Ok(count)
+    }
+}
+
+fn depth_features(orderbook: &Orderbook, depth: u16) -> Option<FeatureData> {
+    let best_bid = orderbook.best_bid()?.price;
+    let best_ask = orderbook.best_ask()?.price;
+
+    let bids = top(&orderbook.bids, depth);
+    let asks = top(&orderbook.asks, depth);
+
+    let bid_volume: Decimal = bids.iter().map(|level| level.quantity).sum();
+    let ask_volume: Decimal = asks.iter().map(|level| level.quantity).sum();
+    let tav = bid_volume + ask_volume;
+    if tav.is_zero() {
+        return None;
+    }
+
+    let notional: Decimal = bids
+        .iter()
+        .chain(asks)
+        .map(|level| level.price * level.quantity)
+        .sum();
+
+    // More bid volume moves the price towards the ask, and the reverse
+    let w_midprice = (best_bid * ask_volume + best_ask * bid_volume) / tav;
+
+    FeatureData::builder()
+        .feature_ts(orderbook.timestamp.timestamp_millis() as u64)
+        .symbol(orderbook.symbol.clone())
+        .exchange(orderbook.exchange.clone())
+        .depth(depth)
+        .spread(orderbook.spread()?)
+        .midprice(orderbook.mid_price()?)
+        .w_midprice(w_midprice)
+        .vwap(notional / tav)
+        .imb((bid_volume - ask_volume) / tav)
+        .tav(tav)
+        .build()
+        .ok()
+}
+
+fn top(levels: &[PriceLevel], depth: u16) -> &[PriceLevel] {
+    &levels[..levels.len().min(depth as usize)]
+}
@@ -0,0 +1,637 @@
+//! Signal execution
+//!
+//! The [`ExecutionEngine`] turns actionable signals into market orders sized
+//! as a fraction of the equity, on the exchange of its [`TradingClient`]. Each
+//! order carries a client order id derived from its signal, see
+//! [`order_link_id`], so a signal is ordered at most once: the id is looked up
+//! in the `orders` table and on the exchange before sending, and the exchanges
+//! reject a second order with the same id. The pipelines repeat their signal
+//! every second, a side is ordered again only once the signals of the symbol
+//! and profile left it, see [`SignalSides`]. Every state of an order, from its
+//! submission to its fill, is written to `orders`. An order whose placement
+//! went unanswered and that is not on the exchange yet is kept as unknown and
+//! looked up again, it is rejected once still missing after a grace period.
+//!
+//! Orders pass the [`RiskManager`] of the account before they are sent, the
+//! refused ones are written to `risk_rejections`. The equity it checks is the
+//! value of every coin held, in the quote coin at the last traded price. The
+//! positions it limits start from the spot balances read at startup, and the
+//! unfilled quantity of the open orders counts towards them.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use ix_cex::TradingClient;
+use ix_cex::models::trades::TradeSide;
+use ix_cex::models::trading::{Balance, NewOrder, OrderKey, OrderQty};
+use rust_decimal::Decimal;
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+
+use crate::orders::read_tables::read_orders;
+use crate::orders::{
+    OrderRow, QTY_UNIT_BASE, QTY_UNIT_QUOTE, STATUS_REJECTED, STATUS_SUBMITTED,
+    STATUS_UNKNOWN, latest_orders,
+};
+use crate::rejections::RiskRejectionRow;
+use crate::risk::{OrderCheck, RiskManager, RiskViolation};
+use crate::risk_state::RiskStateRow;
+use crate::risk_state::read_tables::read_risk_state;
+use crate::select::SortOrder;
+use crate::signals::SignalRow;
+use crate::signals::read_tables::read_signals;
+use crate::trades::read_tables::read_trade_rows;
+use crate::{BatchWriter, ClickHouseClient, DatabaseError, DatabaseResult};
+
+pub use ix_cex::models::trading::LotSize;
+
+/// Prefix of the client order id of the orders sent by the engine
+pub const ORDER_LINK_ID_PREFIX: &str = "ix-";
+
+/// Account and sizing of the orders
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionConfig {
+    /// Account of the risk limits
+    pub account: String,
+    /// Exchange of the signals acted on, the one of the trading client
+    pub exchange: String,
+    /// Coin the equity is valued in, the quote coin of the traded symbols
+    pub coin: String,
+    /// Fraction of the equity put in each order
+    pub risk_fraction: Decimal,
+}
+
+impl Default for ExecutionConfig {
+    fn default() -> Self {
+        Self {
+            account: "main".to_string(),
+            exchange: "Bybit".to_string(),
+            coin: "USDT".to_string(),
+            risk_fraction: Decimal::new(1, 2),
+        }
+    }
+}
+
+/// Client order id of the order of a signal, the same for every read of the
+/// row
+pub fn order_link_id(signal: &SignalRow) -> String {
+    let key = format!(
+        "{}|{}|{}|{}|{}",
+        signal.exchange,
+        signal.symbol,
+        signal.timestamp.timestamp_micros(),
+        signal.side,
+        signal.profile
+    );
+    let digest = hex::encode(Sha256::digest(key.as_bytes()));
+
+    // Bybit and Binance accept up to 36 characters
+    format!("{}{}", ORDER_LINK_ID_PREFIX, &digest[..32])
+}
+
+/// Order side of a signal, `None` for `NEUTRAL`
+pub fn order_side(signal: &SignalRow) -> Option<TradeSide> {
+    match signal.side.as_str() {
+        "BUY" => Some(TradeSide::Buy),
+        "SELL" => Some(TradeSide::Sell),
+        _ => None,
+    }
+}
+
+/// Value of the balances in `coin`, the other coins at their price in `coin`
+/// from `prices`, by coin. Coins without a price are left out.
+pub fn account_equity(
+    balances: &[Balance],
+    coin: &str,
+    prices: &HashMap<String, Decimal>,
+) -> Decimal {
+    balances
+        .iter()
+        .map(|balance| match prices.get(&balance.coin) {
+            _ if balance.coin == coin => balance.total,
+            Some(price) => balance.total * price,
+            None => Decimal::ZERO,
+        })
+        .sum()
+}
+
+/// Spot position of every coin held other than `coin`, by symbol of the coin
+/// quoted in `coin`, e.g. `SOLUSDT`
+pub fn spot_positions(balances: &[Balance], coin: &str) -> HashMap<String, Decimal> {
+    balances
+        .iter()
+        .filter(|balance| balance.coin != coin)
+        .map(|balance| (spot_symbol(&balance.coin, coin), balance.total))
+        .collect()
+}
+
+/// Side of the last order of each exchange, symbol and profile, kept until a
+/// signal of another side
+#[derive(Debug, Clone, Default)]
+pub struct SignalSides {
+    ordered: HashMap<(String, String, String), TradeSide>,
+}
+
+impl SignalSides {
+    /// Side to order for `signal`, `None` when it is not acted on or repeats
+    /// the side of the last order
+    pub fn to_order(&mut self, signal: &SignalRow) -> Option<TradeSide> {
+        let key = signal_key(signal);
+        let side = order_side(signal);
+        let last = self.ordered.get(&key).copied();
+        if last.is_some() && last != side {
+            self.ordered.remove(&key);
+        }
+
+        let side = side.filter(|_| signal.should_alert())?;
+        (!self.ordered.contains_key(&key)).then_some(side)
+    }
+
+    /// Record the order of `signal` on `side`
+    pub fn ordered(&mut self, signal: &SignalRow, side: TradeSide) {
+        self.ordered.insert(signal_key(signal), side);
+    }
+}
+
+fn signal_key(signal: &SignalRow) -> (String, String, String) {
+    (
+        signal.exchange.clone(),
+        signal.symbol.clone(),
+        signal.profile.clone(),
+    )
+}
+
+/// Symbol of `base` quoted in `quote`
+fn spot_symbol(base: &str, quote: &str) -> String {
+    format!("{base}{quote}")
+}
+
+/// Places the orders of signals and follows them until they are closed
+pub struct ExecutionEngine {
+    client: Box<dyn TradingClient>,
+    database: ClickHouseClient,
+    writer: BatchWriter<OrderRow>,
+    config: ExecutionConfig,
+    risk: RiskManager,
+    rejections: BatchWriter<RiskRejectionRow>,
+    risk_state: BatchWriter<RiskStateRow>,
+    lot_sizes: HashMap<String, LotSize>,
+    /// Client order id of every order already sent
+    known: HashSet<String>,
+    sides: SignalSides,
+    /// Latest state of the orders not closed yet, by client order id
+    open: HashMap<String, OrderRow>,
+}
+
+impl ExecutionEngine {
+    pub fn new(
+        client: Box<dyn TradingClient>,
+        database: ClickHouseClient,
+        writer: BatchWriter<OrderRow>,
+        config: ExecutionConfig,
+        risk: RiskManager,
+        rejections: BatchWriter<RiskRejectionRow>,
+        risk_state: BatchWriter<RiskStateRow>,
+    ) -> Self {
+        Self {
+            client,
+            database,
+            writer,
+            config,
+            risk,
+            rejections,
+            risk_state,
+            lot_sizes: HashMap::new(),
+            known: HashSet::new(),
+            sides: SignalSides::default(),
+            open: HashMap::new(),
+        }
+    }
+
+    /// Name of the transport of the orders
+    pub fn transport(&self) -> &'static str {
+        self.client.transport()
+    }
+
+    pub fn config(&self) -> &ExecutionConfig {
+        &self.config
+    }
+
+    pub fn risk(&self) -> &RiskManager {
+        &self.risk
+    }
+
+    /// Orders not closed yet
+    pub fn open_orders(&self) -> impl Iterator<Item = &OrderRow> {
+        self.open.values()
+    }
+
+    /// Load the orders of the signals since `since`, so they are not sent
+    /// again, and follow the ones still open. The signals since `since` are
+    /// replayed so their side is not ordered again. The positions of the risk limits
+    /// start from the balances of the account, which hold every fill so far,
+    /// and the limits continue from the last persisted risk state of the
+    /// account. Returns the open orders.
+    pub async fn resume(&mut self, since: DateTime<Utc>) -> DatabaseResult<usize> {
+        let states = read_risk_state()
+            .exchange(self.config.exchange.clone())
+            .order(SortOrder::Desc)
+            .fetch_all(&self.database)
+            .await?;
+        if let Some(state) = states
+            .iter()
+            .find(|state| state.account == self.config.account)
+        {
+            info!(
+                "Resumed risk state of {}, day start equity {} on {}, kill switch {}",
+                state.account, state.day_start_equity, state.day, state.kill_switch
+            );
+            self.risk.restore(state);
+        }
+
+        let balances = self.client.balances().await?;
+        for (symbol, position) in spot_positions(&balances, &self.config.coin) {
+            info!("Position of {} starts at {}", symbol, position);
+            self.risk.set_position(&symbol, position);
+        }
+
+        let rows = read_orders()
+            .exchange(self.config.exchange.clone())
+            .from(since)
+            .fetch_all(&self.database)
+            .await?;
+
+        for row in latest_orders(rows) {
+            self.known.insert(row.order_link_id.clone());
+            if !row.is_terminal() {
+                // Filled so far is in the balances, the rest is reserved
+                let price = self
+                    .last_trade_price(&row.symbol)
+                    .await?
+                    .unwrap_or_default();
+                self.risk.reserve(&row, row.unfilled_qty(price));
+                self.open.insert(row.order_link_id.clone(), row);
+            }
+        }
+
+        let signals = read_signals()
+            .exchange(self.config.exchange.clone())
+            .from(since)
+            .order(SortOrder::Asc)
+            .fetch_all(&self.database)
+            .await?;
+        for signal in &signals {
+            if let Some(side) = self.sides.to_order(signal)
+                && self.known.contains(&order_link_id(signal))
+            {
+                self.sides.ordered(signal, side);
+            }
+        }
+
+        info!(
+            "Resumed {} orders, {} open",
+            self.known.len(),
+            self.open.len()
+        );
+        Ok(self.open.len())
+    }
+
+    /// Order a signal, returns the state written to `orders`. `None` when the
+    /// signal is not acted on, repeats the side of the last order of its
+    /// symbol and profile, or its order was already sent.
+    pub async fn on_signal(
+        &mut self,
+        signal: &SignalRow,
+    ) -> DatabaseResult<Option<OrderRow>> {
+        if signal.exchange != self.config.exchange {
+            return Ok(None);
+        }
+        let Some(side) = self.sides.to_order(signal) else {
+            return Ok(None);
+        };
+
+        let link_id = order_link_id(signal);
+        if self.known.contains(&link_id) {
+            self.sides.ordered(signal, side);
+            return Ok(None);
+        }
+
+        let row = self.order(signal, side, link_id).await?;
+        self.sides.ordered(signal, side);
+        Ok(Some(row))
+    }
+
+    /// Send the order of a signal, returns the state written to `orders`
+    async fn order(
+        &mut self,
+        signal: &SignalRow,
+        side: TradeSide,
+        link_id: String,
+    ) -> DatabaseResult<OrderRow> {
+        let mut row = OrderRow {
+            timestamp: Utc::now(),
+            order_link_id: link_id.clone(),
+            order_id: String::new(),
+            exchange: signal.exchange.clone(),
+            symbol: signal.symbol.clone(),
+            side: side.to_string(),
+            order_type: "Market".to_string(),
+            qty: Decimal::ZERO,
+            qty_unit: String::new(),
+            status: STATUS_SUBMITTED.to_string(),
+            filled_qty: Decimal::ZERO,
+            avg_price: Decimal::ZERO,
+            fee: Decimal::ZERO,
+            reject_reason: String::new(),
+            signal_timestamp: signal.timestamp,
+        };
+
+        // Sent before the last restart but not recorded
+        let key = OrderKey::ClientOrderId(link_id.clone());
+        if let Some(order) = self.client.get_order(&signal.symbol, &key).await? {
+            warn!("Order {} already on the exchange", link_id);
+            let mut row = row.updated(&order);
+            // Sized by `LotSize::market_qty`, in the quote coin for buys
+            row.qty = order.qty;
+            row.qty_unit = match side {
+                TradeSide::Buy => QTY_UNIT_QUOTE,
+                TradeSide::Sell => QTY_UNIT_BASE,
+            }
+            .to_string();
+            if !row.is_terminal() {
+                let price = self.last_price(signal).await?;
+                self.risk.reserve(&row, row.unfilled_qty(price));
+            }
+            return self.record(row).await;
+        }
+
+        let price = self.last_price(signal).await?;
+        let equity = self.equity().await?;
+        let lot_size = self.lot_size(&signal.symbol).await?;
+        let notional = equity * self.config.risk_fraction;
+
+        match lot_size.market_qty(side, notional, price) {
+            None => {
+                row.status = STATUS_REJECTED.to_string();
+                row.reject_reason =
+                    format!("Below the minimum order size, notional {notional}");
+            }
+            Some(qty) => {
+                row.set_qty(qty);
+                let side_name = side.to_string();
+                let check = OrderCheck {
+                    symbol: &signal.symbol,
+                    side: &side_name,
+                    notional: match qty {
+                        OrderQty::Quote(qty) => qty,
+                        OrderQty::Base(qty) => qty * price,
+                    },
+                    price,
+                    equity,
+                };
+
+                let now = Utc::now();
+                let day_start = self.risk.day_start();
+                let checked = self.risk.check(&check, now);
+                if self.risk.day_start() != day_start {
+                    self.save_risk_state().await?;
+                }
+                if let Err(violation) = checked {
+                    let notional = check.notional;
+                    self.reject(&mut row, notional, violation).await?;
+                    return self.record(row).await;
+                }
+
+                let order = NewOrder::market(&signal.symbol, side, qty)
+                    .client_order_id(&link_id);
+
+                self.risk.on_order(&row, row.unfilled_qty(price), now);
+                match self.client.place_order(&order).await {
+                    Ok(ack) => {
+                        info!(
+                            "{} {} {} sent as {} over {}",
+                            side,
+                            row.qty,
+                            signal.symbol,
+                            ack.order_id,
+                            self.client.transport()
+                        );
+                        row.order_id = ack.order_id;
+                    }
+                    // No answer, the order may still have been placed, it is
+                    // looked up again by `update_orders`
+                    Err(e) if e.is_retryable() => {
+                        warn!("Order {} unanswered: {}", link_id, e);
+                        match self.client.get_order(&signal.symbol, &key).await {
+                            Ok(Some(order)) => row.order_id = order.order_id,
+                            Ok(None) | Err(_) => {
+                                row.status = STATUS_UNKNOWN.to_string();
+                                row.reject_reason = e.to_string();
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        warn!("Order {} rejected: {}", link_id, e);
+                        row.status = STATUS_REJECTED.to_string();
+                        row.reject_reason = e.to_string();
+                    }
+                }
+            }
+        }
+
+        self.record(row).await
+    }
+
+    /// Read the state of the open orders and write the ones that changed,
+    /// returns how many changed. The orders of unknown status still not on
+    /// the exchange after the grace period are rejected. An order that could
+    /// not be read is left as it is, the others are still updated and the
+    /// read errors returned together.
+    pub async fn update_orders(&mut self) -> DatabaseResult<usize> {
+        let mut changed = Vec::new();
+        let mut errors = Vec::new();
+
+        for (link_id, row) in &self.open {
+            let key = OrderKey::ClientOrderId(link_id.clone());
+            match self.client.get_order(&row.symbol, &key).await {
+                Ok(Some(order)) => {
+                    let updated = row.updated(&order);
+                    if !updated.same_state(row) {
+                        changed.push(updated);
+                    }
+                }
+                Ok(None) => {
+                    if let Some(rejected) = row.not_found(Utc::now()) {
+                        changed.push(rejected);
+                    }
+                }
+                Err(e) => {
+                    warn!("Order {} not read: {}", link_id, e);
+                    errors.push(format!("{link_id}: {e}"));
+                }
+            }
+        }
+
+        let count = changed.len();
+        for row in changed {
+            info!(
+                "Order {} {}, filled {}",
+                row.order_link_id, row.status, row.filled_qty
+            );
+            self.record(row).await?;
+        }
+
+        if !errors.is_empty() {
+            return Err(DatabaseError::OperationFailed(format!(
+                "{} open orders not read, {}",
+                errors.len(),
+                errors.join("; ")
+            )));
+        }
+        Ok(count)
+    }
+
+    /// Accept orders again after the kill switch was engaged
+    pub async fn reset_kill_switch(&mut self) -> DatabaseResult<()> {
+        if self.risk.is_killed() {
+            warn!("Kill switch of {} reset", self.config.account);
+            self.risk.reset_kill_switch();
+            self.save_risk_state().await?;
+        }
+        Ok(())
+    }
+
+    /// Flush the rows of `orders`, `risk_rejections` and `risk_state`
+    pub async fn shutdown(self) -> DatabaseResult<()> {
+        self.writer.shutdown().await?;
+        self.rejections.shutdown().await?;
+        self.risk_state.shutdown().await?;
+        Ok(())
+    }
+
+    /// Refuse an order, engaging the kill switch on the breaches that call for
+    /// it
+    async fn reject(
+        &mut self,
+        row: &mut OrderRow,
+        notional: Decimal,
+        violation: RiskViolation,
+    ) -> DatabaseResult<()> {
+        warn!("Order {} refused: {}", row.order_link_id, violation);
+
+        if self.risk.limits().engages_kill_switch(&violation) && !self.risk.is_killed() {
+            self.risk.engage_kill_switch();
+            self.save_risk_state().await?;
+            warn!("Kill switch engaged, cancelling the open orders");
+            if let Err(e) = self.client.cancel_all_orders(None).await {
+                warn!("Open orders not cancelled: {}", e);
+            }
+        }
+
+        row.status = STATUS_REJECTED.to_string();
+        row.reject_reason = violation.to_string();
+
+        self.rejections
+            .write(RiskRejectionRow {
+                timestamp: Utc::now(),
+                account: self.config.account.clone(),
+                exchange: row.exchange.clone(),
+                symbol: row.symbol.clone(),
+                side: row.side.clone(),
+                order_link_id: row.order_link_id.clone(),
+                rule: violation.rule().to_string(),
+                reason: violation.to_string(),
+                notional,
+                kill_switch: self.risk.is_killed(),
+            })
+            .await
+    }
+
+    /// Write the risk state and flush it, so it outlives a crash
+    async fn save_risk_state(&self) -> DatabaseResult<()> {
+        if let Some(state) = self.risk.state(&self.config.exchange, Utc::now()) {
+            self.risk_state.write(state).await?;
+            self.risk_state.flush().await?;
+        }
+        Ok(())
+    }
+
+    async fn record(&mut self, row: OrderRow) -> DatabaseResult<OrderRow> {
+        self.risk.on_fill(self.open.get(&row.order_link_id), &row);
+        self.known.insert(row.order_link_id.clone());
+        if row.is_terminal() {
+            self.open.remove(&row.order_link_id);
+        } else {
+            self.open.insert(row.order_link_id.clone(), row.clone());
+        }
+
+        self.writer.write(row.clone()).await?;
+        Ok(row)
+    }
+
+    async fn last_price(&self, signal: &SignalRow) -> DatabaseResult<Decimal> {
+        self.last_trade_price(&signal.symbol).await?.ok_or_else(|| {
+            DatabaseError::OperationFailed(format!(
+                "No trades of {} {}",
+                signal.exchange, signal.symbol
+            ))
+        })
+    }
+
+    /// Price of the last trade of `symbol` on the exchange of the engine
+    async fn last_trade_price(&self, symbol: &str) -> DatabaseResult<Option<Decimal>> {
+        Ok(read_trade_rows()
+            .exchange(self.config.exchange.clone())
+            .symbol(symbol)
+            .order(SortOrder::Desc)
+            .limit(1)
+            .fetch_all(&self.database)
+            .await?
+            .first()
+            .map(|trade| trade.price))
+    }
+
+    /// Value of every coin held, in the quote coin
+    async fn equity(&self) -> DatabaseResult<Decimal> {
+        let balances = self.client.balances().await?;
+        if !balances
+            .iter()
+            .any(|balance| balance.coin == self.config.coin)
+        {
+            return Err(DatabaseError::OperationFailed(format!(
+                "No {} balance on {}",
+                self.config.coin,
+                self.client.exchange_name()
+            )));
+        }
+
+        let mut prices = HashMap::new();
+        for balance in &balances {
+            if balance.coin == self.config.coin || balance.total.is_zero() {
+                continue;
+            }
+            let symbol = spot_symbol(&balance.coin, &self.config.coin);
+            match self.last_trade_price(&symbol).await? {
+                Some(price) => {
+                    prices.insert(balance.coin.clone(), price);
+                }
+                None => warn!(
+                    "No trades of {}, {} {} left out of the equity",
+                    symbol, balance.total, balance.coin
+                ),
+            }
+        }
+
+        Ok(account_equity(&balances, &self.config.coin, &prices))
+    }
+
+    async fn lot_size(&mut self, symbol: &str) -> DatabaseResult<LotSize> {
+        if let Some(lot_size) = self.lot_sizes.get(symbol) {
+            return Ok(lot_size.clone());
+        }
+
+        let lot_size = self.client.lot_size(symbol).await?;
+        self.lot_sizes.insert(symbol.to_string(), lot_size.clone());
+        Ok(lot_size)
+    }
+}
@@ -7,6 +7,12 @@
 //! - Orderbook data structures and operations
 //! - Parquet file storage and partitioning
 //! - System table monitoring and reporting
+//! - Versioned schema migrations
+//! - Orderbook feature engine
+//! - Signal pipeline configuration
+//! - Signal broadcasting over WebSocket and SSE
+//! - Signal execution on Bybit and Binance spot
+//! - Pre-trade risk limits
 //!
 
 use std::collections::HashMap;
@@ -24,6 +30,20 @@ pub use client::*;
 pub mod queries;
 pub use queries::*;
 
+pub mod migrations;
+
+pub mod storage;
+
+pub mod engine;
+
+pub mod producer;
+
+pub mod broadcast;
+
+pub mod execution;
+
+pub mod risk;
+
 /// Main errors for the ix-execution library
 #[derive(Error, Debug)]
 pub enum DatabaseError {
@@ -42,8 +62,20 @@ pub enum DatabaseError {
     #[error("Parquet error: {0}")]
     ParquetError(String),
 
+    #[error("Invalid row: {0}")]
+    InvalidRow(String),
+
     #[error("Database operation failed: {0}")]
     OperationFailed(String),
+
+    #[error("Migration error: {0}")]
+    MigrationError(String),
+
+    #[error("Configuration error: {0}")]
+    ConfigError(String),
+
+    #[error("Exchange error: {0}")]
+    ExchangeError(#[from] ix_cex::ExchangeError),
 }
 
 /// Result type for database operations
@@ -154,6 +186,11 @@ impl ClickHouseClient {
     pub fn client(&self) -> &Client {
         &self.client
     }
+
+    /// Name of the database queries run against
+    pub fn database(&self) -> &str {
+        &self.database
+    }
 }
 
 /// System table structure for queries
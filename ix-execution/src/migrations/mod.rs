@@ -0,0 +1,329 @@
+//! Versioned schema migrations
+//!
+//! Migrations are applied in version order and recorded in the
+//! `schema_migrations` table together with a checksum of their statements.
+//! A recorded migration whose statements have changed since it was applied
+//! stops the runner, so the DDL of released migrations must not be edited,
+//! schema changes go into a new migration instead.
+//!
+//! ClickHouse DDL is not transactional, the statements of a migration are
+//! written to be safe to run again if the runner stops half way.
+
+use chrono::{DateTime, Utc};
+use clickhouse::Row;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+
+use crate::queries::{
+    bars::{BarInterval, create_tables::create_bars_ddl},
+    features::create_tables::{alter_features_numeric_ddl, create_features_table_ddl},
+    liquidations::create_tables::create_liquidations_table_ddl,
+    orderbooks::create_tables::{
+        create_orderbook_levels_table_ddl, create_orderbooks_table_ddl,
+    },
+    orders::create_tables::{alter_orders_qty_unit_ddl, create_orders_table_ddl},
+    rejections::create_tables::create_risk_rejections_table_ddl,
+    risk_state::create_tables::create_risk_state_table_ddl,
+    signals::create_tables::{alter_signals_trade_flow_ddl, create_signals_table_ddl},
+    trades::create_tables::{alter_trades_decimal_ddl, create_trades_table_ddl},
+};
+use crate::{ClickHouseClient, DatabaseError, DatabaseResult};
+
+/// A schema change, its statements are executed one by one
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    pub up: fn() -> Vec<String>,
+}
+
+impl Migration {
+    /// SHA-256 of the statements, hex encoded
+    pub fn checksum(&self) -> String {
+        let mut hasher = Sha256::new();
+        for statement in (self.up)() {
+            hasher.update(statement.as_bytes());
+            hasher.update(b";\n");
+        }
+        hex::encode(hasher.finalize())
+    }
+}
+
+/// All migrations, in version order
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "create_orderbooks",
+        up: || vec![create_orderbooks_table_ddl()],
+    },
+    Migration {
+        version: 2,
+        name: "create_publictrades",
+        up: || vec![create_trades_table_ddl()],
+    },
+    Migration {
+        version: 3,
+        name: "create_liquidations",
+        up: || vec![create_liquidations_table_ddl()],
+    },
+    Migration {
+        version: 4,
+        name: "create_signals",
+        up: || vec![create_signals_table_ddl()],
+    },
+    Migration {
+        version: 5,
+        name: "create_features",
+        up: || vec![create_features_table_ddl()],
+    },
+    Migration {
+        version: 6,
+        name: "publictrades_decimal_columns",
+        up: || vec![alter_trades_decimal_ddl()],
+    },
+    Migration {
+        version: 7,
+        name: "create_orderbook_levels",
+        up: || vec![create_orderbook_levels_table_ddl()],
+    },
+    Migration {
+        version: 8,
+        name: "create_trade_bars",
+        // Fixed list, new intervals are added by a later migration
+        up: || {
+            create_bars_ddl(&[
+                BarInterval::OneSecond,
+                BarInterval::OneMinute,
+                BarInterval::FiveMinutes,
+                BarInterval::OneHour,
+            ])
+        },
+    },
+    Migration {
+        version: 9,
+        name: "features_numeric_columns",
+        up: || vec![alter_features_numeric_ddl()],
+    },
+    Migration {
+        version: 10,
+        name: "signals_trade_flow_columns",
+        up: || vec![alter_signals_trade_flow_ddl()],
+    },
+    Migration {
+        version: 11,
+        name: "create_orders",
+        up: || vec![create_orders_table_ddl()],
+    },
+    Migration {
+        version: 12,
+        name: "create_risk_rejections",
+        up: || vec![create_risk_rejections_table_ddl()],
+    },
+    Migration {
+        version: 13,
+        name: "orders_qty_unit_column",
+        up: || vec![alter_orders_qty_unit_ddl()],
+    },
+    Migration {
+        version: 14,
+        name: "create_risk_state",
+        up: || vec![create_risk_state_table_ddl()],
+    },
+];
+
+// Create the schema_migrations table DDL
+pub fn create_schema_migrations_table_ddl() -> String {
+    r#"
+CREATE TABLE IF NOT EXISTS schema_migrations (
+    version UInt32,
+    name String,
+    checksum String,
+    applied_at DateTime64(6, 'UTC')
+) ENGINE = MergeTree()
+ORDER BY version
+"#
+    .trim()
+    .to_string()
+}
+
+/// Row of the `schema_migrations` table
+#[derive(Debug, Clone, Serialize, Deserialize, Row)]
+pub struct AppliedMigration {
+    pub version: u32,
+    pub name: String,
+    pub checksum: String,
+    #[serde(with = "clickhouse::serde::chrono::datetime64::micros")]
+    pub applied_at: DateTime<Utc>,
+}
+
+/// State of a known migration in the database
+#[derive(Debug, Clone)]
+pub struct MigrationStatus {
+    pub migration: Migration,
+    pub applied: Option<AppliedMigration>,
+}
+
+/// Applies the pending migrations of a database
+pub struct Migrator<'a> {
+    client: &'a ClickHouseClient,
+    migrations: &'a [Migration],
+}
+
+impl<'a> Migrator<'a> {
+    /// Migrator of the crate migrations
+    pub fn new(client: &'a ClickHouseClient) -> Self {
+        Self::with_migrations(client, MIGRATIONS)
+    }
+
+    /// Migrator of a custom list, versions must be strictly increasing
+    pub fn with_migrations(
+        client: &'a ClickHouseClient,
+        migrations: &'a [Migration],
+    ) -> Self {
+        Self { client, migrations }
+    }
+
+    /// Create the database and the `schema_migrations` table if missing
+    pub async fn init(&self) -> DatabaseResult<()> {
+        // The client database may not exist yet, create it from `default`
+        self.client
+            .client()
+            .clone()
+            .with_database("default")
+            .query(&format!(
+                "CREATE DATABASE IF NOT EXISTS {}",
+                self.client.database()
+            ))
+            .execute()
+            .await?;
+
+        self.client
+            .create_table(&create_schema_migrations_table_ddl())
+            .await
+    }
+
+    /// Migrations recorded in the database, in version order
+    pub async fn applied(&self) -> DatabaseResult<Vec<AppliedMigration>> {
+        self.client
+            .read_table(
+                "SELECT version, name, checksum, applied_at \
+                 FROM schema_migrations ORDER BY version",
+            )
+            .await
+    }
+
+    /// Every known migration with its applied record, checksums are verified
+    pub async fn status(&self) -> DatabaseResult<Vec<MigrationStatus>> {
+        self.check_order()?;
+        let mut applied = self.applied().await?;
+
+        for record in &applied {
+            match self.migrations.iter().find(|m| m.version == record.version) {
+                Some(migration) => {
+                    let checksum = migration.checksum();
+                    if checksum != record.checksum {
+                        return Err(DatabaseError::MigrationError(format!(
+                            "Checksum of migration {} ({}) changed since it was \
+                             applied: {} != {}",
+                            record.version, record.name, checksum, record.checksum
+                        )));
+                    }
+                }
+                None => {
+                    return Err(DatabaseError::MigrationError(format!(
+                        "Migration {} ({}) is applied but unknown to this build",
+                        record.version, record.name
+                    )));
+                }
+            }
+        }
+
+        Ok(self
+            .migrations
+            .iter()
+            .map(|migration| {
+                let position =
+                    applied.iter().position(|r| r.version == migration.version);
+                MigrationStatus {
+                    migration: *migration,
+                    applied: position.map(|i| applied.remove(i)),
+                }
+            })
+            .collect())
+    }
+
+    /// Migrations not applied yet, in version order
+    pub async fn pending(&self) -> DatabaseResult<Vec<Migration>> {
+        Ok(self
+            .status()
+            .await?
+            .into_iter()
+            .filter(|status| status.applied.is_none())
+            .map(|status| status.migration)
+            .collect())
+    }
+
+    /// Apply the pending migrations, returns the applied versions
+    pub async fn migrate(&self) -> DatabaseResult<Vec<u32>> {
+        self.init().await?;
+
+        let pending = self.pending().await?;
+        if pending.is_empty() {
+            info!("Schema of {} is up to date", self.client.database());
+            return Ok(Vec::new());
+        }
+
+        let mut versions = Vec::with_capacity(pending.len());
+
+        for migration in pending {
+            info!(
+                "Applying migration {} ({})",
+                migration.version, migration.name
+            );
+
+            for statement in (migration.up)() {
+                self.client.write_table(&statement).await.map_err(|e| {
+                    warn!(
+                        "Migration {} ({}) failed: {}",
+                        migration.version, migration.name, e
+                    );
+                    DatabaseError::MigrationError(format!(
+                        "Migration {} ({}) failed: {}",
+                        migration.version, migration.name, e
+                    ))
+                })?;
+            }
+
+            let record = AppliedMigration {
+                version: migration.version,
+                name: migration.name.to_string(),
+                checksum: migration.checksum(),
+                applied_at: Utc::now(),
+            };
+
+            let mut insert = self
+                .client
+                .client()
+                .insert::<AppliedMigration>("schema_migrations")?;
+            insert.write(&record).await?;
+            insert.end().await?;
+
+            versions.push(migration.version);
+        }
+
+        Ok(versions)
+    }
+
+    fn check_order(&self) -> DatabaseResult<()> {
+        for pair in self.migrations.windows(2) {
+            if pair[0].version >= pair[1].version {
+                return Err(DatabaseError::MigrationError(format!(
+                    "Migration {} ({}) is not ordered after {} ({})",
+                    pair[1].version, pair[1].name, pair[0].version, pair[0].name
+                )));
+            }
+        }
+        Ok(())
+    }
+}
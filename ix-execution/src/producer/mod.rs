@@ -0,0 +1,309 @@
+//! Signal pipeline configuration
+//!
+//! The dataproducer reads a TOML file with named VPIN parameter profiles, the
+//! signal thresholds and the `(exchange, symbol)` targets, and runs one VPIN
+//! pipeline per target with the profile the target names.
+//!
+//! ```toml
+//! [dataproducer]
+//! profile = "ultra-fast"
+//!
+//! [profiles.ultra-fast]
+//! volume_per_bar = 100.0
+//! window_size = 8
+//! batch_size = 4
+//! data_limit_minutes = 1
+//! toxicity_threshold = 0.5
+//! momentum_window = 4
+//! target_bars_per_day = 7000.0
+//!
+//! [[targets]]
+//! exchange = "Bybit"
+//! symbol = "SOLUSDT"
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+use crate::trades::stats::TradingStats;
+use crate::{DatabaseError, DatabaseResult};
+
+pub mod signal;
+pub use signal::TradeFlowSignal;
+
+/// Config file read when none is given
+pub const DEFAULT_CONFIG_PATH: &str = "dataproducer_config.toml";
+
+/// Environment variable with the path of the config file
+pub const CONFIG_PATH_ENV: &str = "DATAPRODUCER_CONFIG";
+
+const DEFAULT_DAILY_VOLUME: f64 = 6_000_000.0;
+const DEFAULT_TRADES_PER_MINUTE: f64 = 25.0;
+
+/// Contents of the dataproducer config file
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ProducerConfig {
+    pub dataproducer: ProducerSection,
+    #[serde(default)]
+    pub database: Option<DatabaseSection>,
+    #[serde(default)]
+    pub signal: SignalThresholds,
+    pub profiles: BTreeMap<String, VpinProfile>,
+    #[serde(default)]
+    pub targets: Vec<Target>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ProducerSection {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub log_level: Option<String>,
+    /// Profile of the targets that do not name one
+    pub profile: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DatabaseSection {
+    pub clickhouse_url: String,
+    pub database: String,
+}
+
+/// VPIN calculator parameters
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct VpinProfile {
+    pub volume_per_bar: f64,
+    pub window_size: usize,
+    pub batch_size: usize,
+    /// Minutes of trades read on each iteration
+    pub data_limit_minutes: usize,
+    pub toxicity_threshold: f64,
+    pub momentum_window: usize,
+    pub target_bars_per_day: f64,
+}
+
+/// Parameters of one VPIN pipeline, a profile adjusted to the pair's activity
+#[derive(Debug, Clone, PartialEq)]
+pub struct VpinParameters {
+    pub volume_per_bar: f64,
+    pub window_size: usize,
+    pub batch_size: usize,
+    /// Trades read on each iteration, at least one batch
+    pub limit: u64,
+    pub toxicity_threshold: f64,
+    pub momentum_window: usize,
+}
+
+impl VpinParameters {
+    /// Parameters of `profile` for a pair trading like `stats`
+    pub fn from_stats(stats: &TradingStats, profile: &VpinProfile) -> Self {
+        let volume_per_bar = (stats.avg_daily_volume / profile.target_bars_per_day)
+            .max(profile.volume_per_bar * 0.5) // Min 50% of configured value
+            .min(profile.volume_per_bar * 2.0); // Max 200% of configured value
+
+        // Use the configured window size with minor adjustments based on activity
+        let window_size = match stats.avg_trades_per_minute {
+            0.0..=30.0 => profile.window_size + 2, // Add 2 for low activity
+            30.0..=80.0 => profile.window_size,    // Use configured value
+            _ => profile.window_size.saturating_sub(2).max(1), // Subtract 2 for high activity
+        };
+
+        let trades_per_second = stats.avg_trades_per_minute / 60.0;
+        let batch_size = (trades_per_second * 2.0)
+            .max(profile.batch_size as f64 * 0.5)
+            .min(profile.batch_size as f64 * 2.0)
+            .max(1.0) as usize;
+
+        // Pairs trading less than once per `data_limit_minutes` still read trades
+        let limit = ((stats.avg_trades_per_minute * profile.data_limit_minutes as f64)
+            as u64)
+            .max(batch_size as u64);
+
+        Self {
+            volume_per_bar,
+            window_size,
+            batch_size,
+            limit,
+            toxicity_threshold: profile.toxicity_threshold,
+            momentum_window: profile.momentum_window,
+        }
+    }
+}
+
+/// Thresholds turning VPIN values into trade flow signals
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct SignalThresholds {
+    pub high_momentum_sell: f64,
+    pub medium_momentum_sell: f64,
+    pub high_momentum_buy: f64,
+    pub medium_momentum_buy: f64,
+    pub weak_momentum: f64,
+    pub high_vpin_sell: f64,
+    pub low_vpin_buy: f64,
+    pub high_toxicity_level: f64,
+    pub medium_toxicity_level: f64,
+}
+
+impl Default for SignalThresholds {
+    fn default() -> Self {
+        Self {
+            high_momentum_sell: 0.025,
+            medium_momentum_sell: 0.015,
+            high_momentum_buy: 0.025,
+            medium_momentum_buy: 0.015,
+            weak_momentum: 0.008,
+            high_vpin_sell: 0.8,
+            low_vpin_buy: 0.2,
+            high_toxicity_level: 0.85,
+            medium_toxicity_level: 0.65,
+        }
+    }
+}
+
+/// Pair a pipeline runs on
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Target {
+    pub exchange: String,
+    pub symbol: String,
+    /// Profile name, the `[dataproducer]` profile when not set
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// Estimated daily volume, used to size the volume bars while no trades of
+    /// the target are stored
+    #[serde(default = "default_daily_volume")]
+    pub daily_volume: f64,
+    #[serde(default = "default_trades_per_minute")]
+    pub trades_per_minute: f64,
+}
+
+fn default_daily_volume() -> f64 {
+    DEFAULT_DAILY_VOLUME
+}
+
+fn default_trades_per_minute() -> f64 {
+    DEFAULT_TRADES_PER_MINUTE
+}
+
+impl Target {
+    /// `exchange/symbol`
+    pub fn label(&self) -> String {
+        format!("{}/{}", self.exchange, self.symbol)
+    }
+}
+
+impl FromStr for ProducerConfig {
+    type Err = DatabaseError;
+
+    /// Parse and validate a config
+    fn from_str(s: &str) -> DatabaseResult<Self> {
+        let config: Self =
+            toml::from_str(s).map_err(|e| DatabaseError::ConfigError(e.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+impl ProducerConfig {
+    /// Read, parse and validate a config file
+    pub fn from_file(path: impl AsRef<Path>) -> DatabaseResult<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            DatabaseError::ConfigError(format!(
+                "Failed to read {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        contents.parse().map_err(|e| match e {
+            DatabaseError::ConfigError(message) => DatabaseError::ConfigError(format!(
+                "Invalid config {}: {}",
+                path.display(),
+                message
+            )),
+            e => e,
+        })
+    }
+
+    /// Path of the config file, from [`CONFIG_PATH_ENV`] or the default
+    pub fn path_from_env() -> String {
+        std::env::var(CONFIG_PATH_ENV).unwrap_or_else(|_| DEFAULT_CONFIG_PATH.to_string())
+    }
+
+    /// Target of an `(exchange, symbol)`
+    pub fn target(&self, exchange: &str, symbol: &str) -> Option<&Target> {
+        self.targets
+            .iter()
+            .find(|t| t.exchange == exchange && t.symbol == symbol)
+    }
+
+    /// Name of the profile a target runs with
+    pub fn profile_name<'a>(&'a self, target: &'a Target) -> &'a str {
+        target
+            .profile
+            .as_deref()
+            .unwrap_or(&self.dataproducer.profile)
+    }
+
+    /// Profile a target runs with
+    pub fn profile(&self, target: &Target) -> DatabaseResult<&VpinProfile> {
+        let name = self.profile_name(target);
+        self.profiles.get(name).ok_or_else(|| {
+            DatabaseError::ConfigError(format!(
+                "Unknown profile {} of {}",
+                name,
+                target.label()
+            ))
+        })
+    }
+
+    fn validate(&self) -> DatabaseResult<()> {
+        if self.targets.is_empty() {
+            return Err(DatabaseError::ConfigError("No targets".to_string()));
+        }
+
+        for (name, profile) in &self.profiles {
+            profile.validate().map_err(|message| {
+                DatabaseError::ConfigError(format!("Profile {name}: {message}"))
+            })?;
+        }
+
+        for (i, target) in self.targets.iter().enumerate() {
+            self.profile(target)?;
+            if self.targets[..i]
+                .iter()
+                .any(|t| t.exchange == target.exchange && t.symbol == target.symbol)
+            {
+                return Err(DatabaseError::ConfigError(format!(
+                    "Duplicate target {}",
+                    target.label()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl VpinProfile {
+    fn validate(&self) -> Result<(), String> {
+        if self.volume_per_bar <= 0.0 || self.target_bars_per_day <= 0.0 {
+            return Err("volume_per_bar and target_bars_per_day must be positive".into());
+        }
+        if self.window_size == 0
+            || self.batch_size == 0
+            || self.data_limit_minutes == 0
+            || self.momentum_window == 0
+        {
+            return Err("window and batch sizes must be positive".into());
+        }
+        if !(0.0..=1.0).contains(&self.toxicity_threshold) {
+            return Err("toxicity_threshold must be within [0, 1]".into());
+        }
+        Ok(())
+    }
+}
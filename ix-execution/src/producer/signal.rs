@@ -0,0 +1,131 @@
+//! Trade flow signals of the VPIN pipelines
+
+use chrono::{DateTime, Utc};
+
+use super::SignalThresholds;
+use crate::queries::format_symbol_for_clickhouse;
+use crate::signals::SignalRow;
+
+/// Trade flow signal of a VPIN value and its momentum
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeFlowSignal {
+    pub timestamp: DateTime<Utc>,
+    pub signal: String,
+    pub strength: f64,
+    pub confidence: String,
+    pub vpin_value: f64,
+    pub vpin_momentum: f64,
+    pub toxicity_level: String,
+    pub bars_in_calculation: usize,
+    pub error_status: String,
+}
+
+impl TradeFlowSignal {
+    /// Signal of the latest VPIN, neutral when the calculation failed
+    pub fn generate(
+        vpin_value: f64,
+        vpin_momentum: f64,
+        is_toxic: bool,
+        bars_count: usize,
+        has_error: bool,
+        thresholds: &SignalThresholds,
+    ) -> Self {
+        let timestamp = Utc::now();
+
+        if has_error {
+            return TradeFlowSignal {
+                timestamp,
+                signal: "NEUTRAL".to_string(),
+                strength: 0.0,
+                confidence: "ERROR".to_string(),
+                vpin_value,
+                vpin_momentum: 0.0,
+                toxicity_level: "UNKNOWN".to_string(),
+                bars_in_calculation: bars_count,
+                error_status: "ERROR_FALLBACK".to_string(),
+            };
+        }
+
+        // Use configurable thresholds for signal generation
+        let (signal, confidence) = match (vpin_momentum.abs(), is_toxic, vpin_value) {
+            // Strong sell signals
+            (momentum, true, vpin)
+                if momentum > thresholds.high_momentum_sell
+                    && vpin > thresholds.high_vpin_sell =>
+            {
+                ("SELL", "HIGH")
+            }
+            (momentum, true, _) if momentum > thresholds.medium_momentum_sell => {
+                ("SELL", "MEDIUM")
+            }
+
+            // Strong buy signals
+            (momentum, false, vpin)
+                if momentum > thresholds.high_momentum_buy
+                    && vpin < thresholds.low_vpin_buy =>
+            {
+                ("BUY", "HIGH")
+            }
+            (momentum, false, _) if momentum > thresholds.medium_momentum_buy => {
+                ("BUY", "MEDIUM")
+            }
+
+            // Weak signals
+            (momentum, _, _)
+                if momentum > thresholds.weak_momentum && vpin_momentum > 0.0 =>
+            {
+                ("SELL", "LOW")
+            }
+            (momentum, _, _)
+                if momentum > thresholds.weak_momentum && vpin_momentum < 0.0 =>
+            {
+                ("BUY", "LOW")
+            }
+
+            _ => ("NEUTRAL", "LOW"),
+        };
+
+        let strength = (vpin_momentum.abs() * 10.0).min(1.0);
+
+        let toxicity_level = match vpin_value {
+            v if v > thresholds.high_toxicity_level => "HIGH",
+            v if v > thresholds.medium_toxicity_level => "MEDIUM",
+            _ => "LOW",
+        };
+
+        TradeFlowSignal {
+            timestamp,
+            signal: signal.to_string(),
+            strength,
+            confidence: confidence.to_string(),
+            vpin_value,
+            vpin_momentum,
+            toxicity_level: toxicity_level.to_string(),
+            bars_in_calculation: bars_count,
+            error_status: "OK".to_string(),
+        }
+    }
+
+    /// Confident enough to be acted on
+    pub fn should_alert(&self) -> bool {
+        crate::signals::should_alert(&self.error_status, &self.confidence, self.strength)
+    }
+
+    /// Row of the `signals` table, `symbol` is normalized as it is stored
+    pub fn to_row(&self, exchange: &str, symbol: &str, profile: &str) -> SignalRow {
+        SignalRow {
+            timestamp: self.timestamp,
+            symbol: format_symbol_for_clickhouse(symbol),
+            side: self.signal.clone(),
+            exchange: exchange.to_string(),
+            strength: self.strength,
+            confidence: self.confidence.clone(),
+            vpin_value: self.vpin_value,
+            vpin_momentum: self.vpin_momentum,
+            toxicity_level: self.toxicity_level.clone(),
+            bars_in_calculation: self.bars_in_calculation as u32,
+            error_status: self.error_status.clone(),
+            profile: profile.to_string(),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use super::BarInterval;
+
+// Create the bars table DDL of an interval
+pub fn create_bars_table_ddl(interval: BarInterval) -> String {
+    format!(
+        r#"
+CREATE TABLE IF NOT EXISTS {} (
+    timestamp DateTime('UTC'),
+    exchange LowCardinality(String),
+    symbol LowCardinality(String),
+    open AggregateFunction(argMin, Decimal(38, 18), DateTime64(6, 'UTC')),
+    high AggregateFunction(max, Decimal(38, 18)),
+    low AggregateFunction(min, Decimal(38, 18)),
+    close AggregateFunction(argMax, Decimal(38, 18), DateTime64(6, 'UTC')),
+    volume AggregateFunction(sum, Decimal(38, 18)),
+    quote_volume AggregateFunction(sum, Decimal(38, 18)),
+    buy_volume AggregateFunction(sumIf, Decimal(38, 18), UInt8),
+    sell_volume AggregateFunction(sumIf, Decimal(38, 18), UInt8),
+    trades AggregateFunction(count)
+) ENGINE = AggregatingMergeTree()
+PARTITION BY toYYYYMM(timestamp)
+ORDER BY (exchange, symbol, timestamp)
+SETTINGS index_granularity = 8192
+"#,
+        interval.table()
+    )
+    .trim()
+    .to_string()
+}
+
+// Create the materialized view rolling publictrades into the bars of an interval.
+// The trade time is renamed so it can't be confused with the bar `timestamp`.
+pub fn create_bars_view_ddl(interval: BarInterval) -> String {
+    format!(
+        r#"
+CREATE MATERIALIZED VIEW IF NOT EXISTS {} TO {} AS
+SELECT
+    toStartOfInterval(toDateTime(trade_time, 'UTC'), {}) AS timestamp,
+    exchange,
+    symbol,
+    argMinState(price, trade_time) AS open,
+    maxState(price) AS high,
+    minState(price) AS low,
+    argMaxState(price, trade_time) AS close,
+    sumState(amount) AS volume,
+    sumState(toDecimal128(multiplyDecimal(price, amount, 18), 18)) AS quote_volume,
+    sumIfState(amount, lower(side) = 'buy') AS buy_volume,
+    sumIfState(amount, lower(side) = 'sell') AS sell_volume,
+    countState() AS trades
+FROM (
+    SELECT timestamp AS trade_time, exchange, symbol, side, price, amount
+    FROM publictrades
+)
+GROUP BY timestamp, exchange, symbol
+"#,
+        interval.view(),
+        interval.table(),
+        interval.sql_interval()
+    )
+    .trim()
+    .to_string()
+}
+
+// Statements creating the tables and views of `intervals`
+pub fn create_bars_ddl(intervals: &[BarInterval]) -> Vec<String> {
+    intervals
+        .iter()
+        .copied()
+        .flat_map(|interval| {
+            [
+                create_bars_table_ddl(interval),
+                create_bars_view_ddl(interval),
+            ]
+        })
+        .collect()
+}
@@ -0,0 +1,120 @@
+//! OHLCV bars of `publictrades`
+//!
+//! A materialized view per [`BarInterval`] rolls the inserted trades into
+//! aggregate states of an `AggregatingMergeTree` table, merged on read. The
+//! views only see trades inserted after they were created.
+
+use chrono::{DateTime, Utc};
+use clickhouse::Row;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+pub mod create_tables;
+pub mod read_tables;
+
+/// Width of the bars of a view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BarInterval {
+    OneSecond,
+    OneMinute,
+    FiveMinutes,
+    OneHour,
+}
+
+impl BarInterval {
+    /// Every interval with a view
+    pub const ALL: [BarInterval; 4] = [
+        BarInterval::OneSecond,
+        BarInterval::OneMinute,
+        BarInterval::FiveMinutes,
+        BarInterval::OneHour,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BarInterval::OneSecond => "1s",
+            BarInterval::OneMinute => "1m",
+            BarInterval::FiveMinutes => "5m",
+            BarInterval::OneHour => "1h",
+        }
+    }
+
+    /// Table holding the bars, e.g. `bars_1m`
+    pub fn table(&self) -> String {
+        format!("bars_{}", self.as_str())
+    }
+
+    /// Materialized view filling the table
+    pub fn view(&self) -> String {
+        format!("bars_{}_mv", self.as_str())
+    }
+
+    /// ClickHouse `INTERVAL` of a bar
+    fn sql_interval(&self) -> &'static str {
+        match self {
+            BarInterval::OneSecond => "INTERVAL 1 SECOND",
+            BarInterval::OneMinute => "INTERVAL 1 MINUTE",
+            BarInterval::FiveMinutes => "INTERVAL 5 MINUTE",
+            BarInterval::OneHour => "INTERVAL 1 HOUR",
+        }
+    }
+}
+
+impl fmt::Display for BarInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for BarInterval {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BarInterval::ALL
+            .into_iter()
+            .find(|interval| interval.as_str() == s)
+            .ok_or_else(|| format!("Unknown bar interval {s}, use 1s, 1m, 5m or 1h"))
+    }
+}
+
+/// Merged bar of one `(exchange, symbol)`, `timestamp` is the start of the bar
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Row)]
+pub struct BarRow {
+    #[serde(with = "clickhouse::serde::chrono::datetime")]
+    pub timestamp: DateTime<Utc>,
+    pub exchange: String,
+    pub symbol: String,
+    #[serde(with = "crate::queries::decimal")]
+    pub open: Decimal,
+    #[serde(with = "crate::queries::decimal")]
+    pub high: Decimal,
+    #[serde(with = "crate::queries::decimal")]
+    pub low: Decimal,
+    #[serde(with = "crate::queries::decimal")]
+    pub close: Decimal,
+    /// Traded base amount
+    #[serde(with = "crate::queries::decimal")]
+    pub volume: Decimal,
+    /// Sum of `price * amount`
+    #[serde(with = "crate::queries::decimal")]
+    pub quote_volume: Decimal,
+    #[serde(with = "crate::queries::decimal")]
+    pub buy_volume: Decimal,
+    #[serde(with = "crate::queries::decimal")]
+    pub sell_volume: Decimal,
+    pub trades: u64,
+}
+
+impl BarRow {
+    /// Volume weighted average price, `None` without volume
+    pub fn vwap(&self) -> Option<Decimal> {
+        self.quote_volume.checked_div(self.volume)
+    }
+
+    /// Buy minus sell volume
+    pub fn volume_imbalance(&self) -> Decimal {
+        self.buy_volume - self.sell_volume
+    }
+}
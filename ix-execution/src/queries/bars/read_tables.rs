@@ -0,0 +1,53 @@
+use chrono::{DateTime, Utc};
+use std::ops::Range;
+
+use super::{BarInterval, BarRow};
+use crate::{ClickHouseClient, DatabaseResult};
+
+// Merge the aggregate states of the bars of one (exchange, symbol) in a time range
+pub fn q_read_bars(interval: BarInterval) -> String {
+    format!(
+        r#"SELECT
+            timestamp,
+            exchange,
+            symbol,
+            argMinMerge(open),
+            maxMerge(high),
+            minMerge(low),
+            argMaxMerge(close),
+            sumMerge(volume),
+            sumMerge(quote_volume),
+            sumIfMerge(buy_volume),
+            sumIfMerge(sell_volume),
+            countMerge(trades)
+        FROM {}
+        WHERE exchange = ? AND symbol = ?
+            AND timestamp >= toDateTime(?, 'UTC') AND timestamp < toDateTime(?, 'UTC')
+        GROUP BY exchange, symbol, timestamp
+        ORDER BY timestamp"#,
+        interval.table()
+    )
+}
+
+impl ClickHouseClient {
+    /// Bars of `interval` starting within `range`, oldest first
+    pub async fn read_bars(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        interval: BarInterval,
+        range: Range<DateTime<Utc>>,
+    ) -> DatabaseResult<Vec<BarRow>> {
+        let bars = self
+            .client()
+            .query(&q_read_bars(interval))
+            .bind(exchange)
+            .bind(crate::queries::format_symbol_for_clickhouse(symbol))
+            .bind(range.start.timestamp())
+            .bind(range.end.timestamp())
+            .fetch_all::<BarRow>()
+            .await?;
+
+        Ok(bars)
+    }
+}
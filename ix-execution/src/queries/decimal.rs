@@ -0,0 +1,101 @@
+//! Serde helpers for `Decimal(38, 18)` columns.
+//!
+//! RowBinary encodes them as 128 bit integers scaled by 10^18, use with
+//! `#[serde(with = "crate::queries::decimal")]`. Columns of another scale use
+//! the matching submodule, e.g. [`scale8`] for `Decimal(32, 8)`.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer, Serializer, de, ser};
+
+use crate::{DatabaseError, DatabaseResult};
+
+/// Scale of the decimal columns
+pub const SCALE: u32 = 18;
+
+pub fn serialize<S>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serialize_scaled(value, SCALE, serializer)
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_scaled(SCALE, deserializer)
+}
+
+/// `Decimal(32, 8)` columns, also encoded as 128 bit integers
+pub mod scale8 {
+    use rust_decimal::Decimal;
+    use serde::{Deserializer, Serializer};
+
+    /// Scale of the decimal columns
+    pub const SCALE: u32 = 8;
+
+    pub fn serialize<S>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize_scaled(value, SCALE, serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize_scaled(SCALE, deserializer)
+    }
+}
+
+/// `value` as an integer scaled by 10^`scale`, rounded to `scale` decimals,
+/// `None` when it overflows
+pub fn to_scaled(value: &Decimal, scale: u32) -> Option<i128> {
+    let value = value.round_dp(scale);
+    value
+        .mantissa()
+        .checked_mul(10i128.pow(scale - value.scale()))
+}
+
+/// Fail when `value` can't be written to a column of scale `scale`
+pub fn check(value: &Decimal, scale: u32) -> DatabaseResult<()> {
+    match to_scaled(value, scale) {
+        Some(_) => Ok(()),
+        None => Err(DatabaseError::InvalidRow(format!(
+            "{value} overflows a decimal of scale {scale}"
+        ))),
+    }
+}
+
+/// Decimal of an integer scaled by 10^`scale`
+pub(crate) fn from_scaled(
+    scaled: i128,
+    scale: u32,
+) -> Result<Decimal, rust_decimal::Error> {
+    Decimal::try_from_i128_with_scale(scaled, scale).map(|value| value.normalize())
+}
+
+fn serialize_scaled<S>(
+    value: &Decimal,
+    scale: u32,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let scaled = to_scaled(value, scale).ok_or_else(|| {
+        ser::Error::custom(format!("{value} overflows a decimal of scale {scale}"))
+    })?;
+
+    serializer.serialize_i128(scaled)
+}
+
+fn deserialize_scaled<'de, D>(scale: u32, deserializer: D) -> Result<Decimal, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let scaled = i128::deserialize(deserializer)?;
+
+    from_scaled(scaled, scale).map_err(de::Error::custom)
+}
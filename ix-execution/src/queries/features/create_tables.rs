@@ -19,3 +19,19 @@ SETTINGS index_granularity = 8192
     .trim()
     .to_string()
 }
+
+// Store the features as exact decimals, one row per depth of a snapshot
+pub fn alter_features_numeric_ddl() -> String {
+    r#"
+ALTER TABLE features
+    ADD COLUMN IF NOT EXISTS depth UInt16 AFTER exchange,
+    MODIFY COLUMN spread Decimal(38, 18),
+    MODIFY COLUMN midprice Decimal(38, 18),
+    MODIFY COLUMN w_midprice Decimal(38, 18),
+    MODIFY COLUMN vwap Decimal(38, 18),
+    MODIFY COLUMN imb Decimal(38, 18),
+    MODIFY COLUMN tav Decimal(38, 18)
+"#
+    .trim()
+    .to_string()
+}
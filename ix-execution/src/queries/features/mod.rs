@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
+use chrono::{DateTime, TimeZone, Utc};
 use clickhouse::Row;
+use rust_decimal::Decimal;
 
-pub mod write_tables;
 pub mod create_tables;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Row)]
@@ -9,12 +10,13 @@ pub struct FeatureData {
     feature_ts: u64,
     symbol: String,
     exchange: String,
-    spread: String,
-    midprice: String,
-    w_midprice: String,
-    vwap: String,
-    imb: String,
-    tav: String,
+    depth: u16,
+    spread: Decimal,
+    midprice: Decimal,
+    w_midprice: Decimal,
+    vwap: Decimal,
+    imb: Decimal,
+    tav: Decimal,
 }
 
 impl FeatureData {
@@ -30,12 +32,13 @@ pub struct FeatureDataBuilder {
     feature_ts: Option<u64>,
     symbol: Option<String>,
     exchange: Option<String>,
-    spread: Option<String>,
-    midprice: Option<String>,
-    w_midprice: Option<String>,
-    vwap: Option<String>,
-    imb: Option<String>,
-    tav: Option<String>,
+    depth: Option<u16>,
+    spread: Option<Decimal>,
+    midprice: Option<Decimal>,
+    w_midprice: Option<Decimal>,
+    vwap: Option<Decimal>,
+    imb: Option<Decimal>,
+    tav: Option<Decimal>,
 }
 
 impl Default for FeatureDataBuilder {
@@ -52,6 +55,7 @@ impl FeatureDataBuilder {
             feature_ts: None,
             symbol: None,
             exchange: None,
+            depth: None,
             spread: None,
             midprice: None,
             w_midprice: None,
@@ -77,33 +81,38 @@ impl FeatureDataBuilder {
         self
     }
 
-    pub fn spread(mut self, spread: String) -> Self {
+    pub fn depth(mut self, depth: u16) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+
+    pub fn spread(mut self, spread: Decimal) -> Self {
         self.spread = Some(spread);
         self
     }
 
-    pub fn midprice(mut self, midprice: String) -> Self {
+    pub fn midprice(mut self, midprice: Decimal) -> Self {
         self.midprice = Some(midprice);
         self
     }
 
-    pub fn w_midprice(mut self, w_midprice: String) -> Self {
+    pub fn w_midprice(mut self, w_midprice: Decimal) -> Self {
         self.w_midprice = Some(w_midprice);
         self
     }
 
-    pub fn vwap(mut self, vwap: String) -> Self {
+    pub fn vwap(mut self, vwap: Decimal) -> Self {
         self.vwap = Some(vwap);
         self
     }
 
 
-    pub fn imb(mut self, imb: String) -> Self {
+    pub fn imb(mut self, imb: Decimal) -> Self {
         self.imb =Some(imb);
         self
     }
 
-    pub fn tav(mut self, tav: String) -> Self {
+    pub fn tav(mut self, tav: Decimal) -> Self {
         self.tav = Some(tav);
         self
     }
@@ -113,6 +122,7 @@ impl FeatureDataBuilder {
         let feature_ts = self.feature_ts.ok_or("Missing feature_ts")?;
         let symbol = self.symbol.ok_or("Missing symbol")?;
         let exchange = self.exchange.ok_or("Missing exchange")?;
+        let depth = self.depth.ok_or("Missing depth")?;
         let spread = self.spread.ok_or("Missing spread")?;
         let midprice = self.midprice.ok_or("Mising midprice")?;
         let w_midprice = self.w_midprice.ok_or("Missing w_midprice")?;
@@ -124,6 +134,7 @@ impl FeatureDataBuilder {
             feature_ts,
             symbol,
             exchange,
+            depth,
             spread, 
             midprice,
             w_midprice,
@@ -136,3 +147,45 @@ impl FeatureDataBuilder {
 
 }
 
+/// Row of the `features` table
+#[derive(Debug, Clone, Serialize, Deserialize, Row)]
+pub struct FeatureRow {
+    #[serde(with = "clickhouse::serde::chrono::datetime64::micros")]
+    pub timestamp: DateTime<Utc>,
+    pub symbol: String,
+    pub exchange: String,
+    /// Levels per side the features are computed over
+    pub depth: u16,
+    #[serde(with = "crate::queries::decimal")]
+    pub spread: Decimal,
+    #[serde(with = "crate::queries::decimal")]
+    pub midprice: Decimal,
+    #[serde(with = "crate::queries::decimal")]
+    pub w_midprice: Decimal,
+    #[serde(with = "crate::queries::decimal")]
+    pub vwap: Decimal,
+    #[serde(with = "crate::queries::decimal")]
+    pub imb: Decimal,
+    #[serde(with = "crate::queries::decimal")]
+    pub tav: Decimal,
+}
+
+impl From<&FeatureData> for FeatureRow {
+    fn from(features: &FeatureData) -> Self {
+        Self {
+            timestamp: Utc
+                .timestamp_millis_opt(features.feature_ts as i64)
+                .single()
+                .unwrap_or_default(),
+            symbol: super::format_symbol_for_clickhouse(&features.symbol),
+            exchange: features.exchange.clone(),
+            depth: features.depth,
+            spread: features.spread,
+            midprice: features.midprice,
+            w_midprice: features.w_midprice,
+            vwap: features.vwap,
+            imb: features.imb,
+            tav: features.tav,
+        }
+    }
+}
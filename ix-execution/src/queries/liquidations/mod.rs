@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
 use clickhouse::Row;
+use ix_cex::models::trades::Liquidation;
 
 pub mod create_tables;
-pub mod write_tables;
 pub mod read_tables;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Row)]
@@ -15,3 +16,40 @@ pub struct LiquidationNew {
     pub exchange: String,
 }
 
+impl From<&Liquidation> for LiquidationNew {
+    fn from(liquidation: &Liquidation) -> Self {
+        Self {
+            ts: liquidation.timestamp.timestamp_millis() as u64,
+            symbol: liquidation.symbol.clone(),
+            side: liquidation.side.to_string(),
+            amount: liquidation.amount.to_string(),
+            price: liquidation.price.to_string(),
+            exchange: liquidation.exchange.clone(),
+        }
+    }
+}
+
+/// Row of the `liquidations` table
+#[derive(Debug, Clone, Serialize, Deserialize, Row)]
+pub struct LiquidationRow {
+    #[serde(with = "clickhouse::serde::chrono::datetime64::micros")]
+    pub timestamp: DateTime<Utc>,
+    pub symbol: String,
+    pub exchange: String,
+    pub side: String,
+    pub amount: String,
+    pub price: String,
+}
+
+impl From<&Liquidation> for LiquidationRow {
+    fn from(liquidation: &Liquidation) -> Self {
+        Self {
+            timestamp: liquidation.timestamp,
+            symbol: super::format_symbol_for_clickhouse(&liquidation.symbol),
+            exchange: liquidation.exchange.clone(),
+            side: liquidation.side.to_string(),
+            amount: liquidation.amount.to_string(),
+            price: liquidation.price.to_string(),
+        }
+    }
+}
@@ -1,21 +1,23 @@
-pub async fn read_liquidations_table(
-    p_exchange: String,
-    p_symbol: String,
-) -> Result<String, Box<dyn std::error::Error>> {
-    let query = format!(
-        r#"SELECT 
-            timestamp, 
-            symbol, 
-            exchange, 
-            side, 
-            amount, 
-            price,
-        FROM liquidations 
-        WHERE exchange = '{}' AND symbol = '{}' 
-        LIMIT 10
-        "#,
-        p_exchange, p_symbol
-    );
-    Ok(query)
+use super::{LiquidationNew, LiquidationRow};
+use crate::queries::select::{ReadRow, SelectQuery};
+
+impl ReadRow for LiquidationNew {
+    const TABLE: &'static str = "liquidations";
+    const COLUMNS: &'static str = "toUnixTimestamp64Milli(timestamp) AS ts, \
+         symbol, side, amount, price, exchange";
+}
+
+impl ReadRow for LiquidationRow {
+    const TABLE: &'static str = "liquidations";
+    const COLUMNS: &'static str = "timestamp, symbol, exchange, side, amount, price";
 }
 
+/// Query of the `liquidations` table, timestamps in milliseconds
+pub fn read_liquidations() -> SelectQuery<LiquidationNew> {
+    SelectQuery::new()
+}
+
+/// Query of the `liquidations` table as stored
+pub fn read_liquidation_rows() -> SelectQuery<LiquidationRow> {
+    SelectQuery::new()
+}
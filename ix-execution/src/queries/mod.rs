@@ -3,3 +3,15 @@ pub mod orderbooks;
 pub mod signals;
 pub mod trades;
 pub mod features;
+pub mod bars;
+pub mod orders;
+pub mod rejections;
+pub mod risk_state;
+
+pub mod decimal;
+pub mod select;
+
+/// Normalize an exchange symbol for storage, e.g. `SOL-USDT` to `SOLUSDT`
+pub(crate) fn format_symbol_for_clickhouse(symbol: &str) -> String {
+    symbol.replace(['-', '/'], "").to_uppercase()
+}
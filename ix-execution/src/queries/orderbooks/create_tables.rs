@@ -15,3 +15,23 @@ SETTINGS index_granularity = 8192
     .trim()
     .to_string()
 }
+
+// Create the orderbook_levels table DDL, one row per price level of a snapshot
+pub fn create_orderbook_levels_table_ddl() -> String {
+    r#"
+CREATE TABLE IF NOT EXISTS orderbook_levels (
+    timestamp DateTime64(6, 'UTC'),
+    exchange LowCardinality(String),
+    symbol LowCardinality(String),
+    side Enum8('bid' = 0, 'ask' = 1),
+    price Decimal(32, 8),
+    amount Decimal(32, 8),
+    level UInt16
+) ENGINE = MergeTree()
+PARTITION BY toYYYYMM(timestamp)
+ORDER BY (symbol, exchange, side, price, timestamp)
+SETTINGS index_granularity = 8192
+"#
+    .trim()
+    .to_string()
+}
@@ -1,12 +1,18 @@
 use serde::{Deserialize, Serialize};
+use chrono::{DateTime, NaiveDateTime, Utc};
 use clickhouse::Row;
+use ix_cex::models::orderbook::{
+    Orderbook as CexOrderbook, OrderbookInputError, PriceLevel,
+};
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
 use std::collections::hash_map::DefaultHasher;
+use std::str::FromStr;
 use std::hash::{Hash, Hasher};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 pub mod create_tables;
 pub mod read_tables;
-pub mod write_tables;
 
 // Import the atelier-base types
 use atelier_base::{
@@ -15,6 +21,41 @@ use atelier_base::{
     orders::{Order, OrderSide, OrderType},
 };
 
+/// Row of the `orderbooks` table, levels are `(price, quantity)` decimal strings
+#[derive(Debug, Clone, Serialize, Deserialize, Row)]
+pub struct OrderbookRow {
+    #[serde(with = "clickhouse::serde::chrono::datetime64::micros")]
+    pub timestamp: DateTime<Utc>,
+    pub symbol: String,
+    pub exchange: String,
+    pub bids: Vec<(String, String)>,
+    pub asks: Vec<(String, String)>,
+}
+
+impl From<&CexOrderbook> for OrderbookRow {
+    fn from(orderbook: &CexOrderbook) -> Self {
+        let levels = |levels: &[PriceLevel]| {
+            levels
+                .iter()
+                .map(|level| {
+                    (
+                        level.price.normalize().to_string(),
+                        level.quantity.normalize().to_string(),
+                    )
+                })
+                .collect()
+        };
+
+        Self {
+            timestamp: orderbook.timestamp,
+            symbol: super::format_symbol_for_clickhouse(&orderbook.symbol),
+            exchange: orderbook.exchange.clone(),
+            bids: levels(&orderbook.bids),
+            asks: levels(&orderbook.asks),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Row)]
 pub struct OrderbookCH {
     pub timestamp: String,
@@ -55,6 +96,11 @@ impl OrderbookCH {
             return Ok(parsed.timestamp_micros() as u64);
         }
         
+        // Format 1b: ClickHouse DateTime64 string (e.g., "2023-12-01 10:30:45.123456")
+        if let Ok(parsed) = parse_clickhouse_timestamp(&self.timestamp) {
+            return Ok(parsed.timestamp_micros() as u64);
+        }
+        
         // Format 2: Unix timestamp as string (seconds)
         if let Ok(secs) = self.timestamp.parse::<f64>() {
             return Ok((secs * 1_000_000.0) as u64);
@@ -134,3 +180,174 @@ impl OrderbookCH {
     }
 }
 
+
+/// `toString` format of the `DateTime64(6, 'UTC')` columns
+const CLICKHOUSE_TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.6f";
+
+fn parse_clickhouse_timestamp(value: &str) -> Result<DateTime<Utc>, chrono::ParseError> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f").map(|ts| ts.and_utc())
+}
+
+impl From<&CexOrderbook> for OrderbookCH {
+    fn from(orderbook: &CexOrderbook) -> Self {
+        let row = OrderbookRow::from(orderbook);
+
+        Self {
+            timestamp: row
+                .timestamp
+                .format(CLICKHOUSE_TIMESTAMP_FORMAT)
+                .to_string(),
+            symbol: row.symbol,
+            exchange: row.exchange,
+            bids: row.bids,
+            asks: row.asks,
+        }
+    }
+}
+
+impl TryFrom<&OrderbookCH> for CexOrderbook {
+    type Error = OrderbookInputError;
+
+    fn try_from(orderbook: &OrderbookCH) -> Result<Self, Self::Error> {
+        let timestamp = match DateTime::parse_from_rfc3339(&orderbook.timestamp) {
+            Ok(timestamp) => timestamp.with_timezone(&Utc),
+            Err(_) => parse_clickhouse_timestamp(&orderbook.timestamp)?,
+        };
+
+        let levels = |levels: &[(String, String)]| {
+            levels
+                .iter()
+                .map(|(price, quantity)| {
+                    Ok(PriceLevel::new(
+                        Decimal::from_str(price)?,
+                        Decimal::from_str(quantity)?,
+                    ))
+                })
+                .collect::<Result<Vec<_>, rust_decimal::Error>>()
+        };
+
+        Ok(CexOrderbook::new(
+            orderbook.symbol.clone(),
+            orderbook.exchange.clone(),
+            timestamp,
+            levels(&orderbook.bids)?,
+            levels(&orderbook.asks)?,
+            None,
+            None,
+        ))
+    }
+}
+
+/// Side of an `orderbook_levels` row, stored as `Enum8('bid' = 0, 'ask' = 1)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LevelSide {
+    Bid,
+    Ask,
+}
+
+impl Serialize for LevelSide {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i8(match self {
+            LevelSide::Bid => 0,
+            LevelSide::Ask => 1,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for LevelSide {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match i8::deserialize(deserializer)? {
+            0 => Ok(LevelSide::Bid),
+            1 => Ok(LevelSide::Ask),
+            other => Err(serde::de::Error::custom(format!(
+                "unknown orderbook side {other}"
+            ))),
+        }
+    }
+}
+
+/// Row of the `orderbook_levels` table, one per price level of a snapshot.
+/// `level` counts from the top of the book, prices and amounts are kept to
+/// 8 decimals.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Row)]
+pub struct OrderbookLevelRow {
+    #[serde(with = "clickhouse::serde::chrono::datetime64::micros")]
+    pub timestamp: DateTime<Utc>,
+    pub exchange: String,
+    pub symbol: String,
+    pub side: LevelSide,
+    #[serde(with = "crate::queries::decimal::scale8")]
+    pub price: Decimal,
+    #[serde(with = "crate::queries::decimal::scale8")]
+    pub amount: Decimal,
+    pub level: u16,
+}
+
+impl OrderbookLevelRow {
+    /// Explode a snapshot into its level rows, bids first
+    pub fn from_orderbook(orderbook: &CexOrderbook) -> Vec<Self> {
+        let symbol = super::format_symbol_for_clickhouse(&orderbook.symbol);
+
+        let rows = |side: LevelSide, levels: &[PriceLevel]| {
+            levels
+                .iter()
+                .zip(0u16..)
+                .map(|(level, index)| Self {
+                    timestamp: orderbook.timestamp,
+                    exchange: orderbook.exchange.clone(),
+                    symbol: symbol.clone(),
+                    side,
+                    price: level.price,
+                    amount: level.quantity,
+                    level: index,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut levels = rows(LevelSide::Bid, &orderbook.bids);
+        levels.extend(rows(LevelSide::Ask, &orderbook.asks));
+        levels
+    }
+}
+
+/// Rebuild the snapshots of `orderbook_levels` rows, in timestamp order.
+/// Rows of one snapshot may come in any order.
+pub fn orderbooks_from_levels<I>(rows: I) -> Vec<CexOrderbook>
+where
+    I: IntoIterator<Item = OrderbookLevelRow>,
+{
+    type Levels = (Vec<(u16, PriceLevel)>, Vec<(u16, PriceLevel)>);
+    let mut snapshots: BTreeMap<(DateTime<Utc>, String, String), Levels> =
+        BTreeMap::new();
+
+    for row in rows {
+        let (bids, asks) = snapshots
+            .entry((row.timestamp, row.exchange, row.symbol))
+            .or_default();
+        let level = (row.level, PriceLevel::new(row.price, row.amount));
+        match row.side {
+            LevelSide::Bid => bids.push(level),
+            LevelSide::Ask => asks.push(level),
+        }
+    }
+
+    let sorted = |mut levels: Vec<(u16, PriceLevel)>| {
+        levels.sort_by_key(|(index, _)| *index);
+        levels.into_iter().map(|(_, level)| level).collect()
+    };
+
+    snapshots
+        .into_iter()
+        .map(|((timestamp, exchange, symbol), (bids, asks))| {
+            CexOrderbook::new(
+                symbol,
+                exchange,
+                timestamp,
+                sorted(bids),
+                sorted(asks),
+                None,
+                None,
+            )
+        })
+        .collect()
+}
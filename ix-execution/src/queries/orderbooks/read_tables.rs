@@ -1,19 +1,71 @@
+use ix_cex::models::orderbook::Orderbook as CexOrderbook;
 
-pub async fn read_orderbooks_table(
-    p_exchange: String,
-    p_symbol: String,
-) -> Result<String, Box<dyn std::error::Error>> {
-    let query = format!(
-        r#"SELECT 
-            CAST(timestamp AS String) as timestamp,
-            symbol,
-            exchange,
-            bids,
-            asks
-        FROM orderbooks 
-        WHERE exchange = '{}' AND symbol = '{}'
-        "#,
-        p_exchange, p_symbol
-    );
-    Ok(query)
+use super::{OrderbookCH, OrderbookLevelRow, orderbooks_from_levels};
+use crate::queries::select::{ReadRow, SelectQuery};
+use crate::{ClickHouseClient, DatabaseError, DatabaseResult};
+
+impl ReadRow for OrderbookCH {
+    const TABLE: &'static str = "orderbooks";
+    const COLUMNS: &'static str =
+        "toString(timestamp) AS timestamp_str, symbol, exchange, bids, asks";
+}
+
+impl ReadRow for OrderbookLevelRow {
+    const TABLE: &'static str = "orderbook_levels";
+    const COLUMNS: &'static str =
+        "timestamp, exchange, symbol, side, price, amount, level";
+}
+
+/// Query of the `orderbooks` table
+pub fn read_orderbooks() -> SelectQuery<OrderbookCH> {
+    SelectQuery::new()
+}
+
+/// Query of the `orderbook_levels` table, limit and offset count levels
+pub fn read_orderbook_levels() -> SelectQuery<OrderbookLevelRow> {
+    SelectQuery::new()
+}
+
+impl SelectQuery<OrderbookCH> {
+    /// Run the query and parse the snapshots
+    pub async fn fetch_orderbooks(
+        &self,
+        client: &ClickHouseClient,
+    ) -> DatabaseResult<Vec<CexOrderbook>> {
+        self.fetch_all(client)
+            .await?
+            .iter()
+            .map(|orderbook| {
+                CexOrderbook::try_from(orderbook).map_err(|e| {
+                    DatabaseError::OperationFailed(format!(
+                        "Invalid orderbook row {}/{} at {}: {e}",
+                        orderbook.exchange, orderbook.symbol, orderbook.timestamp
+                    ))
+                })
+            })
+            .collect()
+    }
+}
+
+impl SelectQuery<OrderbookLevelRow> {
+    /// Run the query and rebuild the snapshots of the levels
+    pub async fn fetch_orderbooks(
+        &self,
+        client: &ClickHouseClient,
+    ) -> DatabaseResult<Vec<CexOrderbook>> {
+        Ok(orderbooks_from_levels(self.fetch_all(client).await?))
+    }
+
+    /// Run the query and rebuild the snapshots in the `orderbooks` layout
+    pub async fn fetch_orderbooks_ch(
+        &self,
+        client: &ClickHouseClient,
+    ) -> DatabaseResult<Vec<OrderbookCH>> {
+        Ok(self
+            .fetch_orderbooks(client)
+            .await?
+            .iter()
+            .map(OrderbookCH::from)
+            .collect())
+    }
 }
@@ -0,0 +1,36 @@
+// Create the orders table DDL, one row per state of an order
+pub fn create_orders_table_ddl() -> String {
+    r#"
+CREATE TABLE IF NOT EXISTS orders (
+    timestamp DateTime64(6, 'UTC'),
+    order_link_id String,
+    order_id String,
+    exchange LowCardinality(String),
+    symbol LowCardinality(String),
+    side LowCardinality(String),
+    order_type LowCardinality(String),
+    qty Decimal(38, 18),
+    status LowCardinality(String),
+    filled_qty Decimal(38, 18),
+    avg_price Decimal(38, 18),
+    fee Decimal(38, 18),
+    reject_reason String,
+    signal_timestamp DateTime64(6, 'UTC')
+) ENGINE = ReplacingMergeTree(timestamp)
+PARTITION BY toYYYYMM(signal_timestamp)
+ORDER BY (exchange, order_link_id)
+SETTINGS index_granularity = 8192
+"#
+    .trim()
+    .to_string()
+}
+
+// Record the unit of `qty`, empty on the rows written before
+pub fn alter_orders_qty_unit_ddl() -> String {
+    r#"
+ALTER TABLE orders
+    ADD COLUMN IF NOT EXISTS qty_unit LowCardinality(String) AFTER qty
+"#
+    .trim()
+    .to_string()
+}
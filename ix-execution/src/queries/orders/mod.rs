@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, Utc};
+use clickhouse::Row;
+use ix_cex::models::trading::{OrderQty, OrderState};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+pub mod create_tables;
+pub mod read_tables;
+
+/// Status of an order sent to the exchange, before its first update
+pub const STATUS_SUBMITTED: &str = "Submitted";
+
+/// Status of an order that was not accepted
+pub const STATUS_REJECTED: &str = "Rejected";
+
+/// Status of an order whose placement went unanswered and that was not found
+/// on the exchange yet, it may still land
+pub const STATUS_UNKNOWN: &str = "Unknown";
+
+/// Seconds an order of unknown status is looked up by its `order_link_id`
+/// before it is taken as rejected
+pub const UNKNOWN_GRACE_SECONDS: i64 = 60;
+
+/// `qty_unit` of the orders sized in the base coin
+pub const QTY_UNIT_BASE: &str = "Base";
+
+/// `qty_unit` of the orders sized in the quote coin, spot market buys
+pub const QTY_UNIT_QUOTE: &str = "Quote";
+
+/// Statuses after which an order no longer changes, the last two are the
+/// Bybit ones of the rows written before the statuses were normalized
+pub const TERMINAL_STATUSES: [&str; 5] = [
+    "Filled",
+    "Cancelled",
+    "Rejected",
+    "PartiallyFilledCanceled",
+    "Deactivated",
+];
+
+/// Row of the `orders` table, one per state of an order
+///
+/// Orders are identified by their `order_link_id`, the latest state has the
+/// greatest `timestamp`. `qty` is in the unit the order was sent in, given by
+/// `qty_unit`: the quote coin for spot market buys, the base coin otherwise.
+/// `filled_qty` is always in the base coin.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Row)]
+pub struct OrderRow {
+    #[serde(with = "clickhouse::serde::chrono::datetime64::micros")]
+    pub timestamp: DateTime<Utc>,
+    pub order_link_id: String,
+    pub order_id: String,
+    pub exchange: String,
+    pub symbol: String,
+    pub side: String,
+    pub order_type: String,
+    #[serde(with = "crate::queries::decimal")]
+    pub qty: Decimal,
+    pub qty_unit: String,
+    pub status: String,
+    #[serde(with = "crate::queries::decimal")]
+    pub filled_qty: Decimal,
+    #[serde(with = "crate::queries::decimal")]
+    pub avg_price: Decimal,
+    #[serde(with = "crate::queries::decimal")]
+    pub fee: Decimal,
+    pub reject_reason: String,
+    #[serde(with = "clickhouse::serde::chrono::datetime64::micros")]
+    pub signal_timestamp: DateTime<Utc>,
+}
+
+impl OrderRow {
+    /// The order no longer changes
+    pub fn is_terminal(&self) -> bool {
+        TERMINAL_STATUSES.contains(&self.status.as_str())
+    }
+
+    /// Set the quantity and its unit
+    pub fn set_qty(&mut self, qty: OrderQty) {
+        self.qty = qty.value();
+        self.qty_unit = qty_unit(&qty).to_string();
+    }
+
+    /// Base quantity still to fill, quote quantities converted at `price`
+    pub fn unfilled_qty(&self, price: Decimal) -> Decimal {
+        let unfilled = match self.qty_unit.as_str() {
+            QTY_UNIT_QUOTE if price.is_zero() => Decimal::ZERO,
+            QTY_UNIT_QUOTE => (self.qty - self.filled_qty * self.avg_price) / price,
+            _ => self.qty - self.filled_qty,
+        };
+        unfilled.max(Decimal::ZERO)
+    }
+
+    /// The placement went unanswered and the order was not found since
+    pub fn is_unknown(&self) -> bool {
+        self.status == STATUS_UNKNOWN
+    }
+
+    /// Rejected state of an order of unknown status still not found on the
+    /// exchange at `now`, once [`UNKNOWN_GRACE_SECONDS`] have passed since its
+    /// placement
+    pub fn not_found(&self, now: DateTime<Utc>) -> Option<Self> {
+        let grace = Duration::seconds(UNKNOWN_GRACE_SECONDS);
+        if !self.is_unknown() || now - self.timestamp < grace {
+            return None;
+        }
+
+        Some(Self {
+            timestamp: now,
+            status: STATUS_REJECTED.to_string(),
+            reject_reason: format!(
+                "Not found on the exchange {}s after an unanswered placement",
+                UNKNOWN_GRACE_SECONDS
+            ),
+            ..self.clone()
+        })
+    }
+
+    /// State of the order reported by the exchange, at the current time
+    pub fn updated(&self, order: &OrderState) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            order_id: order.order_id.clone(),
+            status: order.status.to_string(),
+            filled_qty: order.filled_qty,
+            avg_price: order.avg_price,
+            fee: order.fee,
+            reject_reason: order.reject_reason.clone(),
+            ..self.clone()
+        }
+    }
+
+    /// Same status and fill as `other`
+    pub fn same_state(&self, other: &OrderRow) -> bool {
+        self.order_id == other.order_id
+            && self.status == other.status
+            && self.filled_qty == other.filled_qty
+            && self.fee == other.fee
+    }
+}
+
+/// `qty_unit` of a quantity
+pub fn qty_unit(qty: &OrderQty) -> &'static str {
+    match qty {
+        OrderQty::Base(_) => QTY_UNIT_BASE,
+        OrderQty::Quote(_) => QTY_UNIT_QUOTE,
+    }
+}
+
+/// Latest state of each order, by `order_link_id`
+pub fn latest_orders(rows: Vec<OrderRow>) -> Vec<OrderRow> {
+    let mut latest: BTreeMap<String, OrderRow> = BTreeMap::new();
+    for row in rows {
+        match latest.get(&row.order_link_id) {
+            Some(current) if current.timestamp > row.timestamp => {}
+            _ => {
+                latest.insert(row.order_link_id.clone(), row);
+            }
+        }
+    }
+    latest.into_values().collect()
+}
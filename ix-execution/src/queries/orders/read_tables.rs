@@ -0,0 +1,14 @@
+use super::OrderRow;
+use crate::queries::select::{ReadRow, SelectQuery};
+
+impl ReadRow for OrderRow {
+    const TABLE: &'static str = "orders";
+    const COLUMNS: &'static str = "timestamp, order_link_id, order_id, exchange, symbol, \
+         side, order_type, qty, qty_unit, status, filled_qty, avg_price, fee, reject_reason, \
+         signal_timestamp";
+}
+
+/// Query of the `orders` table, every recorded state
+pub fn read_orders() -> SelectQuery<OrderRow> {
+    SelectQuery::new()
+}
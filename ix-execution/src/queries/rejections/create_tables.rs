@@ -0,0 +1,23 @@
+// Create the risk_rejections table DDL, the audit of the orders refused by the
+// risk manager
+pub fn create_risk_rejections_table_ddl() -> String {
+    r#"
+CREATE TABLE IF NOT EXISTS risk_rejections (
+    timestamp DateTime64(6, 'UTC'),
+    account LowCardinality(String),
+    exchange LowCardinality(String),
+    symbol LowCardinality(String),
+    side LowCardinality(String),
+    order_link_id String,
+    rule LowCardinality(String),
+    reason String,
+    notional Decimal(38, 18),
+    kill_switch Bool
+) ENGINE = MergeTree()
+PARTITION BY toYYYYMM(timestamp)
+ORDER BY (account, timestamp)
+SETTINGS index_granularity = 8192
+"#
+    .trim()
+    .to_string()
+}
@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use clickhouse::Row;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+pub mod create_tables;
+pub mod read_tables;
+
+/// Row of the `risk_rejections` table, one per order refused before it was sent
+///
+/// `rule` is the limit that was breached and `notional` the value of the order
+/// in the quote coin. `kill_switch` is set when the kill switch was engaged.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Row)]
+pub struct RiskRejectionRow {
+    #[serde(with = "clickhouse::serde::chrono::datetime64::micros")]
+    pub timestamp: DateTime<Utc>,
+    pub account: String,
+    pub exchange: String,
+    pub symbol: String,
+    pub side: String,
+    pub order_link_id: String,
+    pub rule: String,
+    pub reason: String,
+    #[serde(with = "crate::queries::decimal")]
+    pub notional: Decimal,
+    pub kill_switch: bool,
+}
@@ -0,0 +1,13 @@
+use super::RiskRejectionRow;
+use crate::queries::select::{ReadRow, SelectQuery};
+
+impl ReadRow for RiskRejectionRow {
+    const TABLE: &'static str = "risk_rejections";
+    const COLUMNS: &'static str = "timestamp, account, exchange, symbol, side, \
+         order_link_id, rule, reason, notional, kill_switch";
+}
+
+/// Query of the `risk_rejections` table
+pub fn read_risk_rejections() -> SelectQuery<RiskRejectionRow> {
+    SelectQuery::new()
+}
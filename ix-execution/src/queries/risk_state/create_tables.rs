@@ -0,0 +1,18 @@
+// Create the risk_state table DDL, the day start equity and kill switch of
+// each account
+pub fn create_risk_state_table_ddl() -> String {
+    r#"
+CREATE TABLE IF NOT EXISTS risk_state (
+    timestamp DateTime64(6, 'UTC'),
+    account LowCardinality(String),
+    exchange LowCardinality(String),
+    day Date,
+    day_start_equity Decimal(38, 18),
+    kill_switch Bool
+) ENGINE = ReplacingMergeTree(timestamp)
+ORDER BY (account, exchange)
+SETTINGS index_granularity = 8192
+"#
+    .trim()
+    .to_string()
+}
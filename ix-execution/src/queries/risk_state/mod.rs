@@ -0,0 +1,26 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use clickhouse::Row;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+pub mod create_tables;
+pub mod read_tables;
+
+/// Row of the `risk_state` table, the state of the risk manager of an account
+/// that outlives a restart
+///
+/// `day_start_equity` is the first equity read on `day`, the reference of the
+/// daily loss limit. The latest row of an account, by `timestamp`, is the
+/// current state.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Row)]
+pub struct RiskStateRow {
+    #[serde(with = "clickhouse::serde::chrono::datetime64::micros")]
+    pub timestamp: DateTime<Utc>,
+    pub account: String,
+    pub exchange: String,
+    #[serde(with = "clickhouse::serde::chrono::date")]
+    pub day: NaiveDate,
+    #[serde(with = "crate::queries::decimal")]
+    pub day_start_equity: Decimal,
+    pub kill_switch: bool,
+}
@@ -0,0 +1,13 @@
+use super::RiskStateRow;
+use crate::queries::select::{ReadRow, SelectQuery};
+
+impl ReadRow for RiskStateRow {
+    const TABLE: &'static str = "risk_state";
+    const COLUMNS: &'static str =
+        "timestamp, account, exchange, day, day_start_equity, kill_switch";
+}
+
+/// Query of the `risk_state` table, every recorded state
+pub fn read_risk_state() -> SelectQuery<RiskStateRow> {
+    SelectQuery::new()
+}
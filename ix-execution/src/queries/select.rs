@@ -0,0 +1,225 @@
+//! Parameterized read queries
+//!
+//! A [`SelectQuery`] builds a `SELECT` over one table and fetches typed rows.
+//! Symbols, exchanges, the time window and the pagination are sent as `?` bind
+//! parameters, caller values are never spliced into the SQL text.
+
+use std::marker::PhantomData;
+
+use chrono::{DateTime, Utc};
+use clickhouse::Row;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::{ClickHouseClient, DatabaseResult};
+
+/// Row type that can be read with a [`SelectQuery`]
+pub trait ReadRow: Row + DeserializeOwned {
+    /// Table the rows are read from
+    const TABLE: &'static str;
+    /// Select list, one expression per field in the order of the struct
+    const COLUMNS: &'static str;
+}
+
+/// Order of the rows by timestamp
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    fn as_sql(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        }
+    }
+}
+
+/// Value of a `?` placeholder
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+enum Param {
+    List(Vec<String>),
+    Micros(i64),
+    Count(u64),
+}
+
+/// Typed `SELECT` with symbol, exchange and time range filters
+#[derive(Debug, Clone)]
+pub struct SelectQuery<T> {
+    symbols: Vec<String>,
+    exchanges: Vec<String>,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    order: SortOrder,
+    limit: Option<u64>,
+    offset: Option<u64>,
+    row: PhantomData<T>,
+}
+
+impl<T: ReadRow> Default for SelectQuery<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ReadRow> SelectQuery<T> {
+    /// Query of every row of the table, oldest first
+    pub fn new() -> Self {
+        Self {
+            symbols: Vec::new(),
+            exchanges: Vec::new(),
+            from: None,
+            to: None,
+            order: SortOrder::default(),
+            limit: None,
+            offset: None,
+            row: PhantomData,
+        }
+    }
+
+    /// Keep the rows of `symbol`, normalized as it is stored, e.g. `SOLUSDT`
+    pub fn symbol(mut self, symbol: impl AsRef<str>) -> Self {
+        self.symbols
+            .push(super::format_symbol_for_clickhouse(symbol.as_ref()));
+        self
+    }
+
+    /// Keep the rows of any of `symbols`
+    pub fn symbols<I, S>(self, symbols: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        symbols.into_iter().fold(self, |query, s| query.symbol(s))
+    }
+
+    /// Keep the rows of `exchange`
+    pub fn exchange(mut self, exchange: impl Into<String>) -> Self {
+        self.exchanges.push(exchange.into());
+        self
+    }
+
+    /// Keep the rows of any of `exchanges`
+    pub fn exchanges<I, S>(self, exchanges: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        exchanges
+            .into_iter()
+            .fold(self, |query, e| query.exchange(e))
+    }
+
+    /// Keep the rows at or after `from`
+    pub fn from(mut self, from: DateTime<Utc>) -> Self {
+        self.from = Some(from);
+        self
+    }
+
+    /// Keep the rows strictly before `to`
+    pub fn to(mut self, to: DateTime<Utc>) -> Self {
+        self.to = Some(to);
+        self
+    }
+
+    pub fn order(mut self, order: SortOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Skip the first `offset` rows
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Rows of the zero based `page` of `size` rows
+    pub fn page(self, page: u64, size: u64) -> Self {
+        self.limit(size).offset(page.saturating_mul(size))
+    }
+
+    /// SQL text with its `?` placeholders
+    pub fn sql(&self) -> String {
+        self.build().0
+    }
+
+    /// Run the query
+    pub async fn fetch_all(&self, client: &ClickHouseClient) -> DatabaseResult<Vec<T>> {
+        let (sql, params) = self.build();
+
+        let mut query = client.client().query(&sql);
+        for param in params {
+            query = query.bind(param);
+        }
+
+        Ok(query.fetch_all::<T>().await?)
+    }
+
+    fn build(&self) -> (String, Vec<Param>) {
+        // Selected expressions are aliased apart from `timestamp`, which would
+        // otherwise shadow the column the filters compare against
+        let mut conditions = Vec::new();
+        let mut params = Vec::new();
+
+        if !self.symbols.is_empty() {
+            conditions.push("symbol IN ?".to_string());
+            params.push(Param::List(self.symbols.clone()));
+        }
+        if !self.exchanges.is_empty() {
+            conditions.push("exchange IN ?".to_string());
+            params.push(Param::List(self.exchanges.clone()));
+        }
+        if let Some(from) = self.from {
+            conditions.push(format!(
+                "{}.timestamp >= fromUnixTimestamp64Micro(?)",
+                T::TABLE
+            ));
+            params.push(Param::Micros(from.timestamp_micros()));
+        }
+        if let Some(to) = self.to {
+            conditions.push(format!(
+                "{}.timestamp < fromUnixTimestamp64Micro(?)",
+                T::TABLE
+            ));
+            params.push(Param::Micros(to.timestamp_micros()));
+        }
+
+        let mut sql = format!("SELECT {} FROM {}", T::COLUMNS, T::TABLE);
+        if !conditions.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&conditions.join(" AND "));
+        }
+        sql.push_str(&format!(
+            " ORDER BY {}.timestamp {}",
+            T::TABLE,
+            self.order.as_sql()
+        ));
+
+        match (self.limit, self.offset) {
+            (Some(limit), offset) => {
+                sql.push_str(" LIMIT ?");
+                params.push(Param::Count(limit));
+                if let Some(offset) = offset {
+                    sql.push_str(" OFFSET ?");
+                    params.push(Param::Count(offset));
+                }
+            }
+            (None, Some(offset)) => {
+                sql.push_str(" OFFSET ? ROWS");
+                params.push(Param::Count(offset));
+            }
+            (None, None) => {}
+        }
+
+        (sql, params)
+    }
+}
@@ -13,3 +13,21 @@ SETTINGS index_granularity = 8192
     .trim()
     .to_string()
 }
+
+// Carry every field of a trade flow signal, with its exchange and profile
+pub fn alter_signals_trade_flow_ddl() -> String {
+    r#"
+ALTER TABLE signals
+    ADD COLUMN IF NOT EXISTS exchange LowCardinality(String) AFTER side,
+    ADD COLUMN IF NOT EXISTS strength Float64 AFTER exchange,
+    ADD COLUMN IF NOT EXISTS confidence LowCardinality(String) AFTER strength,
+    ADD COLUMN IF NOT EXISTS vpin_value Float64 AFTER confidence,
+    ADD COLUMN IF NOT EXISTS vpin_momentum Float64 AFTER vpin_value,
+    ADD COLUMN IF NOT EXISTS toxicity_level LowCardinality(String) AFTER vpin_momentum,
+    ADD COLUMN IF NOT EXISTS bars_in_calculation UInt32 AFTER toxicity_level,
+    ADD COLUMN IF NOT EXISTS error_status LowCardinality(String) AFTER bars_in_calculation,
+    ADD COLUMN IF NOT EXISTS profile LowCardinality(String) AFTER error_status
+"#
+    .trim()
+    .to_string()
+}
@@ -1,5 +1,9 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, TimeZone, Utc};
+use clickhouse::Row;
+
 pub mod create_tables;
-pub mod write_tables;
+pub mod read_tables;
 
 #[derive(Debug, Clone)]
 pub struct SignalNew {
@@ -8,3 +12,64 @@ pub struct SignalNew {
     pub side: String,
     pub exchange: String,
 }
+
+/// Row of the `signals` table
+///
+/// `side` is the signal, `BUY`, `SELL` or `NEUTRAL`, and `profile` the name of
+/// the parameter profile of the pipeline that generated it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Row)]
+pub struct SignalRow {
+    #[serde(with = "clickhouse::serde::chrono::datetime64::micros")]
+    pub timestamp: DateTime<Utc>,
+    pub symbol: String,
+    pub side: String,
+    pub exchange: String,
+    pub strength: f64,
+    pub confidence: String,
+    pub vpin_value: f64,
+    pub vpin_momentum: f64,
+    pub toxicity_level: String,
+    pub bars_in_calculation: u32,
+    pub error_status: String,
+    pub profile: String,
+}
+
+impl SignalRow {
+    /// Confident enough to be acted on, as [`TradeFlowSignal::should_alert`]
+    ///
+    /// [`TradeFlowSignal::should_alert`]: crate::producer::TradeFlowSignal::should_alert
+    pub fn should_alert(&self) -> bool {
+        should_alert(&self.error_status, &self.confidence, self.strength)
+    }
+}
+
+pub(crate) fn should_alert(error_status: &str, confidence: &str, strength: f64) -> bool {
+    error_status == "OK"
+        && match confidence {
+            "HIGH" => true,
+            "MEDIUM" => strength > 0.8,
+            _ => false,
+        }
+}
+
+impl From<&SignalNew> for SignalRow {
+    fn from(signal: &SignalNew) -> Self {
+        Self {
+            timestamp: Utc
+                .timestamp_millis_opt(signal.ts as i64)
+                .single()
+                .unwrap_or_default(),
+            symbol: super::format_symbol_for_clickhouse(&signal.symbol),
+            side: signal.side.clone(),
+            exchange: signal.exchange.clone(),
+            strength: 0.0,
+            confidence: String::new(),
+            vpin_value: 0.0,
+            vpin_momentum: 0.0,
+            toxicity_level: String::new(),
+            bars_in_calculation: 0,
+            error_status: String::new(),
+            profile: String::new(),
+        }
+    }
+}
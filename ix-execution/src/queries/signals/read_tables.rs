@@ -0,0 +1,14 @@
+use super::SignalRow;
+use crate::queries::select::{ReadRow, SelectQuery};
+
+impl ReadRow for SignalRow {
+    const TABLE: &'static str = "signals";
+    const COLUMNS: &'static str = "timestamp, symbol, side, exchange, strength, \
+         confidence, vpin_value, vpin_momentum, toxicity_level, bars_in_calculation, \
+         error_status, profile";
+}
+
+/// Query of the `signals` table
+pub fn read_signals() -> SelectQuery<SignalRow> {
+    SelectQuery::new()
+}
@@ -16,3 +16,14 @@ SETTINGS index_granularity = 8192
     .trim()
     .to_string()
 }
+
+// Store trade amounts and prices as exact decimals instead of strings
+pub fn alter_trades_decimal_ddl() -> String {
+    r#"
+ALTER TABLE publictrades
+    MODIFY COLUMN amount Decimal(38, 18),
+    MODIFY COLUMN price Decimal(38, 18)
+"#
+    .trim()
+    .to_string()
+}
@@ -1,16 +1,23 @@
 // In y
 // our ix-execution/src/queries/trades/mod.rs
 use serde::{Serialize, Deserialize};
+use chrono::{DateTime, Utc};
 use clickhouse::Row;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
 use atelier_quant::data::HasTradeFields; // Import the correct trait
+use ix_cex::models::trades::PublicTrade;
+
+use crate::DatabaseError;
 
 pub mod create_tables;
 pub mod read_tables;
-pub mod write_tables;
+pub mod stats;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Row)]
 pub struct ClickhouseTradeData {
-    pub timestamp: u64,
+    /// Milliseconds, aliased apart from the `timestamp` column the filters use
+    pub timestamp_ms: u64,
     pub symbol: String,
     pub side: String,
     pub amount: String,
@@ -33,7 +40,58 @@ impl HasTradeFields for ClickhouseTradeData {
     }
     
     fn trade_ts(&self) -> u64 {
-        self.timestamp
+        self.timestamp_ms
     }
     
 }
+
+impl From<&PublicTrade> for ClickhouseTradeData {
+    fn from(trade: &PublicTrade) -> Self {
+        Self {
+            timestamp_ms: trade.timestamp.timestamp_millis() as u64,
+            symbol: trade.symbol.clone(),
+            side: trade.side.to_string(),
+            amount: trade.amount.to_string(),
+            price: trade.price.to_string(),
+            exchange: trade.exchange.clone(),
+        }
+    }
+}
+
+/// Row of the `publictrades` table
+#[derive(Debug, Clone, Serialize, Deserialize, Row)]
+pub struct TradeRow {
+    #[serde(with = "clickhouse::serde::chrono::datetime64::micros")]
+    pub timestamp: DateTime<Utc>,
+    pub symbol: String,
+    pub side: String,
+    #[serde(with = "crate::queries::decimal")]
+    pub amount: Decimal,
+    #[serde(with = "crate::queries::decimal")]
+    pub price: Decimal,
+    pub exchange: String,
+}
+
+impl TryFrom<&PublicTrade> for TradeRow {
+    type Error = DatabaseError;
+
+    /// Fails on an amount or price that is not a finite decimal
+    fn try_from(trade: &PublicTrade) -> Result<Self, Self::Error> {
+        let decimal = |field: &str, value: f64| {
+            Decimal::from_f64(value).ok_or_else(|| {
+                DatabaseError::InvalidRow(format!(
+                    "trade {field} {value} is not a decimal"
+                ))
+            })
+        };
+
+        Ok(Self {
+            timestamp: trade.timestamp,
+            symbol: super::format_symbol_for_clickhouse(&trade.symbol),
+            side: trade.side.to_string(),
+            amount: decimal("amount", trade.amount)?,
+            price: decimal("price", trade.price)?,
+            exchange: trade.exchange.clone(),
+        })
+    }
+}
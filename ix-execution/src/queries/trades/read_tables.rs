@@ -1,21 +1,23 @@
-pub async fn q_read_trades(
-    p_exchange: String,
-    p_symbol: String,
-    p_limit: String,
-) -> Result<String, Box<dyn std::error::Error>> {
-    let query = format!(
-        r#"SELECT 
-            timestamp, 
-            symbol, 
-            side, 
-            amount, 
-            price,
-            exchange
-        FROM operations.publictrades 
-        WHERE exchange = '{}' AND symbol = '{}' 
-        ORDER BY timestamp DESC
-        LIMIT {}"#,
-        p_exchange, p_symbol, p_limit
-    );
-    Ok(query)
+use super::{ClickhouseTradeData, TradeRow};
+use crate::queries::select::{ReadRow, SelectQuery};
+
+impl ReadRow for ClickhouseTradeData {
+    const TABLE: &'static str = "publictrades";
+    const COLUMNS: &'static str = "toUnixTimestamp64Milli(timestamp) AS timestamp_ms, \
+         symbol, side, toString(amount) AS amount, toString(price) AS price, exchange";
+}
+
+impl ReadRow for TradeRow {
+    const TABLE: &'static str = "publictrades";
+    const COLUMNS: &'static str = "timestamp, symbol, side, amount, price, exchange";
+}
+
+/// Query of the `publictrades` table, timestamps in milliseconds
+pub fn read_trades() -> SelectQuery<ClickhouseTradeData> {
+    SelectQuery::new()
+}
+
+/// Query of the `publictrades` table as stored
+pub fn read_trade_rows() -> SelectQuery<TradeRow> {
+    SelectQuery::new()
 }
@@ -0,0 +1,131 @@
+//! Trading activity statistics of `publictrades`
+//!
+//! Trades are summed per minute in ClickHouse and the statistics of a window
+//! are derived from the minutes, see [`TradingStats::from_minutes`].
+
+use chrono::{DateTime, Duration, Utc};
+use clickhouse::Row;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+use crate::{ClickHouseClient, DatabaseResult};
+
+const MINUTES_PER_DAY: f64 = 1440.0;
+
+/// Trades of one minute
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Row)]
+pub struct MinuteStats {
+    #[serde(with = "clickhouse::serde::chrono::datetime")]
+    pub minute: DateTime<Utc>,
+    pub volume: f64,
+    pub trades: u64,
+    /// Price of the last trade
+    pub close: f64,
+}
+
+/// Activity of a pair over a trailing window
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradingStats {
+    /// Traded amount per day
+    pub avg_daily_volume: f64,
+    pub avg_trades_per_minute: f64,
+    /// Standard deviation of the one minute log returns, scaled to a day
+    pub recent_volatility: f64,
+    /// Trades in the window
+    pub trades: u64,
+}
+
+impl TradingStats {
+    /// Statistics of the minutes of a window ending at `end`, oldest first.
+    ///
+    /// Volume and trade rates are averaged from the first traded minute, so a
+    /// window only partly covered by stored trades is not diluted. `None` when
+    /// there are no trades.
+    pub fn from_minutes(minutes: &[MinuteStats], end: DateTime<Utc>) -> Option<Self> {
+        let first = minutes.first()?;
+        let trades: u64 = minutes.iter().map(|m| m.trades).sum();
+        if trades == 0 {
+            return None;
+        }
+
+        let span = ((end - first.minute).num_seconds() as f64 / 60.0).max(1.0);
+        let volume: f64 = minutes.iter().map(|m| m.volume).sum();
+
+        let returns: Vec<f64> = minutes
+            .windows(2)
+            .filter(|pair| pair[0].close > 0.0 && pair[1].close > 0.0)
+            .map(|pair| (pair[1].close / pair[0].close).ln())
+            .collect();
+
+        Some(Self {
+            avg_daily_volume: volume * MINUTES_PER_DAY / span,
+            avg_trades_per_minute: trades as f64 / span,
+            recent_volatility: sample_std(&returns) * MINUTES_PER_DAY.sqrt(),
+            trades,
+        })
+    }
+}
+
+fn sample_std(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    variance.sqrt()
+}
+
+// Volume, trade count and closing price per minute of one (exchange, symbol)
+pub fn q_read_minute_stats() -> String {
+    r#"SELECT
+            toStartOfMinute(timestamp) AS minute,
+            toFloat64(sum(amount)) AS volume,
+            count() AS trades,
+            toFloat64(argMax(price, timestamp)) AS close
+        FROM publictrades
+        WHERE exchange = ? AND symbol = ?
+            AND timestamp >= fromUnixTimestamp64Micro(?)
+            AND timestamp < fromUnixTimestamp64Micro(?)
+        GROUP BY minute
+        ORDER BY minute"#
+        .to_string()
+}
+
+impl ClickHouseClient {
+    /// Trades per minute of one pair within `range`, oldest first
+    pub async fn read_minute_stats(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        range: Range<DateTime<Utc>>,
+    ) -> DatabaseResult<Vec<MinuteStats>> {
+        let minutes = self
+            .client()
+            .query(&q_read_minute_stats())
+            .bind(exchange)
+            .bind(crate::queries::format_symbol_for_clickhouse(symbol))
+            .bind(range.start.timestamp_micros())
+            .bind(range.end.timestamp_micros())
+            .fetch_all::<MinuteStats>()
+            .await?;
+
+        Ok(minutes)
+    }
+
+    /// Statistics of the trailing `window` of one pair, `None` without trades
+    pub async fn trading_stats(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        window: Duration,
+    ) -> DatabaseResult<Option<TradingStats>> {
+        let end = Utc::now();
+        let minutes = self
+            .read_minute_stats(exchange, symbol, end - window..end)
+            .await?;
+
+        Ok(TradingStats::from_minutes(&minutes, end))
+    }
+}
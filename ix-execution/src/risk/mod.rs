@@ -0,0 +1,475 @@
+//! Pre-trade risk limits
+//!
+//! The [`RiskManager`] checks every order of the execution engine against the
+//! [`RiskLimits`] of its account before it is sent:
+//!
+//! - `max_order_notional`, value of a single order in the quote coin
+//! - `max_position`, net base coin quantity held per symbol, with overrides in
+//!   `max_positions`. It starts from the positions set at startup and follows
+//!   the fills. The unfilled quantity of the open orders counts towards it too,
+//!   so orders sent before their fills are known can't exceed it. An order
+//!   that brings a position beyond the limit closer to zero is let through.
+//! - `max_daily_loss`, drop of the equity since its first read of the UTC day
+//! - `max_orders_per_minute`, orders sent in the trailing minute
+//!
+//! Breaching one of the limits listed in `kill_switch_rules`, the daily loss
+//! limit by default, engages the kill switch: the open orders of the account
+//! are cancelled and every later order is refused until the switch is reset.
+//! The kill switch and the equity the daily loss is measured from are kept in
+//! the `risk_state` table, so a restart does not clear them. Limits are read
+//! from a TOML file with one table per account.
+//!
+//! ```toml
+//! [accounts.main]
+//! max_position = 50.0
+//! max_order_notional = 500.0
+//! max_daily_loss = 250.0
+//! max_orders_per_minute = 6
+//! kill_switch_rules = ["max_daily_loss", "max_position"]
+//!
+//! [accounts.main.max_positions]
+//! BTCUSDT = 0.05
+//! ```
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::orders::OrderRow;
+use crate::risk_state::RiskStateRow;
+use crate::{DatabaseError, DatabaseResult};
+
+/// Config file read when none is given
+pub const DEFAULT_CONFIG_PATH: &str = "risk_config.toml";
+
+/// Environment variable with the path of the config file
+pub const CONFIG_PATH_ENV: &str = "RISK_CONFIG";
+
+/// Rules of the limits, the names accepted in `kill_switch_rules`
+pub const LIMIT_RULES: [&str; 4] = [
+    "max_order_notional",
+    "max_position",
+    "max_daily_loss",
+    "max_orders_per_minute",
+];
+
+/// Contents of the risk config file
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RiskConfig {
+    pub accounts: BTreeMap<String, RiskLimits>,
+}
+
+/// Limits of the orders of an account, values in the quote coin except for
+/// positions
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RiskLimits {
+    /// Largest net position of a symbol, in the base coin
+    pub max_position: Decimal,
+    /// `max_position` of single symbols
+    #[serde(default)]
+    pub max_positions: BTreeMap<String, Decimal>,
+    pub max_order_notional: Decimal,
+    pub max_daily_loss: Decimal,
+    pub max_orders_per_minute: usize,
+    /// Rules of the breaches that engage the kill switch
+    #[serde(default = "default_kill_switch_rules")]
+    pub kill_switch_rules: Vec<String>,
+}
+
+fn default_kill_switch_rules() -> Vec<String> {
+    vec!["max_daily_loss".to_string()]
+}
+
+impl RiskLimits {
+    /// Largest net position of `symbol`
+    pub fn max_position(&self, symbol: &str) -> Decimal {
+        self.max_positions
+            .get(symbol)
+            .copied()
+            .unwrap_or(self.max_position)
+    }
+
+    /// The breach engages the kill switch
+    pub fn engages_kill_switch(&self, violation: &RiskViolation) -> bool {
+        self.kill_switch_rules
+            .iter()
+            .any(|rule| rule == violation.rule())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let limits = [
+            self.max_position,
+            self.max_order_notional,
+            self.max_daily_loss,
+        ];
+        if limits
+            .iter()
+            .chain(self.max_positions.values())
+            .any(|limit| limit.is_sign_negative())
+        {
+            return Err("limits must not be negative".into());
+        }
+        if let Some(rule) = self
+            .kill_switch_rules
+            .iter()
+            .find(|rule| !LIMIT_RULES.contains(&rule.as_str()))
+        {
+            return Err(format!("unknown kill switch rule {rule}"));
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for RiskConfig {
+    type Err = DatabaseError;
+
+    /// Parse and validate a config
+    fn from_str(s: &str) -> DatabaseResult<Self> {
+        let config: Self =
+            toml::from_str(s).map_err(|e| DatabaseError::ConfigError(e.to_string()))?;
+
+        if config.accounts.is_empty() {
+            return Err(DatabaseError::ConfigError("No accounts".to_string()));
+        }
+        for (name, limits) in &config.accounts {
+            limits.validate().map_err(|message| {
+                DatabaseError::ConfigError(format!("Account {name}: {message}"))
+            })?;
+        }
+
+        Ok(config)
+    }
+}
+
+impl RiskConfig {
+    /// Read, parse and validate a config file
+    pub fn from_file(path: impl AsRef<Path>) -> DatabaseResult<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            DatabaseError::ConfigError(format!(
+                "Failed to read {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        contents.parse().map_err(|e| match e {
+            DatabaseError::ConfigError(message) => DatabaseError::ConfigError(format!(
+                "Invalid config {}: {}",
+                path.display(),
+                message
+            )),
+            e => e,
+        })
+    }
+
+    /// Path of the config file, from [`CONFIG_PATH_ENV`] or the default
+    pub fn path_from_env() -> String {
+        std::env::var(CONFIG_PATH_ENV).unwrap_or_else(|_| DEFAULT_CONFIG_PATH.to_string())
+    }
+
+    /// Limits of an account
+    pub fn limits(&self, account: &str) -> DatabaseResult<&RiskLimits> {
+        self.accounts.get(account).ok_or_else(|| {
+            DatabaseError::ConfigError(format!("Unknown account {account}"))
+        })
+    }
+}
+
+/// Order about to be sent
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderCheck<'a> {
+    pub symbol: &'a str,
+    /// `Buy` or `Sell`
+    pub side: &'a str,
+    /// Value of the order in the quote coin
+    pub notional: Decimal,
+    pub price: Decimal,
+    /// Current equity of the account
+    pub equity: Decimal,
+}
+
+/// Limit an order would breach
+#[derive(Debug, Clone, PartialEq)]
+pub enum RiskViolation {
+    OrderNotional {
+        notional: Decimal,
+        limit: Decimal,
+    },
+    Position {
+        symbol: String,
+        position: Decimal,
+        limit: Decimal,
+    },
+    DailyLoss {
+        loss: Decimal,
+        limit: Decimal,
+    },
+    OrderRate {
+        orders: usize,
+        limit: usize,
+    },
+    KillSwitch,
+}
+
+impl RiskViolation {
+    /// Name of the limit, the `rule` of the audit rows
+    pub fn rule(&self) -> &'static str {
+        match self {
+            RiskViolation::OrderNotional { .. } => "max_order_notional",
+            RiskViolation::Position { .. } => "max_position",
+            RiskViolation::DailyLoss { .. } => "max_daily_loss",
+            RiskViolation::OrderRate { .. } => "max_orders_per_minute",
+            RiskViolation::KillSwitch => "kill_switch",
+        }
+    }
+}
+
+impl fmt::Display for RiskViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RiskViolation::OrderNotional { notional, limit } => {
+                write!(f, "Order notional {notional} above {limit}")
+            }
+            RiskViolation::Position {
+                symbol,
+                position,
+                limit,
+            } => write!(f, "Position {position} of {symbol} beyond {limit}"),
+            RiskViolation::DailyLoss { loss, limit } => {
+                write!(f, "Daily loss {loss} above {limit}")
+            }
+            RiskViolation::OrderRate { orders, limit } => {
+                write!(f, "{orders} orders in the last minute, limit {limit}")
+            }
+            RiskViolation::KillSwitch => write!(f, "Kill switch engaged"),
+        }
+    }
+}
+
+/// Base quantity of an open order not filled yet
+#[derive(Debug, Clone, PartialEq)]
+struct Reservation {
+    symbol: String,
+    side: String,
+    qty: Decimal,
+}
+
+/// Checks orders against the limits of an account and keeps the state the
+/// limits depend on
+#[derive(Debug, Clone)]
+pub struct RiskManager {
+    account: String,
+    limits: RiskLimits,
+    /// Net base quantity held by symbol
+    positions: HashMap<String, Decimal>,
+    /// Unfilled quantity of the open orders, by client order id
+    reservations: HashMap<String, Reservation>,
+    /// First equity read of the current UTC day
+    day_start: Option<(NaiveDate, Decimal)>,
+    /// Times of the orders sent in the trailing minute
+    orders: VecDeque<DateTime<Utc>>,
+    killed: bool,
+}
+
+impl RiskManager {
+    pub fn new(account: impl Into<String>, limits: RiskLimits) -> Self {
+        Self {
+            account: account.into(),
+            limits,
+            positions: HashMap::new(),
+            reservations: HashMap::new(),
+            day_start: None,
+            orders: VecDeque::new(),
+            killed: false,
+        }
+    }
+
+    pub fn account(&self) -> &str {
+        &self.account
+    }
+
+    pub fn limits(&self) -> &RiskLimits {
+        &self.limits
+    }
+
+    /// Net base quantity held of a symbol
+    pub fn position(&self, symbol: &str) -> Decimal {
+        self.positions.get(symbol).copied().unwrap_or_default()
+    }
+
+    /// Start the position of a symbol from `position`, the fills are added
+    /// to it
+    pub fn set_position(&mut self, symbol: &str, position: Decimal) {
+        self.positions.insert(symbol.to_string(), position);
+    }
+
+    /// Unfilled base quantity of the open orders of a symbol on `side`
+    pub fn reserved(&self, symbol: &str, side: &str) -> Decimal {
+        self.reservations
+            .values()
+            .filter(|reservation| {
+                reservation.symbol == symbol && reservation.side == side
+            })
+            .map(|reservation| reservation.qty)
+            .sum()
+    }
+
+    pub fn is_killed(&self) -> bool {
+        self.killed
+    }
+
+    /// Refuse every order from now on
+    pub fn engage_kill_switch(&mut self) {
+        self.killed = true;
+    }
+
+    /// Accept orders again
+    pub fn reset_kill_switch(&mut self) {
+        self.killed = false;
+    }
+
+    /// Day and equity the daily loss is measured from, once an order was
+    /// checked
+    pub fn day_start(&self) -> Option<(NaiveDate, Decimal)> {
+        self.day_start
+    }
+
+    /// State to persist at `now`, `None` before the first check
+    pub fn state(&self, exchange: &str, now: DateTime<Utc>) -> Option<RiskStateRow> {
+        self.day_start.map(|(day, equity)| RiskStateRow {
+            timestamp: now,
+            account: self.account.clone(),
+            exchange: exchange.to_string(),
+            day,
+            day_start_equity: equity,
+            kill_switch: self.killed,
+        })
+    }
+
+    /// Continue from a persisted state, the day start equity only counts on
+    /// its own day
+    pub fn restore(&mut self, state: &RiskStateRow) {
+        self.day_start = Some((state.day, state.day_start_equity));
+        self.killed = state.kill_switch;
+    }
+
+    /// Check an order at `now`, the daily loss is measured from the first
+    /// equity seen on the day of `now`
+    pub fn check(
+        &mut self,
+        order: &OrderCheck<'_>,
+        now: DateTime<Utc>,
+    ) -> Result<(), RiskViolation> {
+        if self.killed {
+            return Err(RiskViolation::KillSwitch);
+        }
+
+        let today = now.date_naive();
+        let start_equity = match self.day_start {
+            Some((day, equity)) if day == today => equity,
+            _ => {
+                self.day_start = Some((today, order.equity));
+                order.equity
+            }
+        };
+        let loss = start_equity - order.equity;
+        if loss > self.limits.max_daily_loss {
+            return Err(RiskViolation::DailyLoss {
+                loss,
+                limit: self.limits.max_daily_loss,
+            });
+        }
+
+        if order.notional > self.limits.max_order_notional {
+            return Err(RiskViolation::OrderNotional {
+                notional: order.notional,
+                limit: self.limits.max_order_notional,
+            });
+        }
+
+        if order.price > Decimal::ZERO {
+            // Open orders of the same side are taken as filled
+            let qty =
+                order.notional / order.price + self.reserved(order.symbol, order.side);
+            let position = match order.side {
+                "Sell" => self.position(order.symbol) - qty,
+                _ => self.position(order.symbol) + qty,
+            };
+            let limit = self.limits.max_position(order.symbol);
+            if position.abs() > limit
+                && position.abs() > self.position(order.symbol).abs()
+            {
+                return Err(RiskViolation::Position {
+                    symbol: order.symbol.to_string(),
+                    position: position.round_dp(8),
+                    limit,
+                });
+            }
+        }
+
+        let minute_ago = now - Duration::minutes(1);
+        while self.orders.front().is_some_and(|time| *time <= minute_ago) {
+            self.orders.pop_front();
+        }
+        if self.orders.len() >= self.limits.max_orders_per_minute {
+            return Err(RiskViolation::OrderRate {
+                orders: self.orders.len(),
+                limit: self.limits.max_orders_per_minute,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Count an order sent at `time` towards the order rate, and its base
+    /// quantity `qty` towards the position until it is filled or closed
+    pub fn on_order(&mut self, order: &OrderRow, qty: Decimal, time: DateTime<Utc>) {
+        self.orders.push_back(time);
+        self.reserve(order, qty);
+    }
+
+    /// Count the unfilled base quantity `qty` of an open order towards the
+    /// position
+    pub fn reserve(&mut self, order: &OrderRow, qty: Decimal) {
+        self.reservations.insert(
+            order.order_link_id.clone(),
+            Reservation {
+                symbol: order.symbol.clone(),
+                side: order.side.clone(),
+                qty,
+            },
+        );
+    }
+
+    /// Add the fill between two states of an order to its position, and
+    /// release its reservation by the same quantity, or whole once the order
+    /// is closed
+    pub fn on_fill(&mut self, previous: Option<&OrderRow>, current: &OrderRow) {
+        let filled = previous.map_or(Decimal::ZERO, |row| row.filled_qty);
+        let delta = current.filled_qty - filled;
+
+        if current.is_terminal() {
+            self.reservations.remove(&current.order_link_id);
+        } else if let Some(reservation) =
+            self.reservations.get_mut(&current.order_link_id)
+        {
+            reservation.qty = (reservation.qty - delta).max(Decimal::ZERO);
+        }
+
+        if delta.is_zero() {
+            return;
+        }
+
+        let position = self.positions.entry(current.symbol.clone()).or_default();
+        match current.side.as_str() {
+            "Sell" => *position -= delta,
+            _ => *position += delta,
+        }
+    }
+}
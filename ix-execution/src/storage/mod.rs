@@ -0,0 +1,278 @@
+//! Parquet file storage
+//!
+//! Rows are stored in one file per hour of each `(exchange, symbol)`, under a
+//! directory per table. Files are named like [`Orderbook::parquet_path`] of the
+//! start of the hour, e.g. `orderbooks/Bybit-20250708-18-00-SOLUSDT.parquet`.
+//! Order books are stored one row per price level, see [`OrderbookLevelRow`].
+//!
+//! A partition is always written whole, exporting it again replaces the file.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use arrow_array::RecordBatch;
+use arrow_schema::{ArrowError, SchemaRef};
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use ix_cex::models::orderbook::Orderbook;
+use parquet::arrow::ArrowWriter;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::basic::Compression;
+use parquet::errors::ParquetError;
+use parquet::file::properties::WriterProperties;
+use tracing::info;
+
+use crate::orderbooks::{OrderbookLevelRow, orderbooks_from_levels};
+use crate::queries::select::{ReadRow, SelectQuery};
+use crate::{BatchWriter, ClickHouseClient, DatabaseError, DatabaseResult, WriteRow};
+
+pub mod rows;
+
+/// Row type that can be stored in Parquet files
+pub trait ParquetRow: Sized + Clone {
+    /// Directory of the files under the storage root
+    const DIRECTORY: &'static str;
+
+    /// Arrow schema of the files
+    fn schema() -> SchemaRef;
+
+    /// Columns of `rows`
+    fn to_batch(rows: &[Self]) -> DatabaseResult<RecordBatch>;
+
+    /// Rows of a batch read from a file
+    fn from_batch(batch: &RecordBatch) -> DatabaseResult<Vec<Self>>;
+
+    fn exchange(&self) -> &str;
+    fn symbol(&self) -> &str;
+    fn timestamp(&self) -> DateTime<Utc>;
+}
+
+impl From<ParquetError> for DatabaseError {
+    fn from(e: ParquetError) -> Self {
+        DatabaseError::ParquetError(e.to_string())
+    }
+}
+
+impl From<ArrowError> for DatabaseError {
+    fn from(e: ArrowError) -> Self {
+        DatabaseError::ParquetError(e.to_string())
+    }
+}
+
+/// Name of the file holding the hour of `timestamp`
+pub fn partition_file_name(
+    exchange: &str,
+    symbol: &str,
+    timestamp: DateTime<Utc>,
+) -> String {
+    let hour = timestamp
+        .duration_trunc(Duration::hours(1))
+        .unwrap_or(timestamp);
+
+    format!(
+        "{}-{}-{}-{}-{}.parquet",
+        exchange,
+        hour.format("%Y%m%d"),
+        hour.format("%H"),
+        hour.format("%M"),
+        symbol
+    )
+}
+
+/// Write `rows` to a single file, replacing it if it exists
+pub fn write_file<T: ParquetRow>(path: &Path, rows: &[T]) -> DatabaseResult<()> {
+    let batch = T::to_batch(rows)?;
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+
+    // Readers never see a partially written file
+    let partial = path.with_extension("parquet.partial");
+    let mut writer =
+        ArrowWriter::try_new(File::create(&partial)?, T::schema(), Some(properties))?;
+    writer.write(&batch)?;
+    writer.close()?;
+    fs::rename(&partial, path)?;
+
+    Ok(())
+}
+
+/// Rows of a file
+pub fn read_file<T: ParquetRow>(path: &Path) -> DatabaseResult<Vec<T>> {
+    let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(path)?)?.build()?;
+
+    let mut rows = Vec::new();
+    for batch in reader {
+        rows.extend(T::from_batch(&batch?)?);
+    }
+    Ok(rows)
+}
+
+/// Directory of hourly partitioned Parquet files
+#[derive(Debug, Clone)]
+pub struct ParquetStore {
+    root: PathBuf,
+}
+
+impl ParquetStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Directory of the files of `T`
+    pub fn directory<T: ParquetRow>(&self) -> PathBuf {
+        self.root.join(T::DIRECTORY)
+    }
+
+    /// Write `rows` grouped by partition, returns the written files
+    pub fn write<T: ParquetRow>(&self, rows: &[T]) -> DatabaseResult<Vec<PathBuf>> {
+        let mut partitions: BTreeMap<String, Vec<T>> = BTreeMap::new();
+        for row in rows {
+            partitions
+                .entry(partition_file_name(
+                    row.exchange(),
+                    row.symbol(),
+                    row.timestamp(),
+                ))
+                .or_default()
+                .push(row.clone());
+        }
+
+        let directory = self.directory::<T>();
+        fs::create_dir_all(&directory)?;
+
+        let mut paths = Vec::with_capacity(partitions.len());
+        for (name, rows) in partitions {
+            let path = directory.join(name);
+            write_file(&path, &rows)?;
+            paths.push(path);
+        }
+
+        Ok(paths)
+    }
+
+    /// Store order book snapshots as level rows
+    pub fn write_orderbooks(
+        &self,
+        orderbooks: &[Orderbook],
+    ) -> DatabaseResult<Vec<PathBuf>> {
+        let rows: Vec<OrderbookLevelRow> = orderbooks
+            .iter()
+            .flat_map(OrderbookLevelRow::from_orderbook)
+            .collect();
+        self.write(&rows)
+    }
+
+    /// Every file of `T`, sorted by name
+    pub fn files<T: ParquetRow>(&self) -> DatabaseResult<Vec<PathBuf>> {
+        let directory = self.directory::<T>();
+        if !directory.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(directory)? {
+            let path = entry?.path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "parquet")
+            {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        Ok(paths)
+    }
+
+    /// Rows of one `(exchange, symbol)` within `range`, in file order. The
+    /// symbol is normalized as it is stored, e.g. `SOLUSDT`
+    pub fn read<T: ParquetRow>(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        range: Range<DateTime<Utc>>,
+    ) -> DatabaseResult<Vec<T>> {
+        let directory = self.directory::<T>();
+        let symbol = crate::queries::format_symbol_for_clickhouse(symbol);
+        let mut rows = Vec::new();
+
+        let mut hour = range
+            .start
+            .duration_trunc(Duration::hours(1))
+            .unwrap_or(range.start);
+        while hour < range.end {
+            let path = directory.join(partition_file_name(exchange, &symbol, hour));
+            if path.exists() {
+                rows.extend(
+                    read_file::<T>(&path)?
+                        .into_iter()
+                        .filter(|row| range.contains(&row.timestamp())),
+                );
+            }
+            hour += Duration::hours(1);
+        }
+
+        Ok(rows)
+    }
+
+    /// Order book snapshots of one `(exchange, symbol)` within `range`
+    pub fn read_orderbooks(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        range: Range<DateTime<Utc>>,
+    ) -> DatabaseResult<Vec<Orderbook>> {
+        let rows = self.read::<OrderbookLevelRow>(exchange, symbol, range)?;
+        Ok(orderbooks_from_levels(rows))
+    }
+
+    /// Export the rows of a query, the query should cover whole hours
+    pub async fn export<T>(
+        &self,
+        client: &ClickHouseClient,
+        query: &SelectQuery<T>,
+    ) -> DatabaseResult<Vec<PathBuf>>
+    where
+        T: ReadRow + ParquetRow,
+    {
+        let rows = query.fetch_all(client).await?;
+        let paths = self.write(&rows)?;
+
+        info!(
+            "Exported {} rows of {} into {} files",
+            rows.len(),
+            T::TABLE,
+            paths.len()
+        );
+        Ok(paths)
+    }
+
+    /// Load files into ClickHouse through `writer`, returns the rows sent
+    pub async fn import<T>(
+        &self,
+        writer: &BatchWriter<T>,
+        paths: &[PathBuf],
+    ) -> DatabaseResult<usize>
+    where
+        T: ParquetRow + WriteRow + Send + 'static,
+    {
+        let mut count = 0;
+        for path in paths {
+            let rows = read_file::<T>(path)?;
+            count += rows.len();
+            for row in rows {
+                writer.write(row).await?;
+            }
+        }
+
+        writer.flush().await?;
+        info!(
+            "Imported {} rows from {} files into {}",
+            count,
+            paths.len(),
+            writer.table()
+        );
+        Ok(count)
+    }
+}
@@ -0,0 +1,310 @@
+//! Arrow columns of the stored row types
+//!
+//! Timestamps are UTC microseconds and decimals are `Decimal128(38, 18)`, as
+//! in the ClickHouse tables.
+
+use std::sync::{Arc, LazyLock};
+
+use arrow_array::{
+    Array, ArrayRef, Decimal128Array, RecordBatch, StringArray,
+    TimestampMicrosecondArray, UInt16Array,
+};
+use arrow_schema::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+
+use super::ParquetRow;
+use crate::liquidations::LiquidationRow;
+use crate::orderbooks::{LevelSide, OrderbookLevelRow};
+use crate::queries::decimal::{self, SCALE};
+use crate::trades::TradeRow;
+use crate::{DatabaseError, DatabaseResult};
+
+const PRECISION: u8 = 38;
+
+fn timestamp_field(name: &str) -> Field {
+    Field::new(
+        name,
+        DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+        false,
+    )
+}
+
+fn decimal_field(name: &str) -> Field {
+    Field::new(name, DataType::Decimal128(PRECISION, SCALE as i8), false)
+}
+
+fn string_field(name: &str) -> Field {
+    Field::new(name, DataType::Utf8, false)
+}
+
+fn timestamps(values: impl Iterator<Item = DateTime<Utc>>) -> ArrayRef {
+    Arc::new(
+        TimestampMicrosecondArray::from_iter_values(
+            values.map(|ts| ts.timestamp_micros()),
+        )
+        .with_timezone("UTC"),
+    )
+}
+
+fn decimals<'a>(values: impl Iterator<Item = &'a Decimal>) -> DatabaseResult<ArrayRef> {
+    let scaled = values
+        .map(|value| {
+            decimal::to_scaled(value, SCALE).ok_or_else(|| {
+                DatabaseError::ParquetError(format!("{value} overflows Decimal(38, 18)"))
+            })
+        })
+        .collect::<DatabaseResult<Vec<_>>>()?;
+
+    Ok(Arc::new(
+        Decimal128Array::from(scaled).with_precision_and_scale(PRECISION, SCALE as i8)?,
+    ))
+}
+
+fn strings<'a>(values: impl Iterator<Item = &'a str>) -> ArrayRef {
+    Arc::new(StringArray::from_iter_values(values))
+}
+
+fn column<'a, A: Array + 'static>(
+    batch: &'a RecordBatch,
+    name: &str,
+) -> DatabaseResult<&'a A> {
+    batch
+        .column_by_name(name)
+        .and_then(|column| column.as_any().downcast_ref::<A>())
+        .ok_or_else(|| {
+            DatabaseError::ParquetError(format!("Missing or invalid column {name}"))
+        })
+}
+
+fn timestamp_at(
+    column: &TimestampMicrosecondArray,
+    i: usize,
+) -> DatabaseResult<DateTime<Utc>> {
+    DateTime::from_timestamp_micros(column.value(i)).ok_or_else(|| {
+        DatabaseError::ParquetError(format!("Invalid timestamp {}", column.value(i)))
+    })
+}
+
+fn decimal_at(column: &Decimal128Array, i: usize) -> DatabaseResult<Decimal> {
+    decimal::from_scaled(column.value(i), column.scale() as u32)
+        .map_err(|e| DatabaseError::ParquetError(e.to_string()))
+}
+
+static TRADE_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
+    Arc::new(Schema::new(vec![
+        timestamp_field("timestamp"),
+        string_field("symbol"),
+        string_field("side"),
+        decimal_field("amount"),
+        decimal_field("price"),
+        string_field("exchange"),
+    ]))
+});
+
+impl ParquetRow for TradeRow {
+    const DIRECTORY: &'static str = "publictrades";
+
+    fn schema() -> SchemaRef {
+        TRADE_SCHEMA.clone()
+    }
+
+    fn to_batch(rows: &[Self]) -> DatabaseResult<RecordBatch> {
+        Ok(RecordBatch::try_new(
+            Self::schema(),
+            vec![
+                timestamps(rows.iter().map(|r| r.timestamp)),
+                strings(rows.iter().map(|r| r.symbol.as_str())),
+                strings(rows.iter().map(|r| r.side.as_str())),
+                decimals(rows.iter().map(|r| &r.amount))?,
+                decimals(rows.iter().map(|r| &r.price))?,
+                strings(rows.iter().map(|r| r.exchange.as_str())),
+            ],
+        )?)
+    }
+
+    fn from_batch(batch: &RecordBatch) -> DatabaseResult<Vec<Self>> {
+        let timestamp = column::<TimestampMicrosecondArray>(batch, "timestamp")?;
+        let symbol = column::<StringArray>(batch, "symbol")?;
+        let side = column::<StringArray>(batch, "side")?;
+        let amount = column::<Decimal128Array>(batch, "amount")?;
+        let price = column::<Decimal128Array>(batch, "price")?;
+        let exchange = column::<StringArray>(batch, "exchange")?;
+
+        (0..batch.num_rows())
+            .map(|i| {
+                Ok(TradeRow {
+                    timestamp: timestamp_at(timestamp, i)?,
+                    symbol: symbol.value(i).to_string(),
+                    side: side.value(i).to_string(),
+                    amount: decimal_at(amount, i)?,
+                    price: decimal_at(price, i)?,
+                    exchange: exchange.value(i).to_string(),
+                })
+            })
+            .collect()
+    }
+
+    fn exchange(&self) -> &str {
+        &self.exchange
+    }
+
+    fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+}
+
+static LIQUIDATION_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
+    Arc::new(Schema::new(vec![
+        timestamp_field("timestamp"),
+        string_field("symbol"),
+        string_field("exchange"),
+        string_field("side"),
+        string_field("amount"),
+        string_field("price"),
+    ]))
+});
+
+impl ParquetRow for LiquidationRow {
+    const DIRECTORY: &'static str = "liquidations";
+
+    fn schema() -> SchemaRef {
+        LIQUIDATION_SCHEMA.clone()
+    }
+
+    fn to_batch(rows: &[Self]) -> DatabaseResult<RecordBatch> {
+        Ok(RecordBatch::try_new(
+            Self::schema(),
+            vec![
+                timestamps(rows.iter().map(|r| r.timestamp)),
+                strings(rows.iter().map(|r| r.symbol.as_str())),
+                strings(rows.iter().map(|r| r.exchange.as_str())),
+                strings(rows.iter().map(|r| r.side.as_str())),
+                strings(rows.iter().map(|r| r.amount.as_str())),
+                strings(rows.iter().map(|r| r.price.as_str())),
+            ],
+        )?)
+    }
+
+    fn from_batch(batch: &RecordBatch) -> DatabaseResult<Vec<Self>> {
+        let timestamp = column::<TimestampMicrosecondArray>(batch, "timestamp")?;
+        let symbol = column::<StringArray>(batch, "symbol")?;
+        let exchange = column::<StringArray>(batch, "exchange")?;
+        let side = column::<StringArray>(batch, "side")?;
+        let amount = column::<StringArray>(batch, "amount")?;
+        let price = column::<StringArray>(batch, "price")?;
+
+        (0..batch.num_rows())
+            .map(|i| {
+                Ok(LiquidationRow {
+                    timestamp: timestamp_at(timestamp, i)?,
+                    symbol: symbol.value(i).to_string(),
+                    exchange: exchange.value(i).to_string(),
+                    side: side.value(i).to_string(),
+                    amount: amount.value(i).to_string(),
+                    price: price.value(i).to_string(),
+                })
+            })
+            .collect()
+    }
+
+    fn exchange(&self) -> &str {
+        &self.exchange
+    }
+
+    fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+}
+
+static LEVEL_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
+    Arc::new(Schema::new(vec![
+        timestamp_field("timestamp"),
+        string_field("exchange"),
+        string_field("symbol"),
+        string_field("side"),
+        decimal_field("price"),
+        decimal_field("amount"),
+        Field::new("level", DataType::UInt16, false),
+    ]))
+});
+
+impl ParquetRow for OrderbookLevelRow {
+    const DIRECTORY: &'static str = "orderbooks";
+
+    fn schema() -> SchemaRef {
+        LEVEL_SCHEMA.clone()
+    }
+
+    fn to_batch(rows: &[Self]) -> DatabaseResult<RecordBatch> {
+        Ok(RecordBatch::try_new(
+            Self::schema(),
+            vec![
+                timestamps(rows.iter().map(|r| r.timestamp)),
+                strings(rows.iter().map(|r| r.exchange.as_str())),
+                strings(rows.iter().map(|r| r.symbol.as_str())),
+                strings(rows.iter().map(|r| match r.side {
+                    LevelSide::Bid => "bid",
+                    LevelSide::Ask => "ask",
+                })),
+                decimals(rows.iter().map(|r| &r.price))?,
+                decimals(rows.iter().map(|r| &r.amount))?,
+                Arc::new(UInt16Array::from_iter_values(rows.iter().map(|r| r.level))),
+            ],
+        )?)
+    }
+
+    fn from_batch(batch: &RecordBatch) -> DatabaseResult<Vec<Self>> {
+        let timestamp = column::<TimestampMicrosecondArray>(batch, "timestamp")?;
+        let exchange = column::<StringArray>(batch, "exchange")?;
+        let symbol = column::<StringArray>(batch, "symbol")?;
+        let side = column::<StringArray>(batch, "side")?;
+        let price = column::<Decimal128Array>(batch, "price")?;
+        let amount = column::<Decimal128Array>(batch, "amount")?;
+        let level = column::<UInt16Array>(batch, "level")?;
+
+        (0..batch.num_rows())
+            .map(|i| {
+                let side = match side.value(i) {
+                    "bid" => LevelSide::Bid,
+                    "ask" => LevelSide::Ask,
+                    other => {
+                        return Err(DatabaseError::ParquetError(format!(
+                            "Unknown orderbook side {other}"
+                        )));
+                    }
+                };
+
+                Ok(OrderbookLevelRow {
+                    timestamp: timestamp_at(timestamp, i)?,
+                    exchange: exchange.value(i).to_string(),
+                    symbol: symbol.value(i).to_string(),
+                    side,
+                    price: decimal_at(price, i)?,
+                    amount: decimal_at(amount, i)?,
+                    level: level.value(i),
+                })
+            })
+            .collect()
+    }
+
+    fn exchange(&self) -> &str {
+        &self.exchange
+    }
+
+    fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+}
@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+
+    use chrono::{TimeZone, Utc};
+    use ix_execution::ClickHouseClient;
+    use ix_execution::bars::create_tables::{
+        create_bars_ddl, create_bars_table_ddl, create_bars_view_ddl,
+    };
+    use ix_execution::bars::{BarInterval, BarRow};
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    #[test]
+    fn test_bar_intervals() {
+        assert_eq!(
+            BarInterval::from_str("5m").unwrap(),
+            BarInterval::FiveMinutes
+        );
+        assert!(BarInterval::from_str("2m").is_err());
+        assert_eq!(BarInterval::OneHour.table(), "bars_1h");
+        assert_eq!(BarInterval::OneSecond.view(), "bars_1s_mv");
+    }
+
+    #[test]
+    fn test_bars_ddl() {
+        let statements = create_bars_ddl(&BarInterval::ALL);
+        assert_eq!(statements.len(), 2 * BarInterval::ALL.len());
+
+        let table = create_bars_table_ddl(BarInterval::OneMinute);
+        assert!(table.starts_with("CREATE TABLE IF NOT EXISTS bars_1m ("));
+        assert!(table.contains("ENGINE = AggregatingMergeTree()"));
+
+        let view = create_bars_view_ddl(BarInterval::FiveMinutes);
+        assert!(
+            view.starts_with(
+                "CREATE MATERIALIZED VIEW IF NOT EXISTS bars_5m_mv TO bars_5m"
+            )
+        );
+        assert!(view.contains("INTERVAL 5 MINUTE"));
+    }
+
+    #[test]
+    fn test_bar_vwap() {
+        let mut bar = BarRow {
+            timestamp: Utc.timestamp_opt(1_752_000_000, 0).unwrap(),
+            exchange: "Bybit".to_string(),
+            symbol: "SOLUSDT".to_string(),
+            open: dec("150"),
+            high: dec("152"),
+            low: dec("150"),
+            close: dec("152"),
+            volume: dec("3"),
+            quote_volume: dec("454"),
+            buy_volume: dec("2"),
+            sell_volume: dec("1"),
+            trades: 2,
+        };
+
+        assert_eq!(bar.vwap().unwrap().round_dp(4), dec("151.3333"));
+        assert_eq!(bar.volume_imbalance(), dec("1"));
+
+        bar.volume = Decimal::ZERO;
+        assert_eq!(bar.vwap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_read_bars_reports_connection_errors() {
+        let client = ClickHouseClient::builder()
+            .url("http://127.0.0.1:1")
+            .build()
+            .await
+            .unwrap();
+
+        let start = Utc.timestamp_opt(1_752_000_000, 0).unwrap();
+        let result = client
+            .read_bars(
+                "Bybit",
+                "SOL-USDT",
+                BarInterval::OneMinute,
+                start..start + chrono::Duration::hours(1),
+            )
+            .await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,254 @@
+#[cfg(test)]
+mod tests {
+
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    use chrono::{Duration, TimeZone, Utc};
+    use ix_cex::models::trades::TradeSide;
+    use ix_cex::models::trading::{Balance, OrderQty};
+    use ix_execution::execution::{
+        LotSize, SignalSides, account_equity, order_link_id, order_side, spot_positions,
+    };
+    use ix_execution::migrations::MIGRATIONS;
+    use ix_execution::orders::{OrderRow, UNKNOWN_GRACE_SECONDS, latest_orders};
+    use ix_execution::signals::SignalRow;
+    use rust_decimal::Decimal;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    fn signal(side: &str) -> SignalRow {
+        SignalRow {
+            timestamp: Utc.timestamp_opt(1_752_000_000, 123_456_000).unwrap(),
+            symbol: "SOLUSDT".to_string(),
+            side: side.to_string(),
+            exchange: "Bybit".to_string(),
+            strength: 0.9,
+            confidence: "HIGH".to_string(),
+            vpin_value: 0.15,
+            vpin_momentum: 0.03,
+            toxicity_level: "LOW".to_string(),
+            bars_in_calculation: 8,
+            error_status: "OK".to_string(),
+            profile: "ultra-fast".to_string(),
+        }
+    }
+
+    fn lot_size() -> LotSize {
+        LotSize {
+            base_precision: dec("0.001"),
+            quote_precision: dec("0.0001"),
+            min_order_qty: dec("0.01"),
+            min_order_amt: dec("5"),
+        }
+    }
+
+    fn order(link_id: &str, seconds: i64, status: &str) -> OrderRow {
+        let start = Utc.timestamp_opt(1_752_000_000, 0).unwrap();
+        OrderRow {
+            timestamp: start + Duration::seconds(seconds),
+            order_link_id: link_id.to_string(),
+            order_id: String::new(),
+            exchange: "Bybit".to_string(),
+            symbol: "SOLUSDT".to_string(),
+            side: "Buy".to_string(),
+            order_type: "Market".to_string(),
+            qty: dec("10"),
+            qty_unit: "Quote".to_string(),
+            status: status.to_string(),
+            filled_qty: Decimal::ZERO,
+            avg_price: Decimal::ZERO,
+            fee: Decimal::ZERO,
+            reject_reason: String::new(),
+            signal_timestamp: start,
+        }
+    }
+
+    #[test]
+    fn test_order_link_id_is_deterministic() {
+        let id = order_link_id(&signal("BUY"));
+        assert_eq!(id, order_link_id(&signal("BUY")));
+        assert!(id.starts_with("ix-"));
+        assert!(id.len() <= 36);
+
+        assert_ne!(id, order_link_id(&signal("SELL")));
+        let mut later = signal("BUY");
+        later.timestamp += Duration::microseconds(1);
+        assert_ne!(id, order_link_id(&later));
+    }
+
+    #[test]
+    fn test_actionable_signals() {
+        assert_eq!(order_side(&signal("BUY")), Some(TradeSide::Buy));
+        assert_eq!(order_side(&signal("SELL")), Some(TradeSide::Sell));
+        assert_eq!(order_side(&signal("NEUTRAL")), None);
+
+        let mut medium = signal("BUY");
+        medium.confidence = "MEDIUM".to_string();
+        medium.strength = 0.5;
+        assert!(signal("BUY").should_alert());
+        assert!(!medium.should_alert());
+    }
+
+    #[test]
+    fn test_repeated_signal_ordered_once() {
+        let mut sides = SignalSides::default();
+        let first = signal("BUY");
+        let mut second = signal("BUY");
+        second.timestamp += Duration::seconds(1);
+
+        assert_eq!(sides.to_order(&first), Some(TradeSide::Buy));
+        sides.ordered(&first, TradeSide::Buy);
+        assert_eq!(sides.to_order(&second), None);
+
+        // Another profile is ordered apart
+        let mut other = second.clone();
+        other.profile = "medium".to_string();
+        assert_eq!(sides.to_order(&other), Some(TradeSide::Buy));
+
+        // The side is ordered again once the signals left it
+        let mut neutral = signal("NEUTRAL");
+        neutral.timestamp += Duration::seconds(2);
+        let mut third = signal("BUY");
+        third.timestamp += Duration::seconds(3);
+        assert_eq!(sides.to_order(&neutral), None);
+        assert_eq!(sides.to_order(&third), Some(TradeSide::Buy));
+    }
+
+    #[test]
+    fn test_market_buy_in_quote_coin() {
+        let lot = lot_size();
+        assert_eq!(
+            lot.market_qty(TradeSide::Buy, dec("12.345678"), dec("150")),
+            Some(OrderQty::Quote(dec("12.3456")))
+        );
+        assert_eq!(lot.market_qty(TradeSide::Buy, dec("4.99"), dec("150")), None);
+    }
+
+    #[test]
+    fn test_market_sell_in_base_coin() {
+        let lot = lot_size();
+        // 100 / 150 = 0.6666.. rounded down to the base step
+        assert_eq!(
+            lot.market_qty(TradeSide::Sell, dec("100"), dec("150")),
+            Some(OrderQty::Base(dec("0.666")))
+        );
+        // Above the minimum quantity but below the minimum amount
+        assert_eq!(lot.market_qty(TradeSide::Sell, dec("4"), dec("150")), None);
+        assert_eq!(lot.market_qty(TradeSide::Sell, dec("100"), Decimal::ZERO), None);
+    }
+
+    fn balance(coin: &str, total: &str) -> Balance {
+        Balance {
+            coin: coin.to_string(),
+            total: dec(total),
+            free: dec(total),
+            locked: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_account_equity_values_every_coin() {
+        let balances = vec![
+            balance("USDT", "900"),
+            balance("SOL", "0.5"),
+            balance("BNB", "1"),
+        ];
+        let prices = HashMap::from([("SOL".to_string(), dec("200"))]);
+
+        // A buy of 100 USDT of SOL leaves the equity unchanged
+        assert_eq!(account_equity(&balances, "USDT", &prices), dec("1000"));
+        assert_eq!(
+            account_equity(&[balance("USDT", "1000")], "USDT", &prices),
+            dec("1000")
+        );
+    }
+
+    #[test]
+    fn test_spot_positions_from_balances() {
+        let balances = vec![
+            balance("USDT", "900"),
+            balance("SOL", "40"),
+            balance("BTC", "0"),
+        ];
+
+        // Inventory built before the orders in the database still counts
+        let positions = spot_positions(&balances, "USDT");
+        assert_eq!(positions.len(), 2);
+        assert_eq!(positions["SOLUSDT"], dec("40"));
+        assert_eq!(positions["BTCUSDT"], Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_latest_orders() {
+        let rows = vec![
+            order("ix-a", 0, "Submitted"),
+            order("ix-b", 0, "Submitted"),
+            order("ix-a", 2, "Filled"),
+            order("ix-b", 1, "New"),
+        ];
+
+        let latest = latest_orders(rows);
+        assert_eq!(latest.len(), 2);
+        assert_eq!(latest[0].status, "Filled");
+        assert!(latest[0].is_terminal());
+        assert_eq!(latest[1].status, "New");
+        assert!(!latest[1].is_terminal());
+    }
+
+    #[test]
+    fn test_unknown_order_rejected_after_grace_period() {
+        let row = order("ix-a", 0, "Unknown");
+        assert!(!row.is_terminal());
+
+        let placed = row.timestamp;
+        let early = placed + Duration::seconds(UNKNOWN_GRACE_SECONDS - 1);
+        assert_eq!(row.not_found(early), None);
+
+        let late = placed + Duration::seconds(UNKNOWN_GRACE_SECONDS);
+        let rejected = row.not_found(late).unwrap();
+        assert_eq!(rejected.status, "Rejected");
+        assert_eq!(rejected.timestamp, late);
+        assert!(rejected.is_terminal());
+
+        // Orders sent and acknowledged are left to the exchange updates
+        assert_eq!(order("ix-b", 0, "Submitted").not_found(late), None);
+    }
+
+    #[test]
+    fn test_order_qty_unit() {
+        let mut row = order("ix-a", 0, "Submitted");
+        row.set_qty(OrderQty::Base(dec("0.666")));
+        assert_eq!((row.qty, row.qty_unit.as_str()), (dec("0.666"), "Base"));
+
+        row.set_qty(OrderQty::Quote(dec("12.3456")));
+        assert_eq!((row.qty, row.qty_unit.as_str()), (dec("12.3456"), "Quote"));
+    }
+
+    #[test]
+    fn test_orders_qty_unit_migration() {
+        let migration = MIGRATIONS
+            .iter()
+            .find(|m| m.name == "orders_qty_unit_column")
+            .unwrap();
+
+        let statements = (migration.up)();
+        assert!(statements[0].contains("ALTER TABLE orders"));
+        assert!(statements[0].contains("qty_unit LowCardinality(String) AFTER qty"));
+    }
+
+    #[test]
+    fn test_orders_migration() {
+        let migration = MIGRATIONS
+            .iter()
+            .find(|m| m.name == "create_orders")
+            .unwrap();
+
+        let statements = (migration.up)();
+        assert!(statements[0].contains("CREATE TABLE IF NOT EXISTS orders"));
+        assert!(statements[0].contains("ReplacingMergeTree(timestamp)"));
+        assert!(statements[0].contains("ORDER BY (exchange, order_link_id)"));
+    }
+}
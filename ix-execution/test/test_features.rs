@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+
+    use chrono::{TimeZone, Utc};
+    use ix_cex::models::orderbook::{Orderbook, PriceLevel};
+    use ix_execution::engine::FeatureEngine;
+    use ix_execution::features::FeatureRow;
+    use ix_execution::migrations::MIGRATIONS;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    fn orderbook() -> Orderbook {
+        Orderbook::new(
+            "SOL-USDT".to_string(),
+            "Bybit".to_string(),
+            Utc.timestamp_millis_opt(1_752_000_000_123).unwrap(),
+            vec![
+                PriceLevel::new(dec("100"), dec("3")),
+                PriceLevel::new(dec("99"), dec("2")),
+            ],
+            vec![
+                PriceLevel::new(dec("102"), dec("1")),
+                PriceLevel::new(dec("103"), dec("4")),
+            ],
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_engine_depths() {
+        let engine = FeatureEngine::new([10, 0, 1, 5, 1]);
+        assert_eq!(engine.depths(), &[1, 5, 10]);
+    }
+
+    #[test]
+    fn test_features_by_depth() {
+        let engine = FeatureEngine::new([1, 2]);
+        let rows: Vec<FeatureRow> = engine
+            .compute(&orderbook())
+            .iter()
+            .map(FeatureRow::from)
+            .collect();
+
+        assert_eq!(rows.len(), 2);
+
+        let top = &rows[0];
+        assert_eq!(top.symbol, "SOLUSDT");
+        assert_eq!(top.depth, 1);
+        assert_eq!(top.timestamp.timestamp_millis(), 1_752_000_000_123);
+        assert_eq!(top.spread, dec("2"));
+        assert_eq!(top.midprice, dec("101"));
+        assert_eq!(top.tav, dec("4"));
+        assert_eq!(top.imb, dec("0.5"));
+        // (100 * 1 + 102 * 3) / 4
+        assert_eq!(top.w_midprice, dec("101.5"));
+        // (100 * 3 + 102 * 1) / 4
+        assert_eq!(top.vwap, dec("100.5"));
+
+        let deep = &rows[1];
+        assert_eq!(deep.depth, 2);
+        assert_eq!(deep.spread, dec("2"));
+        assert_eq!(deep.tav, dec("10"));
+        assert_eq!(deep.imb, Decimal::ZERO);
+        // (300 + 198 + 102 + 412) / 10
+        assert_eq!(deep.vwap, dec("101.2"));
+    }
+
+    #[test]
+    fn test_one_sided_book_has_no_features() {
+        let mut orderbook = orderbook();
+        orderbook.asks.clear();
+
+        assert!(FeatureEngine::default().compute(&orderbook).is_empty());
+    }
+
+    #[test]
+    fn test_feature_row_encoding() {
+        let features = FeatureEngine::new([1]).compute(&orderbook());
+        let json = serde_json::to_string(&FeatureRow::from(&features[0])).unwrap();
+
+        assert!(json.contains(r#""depth":1,"#));
+        assert!(json.contains(r#""imb":500000000000000000,"#));
+        assert!(json.contains(r#""w_midprice":101500000000000000000,"#));
+    }
+
+    #[test]
+    fn test_features_numeric_migration() {
+        let migration = MIGRATIONS
+            .iter()
+            .find(|m| m.name == "features_numeric_columns")
+            .unwrap();
+        let statements = (migration.up)();
+
+        assert!(statements[0].contains("ADD COLUMN IF NOT EXISTS depth UInt16"));
+        assert!(statements[0].contains("MODIFY COLUMN tav Decimal(38, 18)"));
+    }
+}
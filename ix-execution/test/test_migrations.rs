@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+
+    use ix_execution::ClickHouseClient;
+    use ix_execution::migrations::{MIGRATIONS, Migration, Migrator};
+    use rust_decimal::Decimal;
+    use serde::{Deserialize, Serialize};
+    use std::collections::HashSet;
+    use std::str::FromStr;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct DecimalColumn {
+        #[serde(with = "ix_execution::queries::decimal")]
+        value: Decimal,
+    }
+
+    #[test]
+    fn test_migrations_are_ordered() {
+        for pair in MIGRATIONS.windows(2) {
+            assert!(pair[0].version < pair[1].version);
+        }
+
+        let names: HashSet<&str> = MIGRATIONS.iter().map(|m| m.name).collect();
+        assert_eq!(names.len(), MIGRATIONS.len());
+    }
+
+    #[test]
+    fn test_migration_checksums() {
+        let checksums: HashSet<String> =
+            MIGRATIONS.iter().map(|m| m.checksum()).collect();
+
+        assert_eq!(checksums.len(), MIGRATIONS.len());
+        assert_eq!(MIGRATIONS[0].checksum(), MIGRATIONS[0].checksum());
+        assert_eq!(MIGRATIONS[0].checksum().len(), 64);
+    }
+
+    #[test]
+    fn test_publictrades_decimal_migration() {
+        let migration = MIGRATIONS
+            .iter()
+            .find(|m| m.name == "publictrades_decimal_columns")
+            .unwrap();
+        let statements = (migration.up)();
+
+        assert_eq!(statements.len(), 1);
+        assert!(statements[0].contains("MODIFY COLUMN price Decimal(38, 18)"));
+        assert!(statements[0].contains("MODIFY COLUMN amount Decimal(38, 18)"));
+    }
+
+    #[test]
+    fn test_trade_bars_migration_is_frozen() {
+        let migration = MIGRATIONS
+            .iter()
+            .find(|m| m.name == "create_trade_bars")
+            .unwrap();
+        let statements = (migration.up)();
+
+        assert_eq!(statements.len(), 8);
+        for table in ["bars_1s", "bars_1m", "bars_5m", "bars_1h"] {
+            let prefix = format!("CREATE TABLE IF NOT EXISTS {table} (");
+            assert!(statements.iter().any(|s| s.starts_with(&prefix)));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unordered_migrations_are_rejected() {
+        let client = ClickHouseClient::builder()
+            .url("http://127.0.0.1:1")
+            .build()
+            .await
+            .unwrap();
+
+        let migrations = [
+            Migration {
+                version: 2,
+                name: "second",
+                up: Vec::new,
+            },
+            Migration {
+                version: 1,
+                name: "first",
+                up: Vec::new,
+            },
+        ];
+
+        let result = Migrator::with_migrations(&client, &migrations)
+            .status()
+            .await;
+        assert!(result.unwrap_err().to_string().contains("not ordered"));
+    }
+
+    #[test]
+    fn test_decimal_column_encoding() {
+        let column = DecimalColumn {
+            value: Decimal::from_str("151.52").unwrap(),
+        };
+
+        let json = serde_json::to_string(&column).unwrap();
+        assert_eq!(json, r#"{"value":151520000000000000000}"#);
+
+        let decoded: DecimalColumn = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.value.to_string(), "151.52");
+
+        // Digits beyond the column scale are rounded
+        let column = DecimalColumn {
+            value: Decimal::from_str("0.0000000000000000015").unwrap(),
+        };
+        let json = serde_json::to_string(&column).unwrap();
+        assert_eq!(json, r#"{"value":2}"#);
+    }
+}
@@ -5,25 +5,31 @@ mod tests {
     use ix_cex::models::orderbook::{
         Orderbook, OrderbookInput, PriceLevel, PriceLevelInput,
     };
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
 
     #[test]
     fn test_price_level_creation() {
-        let level = PriceLevel::new(151.52, 95.568);
-        assert_eq!(level.price, 151.52);
-        assert_eq!(level.quantity, 95.568);
-        assert_eq!(level.price, 151.52);
-        assert_eq!(level.quantity, 95.568);
+        let level = PriceLevel::new(dec("151.52"), dec("95.568"));
+        assert_eq!(level.price, dec("151.52"));
+        assert_eq!(level.quantity, dec("95.568"));
+        assert_eq!(level.price, dec("151.52"));
+        assert_eq!(level.quantity, dec("95.568"));
     }
 
     #[test]
     fn test_orderbook_creation() {
         let bids = vec![
-            PriceLevel::new(151.52, 95.568),
-            PriceLevel::new(151.51, 85.869),
+            PriceLevel::new(dec("151.52"), dec("95.568")),
+            PriceLevel::new(dec("151.51"), dec("85.869")),
         ];
         let asks = vec![
-            PriceLevel::new(151.53, 131.551),
-            PriceLevel::new(151.54, 89.835),
+            PriceLevel::new(dec("151.53"), dec("131.551")),
+            PriceLevel::new(dec("151.54"), dec("89.835")),
         ];
 
         let orderbook = Orderbook::new(
@@ -38,14 +44,20 @@ mod tests {
 
         assert_eq!(orderbook.symbol, "SOLUSDC");
         assert_eq!(orderbook.exchange, "Binance");
-        assert_eq!(orderbook.best_bid().unwrap().price, 151.52);
-        assert_eq!(orderbook.best_ask().unwrap().price, 151.53);
+        assert_eq!(orderbook.best_bid().unwrap().price, dec("151.52"));
+        assert_eq!(orderbook.best_ask().unwrap().price, dec("151.53"));
     }
 
     #[test]
     fn test_orderbook_calculations() {
-        let bids = vec![PriceLevel::new(100.0, 10.0), PriceLevel::new(99.0, 5.0)];
-        let asks = vec![PriceLevel::new(101.0, 8.0), PriceLevel::new(102.0, 12.0)];
+        let bids = vec![
+            PriceLevel::new(dec("100.0"), dec("10.0")),
+            PriceLevel::new(dec("99.0"), dec("5.0")),
+        ];
+        let asks = vec![
+            PriceLevel::new(dec("101.0"), dec("8.0")),
+            PriceLevel::new(dec("102.0"), dec("12.0")),
+        ];
 
         let orderbook = Orderbook::new(
             "TEST".to_string(),
@@ -57,21 +69,59 @@ mod tests {
             None,
         );
 
-        assert_eq!(orderbook.mid_price().unwrap(), 100.5);
-        assert_eq!(orderbook.spread().unwrap(), 1.0);
-        assert_eq!(orderbook.bid_volume(), 15.0);
-        assert_eq!(orderbook.ask_volume(), 20.0);
+        assert_eq!(orderbook.mid_price().unwrap(), dec("100.5"));
+        assert_eq!(orderbook.spread().unwrap(), dec("1.0"));
+        assert_eq!(orderbook.bid_volume(), dec("15.0"));
+        assert_eq!(orderbook.ask_volume(), dec("20.0"));
+    }
+
+    #[test]
+    fn test_orderbook_tick_size_arithmetic() {
+        let bids = vec![
+            PriceLevel::new(dec("151.52"), dec("95.568")),
+            PriceLevel::new(dec("151.37"), dec("85.869")),
+        ];
+        let asks = vec![
+            PriceLevel::new(dec("151.55"), dec("131.551")),
+            PriceLevel::new(dec("151.70"), dec("89.835")),
+        ];
+
+        let orderbook = Orderbook::new(
+            "SOLUSDC".to_string(),
+            "Binance".to_string(),
+            Utc::now(),
+            bids,
+            asks,
+            None,
+            None,
+        )
+        .with_tick_size(dec("0.01"));
+
+        assert_eq!(orderbook.spread().unwrap(), dec("0.03"));
+        assert_eq!(orderbook.spread_in_ticks().unwrap(), dec("3"));
+        assert_eq!(orderbook.mid_price().unwrap(), dec("151.535"));
+
+        // 0.1% of the mid is 0.151535, the band is [151.39, 151.68] on the grid
+        let (bid_liquidity, ask_liquidity) =
+            orderbook.liquidity_within_percentage(dec("0.1"));
+        assert_eq!(bid_liquidity, dec("95.568"));
+        assert_eq!(ask_liquidity, dec("131.551"));
+
+        let (bid_liquidity, ask_liquidity) =
+            orderbook.liquidity_within_percentage(dec("1"));
+        assert_eq!(bid_liquidity, dec("181.437"));
+        assert_eq!(ask_liquidity, dec("221.386"));
     }
 
     #[test]
     fn test_orderbook_validation() {
         let bids = vec![
-            PriceLevel::new(100.0, 10.0),
-            PriceLevel::new(99.0, 5.0), // Correct descending order
+            PriceLevel::new(dec("100.0"), dec("10.0")),
+            PriceLevel::new(dec("99.0"), dec("5.0")), // Correct descending order
         ];
         let asks = vec![
-            PriceLevel::new(101.0, 8.0),
-            PriceLevel::new(102.0, 12.0), // Correct ascending order
+            PriceLevel::new(dec("101.0"), dec("8.0")),
+            PriceLevel::new(dec("102.0"), dec("12.0")), // Correct ascending order
         ];
 
         let orderbook = Orderbook::new(
@@ -109,7 +159,7 @@ mod tests {
 
         assert_eq!(orderbook.symbol, "SOLUSDC");
         assert_eq!(orderbook.exchange, "Binance");
-        assert_eq!(orderbook.best_bid().unwrap().price, 151.52);
-        assert_eq!(orderbook.best_ask().unwrap().price, 151.53);
+        assert_eq!(orderbook.best_bid().unwrap().price, dec("151.52"));
+        assert_eq!(orderbook.best_ask().unwrap().price, dec("151.53"));
     }
 }
@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+
+    use chrono::{TimeZone, Utc};
+    use ix_cex::models::orderbook::{Orderbook, PriceLevel};
+    use ix_execution::orderbooks::{
+        LevelSide, OrderbookCH, OrderbookLevelRow, orderbooks_from_levels,
+    };
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    fn orderbook(millis: i64) -> Orderbook {
+        Orderbook::new(
+            "SOLUSDT".to_string(),
+            "Bybit".to_string(),
+            Utc.timestamp_millis_opt(millis).unwrap(),
+            vec![
+                PriceLevel::new(dec("151.52"), dec("95.568")),
+                PriceLevel::new(dec("151.51"), dec("3")),
+            ],
+            vec![PriceLevel::new(dec("151.53"), dec("0.00000001"))],
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_orderbook_explodes_into_levels() {
+        let rows = OrderbookLevelRow::from_orderbook(&orderbook(1_752_000_000_123));
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[1].side, LevelSide::Bid);
+        assert_eq!(rows[1].level, 1);
+        assert_eq!(rows[1].price, dec("151.51"));
+        assert_eq!(rows[2].side, LevelSide::Ask);
+        assert_eq!(rows[2].level, 0);
+        assert_eq!(rows[2].amount, dec("0.00000001"));
+    }
+
+    #[test]
+    fn test_levels_rebuild_orderbooks() {
+        let mut rows = OrderbookLevelRow::from_orderbook(&orderbook(2_000));
+        rows.extend(OrderbookLevelRow::from_orderbook(&orderbook(1_000)));
+        rows.reverse();
+
+        let orderbooks = orderbooks_from_levels(rows);
+
+        assert_eq!(orderbooks.len(), 2);
+        assert_eq!(orderbooks[0].timestamp.timestamp_millis(), 1_000);
+        assert_eq!(orderbooks[1].bids, orderbook(2_000).bids);
+        assert_eq!(orderbooks[1].asks, orderbook(2_000).asks);
+    }
+
+    #[test]
+    fn test_orderbook_ch_round_trip() {
+        let original = orderbook(1_752_000_000_123);
+        let row = OrderbookCH::from(&original);
+        assert_eq!(row.timestamp, "2025-07-08 18:40:00.123000");
+
+        let rebuilt = Orderbook::try_from(&row).unwrap();
+        assert_eq!(rebuilt.timestamp, original.timestamp);
+        assert_eq!(rebuilt.bids, original.bids);
+        assert_eq!(rebuilt.asks, original.asks);
+
+        // The atelier conversion reads the ClickHouse timestamp too
+        let book = row.to_orderbook().unwrap();
+        assert_eq!(book.orderbook_ts, 1_752_000_000_123_000);
+    }
+
+    #[test]
+    fn test_level_row_encoding() {
+        let row = OrderbookLevelRow::from_orderbook(&orderbook(0)).remove(2);
+        let json = serde_json::to_value(&row).unwrap();
+
+        assert_eq!(json["side"], 1);
+        assert_eq!(json["price"], 15_153_000_000i64);
+        assert_eq!(json["amount"], 1);
+
+        let decoded: OrderbookLevelRow = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, row);
+    }
+}
@@ -0,0 +1,186 @@
+#[cfg(test)]
+mod tests {
+
+    use ix_execution::DatabaseError;
+    use ix_execution::producer::{ProducerConfig, SignalThresholds, VpinParameters};
+    use ix_execution::trades::stats::TradingStats;
+    use std::path::Path;
+
+    const CONFIG: &str = r#"
+        [dataproducer]
+        profile = "fast"
+
+        [signal]
+        weak_momentum = 0.01
+
+        [profiles.fast]
+        volume_per_bar = 100.0
+        window_size = 8
+        batch_size = 4
+        data_limit_minutes = 1
+        toxicity_threshold = 0.5
+        momentum_window = 4
+        target_bars_per_day = 7000.0
+
+        [profiles.slow]
+        volume_per_bar = 300.0
+        window_size = 15
+        batch_size = 10
+        data_limit_minutes = 4
+        toxicity_threshold = 0.75
+        momentum_window = 5
+        target_bars_per_day = 1200.0
+
+        [[targets]]
+        exchange = "Bybit"
+        symbol = "SOLUSDT"
+        daily_volume = 65_000_000.0
+
+        [[targets]]
+        exchange = "Bybit"
+        symbol = "BTCUSDT"
+        profile = "slow"
+    "#;
+
+    fn config_error(config: &str) -> String {
+        match config.parse::<ProducerConfig>() {
+            Err(DatabaseError::ConfigError(message)) => message,
+            other => panic!("expected a config error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_target_profiles() {
+        let config: ProducerConfig = CONFIG.parse().unwrap();
+
+        let sol = config.target("Bybit", "SOLUSDT").unwrap();
+        assert_eq!(sol.label(), "Bybit/SOLUSDT");
+        assert_eq!(config.profile_name(sol), "fast");
+        assert_eq!(config.profile(sol).unwrap().volume_per_bar, 100.0);
+        assert_eq!(sol.daily_volume, 65_000_000.0);
+        assert_eq!(sol.trades_per_minute, 25.0);
+
+        let btc = config.target("Bybit", "BTCUSDT").unwrap();
+        assert_eq!(config.profile_name(btc), "slow");
+        assert_eq!(config.profile(btc).unwrap().window_size, 15);
+
+        assert!(config.target("Binance", "SOLUSDT").is_none());
+    }
+
+    #[test]
+    fn test_signal_defaults() {
+        let config: ProducerConfig = CONFIG.parse().unwrap();
+        assert_eq!(config.signal.weak_momentum, 0.01);
+        assert_eq!(
+            config.signal.high_vpin_sell,
+            SignalThresholds::default().high_vpin_sell
+        );
+        assert!(config.database.is_none());
+    }
+
+    #[test]
+    fn test_unknown_profile() {
+        let message =
+            config_error(&CONFIG.replace(r#"profile = "slow""#, r#"profile = "x""#));
+        assert!(message.contains("Unknown profile x of Bybit/BTCUSDT"));
+    }
+
+    #[test]
+    fn test_duplicate_target() {
+        let message = config_error(&CONFIG.replace("BTCUSDT", "SOLUSDT"));
+        assert!(message.contains("Duplicate target Bybit/SOLUSDT"));
+    }
+
+    #[test]
+    fn test_invalid_profile() {
+        let message = config_error(&CONFIG.replace("batch_size = 10", "batch_size = 0"));
+        assert!(message.starts_with("Profile slow"));
+
+        let message = config_error(
+            &CONFIG.replace("toxicity_threshold = 0.5", "toxicity_threshold = 1.5"),
+        );
+        assert!(message.starts_with("Profile fast"));
+    }
+
+    #[test]
+    fn test_no_targets() {
+        let config = CONFIG.split("[[targets]]").next().unwrap();
+        assert_eq!(config_error(config), "No targets");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        config_error("[dataproducer]\nname = \"vpin'\n");
+    }
+
+    #[test]
+    fn test_shipped_config() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("dataproducer")
+            .join("dataproducer_config.toml");
+        let config = ProducerConfig::from_file(path).unwrap();
+
+        assert_eq!(config.profiles.len(), 3);
+        for name in ["conservative", "responsive", "ultra-fast"] {
+            assert!(config.profiles.contains_key(name), "{name}");
+        }
+        assert_eq!(config.signal, SignalThresholds::default());
+        assert_eq!(config.database.unwrap().database, "operations");
+    }
+
+    #[test]
+    fn test_missing_file() {
+        match ProducerConfig::from_file("does/not/exist.toml") {
+            Err(DatabaseError::ConfigError(message)) => {
+                assert!(message.starts_with("Failed to read does/not/exist.toml"))
+            }
+            other => panic!("expected a config error, got {:?}", other),
+        }
+    }
+
+    fn stats(avg_daily_volume: f64, avg_trades_per_minute: f64) -> TradingStats {
+        TradingStats {
+            avg_daily_volume,
+            avg_trades_per_minute,
+            recent_volatility: 0.0,
+            trades: 0,
+        }
+    }
+
+    #[test]
+    fn test_parameters_from_stats() {
+        let config: ProducerConfig = CONFIG.parse().unwrap();
+        let btc = config.target("Bybit", "BTCUSDT").unwrap();
+        let profile = config.profile(btc).unwrap();
+
+        let params = VpinParameters::from_stats(&stats(1_200_000.0, 120.0), profile);
+        assert_eq!(
+            params,
+            VpinParameters {
+                volume_per_bar: 600.0,
+                window_size: 13,
+                batch_size: 5,
+                limit: 480,
+                toxicity_threshold: 0.75,
+                momentum_window: 5,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parameters_of_illiquid_pair() {
+        let config: ProducerConfig = CONFIG.parse().unwrap();
+        let btc = config.target("Bybit", "BTCUSDT").unwrap();
+        let profile = config.profile(btc).unwrap();
+
+        // Less than one trade per data_limit_minutes still reads a batch
+        let params = VpinParameters::from_stats(&stats(100.0, 0.1), profile);
+        assert_eq!(params.volume_per_bar, 150.0);
+        assert_eq!(params.window_size, 17);
+        assert_eq!(params.batch_size, 5);
+        assert_eq!(params.limit, 5);
+
+        let params = VpinParameters::from_stats(&stats(0.0, 0.0), profile);
+        assert_eq!(params.limit, params.batch_size as u64);
+    }
+}
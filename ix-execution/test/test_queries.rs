@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+
+    use chrono::{TimeZone, Utc};
+    use ix_execution::ClickHouseClient;
+    use ix_execution::queries::liquidations::read_tables::read_liquidations;
+    use ix_execution::queries::orderbooks::read_tables::read_orderbooks;
+    use ix_execution::queries::select::SortOrder;
+    use ix_execution::queries::trades::read_tables::read_trades;
+
+    #[test]
+    fn test_unfiltered_query() {
+        assert_eq!(
+            read_orderbooks().sql(),
+            "SELECT toString(timestamp) AS timestamp_str, symbol, exchange, bids, asks \
+             FROM orderbooks ORDER BY orderbooks.timestamp ASC"
+        );
+    }
+
+    #[test]
+    fn test_filters_are_bound() {
+        let query = read_trades()
+            .symbols(["SOL-USDT", "btc/usdt"])
+            .exchange("Bybit' OR 1=1 --")
+            .from(Utc.timestamp_millis_opt(1_752_000_000_000).unwrap())
+            .to(Utc.timestamp_millis_opt(1_752_000_060_000).unwrap())
+            .order(SortOrder::Desc)
+            .page(2, 100);
+
+        let sql = query.sql();
+        assert!(
+            sql.starts_with("SELECT toUnixTimestamp64Milli(timestamp) AS timestamp_ms,")
+        );
+        assert!(!sql.contains("Bybit"));
+        assert!(!sql.contains("SOL"));
+        assert!(sql.ends_with(
+            "FROM publictrades WHERE symbol IN ? AND exchange IN ? \
+             AND publictrades.timestamp >= fromUnixTimestamp64Micro(?) \
+             AND publictrades.timestamp < fromUnixTimestamp64Micro(?) \
+             ORDER BY publictrades.timestamp DESC LIMIT ? OFFSET ?"
+        ));
+    }
+
+    #[test]
+    fn test_liquidations_select_list() {
+        let sql = read_liquidations().offset(10).sql();
+
+        // The trailing comma before FROM is gone
+        assert!(sql.contains("price, exchange FROM liquidations"));
+        assert!(sql.starts_with("SELECT toUnixTimestamp64Milli(timestamp) AS ts,"));
+        assert!(sql.ends_with("OFFSET ? ROWS"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_reports_connection_errors() {
+        let client = ClickHouseClient::builder()
+            .url("http://127.0.0.1:1")
+            .build()
+            .await
+            .unwrap();
+
+        let result = read_trades()
+            .symbol("SOLUSDT")
+            .limit(10)
+            .fetch_all(&client)
+            .await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,341 @@
+#[cfg(test)]
+mod tests {
+
+    use std::path::Path;
+    use std::str::FromStr;
+
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+    use ix_execution::migrations::MIGRATIONS;
+    use ix_execution::orders::OrderRow;
+    use ix_execution::risk::{OrderCheck, RiskConfig, RiskManager, RiskViolation};
+    use rust_decimal::Decimal;
+
+    const CONFIG: &str = r#"
+[accounts.main]
+max_position = 10.0
+max_order_notional = 500
+max_daily_loss = "100"
+max_orders_per_minute = 2
+
+[accounts.main.max_positions]
+BTCUSDT = 0.05
+"#;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.timestamp_opt(1_752_000_000, 0).unwrap()
+    }
+
+    fn manager() -> RiskManager {
+        let config: RiskConfig = CONFIG.parse().unwrap();
+        RiskManager::new("main", config.limits("main").unwrap().clone())
+    }
+
+    fn buy(notional: &str, equity: &str) -> OrderCheck<'static> {
+        OrderCheck {
+            symbol: "SOLUSDT",
+            side: "Buy",
+            notional: dec(notional),
+            price: dec("100"),
+            equity: dec(equity),
+        }
+    }
+
+    fn fill(side: &str, filled: &str) -> OrderRow {
+        OrderRow {
+            timestamp: now(),
+            order_link_id: "ix-a".to_string(),
+            order_id: "1".to_string(),
+            exchange: "Bybit".to_string(),
+            symbol: "SOLUSDT".to_string(),
+            side: side.to_string(),
+            order_type: "Market".to_string(),
+            qty: dec(filled),
+            qty_unit: "Base".to_string(),
+            status: "Filled".to_string(),
+            filled_qty: dec(filled),
+            avg_price: dec("100"),
+            fee: Decimal::ZERO,
+            reject_reason: String::new(),
+            signal_timestamp: now(),
+        }
+    }
+
+    #[test]
+    fn test_parse_limits() {
+        let config: RiskConfig = CONFIG.parse().unwrap();
+        let limits = config.limits("main").unwrap();
+
+        assert_eq!(limits.max_order_notional, dec("500"));
+        assert_eq!(limits.max_daily_loss, dec("100"));
+        assert_eq!(limits.max_position("SOLUSDT"), dec("10"));
+        assert_eq!(limits.max_position("BTCUSDT"), dec("0.05"));
+        assert!(config.limits("other").is_err());
+    }
+
+    #[test]
+    fn test_invalid_limits() {
+        assert!("accounts = {}".parse::<RiskConfig>().is_err());
+        let negative =
+            CONFIG.replace("max_order_notional = 500", "max_order_notional = -1");
+        assert!(negative.parse::<RiskConfig>().is_err());
+    }
+
+    #[test]
+    fn test_shipped_config() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("signaltrader")
+            .join("risk_config.toml");
+        let config = RiskConfig::from_file(path).unwrap();
+        assert!(config.limits("main").is_ok());
+    }
+
+    #[test]
+    fn test_order_notional() {
+        let mut risk = manager();
+        assert!(risk.check(&buy("500", "1000"), now()).is_ok());
+        assert_eq!(
+            risk.check(&buy("501", "1000"), now()),
+            Err(RiskViolation::OrderNotional {
+                notional: dec("501"),
+                limit: dec("500"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_position_limit() {
+        let mut risk = manager();
+        risk.on_fill(None, &fill("Buy", "9"));
+        assert_eq!(risk.position("SOLUSDT"), dec("9"));
+
+        // 2 more at 100 would be 11
+        let violation = risk.check(&buy("200", "1000"), now()).unwrap_err();
+        assert_eq!(violation.rule(), "max_position");
+
+        // Selling reduces the position
+        let sell = OrderCheck {
+            side: "Sell",
+            ..buy("200", "1000")
+        };
+        assert!(risk.check(&sell, now()).is_ok());
+
+        let partial = fill("Sell", "1");
+        risk.on_fill(None, &partial);
+        risk.on_fill(Some(&partial), &fill("Sell", "3"));
+        assert_eq!(risk.position("SOLUSDT"), dec("6"));
+    }
+
+    #[test]
+    fn test_reducing_an_over_limit_position() {
+        let mut risk = manager();
+        risk.set_position("SOLUSDT", dec("15"));
+
+        // 1 at 100 reduces the position to 14, still beyond 10
+        let sell = OrderCheck {
+            side: "Sell",
+            ..buy("100", "10000")
+        };
+        assert!(risk.check(&sell, now()).is_ok());
+
+        let violation = risk.check(&buy("100", "10000"), now()).unwrap_err();
+        assert_eq!(violation.rule(), "max_position");
+    }
+
+    fn open(link_id: &str, qty: &str) -> OrderRow {
+        OrderRow {
+            order_link_id: link_id.to_string(),
+            qty: dec(qty),
+            status: "New".to_string(),
+            filled_qty: Decimal::ZERO,
+            ..fill("Buy", qty)
+        }
+    }
+
+    #[test]
+    fn test_unfilled_orders_count_towards_position() {
+        let mut risk = manager();
+        risk.on_fill(None, &fill("Buy", "1"));
+
+        // 5 at 100, sent but not filled yet
+        assert!(risk.check(&buy("500", "10000"), now()).is_ok());
+        let first = open("ix-b", "5");
+        risk.on_order(&first, dec("5"), now());
+        assert_eq!(risk.reserved("SOLUSDT", "Buy"), dec("5"));
+        assert_eq!(risk.position("SOLUSDT"), dec("1"));
+
+        // 5 more before the first fill would be 11
+        assert_eq!(
+            risk.check(&buy("500", "10000"), now()),
+            Err(RiskViolation::Position {
+                symbol: "SOLUSDT".to_string(),
+                position: dec("11"),
+                limit: dec("10"),
+            })
+        );
+
+        // Fills move the quantity from the reservation to the position
+        let partial = OrderRow {
+            filled_qty: dec("2"),
+            status: "PartiallyFilled".to_string(),
+            ..first.clone()
+        };
+        risk.on_fill(Some(&first), &partial);
+        assert_eq!(risk.reserved("SOLUSDT", "Buy"), dec("3"));
+        assert_eq!(risk.position("SOLUSDT"), dec("3"));
+
+        // A cancelled order releases the rest
+        let cancelled = OrderRow {
+            status: "Cancelled".to_string(),
+            ..partial.clone()
+        };
+        risk.on_fill(Some(&partial), &cancelled);
+        assert_eq!(risk.reserved("SOLUSDT", "Buy"), Decimal::ZERO);
+        assert!(risk.check(&buy("500", "10000"), now()).is_ok());
+    }
+
+    #[test]
+    fn test_unfilled_qty_of_quote_orders() {
+        let order = OrderRow {
+            qty: dec("500"),
+            qty_unit: "Quote".to_string(),
+            filled_qty: dec("2"),
+            ..open("ix-a", "500")
+        };
+        assert_eq!(order.unfilled_qty(dec("100")), dec("3"));
+        assert_eq!(order.unfilled_qty(Decimal::ZERO), Decimal::ZERO);
+        assert_eq!(open("ix-b", "4").unfilled_qty(dec("100")), dec("4"));
+    }
+
+    #[test]
+    fn test_order_rate() {
+        let mut risk = manager();
+        for seconds in [0, 10] {
+            let time = now() + Duration::seconds(seconds);
+            assert!(risk.check(&buy("10", "1000"), time).is_ok());
+            risk.on_order(&fill("Buy", "0.1"), Decimal::ZERO, time);
+        }
+
+        let violation = risk
+            .check(&buy("10", "1000"), now() + Duration::seconds(30))
+            .unwrap_err();
+        assert_eq!(violation.rule(), "max_orders_per_minute");
+
+        // The first order left the window
+        assert!(
+            risk.check(&buy("10", "1000"), now() + Duration::seconds(61))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_daily_loss_engages_kill_switch() {
+        let mut risk = manager();
+        assert!(risk.check(&buy("10", "1000"), now()).is_ok());
+        assert!(risk.check(&buy("10", "950"), now()).is_ok());
+
+        let violation = risk.check(&buy("10", "899"), now()).unwrap_err();
+        assert_eq!(violation.rule(), "max_daily_loss");
+        assert!(risk.limits().engages_kill_switch(&violation));
+
+        risk.engage_kill_switch();
+        assert_eq!(
+            risk.check(&buy("10", "1000"), now()),
+            Err(RiskViolation::KillSwitch)
+        );
+    }
+
+    #[test]
+    fn test_configured_breaches_engage_kill_switch() {
+        let risk = manager();
+        let position = RiskViolation::Position {
+            symbol: "SOLUSDT".to_string(),
+            position: dec("11"),
+            limit: dec("10"),
+        };
+        assert!(!risk.limits().engages_kill_switch(&position));
+
+        let config: RiskConfig = CONFIG
+            .replace(
+                "max_orders_per_minute = 2",
+                "max_orders_per_minute = 2\nkill_switch_rules = [\"max_position\"]",
+            )
+            .parse()
+            .unwrap();
+        let limits = config.limits("main").unwrap();
+        assert!(limits.engages_kill_switch(&position));
+        let loss = RiskViolation::DailyLoss {
+            loss: dec("101"),
+            limit: dec("100"),
+        };
+        assert!(!limits.engages_kill_switch(&loss));
+
+        let unknown = CONFIG.replace(
+            "max_orders_per_minute = 2",
+            "max_orders_per_minute = 2\nkill_switch_rules = [\"max_leverage\"]",
+        );
+        assert!(unknown.parse::<RiskConfig>().is_err());
+    }
+
+    #[test]
+    fn test_risk_state_survives_restart() {
+        let mut risk = manager();
+        assert_eq!(risk.state("Bybit", now()), None);
+        assert!(risk.check(&buy("10", "1000"), now()).is_ok());
+        risk.engage_kill_switch();
+
+        let state = risk.state("Bybit", now()).unwrap();
+        assert_eq!(state.day_start_equity, dec("1000"));
+        assert!(state.kill_switch);
+
+        let mut restarted = manager();
+        restarted.restore(&state);
+        assert_eq!(restarted.day_start(), risk.day_start());
+        assert_eq!(
+            restarted.check(&buy("10", "1000"), now()),
+            Err(RiskViolation::KillSwitch)
+        );
+
+        // The day start equity still measures the loss after a restart
+        restarted.reset_kill_switch();
+        let violation = restarted.check(&buy("10", "899"), now()).unwrap_err();
+        assert_eq!(violation.rule(), "max_daily_loss");
+    }
+
+    #[test]
+    fn test_daily_loss_resets_each_day() {
+        let mut risk = manager();
+        assert!(risk.check(&buy("10", "1000"), now()).is_ok());
+        assert!(risk.check(&buy("10", "899"), now()).is_err());
+
+        let tomorrow = now() + Duration::days(1);
+        assert!(risk.check(&buy("10", "899"), tomorrow).is_ok());
+    }
+
+    #[test]
+    fn test_risk_rejections_migration() {
+        let migration = MIGRATIONS
+            .iter()
+            .find(|m| m.name == "create_risk_rejections")
+            .unwrap();
+
+        let statements = (migration.up)();
+        assert!(statements[0].contains("CREATE TABLE IF NOT EXISTS risk_rejections"));
+        assert!(statements[0].contains("rule LowCardinality(String)"));
+    }
+
+    #[test]
+    fn test_risk_state_migration() {
+        let migration = MIGRATIONS
+            .iter()
+            .find(|m| m.name == "create_risk_state")
+            .unwrap();
+
+        let statements = (migration.up)();
+        assert!(statements[0].contains("CREATE TABLE IF NOT EXISTS risk_state"));
+        assert!(statements[0].contains("ReplacingMergeTree(timestamp)"));
+    }
+}
@@ -0,0 +1,177 @@
+#[cfg(test)]
+mod tests {
+
+    use chrono::{Duration, TimeZone, Utc};
+    use ix_execution::broadcast::{self, SignalFeed, SignalFilter};
+    use ix_execution::migrations::MIGRATIONS;
+    use ix_execution::producer::{SignalThresholds, TradeFlowSignal};
+    use ix_execution::signals::SignalRow;
+    use ix_execution::signals::read_tables::read_signals;
+    use tokio::net::TcpListener;
+    use tokio::sync::broadcast as channel;
+
+    fn signal(vpin: f64, momentum: f64, is_toxic: bool) -> TradeFlowSignal {
+        TradeFlowSignal::generate(
+            vpin,
+            momentum,
+            is_toxic,
+            12,
+            false,
+            &SignalThresholds::default(),
+        )
+    }
+
+    fn row(millis: i64, symbol: &str) -> SignalRow {
+        let mut row = signal(0.9, 0.03, true).to_row("Bybit", symbol, "ultra-fast");
+        row.timestamp = Utc.timestamp_millis_opt(millis).unwrap();
+        row
+    }
+
+    #[test]
+    fn test_generate_signals() {
+        let sell = signal(0.9, 0.03, true);
+        assert_eq!(
+            (sell.signal.as_str(), sell.confidence.as_str()),
+            ("SELL", "HIGH")
+        );
+        assert_eq!(sell.toxicity_level, "HIGH");
+        assert!(sell.should_alert());
+
+        let buy = signal(0.1, -0.03, false);
+        assert_eq!(
+            (buy.signal.as_str(), buy.confidence.as_str()),
+            ("BUY", "HIGH")
+        );
+        assert_eq!(buy.toxicity_level, "LOW");
+
+        let neutral = signal(0.5, 0.001, false);
+        assert_eq!(neutral.signal, "NEUTRAL");
+        assert!(!neutral.should_alert());
+
+        let error = TradeFlowSignal::generate(
+            0.9,
+            0.03,
+            true,
+            12,
+            true,
+            &SignalThresholds::default(),
+        );
+        assert_eq!(error.error_status, "ERROR_FALLBACK");
+        assert!(!error.should_alert());
+    }
+
+    #[test]
+    fn test_signal_row() {
+        let signal = signal(0.9, 0.03, true);
+        let row = signal.to_row("Bybit", "SOL-USDT", "ultra-fast");
+
+        assert_eq!(row.timestamp, signal.timestamp);
+        assert_eq!(row.symbol, "SOLUSDT");
+        assert_eq!(row.exchange, "Bybit");
+        assert_eq!(row.side, "SELL");
+        assert_eq!(row.confidence, "HIGH");
+        assert_eq!(row.vpin_value, 0.9);
+        assert_eq!(row.vpin_momentum, 0.03);
+        assert_eq!(row.bars_in_calculation, 12);
+        assert_eq!(row.error_status, "OK");
+        assert_eq!(row.profile, "ultra-fast");
+    }
+
+    #[test]
+    fn test_signals_migration() {
+        let migration = MIGRATIONS
+            .iter()
+            .find(|m| m.name == "signals_trade_flow_columns")
+            .unwrap();
+        let ddl = (migration.up)().join("\n");
+
+        for column in [
+            "exchange LowCardinality(String)",
+            "strength Float64",
+            "vpin_value Float64",
+            "bars_in_calculation UInt32",
+            "profile LowCardinality(String)",
+        ] {
+            assert!(ddl.contains(column), "{column}");
+        }
+    }
+
+    #[test]
+    fn test_read_signals_sql() {
+        let sql = read_signals().symbol("SOLUSDT").sql();
+        assert!(sql.starts_with("SELECT timestamp, symbol, side, exchange, strength"));
+        assert!(sql.contains("FROM signals WHERE symbol IN ?"));
+    }
+
+    #[test]
+    fn test_feed_skips_seen_rows() {
+        let mut feed = SignalFeed::new(Duration::seconds(30));
+        let since = Utc.timestamp_millis_opt(1_000).unwrap();
+
+        let first =
+            feed.unseen(vec![row(2_000, "SOLUSDT"), row(3_000, "SOLUSDT")], since);
+        assert_eq!(first.len(), 2);
+
+        // A late batch with an older row and the rows already sent
+        let second = feed.unseen(
+            vec![
+                row(1_500, "SOLUSDT"),
+                row(2_000, "SOLUSDT"),
+                row(2_000, "BTCUSDT"),
+                row(3_000, "SOLUSDT"),
+            ],
+            since,
+        );
+        assert_eq!(second, vec![row(1_500, "SOLUSDT"), row(2_000, "BTCUSDT")]);
+
+        // Rows before the window are forgotten
+        let since = Utc.timestamp_millis_opt(2_500).unwrap();
+        assert_eq!(feed.unseen(vec![row(2_000, "SOLUSDT")], since).len(), 1);
+    }
+
+    #[test]
+    fn test_filter() {
+        let sol = row(2_000, "SOLUSDT");
+
+        assert!(SignalFilter::default().matches(&sol));
+        let filter = SignalFilter {
+            exchange: Some("bybit".to_string()),
+            symbol: Some("SOL-USDT".to_string()),
+        };
+        assert!(filter.matches(&sol));
+        assert!(!filter.matches(&row(2_000, "BTCUSDT")));
+
+        let filter = SignalFilter {
+            exchange: Some("Binance".to_string()),
+            symbol: None,
+        };
+        assert!(!filter.matches(&sol));
+    }
+
+    #[tokio::test]
+    async fn test_server_sent_events() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (sender, _) = channel::channel(16);
+        tokio::spawn(broadcast::serve(listener, sender.clone()));
+
+        let mut response = reqwest::get(format!("http://{addr}/sse?symbol=SOLUSDT"))
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+
+        sender.send(row(2_000, "BTCUSDT")).unwrap();
+        sender.send(row(3_000, "SOLUSDT")).unwrap();
+
+        let mut body = String::new();
+        while !body.contains("\n\n") {
+            let chunk = response.chunk().await.unwrap().unwrap();
+            body.push_str(&String::from_utf8_lossy(&chunk));
+        }
+
+        assert!(body.starts_with("event: signal\ndata: {"));
+        assert!(body.contains(r#""symbol":"SOLUSDT""#));
+        assert!(body.contains(r#""timestamp":3000000"#));
+        assert!(!body.contains("BTCUSDT"));
+    }
+}
@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod tests {
+
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+    use ix_cex::models::orderbook::{Orderbook, PriceLevel};
+    use ix_execution::storage::{ParquetStore, partition_file_name};
+    use ix_execution::trades::TradeRow;
+    use rust_decimal::Decimal;
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    fn store() -> (ParquetStore, PathBuf) {
+        let root =
+            std::env::temp_dir().join(format!("ix-storage-{}", uuid::Uuid::new_v4()));
+        (ParquetStore::new(&root), root)
+    }
+
+    fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 7, 8, 18, 0, 0).unwrap()
+    }
+
+    fn trade(timestamp: DateTime<Utc>, price: &str) -> TradeRow {
+        TradeRow {
+            timestamp,
+            symbol: "SOLUSDT".to_string(),
+            side: "Buy".to_string(),
+            amount: dec("0.000001"),
+            price: dec(price),
+            exchange: "Bybit".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_partition_file_name() {
+        let orderbook = Orderbook::new(
+            "SOLUSDT".to_string(),
+            "Bybit".to_string(),
+            start(),
+            vec![],
+            vec![],
+            None,
+            None,
+        );
+
+        let name =
+            partition_file_name("Bybit", "SOLUSDT", start() + Duration::minutes(42));
+        assert_eq!(name, "Bybit-20250708-18-00-SOLUSDT.parquet");
+        assert_eq!(name, orderbook.parquet_path());
+    }
+
+    #[test]
+    fn test_trades_round_trip() {
+        let (store, root) = store();
+        let trades = vec![
+            trade(start() + Duration::minutes(5), "151.52"),
+            trade(start() + Duration::minutes(65), "151.5300000001"),
+            trade(start() + Duration::minutes(130), "152"),
+        ];
+
+        let paths = store.write(&trades).unwrap();
+        assert_eq!(paths.len(), 3);
+        assert_eq!(store.files::<TradeRow>().unwrap(), paths);
+
+        let read = store
+            .read::<TradeRow>(
+                "Bybit",
+                "SOL-USDT",
+                start()..start() + Duration::minutes(90),
+            )
+            .unwrap();
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[1].price, dec("151.5300000001"));
+        assert_eq!(read[1].amount, dec("0.000001"));
+        assert_eq!(read[1].timestamp, trades[1].timestamp);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_orderbooks_round_trip() {
+        let (store, root) = store();
+        let orderbooks: Vec<Orderbook> = (0..3)
+            .map(|i| {
+                Orderbook::new(
+                    "SOLUSDT".to_string(),
+                    "Bybit".to_string(),
+                    start() + Duration::milliseconds(100 * i),
+                    vec![
+                        PriceLevel::new(dec("151.52"), dec("95.568")),
+                        PriceLevel::new(dec("151.51"), dec("3")),
+                    ],
+                    vec![PriceLevel::new(dec("151.53"), dec("0.00000001"))],
+                    None,
+                    None,
+                )
+            })
+            .collect();
+
+        store.write_orderbooks(&orderbooks).unwrap();
+        let read = store
+            .read_orderbooks("Bybit", "SOLUSDT", start()..start() + Duration::hours(1))
+            .unwrap();
+
+        assert_eq!(read.len(), 3);
+        for (read, original) in read.iter().zip(&orderbooks) {
+            assert_eq!(read.timestamp, original.timestamp);
+            assert_eq!(read.bids, original.bids);
+            assert_eq!(read.asks, original.asks);
+        }
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
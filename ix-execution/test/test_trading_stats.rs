@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+    use ix_execution::ClickHouseClient;
+    use ix_execution::trades::stats::{MinuteStats, TradingStats, q_read_minute_stats};
+
+    fn start() -> DateTime<Utc> {
+        Utc.timestamp_opt(1_752_000_000, 0).unwrap()
+    }
+
+    fn minute(offset: i64, volume: f64, trades: u64, close: f64) -> MinuteStats {
+        MinuteStats {
+            minute: start() + Duration::minutes(offset),
+            volume,
+            trades,
+            close,
+        }
+    }
+
+    #[test]
+    fn test_stats_of_full_day() {
+        let minutes: Vec<MinuteStats> =
+            (0..1440).map(|i| minute(i, 10.0, 30, 100.0)).collect();
+
+        let stats =
+            TradingStats::from_minutes(&minutes, start() + Duration::hours(24)).unwrap();
+        assert_eq!(stats.trades, 43_200);
+        assert!((stats.avg_daily_volume - 14_400.0).abs() < 1e-6);
+        assert!((stats.avg_trades_per_minute - 30.0).abs() < 1e-9);
+        assert_eq!(stats.recent_volatility, 0.0);
+    }
+
+    #[test]
+    fn test_stats_scale_partial_window() {
+        // One hour of trades is a day at the same rate
+        let minutes: Vec<MinuteStats> =
+            (0..60).map(|i| minute(i, 2.0, 6, 100.0)).collect();
+
+        let stats =
+            TradingStats::from_minutes(&minutes, start() + Duration::hours(1)).unwrap();
+        assert!((stats.avg_daily_volume - 2_880.0).abs() < 1e-6);
+        assert!((stats.avg_trades_per_minute - 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_stats_volatility() {
+        // Log returns alternate between +r and -r
+        let up = 101.0_f64;
+        let minutes: Vec<MinuteStats> = (0..5)
+            .map(|i| minute(i, 1.0, 1, if i % 2 == 0 { 100.0 } else { up }))
+            .collect();
+
+        let stats =
+            TradingStats::from_minutes(&minutes, start() + Duration::minutes(5)).unwrap();
+
+        let r = (up / 100.0).ln();
+        // Sample deviation of [r, -r, r, -r] is r * sqrt(4/3)
+        let expected = r * (4.0_f64 / 3.0).sqrt() * 1440.0_f64.sqrt();
+        assert!((stats.recent_volatility - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_stats_without_trades() {
+        let end = start() + Duration::hours(1);
+        assert!(TradingStats::from_minutes(&[], end).is_none());
+        assert!(TradingStats::from_minutes(&[minute(0, 0.0, 0, 0.0)], end).is_none());
+    }
+
+    #[test]
+    fn test_minute_stats_sql() {
+        let sql = q_read_minute_stats();
+        assert!(sql.contains("FROM publictrades"));
+        assert!(sql.contains("WHERE exchange = ? AND symbol = ?"));
+        assert!(sql.contains("GROUP BY minute"));
+    }
+
+    #[tokio::test]
+    async fn test_trading_stats_reports_connection_errors() {
+        let client = ClickHouseClient::builder()
+            .url("http://127.0.0.1:1")
+            .build()
+            .await
+            .unwrap();
+
+        let result = client
+            .trading_stats("Bybit", "SOL-USDT", Duration::hours(24))
+            .await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,156 @@
+#[cfg(test)]
+mod tests {
+
+    use chrono::{TimeZone, Utc};
+    use ix_cex::models::orderbook::{Orderbook, PriceLevel};
+    use ix_cex::models::trades::{PublicTrade, TradeSide};
+    use ix_execution::orderbooks::OrderbookRow;
+    use ix_execution::signals::{SignalNew, SignalRow};
+    use ix_execution::trades::TradeRow;
+    use ix_execution::{ClickHouseClient, DatabaseError, WriteRow, WriterConfig};
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    fn orderbook() -> Orderbook {
+        Orderbook::new(
+            "SOL-USDC".to_string(),
+            "Coinbase".to_string(),
+            Utc.timestamp_millis_opt(1_752_000_000_123).unwrap(),
+            vec![PriceLevel::new(dec("151.5200"), dec("95.568"))],
+            vec![PriceLevel::new(dec("151.53"), dec("0.00000001"))],
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_orderbook_row_keeps_exact_decimals() {
+        let row = OrderbookRow::from(&orderbook());
+
+        assert_eq!(row.symbol, "SOLUSDC");
+        assert_eq!(row.exchange, "Coinbase");
+        assert_eq!(row.timestamp.timestamp_millis(), 1_752_000_000_123);
+        assert_eq!(row.bids, vec![("151.52".to_string(), "95.568".to_string())]);
+        assert_eq!(
+            row.asks,
+            vec![("151.53".to_string(), "0.00000001".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_signal_row_from_millis() {
+        let signal = SignalNew {
+            ts: 1_752_000_000_123,
+            symbol: "sol/usdt".to_string(),
+            side: "buy".to_string(),
+            exchange: "Bybit".to_string(),
+        };
+
+        let row = SignalRow::from(&signal);
+        assert_eq!(row.symbol, "SOLUSDT");
+        assert_eq!(row.timestamp.timestamp_millis(), 1_752_000_000_123);
+    }
+
+    #[tokio::test]
+    async fn test_writer_flush_and_shutdown() {
+        // Nothing listens on this port, inserts fail but the writer keeps running
+        let client = ClickHouseClient::builder()
+            .url("http://127.0.0.1:1")
+            .build()
+            .await
+            .unwrap();
+
+        let config = WriterConfig {
+            capacity: 1,
+            period: Duration::from_millis(50),
+            ..WriterConfig::default()
+        };
+        let writer = client.orderbooks_writer(config).unwrap();
+        assert_eq!(writer.table(), "orderbooks");
+
+        for _ in 0..3 {
+            writer
+                .write(OrderbookRow::from(&orderbook()))
+                .await
+                .unwrap();
+        }
+
+        assert!(writer.flush().await.is_err());
+        assert_eq!(writer.dropped_rows(), 3);
+        // The failure is reported once
+        assert_eq!(writer.flush().await.unwrap().rows, 0);
+
+        writer
+            .write(OrderbookRow::from(&orderbook()))
+            .await
+            .unwrap();
+        let inserted = writer.shutdown().await.unwrap();
+        assert_eq!(inserted.rows, 0);
+    }
+
+    fn trade_row(amount: Decimal) -> TradeRow {
+        TradeRow {
+            timestamp: Utc.timestamp_millis_opt(1_752_000_000_123).unwrap(),
+            symbol: "SOLUSDT".to_string(),
+            side: "buy".to_string(),
+            amount,
+            price: dec("151.52"),
+            exchange: "Bybit".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_check_rejects_decimal_overflow() {
+        assert!(trade_row(dec("95.568")).check().is_ok());
+        assert!(matches!(
+            trade_row(Decimal::MAX).check(),
+            Err(DatabaseError::InvalidRow(_))
+        ));
+    }
+
+    #[test]
+    fn test_trade_row_from_public_trade() {
+        let trade = |price: f64| PublicTrade {
+            symbol: "SOLUSDT".to_string(),
+            exchange: "Bybit".to_string(),
+            timestamp: Utc.timestamp_millis_opt(1_752_000_000_123).unwrap(),
+            side: TradeSide::Buy,
+            price,
+            amount: 95.568,
+            trade_id: "1".to_string(),
+        };
+
+        let row = TradeRow::try_from(&trade(151.52)).unwrap();
+        assert_eq!(row.price, dec("151.52"));
+        assert_eq!(row.amount, dec("95.568"));
+
+        assert!(matches!(
+            TradeRow::try_from(&trade(f64::NAN)),
+            Err(DatabaseError::InvalidRow(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_writer_rejects_invalid_row() {
+        let client = ClickHouseClient::builder()
+            .url("http://127.0.0.1:1")
+            .build()
+            .await
+            .unwrap();
+
+        let writer = client.trades_writer(WriterConfig::default()).unwrap();
+        writer.write(trade_row(dec("95.568"))).await.unwrap();
+
+        let result = writer.write(trade_row(Decimal::MAX)).await;
+        assert!(matches!(result, Err(DatabaseError::InvalidRow(_))));
+
+        // The writer still accepts rows after the rejection
+        writer.write(trade_row(dec("1.5"))).await.unwrap();
+        writer.shutdown().await.unwrap();
+    }
+}
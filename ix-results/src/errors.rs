@@ -74,8 +74,13 @@ pub enum ExchangeError {
     #[error("Rate limit exceeded for exchange: {exchange}")]
     RateLimit { exchange: String },
 
+    /// `code` is the error code of the exchange, when its response has one
     #[error("API error from {exchange}: {message}")]
-    ApiError { exchange: String, message: String },
+    ApiError {
+        exchange: String,
+        message: String,
+        code: Option<i64>,
+    },
 
     #[error("Invalid trading pair: {pair}")]
     InvalidTradingPair { pair: String },
@@ -92,6 +97,9 @@ pub enum ExchangeError {
     #[error("Authentication error: {0}")]
     Authentication(String),
 
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
     #[error("Unknown error: {0}")]
     Unknown(String),
 }
@@ -110,6 +118,14 @@ impl ExchangeError {
         )
     }
 
+    /// Error code of the exchange, for the API errors that carry one
+    pub fn api_code(&self) -> Option<i64> {
+        match self {
+            ExchangeError::ApiError { code, .. } => *code,
+            _ => None,
+        }
+    }
+
     /// Get the exchange name if applicable
     pub fn exchange(&self) -> Option<&str> {
         match self {